@@ -8,6 +8,7 @@ fn main() {
 		feature = "kv-tikv",
 		feature = "kv-rocksdb",
 		feature = "kv-surrealkv",
+		feature = "kv-redb",
 	)) {
 		println!("cargo:rustc-cfg=storage");
 		println!("cargo::rustc-check-cfg=cfg(storage)");
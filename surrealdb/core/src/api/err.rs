@@ -80,6 +80,9 @@ pub enum ApiError {
 	#[error("Failed to parse request in final action handler")]
 	FinalActionRequestParseFailure,
 
+	#[error("The request does not match the types declared by the API")]
+	RequestValidationFailure,
+
 	// Body parsing errors
 	#[error("Request body must be binary data")]
 	RequestBodyNotBinary,
@@ -122,6 +125,7 @@ impl ApiError {
 				..
 			} => StatusCode::INTERNAL_SERVER_ERROR,
 			Self::FinalActionRequestParseFailure => StatusCode::BAD_REQUEST,
+			Self::RequestValidationFailure => StatusCode::BAD_REQUEST,
 			Self::RequestBodyNotBinary => StatusCode::BAD_REQUEST,
 			Self::PermissionDenied => StatusCode::FORBIDDEN,
 			Self::NotFound => StatusCode::NOT_FOUND,
@@ -137,6 +141,7 @@ impl ApiError {
 				..
 			}
 			| Self::FinalActionRequestParseFailure
+			| Self::RequestValidationFailure
 			| Self::InvalidRequestBody
 			| Self::BodyDecodeFailure
 			| Self::InvalidFormat
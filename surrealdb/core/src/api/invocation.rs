@@ -10,6 +10,7 @@ use super::response::ApiResponse;
 use crate::api::X_SURREAL_REQUEST_ID;
 use crate::api::err::ApiError;
 use crate::api::request::ApiRequest;
+use crate::api::validate::RequestTypes;
 use crate::catalog::providers::DatabaseProvider;
 use crate::catalog::{ApiDefinition, MiddlewareDefinition, Permission};
 use crate::ctx::{Context, FrozenContext};
@@ -75,9 +76,9 @@ pub async fn process_api_request_with_stack(
 	// method, and if so should they all be run?
 	let method_action = api.actions.iter().find(|x| x.methods.contains(&req.method));
 
-	let (action_expr, method_config) = match (method_action, &api.fallback) {
-		(Some(x), _) => (x.action.clone(), Some(&x.config)),
-		(None, Some(x)) => (x.clone(), None),
+	let (action_expr, method_config, types) = match (method_action, &api.fallback) {
+		(Some(x), _) => (x.action.clone(), Some(&x.config), RequestTypes::from_action(x)),
+		(None, Some(x)) => (x.clone(), None, None),
 		// nothing to do, just return
 		_ => {
 			trace!(
@@ -151,7 +152,7 @@ pub async fn process_api_request_with_stack(
 		.collect();
 
 	// Create the final action closure (end of the middleware chain)
-	let final_action = create_final_action_closure(req.request_id.clone(), action_expr);
+	let final_action = create_final_action_closure(req.request_id.clone(), action_expr, types);
 
 	// Build the middleware chain backwards, wrapping each middleware around the previous closure
 	let middleware_len = middleware.len();
@@ -185,9 +186,18 @@ pub async fn process_api_request_with_stack(
 /// This closure is the end of the middleware chain and directly executes
 /// the action expression with the request in the context.
 ///
+/// When the action declares types for the request, the request is validated
+/// and coerced before the action is run, and a 400 response listing every
+/// violation is returned if it does not match.
+///
 /// # Arguments
 /// * `action_expr` - The expression to execute as the final action
-fn create_final_action_closure(request_id: String, action_expr: Expr) -> Closure {
+/// * `types` - The types declared for the request by the action, if any
+fn create_final_action_closure(
+	request_id: String,
+	action_expr: Expr,
+	types: Option<RequestTypes>,
+) -> Closure {
 	Closure::Builtin(Arc::new(
 		move |stk: &mut Stk,
 		      ctx: &FrozenContext,
@@ -215,9 +225,22 @@ fn create_final_action_closure(request_id: String, action_expr: Expr) -> Closure
 				);
 			}
 
+			// Validate and coerce the request against the declared types
+			let mut req = Value::from(req);
+			if let Some(types) = &types
+				&& let Err(violations) = types.validate(&mut req)
+			{
+				let res =
+					ApiResponse::from_violations(violations, request_id.clone()).map(|mut res| {
+						res.ensure_request_id_header();
+						Value::from(res)
+					});
+				return Box::pin(std::future::ready(res));
+			}
+
 			// Update context
 			let mut ctx_isolated = Context::new_isolated(ctx);
-			ctx_isolated.add_value("request", Arc::new(req.into()));
+			ctx_isolated.add_value("request", Arc::new(req));
 			let ctx_frozen = ctx_isolated.freeze();
 
 			// Clone required values
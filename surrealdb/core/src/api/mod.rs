@@ -1,9 +1,11 @@
 pub mod err;
 pub mod invocation;
 pub mod middleware;
+pub mod openapi;
 pub mod path;
 pub mod request;
 pub mod response;
+pub(crate) mod validate;
 
 use http::HeaderName;
 
@@ -18,6 +20,8 @@ pub mod format {
 	pub const JSON: &str = "application/json";
 	pub const CBOR: &str = "application/cbor";
 	pub const FLATBUFFERS: &str = "application/vnd.surrealdb.flatbuffers";
+	pub const MSGPACK: &str = "application/vnd.msgpack";
+	pub const PROTOBUF: &str = "application/vnd.surrealdb.protobuf";
 	pub const NATIVE: &str = "application/vnd.surrealdb.native";
 
 	pub const PLAIN: &str = "text/plain";
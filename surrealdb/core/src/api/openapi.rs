@@ -0,0 +1,371 @@
+//! Generates an OpenAPI 3.1 document describing the endpoints of a database
+//! which have been defined with `DEFINE API`.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+use super::format;
+use super::path::Segment;
+use crate::catalog::providers::{ApiProvider, DatabaseProvider};
+use crate::catalog::{ApiActionDefinition, ApiConfigDefinition, ApiDefinition, ApiMethod};
+use crate::expr::Kind;
+use crate::expr::kind::KindLiteral;
+use crate::kvs::Transaction;
+use crate::val::{Object, Value};
+
+/// The version of the OpenAPI specification which the document conforms to
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// The methods which are served by an API definition with a fallback action
+const METHODS: [ApiMethod; 6] = [
+	ApiMethod::Get,
+	ApiMethod::Post,
+	ApiMethod::Put,
+	ApiMethod::Patch,
+	ApiMethod::Delete,
+	ApiMethod::Trace,
+];
+
+/// Generates the OpenAPI document for the APIs defined on the specified
+/// database. A database which does not exist has no paths.
+pub(crate) async fn generate(tx: &Transaction, ns: &str, db: &str) -> Result<Value> {
+	let Some(def) = tx.get_db_by_name(ns, db).await? else {
+		return Ok(document(ns, db, &[], None));
+	};
+	let apis = tx.all_db_apis(def.namespace_id, def.database_id).await?;
+	let global = tx.get_db_config(def.namespace_id, def.database_id, "api").await?;
+	let global = global.as_ref().map(|v| v.try_as_api()).transpose()?;
+	Ok(document(ns, db, &apis, global))
+}
+
+/// Generates the OpenAPI document for the specified API definitions.
+///
+/// The `global` configuration is the database-wide `DEFINE CONFIG API`
+/// configuration, which applies to every API definition.
+pub(crate) fn document(
+	ns: &str,
+	db: &str,
+	apis: &[ApiDefinition],
+	global: Option<&ApiConfigDefinition>,
+) -> Value {
+	let mut paths = BTreeMap::new();
+	for api in apis {
+		paths.insert(path(api), Value::from(path_item(api, global)));
+	}
+	Value::from(map! {
+		"openapi" => Value::from(OPENAPI_VERSION),
+		"info" => Value::from(map! {
+			"title" => Value::from(format!("{ns}/{db}")),
+			"version" => Value::from(env!("CARGO_PKG_VERSION")),
+		}),
+		"servers" => Value::from(vec![Value::from(map! {
+			"url" => Value::from(format!("/api/{ns}/{db}")),
+		})]),
+		"paths" => Value::from(paths),
+	})
+}
+
+/// Converts an API path into an OpenAPI path template
+fn path(api: &ApiDefinition) -> String {
+	let mut out = String::new();
+	for segment in api.path.iter() {
+		out.push('/');
+		match segment {
+			Segment::Fixed(v) => out.push_str(v),
+			Segment::Dynamic(v, _) | Segment::Rest(v) => {
+				out.push('{');
+				out.push_str(v);
+				out.push('}');
+			}
+		}
+	}
+	if out.is_empty() {
+		out.push('/');
+	}
+	out
+}
+
+/// Describes the operations which are available on an API path
+fn path_item(api: &ApiDefinition, global: Option<&ApiConfigDefinition>) -> BTreeMap<String, Value> {
+	let mut item = BTreeMap::new();
+	// Describe the path parameters
+	let parameters = parameters(api);
+	if !parameters.is_empty() {
+		item.insert("parameters".to_string(), Value::from(parameters));
+	}
+	// Describe the operation for each method
+	for method in METHODS {
+		let action = api.actions.iter().find(|x| x.methods.contains(&method));
+		if action.is_none() && api.fallback.is_none() {
+			continue;
+		}
+		item.insert(method.to_string(), Value::from(operation(api, action, method, global)));
+	}
+	item
+}
+
+/// Describes the parameters which are extracted from the API path
+fn parameters(api: &ApiDefinition) -> Vec<Value> {
+	api.path
+		.iter()
+		.filter_map(|segment| {
+			let (name, schema, description) = match segment {
+				Segment::Fixed(_) => return None,
+				Segment::Dynamic(name, kind) => (
+					name,
+					kind.as_ref().map(schema).unwrap_or_else(|| schema(&Kind::String)),
+					None,
+				),
+				Segment::Rest(name) => (
+					name,
+					schema(&Kind::String),
+					Some("The remaining segments of the request path"),
+				),
+			};
+			Some(Value::from(map! {
+				"name" => Value::from(name.clone()),
+				"in" => Value::from("path"),
+				"required" => Value::Bool(true),
+				"description", if let Some(v) = description => Value::from(v),
+				"schema" => schema,
+			}))
+		})
+		.collect()
+}
+
+/// Describes a single operation on an API path
+fn operation(
+	api: &ApiDefinition,
+	action: Option<&ApiActionDefinition>,
+	method: ApiMethod,
+	global: Option<&ApiConfigDefinition>,
+) -> BTreeMap<&'static str, Value> {
+	// The configurations which apply to this operation, from the most specific
+	let configs: Vec<&ApiConfigDefinition> =
+		action.map(|x| &x.config).into_iter().chain(std::iter::once(&api.config)).chain(global).collect();
+	// Describe the typed parameters of the operation
+	let parameters = action.map(|x| typed_parameters(api, x)).unwrap_or_default();
+	// Describe the request body of the operation
+	let body = action.and_then(|x| x.body.as_ref());
+	let request = content(&configs, "api::req::body", body).map(|v| {
+		Value::from(map! {
+			"content" => v,
+			"required", if body.is_some_and(|k| !k.can_be_none()) => Value::Bool(true),
+		})
+	});
+	// Describe the response of the operation
+	let response = map! {
+		"description" => Value::from("The response returned by the API action"),
+		"content", if let Some(v) = content(&configs, "api::res::body", None) => v,
+	};
+	map! {
+		"operationId" => Value::from(operation_id(api, method)),
+		"summary", if let Some(v) = &api.comment => Value::from(v.clone()),
+		"parameters", if !parameters.is_empty() => Value::from(parameters),
+		"requestBody", if let Some(v) = request => v,
+		"responses" => Value::from(map! {
+			"default" => Value::from(response),
+		}),
+	}
+}
+
+/// Describes the path and query parameters which have been declared with
+/// types on an API action
+fn typed_parameters(api: &ApiDefinition, action: &ApiActionDefinition) -> Vec<Value> {
+	let mut out = Vec::new();
+	for (location, kind) in [("path", &action.params), ("query", &action.query)] {
+		let Some(Kind::Literal(KindLiteral::Object(fields))) = kind else {
+			continue;
+		};
+		for (name, kind) in fields {
+			// Only parameters which appear in the path can be described as such
+			if location == "path"
+				&& !api.path.iter().any(|s| matches!(s, Segment::Dynamic(v, _) if v == name))
+			{
+				continue;
+			}
+			out.push(Value::from(map! {
+				"name" => Value::from(name.clone()),
+				"in" => Value::from(location),
+				"required" => Value::Bool(location == "path" || !kind.can_be_none()),
+				"schema" => schema(kind),
+			}));
+		}
+	}
+	out
+}
+
+/// Generates a unique identifier for an operation from its method and path
+fn operation_id(api: &ApiDefinition, method: ApiMethod) -> String {
+	let mut out = method.to_string();
+	for segment in api.path.iter() {
+		let name = match segment {
+			Segment::Fixed(v) | Segment::Dynamic(v, _) | Segment::Rest(v) => v,
+		};
+		out.push('_');
+		out.extend(name.chars().map(|c| {
+			if c.is_ascii_alphanumeric() {
+				c
+			} else {
+				'_'
+			}
+		}));
+	}
+	out
+}
+
+/// Describes the media types accepted or returned by an operation, when the
+/// body format has been declared with the specified middleware, or the body
+/// type has been declared on the action
+fn content(
+	configs: &[&ApiConfigDefinition],
+	middleware: &str,
+	kind: Option<&Kind>,
+) -> Option<Value> {
+	// Find the most specific declaration of the middleware
+	let definition = configs.iter().find_map(|c| c.middleware.iter().find(|m| m.name == middleware));
+	if definition.is_none() && kind.is_none() {
+		return None;
+	}
+	// Fetch the body strategy argument
+	let strategy = match definition.and_then(|d| d.args.first()) {
+		Some(Value::String(v)) => v.to_lowercase(),
+		_ => "auto".to_string(),
+	};
+	let types: &[&str] = match strategy.as_str() {
+		"json" => &[format::JSON],
+		"cbor" => &[format::CBOR],
+		"flatbuffers" => &[format::FLATBUFFERS],
+		"plain" => &[format::PLAIN],
+		"bytes" => &[format::OCTET_STREAM],
+		"native" => &[format::NATIVE],
+		_ => &[
+			format::JSON,
+			format::CBOR,
+			format::FLATBUFFERS,
+			format::PLAIN,
+			format::OCTET_STREAM,
+			format::NATIVE,
+		],
+	};
+	let mut out = Object::default();
+	for &mime in types {
+		let schema = match mime {
+			format::PLAIN => schema(&Kind::String),
+			format::OCTET_STREAM => schema(&Kind::Bytes),
+			_ => schema(kind.unwrap_or(&Kind::Any)),
+		};
+		out.insert(mime.to_string(), Value::from(map! { "schema" => schema }));
+	}
+	Some(Value::from(out))
+}
+
+/// Converts a SurrealQL type into a JSON Schema
+pub(crate) fn schema(kind: &Kind) -> Value {
+	let typed = |ty: &str, format: Option<&str>| {
+		Value::from(map! {
+			"type" => Value::from(ty),
+			"format", if let Some(v) = format => Value::from(v),
+		})
+	};
+	match kind {
+		Kind::Any | Kind::Function(_, _) => Value::from(Object::default()),
+		Kind::None | Kind::Null => typed("null", None),
+		Kind::Bool => typed("boolean", None),
+		Kind::Bytes => typed("string", Some("binary")),
+		Kind::Datetime => typed("string", Some("date-time")),
+		Kind::Decimal => typed("number", Some("decimal")),
+		Kind::Duration => typed("string", Some("duration")),
+		Kind::Float => typed("number", Some("double")),
+		Kind::Int => typed("integer", Some("int64")),
+		Kind::Number => typed("number", None),
+		Kind::Object | Kind::Geometry(_) => typed("object", None),
+		Kind::String | Kind::Table(_) | Kind::Range | Kind::File(_) => typed("string", None),
+		Kind::Uuid => typed("string", Some("uuid")),
+		Kind::Regex => typed("string", Some("regex")),
+		Kind::Record(_) => typed("string", Some("record")),
+		Kind::Either(kinds) => Value::from(map! {
+			"anyOf" => Value::from(kinds.iter().map(schema).collect::<Vec<_>>()),
+		}),
+		Kind::Array(inner, max) | Kind::Set(inner, max) => Value::from(map! {
+			"type" => Value::from("array"),
+			"items" => schema(inner),
+			"maxItems", if let Some(v) = max => Value::from(*v),
+			"uniqueItems", if matches!(kind, Kind::Set(_, _)) => Value::Bool(true),
+		}),
+		Kind::Literal(literal) => match literal {
+			KindLiteral::String(v) => Value::from(map! { "const" => Value::from(v.clone()) }),
+			KindLiteral::Integer(v) => Value::from(map! { "const" => Value::from(*v) }),
+			KindLiteral::Float(v) => Value::from(map! { "const" => Value::from(*v) }),
+			KindLiteral::Decimal(v) => Value::from(map! { "const" => Value::from(*v) }),
+			KindLiteral::Duration(v) => Value::from(map! { "const" => Value::from(v.to_string()) }),
+			KindLiteral::Bool(v) => Value::from(map! { "const" => Value::Bool(*v) }),
+			KindLiteral::Array(kinds) => Value::from(map! {
+				"type" => Value::from("array"),
+				"prefixItems" => Value::from(kinds.iter().map(schema).collect::<Vec<_>>()),
+				"minItems" => Value::from(kinds.len() as u64),
+				"maxItems" => Value::from(kinds.len() as u64),
+			}),
+			KindLiteral::Object(fields) => {
+				let required = fields
+					.iter()
+					.filter(|(_, k)| !k.can_be_none())
+					.map(|(k, _)| Value::from(k.clone()))
+					.collect::<Vec<_>>();
+				Value::from(map! {
+					"type" => Value::from("object"),
+					"properties" => Value::from(
+						fields.iter().map(|(k, v)| (k.clone(), schema(v))).collect::<BTreeMap<_, _>>(),
+					),
+					"required", if !required.is_empty() => Value::from(required),
+					"additionalProperties" => Value::Bool(false),
+				})
+			}
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_types::ToSql;
+
+	use super::*;
+	use crate::syn;
+
+	fn kind(v: &str) -> Kind {
+		syn::kind(v).unwrap().into()
+	}
+
+	#[test]
+	fn test_schema() {
+		assert_eq!(schema(&kind("int")).to_sql(), "{ format: 'int64', type: 'integer' }");
+		assert_eq!(
+			schema(&kind("option<string>")).to_sql(),
+			"{ anyOf: [{ type: 'null' }, { type: 'string' }] }"
+		);
+		assert_eq!(
+			schema(&kind("array<bool, 2>")).to_sql(),
+			"{ items: { type: 'boolean' }, maxItems: 2, type: 'array' }"
+		);
+		assert_eq!(
+			schema(&kind("{ a: string, b: option<int> } | 'x'")).to_sql(),
+			"{ anyOf: [{ additionalProperties: false, properties: { a: { type: 'string' }, b: { anyOf: [{ type: 'null' }, { format: 'int64', type: 'integer' }] } }, required: ['a'], type: 'object' }, { const: 'x' }] }"
+		);
+	}
+
+	#[test]
+	fn test_path() {
+		let api = ApiDefinition {
+			path: "/users/:id<int>/*rest".parse().unwrap(),
+			..Default::default()
+		};
+		assert_eq!(path(&api), "/users/{id}/{rest}");
+		assert_eq!(operation_id(&api, ApiMethod::Get), "get_users_id_rest");
+		let params = Value::from(parameters(&api));
+		assert_eq!(
+			params.to_sql(),
+			"[{ in: 'path', name: 'id', required: true, schema: { format: 'int64', type: 'integer' } }, { description: 'The remaining segments of the request path', in: 'path', name: 'rest', required: true, schema: { type: 'string' } }]"
+		);
+	}
+}
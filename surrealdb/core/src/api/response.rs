@@ -4,6 +4,7 @@ use surrealdb_types::SurrealValue;
 
 use crate::api::X_SURREAL_REQUEST_ID;
 use crate::api::err::ApiError;
+use crate::api::validate::Violation;
 use crate::sql::expression::convert_public_value_to_internal;
 use crate::types::{PublicObject, PublicValue};
 use crate::val::{Value, convert_value_to_public_value};
@@ -37,6 +38,26 @@ impl ApiResponse {
 		}
 	}
 
+	/// Builds a 400 response listing every value in a request which did not
+	/// match the types declared by the API action.
+	pub(crate) fn from_violations(
+		violations: Vec<Violation>,
+		request_id: String,
+	) -> anyhow::Result<Self> {
+		let error = ApiError::RequestValidationFailure;
+		let body = Value::from(map! {
+			"error" => Value::from(error.to_string()),
+			"violations" => Value::from(violations.into_iter().map(Value::from).collect::<Vec<_>>()),
+		});
+		Ok(Self {
+			status: error.status_code(),
+			body: convert_value_to_public_value(body)
+				.context("Failed to convert request violations to public value")?,
+			request_id,
+			..Default::default()
+		})
+	}
+
 	/// Builds an API response from an error in a security-sensitive context (e.g. initial
 	/// middleware). Known API errors (validation, not found, etc.) are converted with correct
 	/// status and message. Internal/unknown errors are masked as 500 with no body to avoid leaking
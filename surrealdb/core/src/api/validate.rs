@@ -0,0 +1,240 @@
+//! Validates and coerces the path parameters, query parameters, and body of
+//! an API request against the types declared on the matching API action.
+
+use surrealdb_types::ToSql;
+
+use crate::catalog::ApiActionDefinition;
+use crate::expr::Kind;
+use crate::expr::kind::KindLiteral;
+use crate::val::{Object, Value};
+
+/// The part of a request in which a value was found
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Location {
+	Params,
+	Query,
+	Body,
+}
+
+impl Location {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Params => "params",
+			Self::Query => "query",
+			Self::Body => "body",
+		}
+	}
+}
+
+/// A value in a request which does not match its declared type
+#[derive(Clone, Debug)]
+pub(crate) struct Violation {
+	/// The part of the request containing the value
+	pub location: Location,
+	/// The name of the field, if the value is a field of an object
+	pub field: Option<String>,
+	/// The type which the value was expected to have
+	pub expected: Kind,
+	/// A description of why the value was rejected
+	pub message: String,
+}
+
+impl From<Violation> for Value {
+	fn from(v: Violation) -> Self {
+		Value::from(map! {
+			"in" => Value::from(v.location.as_str()),
+			"field", if let Some(field) = v.field => Value::from(field),
+			"expected" => Value::from(v.expected.to_sql()),
+			"message" => Value::from(v.message),
+		})
+	}
+}
+
+/// The types which a request must match before an API action is run
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RequestTypes {
+	params: Option<Kind>,
+	query: Option<Kind>,
+	body: Option<Kind>,
+}
+
+impl RequestTypes {
+	/// Returns the request types declared on an action, if any were declared
+	pub(crate) fn from_action(action: &ApiActionDefinition) -> Option<Self> {
+		if action.params.is_none() && action.query.is_none() && action.body.is_none() {
+			return None;
+		}
+		Some(Self {
+			params: action.params.clone(),
+			query: action.query.clone(),
+			body: action.body.clone(),
+		})
+	}
+
+	/// Validates the request, replacing its parameters and body with the
+	/// coerced values. Every violation in the request is returned, rather than
+	/// just the first one encountered.
+	pub(crate) fn validate(&self, request: &mut Value) -> Result<(), Vec<Violation>> {
+		let Value::Object(request) = request else {
+			return Ok(());
+		};
+		let mut violations = Vec::new();
+		for (location, kind) in [
+			(Location::Params, &self.params),
+			(Location::Query, &self.query),
+			(Location::Body, &self.body),
+		] {
+			let Some(kind) = kind else {
+				continue;
+			};
+			let key = location.as_str();
+			let value = request.remove(key).unwrap_or(Value::None);
+			let value = check(location, kind, value, &mut violations);
+			request.insert(key.to_string(), value);
+		}
+		if violations.is_empty() {
+			Ok(())
+		} else {
+			Err(violations)
+		}
+	}
+}
+
+/// Checks a value against a kind. Object types are checked field by field so
+/// that each invalid field is reported separately.
+fn check(location: Location, kind: &Kind, value: Value, violations: &mut Vec<Violation>) -> Value {
+	match (kind, value) {
+		(Kind::Literal(KindLiteral::Object(fields)), Value::Object(mut object)) => {
+			let mut out = Object::default();
+			for (name, kind) in fields {
+				let value = object.remove(name).unwrap_or(Value::None);
+				let missing = value.is_none();
+				match convert(location, kind, value) {
+					Ok(Value::None) => {}
+					Ok(v) => {
+						out.insert(name.clone(), v);
+					}
+					Err(message) => violations.push(Violation {
+						location,
+						field: Some(name.clone()),
+						expected: kind.clone(),
+						message: if missing {
+							"A value is required but none was provided".to_string()
+						} else {
+							message
+						},
+					}),
+				}
+			}
+			match location {
+				// Path and query parameters which are not declared are passed through
+				Location::Params | Location::Query => out.0.append(&mut object.0),
+				// A body object must not contain any undeclared fields
+				Location::Body => {
+					for name in object.0.into_keys() {
+						violations.push(Violation {
+							location,
+							field: Some(name),
+							expected: Kind::None,
+							message: "The field is not allowed".to_string(),
+						});
+					}
+				}
+			}
+			Value::Object(out)
+		}
+		(kind, value) => match convert(location, kind, value) {
+			Ok(v) => v,
+			Err(message) => {
+				violations.push(Violation {
+					location,
+					field: None,
+					expected: kind.clone(),
+					message,
+				});
+				Value::None
+			}
+		},
+	}
+}
+
+/// Converts a single value to a kind. Path and query parameters are received
+/// as strings, so they are cast, whereas a decoded body is coerced.
+fn convert(location: Location, kind: &Kind, value: Value) -> Result<Value, String> {
+	match location {
+		Location::Params | Location::Query => value.cast_to_kind(kind).map_err(|e| e.to_string()),
+		Location::Body => value.coerce_to_kind(kind).map_err(|e| e.to_string()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sql::expression::convert_public_value_to_internal;
+	use crate::syn;
+
+	fn kind(v: &str) -> Kind {
+		syn::kind(v).unwrap().into()
+	}
+
+	fn value(v: &str) -> Value {
+		convert_public_value_to_internal(syn::value(v).unwrap())
+	}
+
+	#[test]
+	fn test_validate_coerces_request() {
+		let types = RequestTypes {
+			params: Some(kind("{ id: int }")),
+			query: Some(kind("{ limit: option<int>, sort: 'asc' | 'desc' }")),
+			body: Some(kind("{ name: string, age: option<int> }")),
+		};
+		let mut request = value(
+			"{ params: { id: '1', rest: ['a'] }, query: { limit: '10', sort: 'asc', page: '2' }, body: { name: 'Tobie' } }",
+		);
+		types.validate(&mut request).unwrap();
+		assert_eq!(
+			request,
+			value(
+				"{ params: { id: 1, rest: ['a'] }, query: { limit: 10, sort: 'asc', page: '2' }, body: { name: 'Tobie' } }"
+			)
+		);
+	}
+
+	#[test]
+	fn test_validate_reports_every_violation() {
+		let types = RequestTypes {
+			params: None,
+			query: Some(kind("{ limit: int, sort: 'asc' | 'desc' }")),
+			body: Some(kind("{ name: string }")),
+		};
+		let mut request = value("{ query: { limit: 'ten' }, body: { name: 1, admin: true } }");
+		let violations = types.validate(&mut request).unwrap_err();
+		let fields = violations
+			.iter()
+			.map(|v| (v.location, v.field.clone().unwrap_or_default()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			fields,
+			vec![
+				(Location::Query, "limit".to_string()),
+				(Location::Query, "sort".to_string()),
+				(Location::Body, "name".to_string()),
+				(Location::Body, "admin".to_string()),
+			]
+		);
+		assert_eq!(violations[1].message, "A value is required but none was provided");
+	}
+
+	#[test]
+	fn test_validate_non_object_body() {
+		let types = RequestTypes {
+			body: Some(kind("array<int>")),
+			..Default::default()
+		};
+		let mut request = value("{ body: 'nope' }");
+		let violations = types.validate(&mut request).unwrap_err();
+		assert_eq!(violations.len(), 1);
+		assert_eq!(violations[0].field, None);
+		assert_eq!(violations[0].expected, kind("array<int>"));
+	}
+}
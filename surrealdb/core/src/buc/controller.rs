@@ -1,10 +1,14 @@
 use core::fmt;
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 
-use super::store::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::metadata::FileMetadata;
+use super::presign::PresignedFile;
+use super::store::{ByteStream, ListOptions, ObjectKey, ObjectMeta, ObjectStore, is_internal_key};
 use crate::catalog::providers::BucketProvider;
 use crate::catalog::{BucketDefinition, Permission};
 use crate::ctx::{Context, FrozenContext};
@@ -80,16 +84,29 @@ impl<'a> BucketController<'a> {
 	/// Attempt to put a file
 	/// `Bytes` and `String` values are supported, and will be converted into
 	/// `Bytes` Create or update permissions will be used, based on if the
-	/// remote file already exists
-	pub(crate) async fn put(&mut self, key: &ObjectKey, value: Value) -> Result<()> {
+	/// remote file already exists. The metadata is stored alongside the file,
+	/// together with a checksum of its contents.
+	pub(crate) async fn put(
+		&mut self,
+		key: &ObjectKey,
+		value: Value,
+		meta: FileMetadata,
+	) -> Result<()> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Put, Some(key), None).await?;
 
+		let meta = meta.with_checksum(&payload);
+		meta.write_pending(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 		self.store
 			.put(key, payload)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		meta.write(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -100,19 +117,75 @@ impl<'a> BucketController<'a> {
 	/// If the key already exists, the operation is a no-op.
 	///
 	/// `Bytes` and `String` values are supported and will be converted into bytes.
-	pub(crate) async fn put_if_not_exists(&mut self, key: &ObjectKey, value: Value) -> Result<()> {
+	pub(crate) async fn put_if_not_exists(
+		&mut self,
+		key: &ObjectKey,
+		value: Value,
+		meta: FileMetadata,
+	) -> Result<()> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Put, Some(key), None).await?;
 
+		// Leave the metadata of an existing file untouched
+		if self.exists_raw(key).await? {
+			return Ok(());
+		}
+
+		let meta = meta.with_checksum(&payload);
+		meta.write_pending(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 		self.store
 			.put_if_not_exists(key, payload)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		meta.write(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
 
+	/// Retrieves the metadata stored alongside an object, such as its content
+	/// type, checksum, and any user-defined metadata.
+	///
+	/// Returns `None` if no metadata is stored for the object.
+	pub(crate) async fn metadata(&mut self, key: &ObjectKey) -> Result<Option<FileMetadata>> {
+		self.check_permission(BucketOperation::Head, Some(key), None).await?;
+
+		FileMetadata::read(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))
+			.map_err(anyhow::Error::new)
+	}
+
+	/// Checks that the contents of an object match its stored checksum.
+	async fn verify(&self, key: &ObjectKey, data: &[u8]) -> Result<()> {
+		let meta = FileMetadata::read(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		if let Some(meta) = meta {
+			ensure!(
+				meta.verify(data),
+				err::Error::FileChecksumMismatch {
+					bucket: self.bucket.name.clone(),
+					key: key.to_string(),
+				}
+			);
+		}
+		Ok(())
+	}
+
+	/// Checks whether an object exists, without checking permissions.
+	async fn exists_raw(&self, key: &ObjectKey) -> Result<bool> {
+		self.store
+			.exists(key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))
+			.map_err(anyhow::Error::new)
+	}
+
 	/// Retrieves metadata for an object without fetching its contents.
 	///
 	/// Returns `None` if the object does not exist.
@@ -141,10 +214,206 @@ impl<'a> BucketController<'a> {
 			Some(v) => v,
 			None => return Ok(None),
 		};
+		self.verify(key, &bytes).await?;
 
 		Ok(Some(Bytes(bytes)))
 	}
 
+	/// Retrieves the contents of an object within a byte range. The end of the
+	/// range is exclusive, and is clamped to the size of the object.
+	///
+	/// Returns `None` if the object does not exist.
+	pub(crate) async fn get_range(
+		&mut self,
+		key: &ObjectKey,
+		range: Range<u64>,
+	) -> Result<Option<Bytes>> {
+		self.check_permission(BucketOperation::Get, Some(key), None).await?;
+
+		let bytes = self
+			.store
+			.get_range(key, range)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(bytes.map(Bytes))
+	}
+
+	/// Retrieves the contents of an object as a stream of chunks, optionally
+	/// limited to a byte range. When the whole object is streamed, the stream
+	/// ends with an error if the contents do not match the stored checksum.
+	///
+	/// Returns `None` if the object does not exist.
+	pub(crate) async fn get_stream(
+		&mut self,
+		key: &ObjectKey,
+		range: Option<Range<u64>>,
+	) -> Result<Option<ByteStream>> {
+		self.check_permission(BucketOperation::Get, Some(key), None).await?;
+
+		let verify = range.is_none();
+		let Some(stream) = self
+			.store
+			.get_stream(key, range)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?
+		else {
+			return Ok(None);
+		};
+		if !verify {
+			return Ok(Some(stream));
+		}
+		let meta = FileMetadata::read(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		Ok(Some(match meta {
+			Some(meta) => meta.verify_stream(key, stream),
+			None => stream,
+		}))
+	}
+
+	/// Stores a stream of chunks at the specified key, overwriting any existing data.
+	/// The metadata is stored alongside the file, together with a checksum of its contents.
+	pub(crate) async fn put_stream(
+		&mut self,
+		key: &ObjectKey,
+		stream: ByteStream,
+		meta: FileMetadata,
+	) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		meta.write_pending(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		let (stream, checksum) = FileMetadata::hash_stream(stream);
+		self.store
+			.put_stream(key, stream)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		let meta = FileMetadata {
+			checksum: Some(checksum.finish()),
+			..meta
+		};
+		meta.write(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
+	/// Starts a multipart upload to the specified key, returning the id of the upload.
+	/// The metadata is held with the upload, and is stored alongside the file once
+	/// the upload is completed.
+	pub(crate) async fn create_multipart(
+		&mut self,
+		key: &ObjectKey,
+		meta: FileMetadata,
+	) -> Result<String> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		let upload = self
+			.store
+			.create_multipart(key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		meta.write(self.store.as_ref(), &FileMetadata::upload(&upload))
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(upload)
+	}
+
+	/// Stores a part of a multipart upload.
+	///
+	/// `Bytes` and `String` values are supported and will be converted into bytes.
+	pub(crate) async fn put_part(
+		&mut self,
+		key: &ObjectKey,
+		upload: &str,
+		part: u32,
+		value: Value,
+	) -> Result<()> {
+		let payload = accept_payload(value)?;
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		self.store
+			.put_part(key, upload, part, payload)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
+	/// Completes a multipart upload, storing the combined parts at the key.
+	pub(crate) async fn complete_multipart(&mut self, key: &ObjectKey, upload: &str) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		let store = self.store.as_ref();
+		let pending = FileMetadata::upload(upload);
+		let meta = FileMetadata::read(store, &pending)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?
+			.unwrap_or_else(FileMetadata::default);
+		meta.write_pending(store, key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		store
+			.complete_multipart(key, upload)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		// The parts were combined by the store, so the checksum is taken from the file
+		let meta = FileMetadata {
+			checksum: FileMetadata::checksum_stored(store, key)
+				.await
+				.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?,
+			..meta
+		};
+		meta.write(store, key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(store, &pending)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
+	/// Aborts a multipart upload, discarding any uploaded parts.
+	pub(crate) async fn abort_multipart(&mut self, key: &ObjectKey, upload: &str) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None).await?;
+
+		self.store
+			.abort_multipart(key, upload)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), &FileMetadata::upload(upload))
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
+	/// Creates a presigned URL path which grants read access to an object for
+	/// the specified duration, without requiring authentication.
+	pub(crate) async fn presign(&mut self, key: &ObjectKey, expiry: Duration) -> Result<String> {
+		self.check_permission(BucketOperation::Get, Some(key), None).await?;
+
+		let (ns, db) = self.opt.ns_db()?;
+		let file = PresignedFile {
+			ns,
+			db,
+			bucket: &self.bucket.name,
+			key: key.as_str(),
+		};
+
+		Ok(file.url(expiry))
+	}
+
 	/// Deletes an object from the bucket.
 	///
 	/// This operation is idempotent - deleting a non-existent object is not an error.
@@ -156,6 +425,9 @@ impl<'a> BucketController<'a> {
 			.delete(key)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -171,6 +443,9 @@ impl<'a> BucketController<'a> {
 			.copy(key, &target)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), key, &target)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -186,10 +461,18 @@ impl<'a> BucketController<'a> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Copy, Some(key), Some(&target)).await?;
 
+		// Leave the metadata of an existing file untouched
+		if self.exists_raw(&target).await? {
+			return Ok(());
+		}
+
 		self.store
 			.copy_if_not_exists(key, &target)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), key, &target)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -206,6 +489,12 @@ impl<'a> BucketController<'a> {
 			.rename(key, &target)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), key, &target)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -221,10 +510,21 @@ impl<'a> BucketController<'a> {
 		self.require_writeable()?;
 		self.check_permission(BucketOperation::Rename, Some(key), Some(&target)).await?;
 
+		// Leave the metadata of an existing file untouched
+		if self.exists_raw(&target).await? {
+			return Ok(());
+		}
+
 		self.store
 			.rename_if_not_exists(key, &target)
 			.await
 			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), key, &target)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), key)
+			.await
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -245,17 +545,20 @@ impl<'a> BucketController<'a> {
 	/// Note: Guest and Record users are not allowed to list files in buckets.
 	pub(crate) async fn list(&mut self, opts: &ListOptions) -> Result<Vec<ObjectMeta>> {
 		self.check_permission(BucketOperation::Exists, None, None).await?;
-		self.store
+		let objects = self
+			.store
 			.list(opts)
 			.await
-			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))
-			.map_err(anyhow::Error::new)
+			.map_err(|e| err::Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		// Hide any multipart uploads and metadata which are stored within the bucket
+		Ok(objects.into_iter().filter(|x| !is_internal_key(&x.key)).collect())
 	}
 
 	/// Checks if the current user has permission to perform the specified operation.
 	///
 	/// This method evaluates the bucket's permission policy against the current user
-	/// and operation. For operations involving files, the `$action`, `$file`, and
+	/// and operation, and rejects keys which are reserved for multipart uploads and
+	/// file metadata. For operations involving files, the `$action`, `$file`, and
 	/// optionally `$target` variables are made available to the permission expression.
 	///
 	/// # Arguments
@@ -268,6 +571,17 @@ impl<'a> BucketController<'a> {
 		key: Option<&ObjectKey>,
 		target: Option<&ObjectKey>,
 	) -> Result<()> {
+		// Multipart uploads and metadata can not be accessed directly
+		for key in key.into_iter().chain(target) {
+			ensure!(
+				!is_internal_key(key),
+				err::Error::FileKeyReserved {
+					bucket: self.bucket.name.clone(),
+					key: key.to_string(),
+				}
+			);
+		}
+
 		if self.opt.check_perms(op.into())? {
 			// Guest and Record users are not allowed to list files in buckets
 			ensure!(
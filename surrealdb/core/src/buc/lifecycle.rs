@@ -0,0 +1,97 @@
+//! Enforcement of the lifecycle rules defined on buckets.
+//!
+//! Lifecycle rules remove files from a bucket once they have not been
+//! modified for longer than the configured duration. The rules are applied
+//! periodically by a background task, so files may remain visible for a short
+//! while after they have expired.
+
+use chrono::{DateTime, Utc};
+
+use super::metadata::FileMetadata;
+use super::store::{ListOptions, ObjectKey, ObjectStore, is_internal_key};
+use crate::catalog::BucketLifecycleRule;
+
+/// The number of files which are listed at a time when applying a rule
+const LIFECYCLE_BATCH_SIZE: usize = 1000;
+
+/// Applies the lifecycle rules of a bucket, removing any expired files
+/// together with their metadata. Returns the number of files removed.
+pub(crate) async fn apply_rules<S: ObjectStore + ?Sized>(
+	store: &S,
+	rules: &[BucketLifecycleRule],
+	now: DateTime<Utc>,
+) -> Result<usize, String> {
+	let mut removed = 0;
+	for rule in rules {
+		// Files modified before this time have expired
+		let Some(cutoff) = chrono::Duration::from_std(rule.expire)
+			.ok()
+			.and_then(|expire| now.checked_sub_signed(expire))
+		else {
+			continue;
+		};
+		let mut opts = ListOptions {
+			start: None,
+			prefix: rule.prefix.clone().map(ObjectKey::new),
+			limit: Some(LIFECYCLE_BATCH_SIZE),
+		};
+		loop {
+			let batch = store.list(&opts).await?;
+			let Some(last) = batch.last() else {
+				break;
+			};
+			opts.start = Some(last.key.clone());
+			let complete = batch.len() < LIFECYCLE_BATCH_SIZE;
+			for meta in batch {
+				if is_internal_key(&meta.key) || meta.updated > cutoff {
+					continue;
+				}
+				store.delete(&meta.key).await?;
+				FileMetadata::remove(store, &meta.key).await?;
+				removed += 1;
+			}
+			if complete {
+				break;
+			}
+		}
+	}
+	Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+	use std::time::Duration;
+
+	use bytes::Bytes;
+
+	use super::*;
+	use crate::buc::store::memory::MemoryStore;
+
+	#[tokio::test]
+	async fn test_apply_rules() {
+		let store = MemoryStore::new();
+		let tmp = ObjectKey::new("/tmp/file.txt");
+		let keep = ObjectKey::new("/keep/file.txt");
+		for key in [&tmp, &keep] {
+			store.put(key, Bytes::from_static(b"data")).await.unwrap();
+			FileMetadata::default().with_checksum(b"data").write(&store, key).await.unwrap();
+		}
+		// Files which have not yet reached the age are kept
+		let rules = vec![BucketLifecycleRule {
+			expire: Duration::from_secs(60),
+			prefix: Some("/tmp".to_string()),
+		}];
+		assert_eq!(apply_rules(&store, &rules, Utc::now()).await.unwrap(), 0);
+		assert!(store.exists(&tmp).await.unwrap());
+		// Expired files matching the prefix are removed, with their metadata
+		let rules = vec![BucketLifecycleRule {
+			expire: Duration::ZERO,
+			prefix: Some("/tmp".to_string()),
+		}];
+		assert_eq!(apply_rules(&store, &rules, Utc::now()).await.unwrap(), 1);
+		assert!(!store.exists(&tmp).await.unwrap());
+		assert_eq!(FileMetadata::read(&store, &tmp).await.unwrap(), None);
+		assert!(store.exists(&keep).await.unwrap());
+		assert!(FileMetadata::read(&store, &keep).await.unwrap().is_some());
+	}
+}
@@ -0,0 +1,305 @@
+//! Metadata stored alongside the files in a bucket.
+//!
+//! Object stores only track the size and modification time of an object, so
+//! the content type, checksum and any user-defined metadata of a file are
+//! stored in a hidden sidecar object under [`METADATA_PREFIX`]. The sidecar
+//! is optional, and a file without one simply has no metadata.
+//!
+//! Object stores can not write the file and its sidecar atomically, so the
+//! sidecar is first written without a checksum, which marks the write as
+//! pending, and is only given its checksum once the file has been written.
+//! A write which fails part way through therefore leaves a file which is not
+//! verified, rather than a file which can never be read again.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::store::{ByteStream, METADATA_PREFIX, MULTIPART_PREFIX, ObjectKey, ObjectStore};
+use crate::err::Error;
+use crate::val::{Object, Value};
+
+/// The metadata of a file in a bucket.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FileMetadata {
+	/// The media type of the file contents
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub content_type: Option<String>,
+	/// The hex encoded SHA-256 checksum of the file contents
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub checksum: Option<String>,
+	/// Custom user-defined metadata
+	#[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+	pub metadata: BTreeMap<String, String>,
+}
+
+impl TryFrom<Object> for FileMetadata {
+	type Error = Error;
+	fn try_from(mut obj: Object) -> Result<Self, Self::Error> {
+		let mut meta = FileMetadata::default();
+
+		if let Some(content_type) = obj.remove("content_type") {
+			meta.content_type = content_type.coerce_to::<Option<String>>()?;
+		}
+
+		if let Some(metadata) = obj.remove("metadata") {
+			meta.metadata = metadata.coerce_to::<BTreeMap<String, String>>()?;
+		}
+
+		Ok(meta)
+	}
+}
+
+impl FileMetadata {
+	/// Returns the key of the sidecar object holding the metadata of a file.
+	fn key(key: &ObjectKey) -> ObjectKey {
+		ObjectKey::new(format!("{METADATA_PREFIX}{key}"))
+	}
+
+	/// Returns the key under which the metadata of a multipart upload is held
+	/// until the upload is completed or aborted.
+	pub(crate) fn upload(upload: &str) -> ObjectKey {
+		ObjectKey::new(format!("{MULTIPART_PREFIX}/{upload}"))
+	}
+
+	/// Computes the checksum of the contents of a file.
+	pub(crate) fn checksum(data: &[u8]) -> String {
+		hex::encode(Sha256::digest(data))
+	}
+
+	/// Sets the checksum of this metadata to match the contents of a file.
+	pub(crate) fn with_checksum(mut self, data: &[u8]) -> Self {
+		self.checksum = Some(Self::checksum(data));
+		self
+	}
+
+	/// Checks that the contents of a file match the stored checksum, if any.
+	pub(crate) fn verify(&self, data: &[u8]) -> bool {
+		match &self.checksum {
+			Some(checksum) => *checksum == Self::checksum(data),
+			None => true,
+		}
+	}
+
+	/// Wraps a stream of the full contents of a file, so that the stream ends
+	/// with an error if the contents do not match the stored checksum.
+	pub(crate) fn verify_stream(&self, key: &ObjectKey, stream: ByteStream) -> ByteStream {
+		let Some(expected) = self.checksum.clone() else {
+			return stream;
+		};
+		let key = key.to_string();
+		let hasher = Arc::new(Mutex::new(Some(Sha256::new())));
+		let check = hasher.clone();
+		let data = stream.inspect_ok(move |chunk| {
+			if let Some(hasher) = hasher.lock().expect("lock should not be poisoned").as_mut() {
+				hasher.update(chunk);
+			}
+		});
+		let end = futures::stream::once(async move {
+			let hasher = check.lock().expect("lock should not be poisoned").take();
+			match hasher.map(|h| hex::encode(h.finalize())) {
+				Some(actual) if actual != expected => {
+					Err(format!("The contents of file `{key}` do not match its checksum"))
+				}
+				_ => Ok(Bytes::new()),
+			}
+		})
+		.try_filter(|chunk| futures::future::ready(!chunk.is_empty()));
+		Box::pin(data.chain(end))
+	}
+
+	/// Wraps a stream which is being stored, returning a handle from which the
+	/// checksum of the streamed contents can be taken once it has been consumed.
+	pub(crate) fn hash_stream(stream: ByteStream) -> (ByteStream, StreamChecksum) {
+		let hasher = Arc::new(Mutex::new(Sha256::new()));
+		let handle = StreamChecksum(hasher.clone());
+		let stream = stream.inspect_ok(move |chunk| {
+			hasher.lock().expect("lock should not be poisoned").update(chunk);
+		});
+		(Box::pin(stream), handle)
+	}
+
+	/// Reads the metadata of a file, if any is stored.
+	pub(crate) async fn read<S: ObjectStore + ?Sized>(
+		store: &S,
+		key: &ObjectKey,
+	) -> Result<Option<Self>, String> {
+		match store.get(&Self::key(key)).await? {
+			Some(data) => serde_json::from_slice(&data)
+				.map(Some)
+				.map_err(|e| format!("Invalid metadata for file `{key}`: {e}")),
+			None => Ok(None),
+		}
+	}
+
+	/// Stores this metadata for a file without its checksum, marking the file
+	/// as pending until [`write`](Self::write) is called once it is stored.
+	pub(crate) async fn write_pending<S: ObjectStore + ?Sized>(
+		&self,
+		store: &S,
+		key: &ObjectKey,
+	) -> Result<(), String> {
+		let pending = Self {
+			checksum: None,
+			..self.clone()
+		};
+		pending.write(store, key).await
+	}
+
+	/// Computes the checksum of a file which is already stored, by streaming
+	/// its contents. Returns `None` if the file does not exist.
+	pub(crate) async fn checksum_stored<S: ObjectStore + ?Sized>(
+		store: &S,
+		key: &ObjectKey,
+	) -> Result<Option<String>, String> {
+		let Some(mut stream) = store.get_stream(key, None).await? else {
+			return Ok(None);
+		};
+		let mut hasher = Sha256::new();
+		while let Some(chunk) = stream.try_next().await? {
+			hasher.update(&chunk);
+		}
+		Ok(Some(hex::encode(hasher.finalize())))
+	}
+
+	/// Stores this metadata for a file, replacing any existing metadata.
+	pub(crate) async fn write<S: ObjectStore + ?Sized>(
+		&self,
+		store: &S,
+		key: &ObjectKey,
+	) -> Result<(), String> {
+		let data = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+		store.put(&Self::key(key), Bytes::from(data)).await
+	}
+
+	/// Removes any metadata stored for a file.
+	pub(crate) async fn remove<S: ObjectStore + ?Sized>(
+		store: &S,
+		key: &ObjectKey,
+	) -> Result<(), String> {
+		store.delete(&Self::key(key)).await
+	}
+
+	/// Copies the metadata of a file to another file, removing any metadata
+	/// stored for the target if the source has none.
+	pub(crate) async fn copy<S: ObjectStore + ?Sized>(
+		store: &S,
+		key: &ObjectKey,
+		target: &ObjectKey,
+	) -> Result<(), String> {
+		match Self::read(store, key).await? {
+			Some(meta) => meta.write(store, target).await,
+			None => Self::remove(store, target).await,
+		}
+	}
+
+	/// Adds the metadata fields to the value describing a file.
+	pub(crate) fn extend_value(self, value: &mut Value) {
+		if let Value::Object(obj) = value {
+			if let Some(v) = self.content_type {
+				obj.insert("content_type".to_string(), Value::from(v));
+			}
+			if let Some(v) = self.checksum {
+				obj.insert("checksum".to_string(), Value::from(v));
+			}
+			let metadata: Object =
+				self.metadata.into_iter().map(|(k, v)| (k, Value::from(v))).collect();
+			obj.insert("metadata".to_string(), Value::from(metadata));
+		}
+	}
+}
+
+/// A handle to the checksum of a stream wrapped with [`FileMetadata::hash_stream`].
+pub(crate) struct StreamChecksum(Arc<Mutex<Sha256>>);
+
+impl StreamChecksum {
+	/// Returns the checksum of the contents which have been streamed so far.
+	pub(crate) fn finish(self) -> String {
+		let hasher = self.0.lock().expect("lock should not be poisoned").clone();
+		hex::encode(hasher.finalize())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use futures::TryStreamExt;
+
+	use super::*;
+	use crate::buc::store::memory::MemoryStore;
+
+	fn stream(chunks: &[&'static [u8]]) -> ByteStream {
+		let chunks: Vec<Result<Bytes, String>> =
+			chunks.iter().map(|c| Ok(Bytes::from_static(c))).collect();
+		Box::pin(futures::stream::iter(chunks))
+	}
+
+	#[tokio::test]
+	async fn test_metadata_sidecar() {
+		let store = MemoryStore::new();
+		let key = ObjectKey::new("/file.txt");
+		let target = ObjectKey::new("/copy.txt");
+		assert_eq!(FileMetadata::read(&store, &key).await.unwrap(), None);
+		let meta = FileMetadata {
+			content_type: Some("text/plain".to_string()),
+			metadata: BTreeMap::from([("owner".to_string(), "admin".to_string())]),
+			..Default::default()
+		}
+		.with_checksum(b"hello");
+		meta.write(&store, &key).await.unwrap();
+		assert_eq!(FileMetadata::read(&store, &key).await.unwrap(), Some(meta.clone()));
+		// Sidecars are stored outside of the file namespace
+		assert!(!store.exists(&key).await.unwrap());
+		// Metadata is copied between files
+		FileMetadata::copy(&store, &key, &target).await.unwrap();
+		assert_eq!(FileMetadata::read(&store, &target).await.unwrap(), Some(meta));
+		FileMetadata::remove(&store, &key).await.unwrap();
+		assert_eq!(FileMetadata::read(&store, &key).await.unwrap(), None);
+	}
+
+	#[tokio::test]
+	async fn test_pending_metadata() {
+		let store = MemoryStore::new();
+		let key = ObjectKey::new("/file.txt");
+		let meta = FileMetadata {
+			content_type: Some("text/plain".to_string()),
+			..Default::default()
+		}
+		.with_checksum(b"hello");
+		// Pending metadata is not verified against the contents
+		meta.write_pending(&store, &key).await.unwrap();
+		let pending = FileMetadata::read(&store, &key).await.unwrap().unwrap();
+		assert_eq!(pending.content_type, meta.content_type);
+		assert!(pending.verify(b"anything"));
+		// The checksum of a stored file is computed from its contents
+		store.put(&key, Bytes::from_static(b"hello")).await.unwrap();
+		let checksum = FileMetadata::checksum_stored(&store, &key).await.unwrap();
+		assert_eq!(checksum, meta.checksum);
+		let missing = ObjectKey::new("/missing.txt");
+		assert_eq!(FileMetadata::checksum_stored(&store, &missing).await.unwrap(), None);
+	}
+
+	#[tokio::test]
+	async fn test_checksums() {
+		let key = ObjectKey::new("/file.txt");
+		let meta = FileMetadata::default().with_checksum(b"hello world");
+		assert!(meta.verify(b"hello world"));
+		assert!(!meta.verify(b"hello there"));
+		assert!(FileMetadata::default().verify(b"anything"));
+		// Streams are hashed as they are consumed
+		let (hashed, checksum) = FileMetadata::hash_stream(stream(&[b"hello", b" world"]));
+		let _: Vec<Bytes> = hashed.try_collect().await.unwrap();
+		assert_eq!(Some(checksum.finish()), meta.checksum);
+		// Matching streams are passed through unchanged
+		let data: Vec<Bytes> =
+			meta.verify_stream(&key, stream(&[b"hello", b" world"])).try_collect().await.unwrap();
+		assert_eq!(data.concat(), b"hello world");
+		// Corrupted streams end with an error
+		let res: Result<Vec<Bytes>, String> =
+			meta.verify_stream(&key, stream(&[b"hello", b" there"])).try_collect().await;
+		assert!(res.is_err());
+	}
+}
@@ -2,30 +2,60 @@
 //!
 //! This module provides abstractions for object storage backends (buckets) that can be used
 //! to store and retrieve binary data (files). It supports multiple storage backends including
-//! in-memory storage and local filesystem storage.
+//! in-memory storage, local filesystem storage, and S3-compatible object storage.
 //!
 //! The module is organized into:
 //! - `BucketController` - Controls bucket operations with permission checking
 //! - `BucketsManager` - Manages bucket connections and caching
 //! - [`store`] - Object store trait and implementations
 
+use std::ops::Range;
 use std::sync::Arc;
 
 mod controller;
 use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
 pub(crate) use controller::BucketController;
 pub use controller::BucketOperation;
 
 use crate::CommunityComposer;
-use crate::buc::store::ObjectStore;
+use crate::buc::store::{ByteStream, ObjectStore};
 #[cfg(not(target_arch = "wasm32"))]
 use crate::buc::store::file::FileStore;
 use crate::buc::store::memory::MemoryStore;
+#[cfg(all(feature = "s3", not(target_arch = "wasm32")))]
+use crate::buc::store::s3::S3Store;
+use crate::ctx::Context;
 use crate::err::Error;
 
+pub(crate) mod lifecycle;
 pub(crate) mod manager;
+pub(crate) mod metadata;
+pub mod presign;
 pub mod store;
 
+/// A file which is streamed from a bucket.
+pub struct FileStream {
+	/// The size of the whole file in bytes
+	pub size: u64,
+	/// Last modification timestamp
+	pub updated: DateTime<Utc>,
+	/// The media type of the file, if known
+	pub content_type: Option<String>,
+	/// The data which is streamed from the file
+	pub content: FileContent,
+}
+
+/// The data which is streamed from a file in a bucket.
+pub enum FileContent {
+	/// The whole file
+	Full(ByteStream),
+	/// A byte range of the file, with an exclusive end
+	Partial(Range<u64>, ByteStream),
+	/// The requested byte range could not be satisfied
+	Unsatisfiable,
+}
+
 /// Marker trait for bucket store provider requirements.
 ///
 /// This trait defines platform-specific requirements for bucket store providers.
@@ -45,16 +75,17 @@ pub trait BucketStoreProviderRequirements: Send + Sync + 'static {}
 /// Trait for creating connections to bucket storage backends.
 ///
 /// Implementors of this trait can parse storage URLs and create appropriate
-/// [`ObjectStore`] instances. The community edition supports `memory://` and
-/// `file://` backends, while enterprise editions may support additional backends
-/// like S3, GCS, or Azure Blob Storage.
+/// [`ObjectStore`] instances. The community edition supports `memory://`,
+/// `file://`, and (with the `s3` feature) `s3://` backends, while enterprise
+/// editions may support additional backends like GCS or Azure Blob Storage.
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
 pub trait BucketStoreProvider: BucketStoreProviderRequirements {
 	/// Connect to a bucket storage backend.
 	///
 	/// # Arguments
-	/// * `url` - The storage backend URL (e.g., `memory://`, `file:///path/to/dir`)
+	/// * `url` - The storage backend URL (e.g., `memory://`, `file:///path/to/dir`,
+	///   `s3://bucket/prefix?endpoint=http://localhost:9000`)
 	/// * `global` - Whether this is a global bucket connection
 	/// * `readonly` - Whether the bucket should be opened in read-only mode
 	///
@@ -88,6 +119,24 @@ impl BucketStoreProvider for CommunityComposer {
 			return Ok(Arc::new(FileStore::new(opts)));
 		}
 
+		#[cfg(all(feature = "s3", not(target_arch = "wasm32")))]
+		if let Some(opts) = S3Store::parse_url(url)? {
+			return Ok(Arc::new(S3Store::new(opts)?));
+		}
+
 		bail!(Error::UnsupportedBackend)
 	}
 }
+
+/// Checks that the network endpoint of a bucket backend, if it has one, is
+/// allowed by the network capabilities of the datastore.
+pub(crate) async fn check_backend_net(ctx: &Context, url: &str) -> Result<()> {
+	#[cfg(all(feature = "s3", not(target_arch = "wasm32")))]
+	if let Some(opts) = S3Store::parse_url(url)? {
+		let endpoint = S3Store::new(opts)?.endpoint()?;
+		ctx.check_allowed_net(&endpoint).await?;
+	}
+	#[cfg(not(all(feature = "s3", not(target_arch = "wasm32"))))]
+	let _ = (ctx, url);
+	Ok(())
+}
@@ -0,0 +1,128 @@
+//! Presigned URLs for bucket files.
+//!
+//! A presigned URL grants time-limited read access to a single file in a
+//! bucket, without requiring the holder of the URL to authenticate. The URL
+//! carries an expiry timestamp and an HMAC-SHA256 signature over the location
+//! of the file and the expiry, created with [`FILE_PRESIGN_SECRET`].
+
+use std::time::Duration;
+
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use url::Url;
+
+use crate::cnf::FILE_PRESIGN_SECRET;
+
+/// The path under which presigned files are served
+pub const PRESIGN_ROUTE: &str = "presigned";
+
+/// The expiry used when none is specified (1 hour)
+pub const DEFAULT_PRESIGN_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+/// The longest expiry which can be requested (7 days)
+pub const MAX_PRESIGN_EXPIRY: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// The location of a file which can be presigned.
+pub struct PresignedFile<'a> {
+	pub ns: &'a str,
+	pub db: &'a str,
+	pub bucket: &'a str,
+	pub key: &'a str,
+}
+
+impl PresignedFile<'_> {
+	/// Computes the signature for this file, expiring at the specified unix timestamp.
+	fn mac(&self, expires: i64) -> Hmac<Sha256> {
+		let mut mac = Hmac::<Sha256>::new_from_slice(&FILE_PRESIGN_SECRET)
+			.expect("HMAC can take a key of any size");
+		// Keys are signed without a leading slash, as they are routed without one
+		let key = self.key.trim_start_matches('/');
+		// Length prefix each part, so that different locations can never collide
+		for part in [self.ns, self.db, self.bucket, key] {
+			mac.update(&(part.len() as u64).to_be_bytes());
+			mac.update(part.as_bytes());
+		}
+		mac.update(&expires.to_be_bytes());
+		mac
+	}
+
+	/// Creates a signature for this file, expiring at the specified unix timestamp.
+	pub fn sign(&self, expires: i64) -> String {
+		hex::encode(self.mac(expires).finalize().into_bytes())
+	}
+
+	/// Checks that a signature is valid for this file, and has not yet expired.
+	pub fn verify(&self, expires: i64, signature: &str) -> bool {
+		if expires < Utc::now().timestamp() {
+			return false;
+		}
+		let Ok(signature) = hex::decode(signature) else {
+			return false;
+		};
+		self.mac(expires).verify_slice(&signature).is_ok()
+	}
+
+	/// Creates a presigned URL path for this file, which is valid for the specified duration.
+	///
+	/// The returned path is relative to the root of the HTTP server, with each
+	/// segment percent-encoded.
+	pub fn url(&self, expiry: Duration) -> String {
+		let expires = Utc::now().timestamp().saturating_add(expiry.as_secs() as i64);
+		let signature = self.sign(expires);
+		// Build the path using a placeholder base, so that segments are correctly encoded
+		let mut url = Url::parse("http://localhost").expect("base URL should be valid");
+		if let Ok(mut path) = url.path_segments_mut() {
+			path.clear().extend([PRESIGN_ROUTE, self.ns, self.db, self.bucket]);
+			path.extend(self.key.trim_start_matches('/').split('/'));
+		}
+		url.query_pairs_mut()
+			.append_pair("expires", &expires.to_string())
+			.append_pair("signature", &signature);
+		format!("{}?{}", url.path(), url.query().unwrap_or_default())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_presign() {
+		let file = PresignedFile {
+			ns: "test",
+			db: "test",
+			bucket: "assets",
+			key: "/images/cat 1.png",
+		};
+		let expires = Utc::now().timestamp() + 60;
+		let signature = file.sign(expires);
+		assert!(file.verify(expires, &signature));
+		// The signature is bound to the expiry
+		assert!(!file.verify(expires + 1, &signature));
+		// The signature is bound to the file
+		let other = PresignedFile {
+			key: "/images/cat 2.png",
+			..file
+		};
+		assert!(!other.verify(expires, &signature));
+		// Expired signatures are rejected
+		let expired = Utc::now().timestamp() - 1;
+		assert!(!file.verify(expired, &file.sign(expired)));
+		// Invalid signatures are rejected
+		assert!(!file.verify(expires, "not a signature"));
+	}
+
+	#[test]
+	fn test_presign_url() {
+		let file = PresignedFile {
+			ns: "test",
+			db: "test",
+			bucket: "assets",
+			key: "/images/cat 1.png",
+		};
+		let url = file.url(DEFAULT_PRESIGN_EXPIRY);
+		assert!(url.starts_with("/presigned/test/test/assets/images/cat%201.png?expires="));
+		assert!(url.contains("&signature="));
+	}
+}
@@ -1,18 +1,24 @@
 use std::future::Future;
+use std::io::SeekFrom;
+use std::ops::Range;
 use std::path::{Path as OsPath, PathBuf};
 use std::pin::Pin;
 
 use bytes::Bytes;
+use futures::StreamExt;
 use path_clean::PathClean;
 use tokio::fs::File;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
 use url::Url;
 use web_time::SystemTime;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{ByteStream, ListOptions, ObjectKey, ObjectMeta, ObjectStore};
 use crate::cnf::BUCKET_FOLDER_ALLOWLIST;
 use crate::err::Error;
 
+/// The size of the chunks in which files are streamed
+const STREAM_CHUNK_SIZE: u64 = 64 * 1024;
+
 /// Options for configuring the FileStore
 #[derive(Clone, Debug)]
 pub struct FileStoreOptions {
@@ -185,6 +191,36 @@ impl FileStore {
 		Ok(full_path)
 	}
 
+	/// Open a file, positioned at the start of a byte range, returning the
+	/// file and the number of bytes to read. The range is clamped to the
+	/// size of the file, and the whole file is read if no range is specified.
+	///
+	/// Returns `Ok(None)` if the file does not exist.
+	async fn open_range(
+		path: &OsPath,
+		range: Option<Range<u64>>,
+	) -> Result<Option<(File, u64)>, String> {
+		// Check if the file exists
+		if !Self::path_exists(path).await? {
+			return Ok(None);
+		}
+
+		let mut file = File::open(path).await.map_err(|e| format!("Failed to open file: {}", e))?;
+
+		let size =
+			file.metadata().await.map_err(|e| format!("Failed to get metadata: {}", e))?.len();
+
+		let range = range.unwrap_or(0..size);
+		let end = range.end.min(size);
+		let start = range.start.min(end);
+
+		file.seek(SeekFrom::Start(start))
+			.await
+			.map_err(|e| format!("Failed to seek file: {}", e))?;
+
+		Ok(Some((file, end - start)))
+	}
+
 	/// Create parent directories for a path if they don't exist
 	async fn ensure_parent_dirs(path: &OsPath) -> Result<(), String> {
 		if let Some(parent) = path.parent() {
@@ -575,4 +611,86 @@ impl ObjectStore for FileStore {
 			Ok(objects)
 		})
 	}
+
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Range<u64>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+
+			let Some((file, len)) = Self::open_range(&os_path, Some(range)).await? else {
+				return Ok(None);
+			};
+
+			let mut data = Vec::with_capacity(len as usize);
+			file.take(len)
+				.read_to_end(&mut data)
+				.await
+				.map_err(|e| format!("Failed to read file: {}", e))?;
+
+			Ok(Some(Bytes::from(data)))
+		})
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<Range<u64>>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+
+			let Some((file, len)) = Self::open_range(&os_path, range).await? else {
+				return Ok(None);
+			};
+
+			// Read the file in chunks until the range has been consumed
+			let stream =
+				futures::stream::try_unfold((file, len), |(mut file, remaining)| async move {
+					if remaining == 0 {
+						return Ok(None);
+					}
+					let mut buf = vec![0; remaining.min(STREAM_CHUNK_SIZE) as usize];
+					let read = file
+						.read(&mut buf)
+						.await
+						.map_err(|e| format!("Failed to read file: {}", e))?;
+					if read == 0 {
+						return Ok(None);
+					}
+					buf.truncate(read);
+					Ok(Some((Bytes::from(buf), (file, remaining - read as u64))))
+				});
+
+			Ok(Some(Box::pin(stream) as ByteStream))
+		})
+	}
+
+	fn put_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		mut stream: ByteStream,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			let os_path = self.to_os_path(key).await?;
+			Self::ensure_parent_dirs(&os_path).await?;
+
+			let mut file = File::create(&os_path)
+				.await
+				.map_err(|e| format!("Failed to create file: {}", e))?;
+
+			// Write each chunk as it is received, rather than buffering the whole stream
+			while let Some(chunk) = stream.next().await {
+				file.write_all(&chunk?)
+					.await
+					.map_err(|e| format!("Failed to write to file: {}", e))?;
+			}
+
+			file.flush().await.map_err(|e| format!("Failed to flush file: {}", e))?;
+
+			Ok(())
+		})
+	}
 }
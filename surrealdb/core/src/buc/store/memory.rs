@@ -4,13 +4,14 @@
 //! useful for testing and development environments where persistence is not required.
 
 use std::future::Future;
+use std::ops::Range;
 use std::pin::Pin;
 
 use bytes::Bytes;
 use dashmap::DashMap;
 use url::Url;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore, slice_range};
 use crate::val::Datetime;
 
 /// Internal storage entry containing data and metadata.
@@ -276,4 +277,15 @@ impl ObjectStore for MemoryStore {
 			Ok(objects)
 		})
 	}
+
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Range<u64>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let data = self.store.get(key).map(|v| slice_range(&v.bytes, &range));
+			Ok(data)
+		})
+	}
 }
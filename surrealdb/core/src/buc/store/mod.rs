@@ -5,13 +5,18 @@
 //! - [`ObjectKey`] - Normalized path representation for object keys
 //! - [`ObjectMeta`] - Metadata about stored objects
 //! - [`ListOptions`] - Options for listing objects in a bucket
+//! - [`ByteStream`] - A stream of chunks of an object's data
+//! - [`ByteRange`] - A byte range requested from an object
 
 use std::future::Future;
+use std::ops::Range;
 use std::pin::Pin;
 use std::sync::Arc;
 
-use bytes::Bytes;
+use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
+use uuid::Uuid;
 
 use crate::err::Error;
 use crate::val::{Datetime, File, Object, Value};
@@ -21,10 +26,43 @@ pub(crate) mod file;
 pub(crate) mod memory;
 pub(crate) mod path;
 pub(crate) mod prefixed;
+#[cfg(all(feature = "s3", not(target_arch = "wasm32")))]
+pub(crate) mod s3;
 
 // Expose type for external composers
 pub use path::ObjectKey;
 
+/// A stream of chunks of an object's data.
+pub type ByteStream = Pin<Box<dyn Stream<Item = Result<Bytes, String>> + Send + 'static>>;
+
+/// The key under which the default multipart implementation stages uploads.
+pub(crate) const MULTIPART_PREFIX: &str = "/.multipart";
+
+/// The key under which the metadata of files is stored.
+pub(crate) const METADATA_PREFIX: &str = "/.metadata";
+
+/// Checks whether a key is used internally to stage multipart uploads or to
+/// store file metadata, and so should not be visible as a file.
+pub(crate) fn is_internal_key(key: &ObjectKey) -> bool {
+	// Some backends ignore empty and current directory segments, and resolve
+	// parent directory segments, so the key is resolved in the same way
+	let mut segments = Vec::new();
+	for v in key.as_str().split('/') {
+		match v {
+			"" | "." => {}
+			".." => {
+				segments.pop();
+			}
+			v => segments.push(v),
+		}
+	}
+	let first = segments.first().copied();
+	[MULTIPART_PREFIX, METADATA_PREFIX].iter().any(|p| first == Some(&p[1..]))
+}
+
+/// The largest part number which can be used in a multipart upload.
+pub const MAX_MULTIPART_PARTS: u32 = 10_000;
+
 /// Metadata for a stored object.
 ///
 /// Contains information about an object's size, last modification time, and key.
@@ -88,6 +126,107 @@ impl TryFrom<Object> for ListOptions {
 	}
 }
 
+/// A byte range requested from an object.
+///
+/// This mirrors the single-range forms of the HTTP `Range` header, and is
+/// resolved against the size of an object with [`ByteRange::resolve`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ByteRange {
+	/// From an offset to the end of the object
+	From(u64),
+	/// Between two offsets, both inclusive
+	Bounded(u64, u64),
+	/// The specified number of bytes at the end of the object
+	Suffix(u64),
+}
+
+impl ByteRange {
+	/// Parses the value of an HTTP `Range` header.
+	///
+	/// Returns `None` if the header is malformed, uses a unit other than
+	/// bytes, or requests multiple ranges.
+	pub fn parse_header(value: &str) -> Option<Self> {
+		let spec = value.trim().strip_prefix("bytes=")?;
+		if spec.contains(',') {
+			return None;
+		}
+		let (start, end) = spec.split_once('-')?;
+		match (start.trim(), end.trim()) {
+			("", "") => None,
+			("", end) => end.parse().ok().map(Self::Suffix),
+			(start, "") => start.parse().ok().map(Self::From),
+			(start, end) => {
+				let (start, end) = (start.parse().ok()?, end.parse().ok()?);
+				(start <= end).then_some(Self::Bounded(start, end))
+			}
+		}
+	}
+
+	/// Resolves the range against the size of an object, returning the
+	/// offsets to read, with an exclusive end.
+	///
+	/// Returns `None` if the range cannot be satisfied.
+	pub fn resolve(self, size: u64) -> Option<Range<u64>> {
+		let range = match self {
+			Self::From(start) => start..size,
+			Self::Bounded(start, end) => start..end.saturating_add(1).min(size),
+			Self::Suffix(len) => size.saturating_sub(len)..size,
+		};
+		(range.start < range.end).then_some(range)
+	}
+}
+
+/// Returns the part of the data within the specified range, clamping the
+/// range to the length of the data.
+pub(crate) fn slice_range(data: &Bytes, range: &Range<u64>) -> Bytes {
+	let len = data.len() as u64;
+	let end = range.end.min(len);
+	let start = range.start.min(end);
+	data.slice(start as usize..end as usize)
+}
+
+/// Returns the key at which the default multipart implementation stages a
+/// file for the specified upload.
+fn multipart_key(upload: &str, name: &str) -> ObjectKey {
+	ObjectKey::new(format!("{MULTIPART_PREFIX}/{upload}/{name}"))
+}
+
+/// Checks that a multipart upload, staged by the default multipart
+/// implementation, exists and was created for the specified key.
+async fn check_multipart<S: ObjectStore + ?Sized>(
+	store: &S,
+	key: &ObjectKey,
+	upload: &str,
+) -> Result<(), String> {
+	// Upload ids are used in keys, so they must be well-formed
+	if Uuid::parse_str(upload).is_err() {
+		return Err(format!("Invalid multipart upload id: {upload}"));
+	}
+	match store.get(&multipart_key(upload, "upload")).await? {
+		Some(v) if v.as_ref() == key.as_str().as_bytes() => Ok(()),
+		_ => Err(format!("Multipart upload {upload} does not exist for key {key}")),
+	}
+}
+
+/// Checks whether a key staged by the default multipart implementation is
+/// one of the uploaded parts, rather than the upload marker.
+fn is_multipart_part(key: &ObjectKey) -> bool {
+	key.as_str().rsplit('/').next().is_some_and(|name| name.starts_with("part-"))
+}
+
+/// Lists the parts, and the marker, staged for a multipart upload by the
+/// default multipart implementation.
+async fn staged_multipart<S: ObjectStore + ?Sized>(
+	store: &S,
+	upload: &str,
+) -> Result<Vec<ObjectKey>, String> {
+	let opts = ListOptions {
+		prefix: Some(ObjectKey::new(format!("{MULTIPART_PREFIX}/{upload}"))),
+		..Default::default()
+	};
+	Ok(store.list(&opts).await?.into_iter().map(|m| m.key).collect())
+}
+
 /// Trait for object storage backends.
 ///
 /// This trait defines the core operations that all object storage implementations
@@ -175,6 +314,130 @@ pub trait ObjectStore: Send + Sync + 'static {
 		&'a self,
 		prefix: &'a ListOptions,
 	) -> Pin<Box<dyn Future<Output = Result<Vec<ObjectMeta>, String>> + Send + 'a>>;
+
+	/// Retrieves the data within a byte range of the specified key. The end of
+	/// the range is exclusive, and is clamped to the size of the object.
+	///
+	/// Returns `Ok(None)` if the key does not exist. The default
+	/// implementation fetches the whole object, so backends which can read
+	/// part of an object should override it.
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Range<u64>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		Box::pin(async move { Ok(self.get(key).await?.map(|data| slice_range(&data, &range))) })
+	}
+
+	/// Retrieves the data at the specified key as a stream of chunks,
+	/// optionally limited to a byte range.
+	///
+	/// Returns `Ok(None)` if the key does not exist. The default
+	/// implementation yields the whole object as a single chunk.
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<Range<u64>>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let data = match range {
+				Some(range) => self.get_range(key, range).await?,
+				None => self.get(key).await?,
+			};
+			Ok(data.map(|data| Box::pin(futures::stream::once(async { Ok(data) })) as ByteStream))
+		})
+	}
+
+	/// Stores a stream of chunks at the specified key, overwriting any
+	/// existing data.
+	///
+	/// The default implementation buffers the whole stream before storing it.
+	fn put_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		mut stream: ByteStream,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			let mut data = BytesMut::new();
+			while let Some(chunk) = stream.next().await {
+				data.extend_from_slice(&chunk?);
+			}
+			self.put(key, data.freeze()).await
+		})
+	}
+
+	/// Starts a multipart upload to the specified key, returning the id of
+	/// the upload.
+	///
+	/// The default implementation stages the uploaded parts as objects under
+	/// a reserved key in the store, and combines them when the upload is
+	/// completed.
+	fn create_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>> {
+		Box::pin(async move {
+			let upload = Uuid::new_v4().to_string();
+			let marker = Bytes::copy_from_slice(key.as_str().as_bytes());
+			self.put(&multipart_key(&upload, "upload"), marker).await?;
+			Ok(upload)
+		})
+	}
+
+	/// Stores a part of a multipart upload, overwriting any existing data for
+	/// the same part. Parts are combined in the order of their part numbers,
+	/// which range from 1 to [`MAX_MULTIPART_PARTS`].
+	fn put_part<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+		part: u32,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			check_multipart(self, key, upload).await?;
+			self.put(&multipart_key(upload, &format!("part-{part:05}")), data).await
+		})
+	}
+
+	/// Completes a multipart upload, storing the combined parts at the key.
+	fn complete_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			check_multipart(self, key, upload).await?;
+			let staged = staged_multipart(self, upload).await?;
+			let mut data = BytesMut::new();
+			for part in staged.iter().filter(|k| is_multipart_part(k)) {
+				let Some(chunk) = self.get(part).await? else {
+					return Err(format!("Multipart upload {upload} is missing part {part}"));
+				};
+				data.extend_from_slice(&chunk);
+			}
+			self.put(key, data.freeze()).await?;
+			for key in &staged {
+				self.delete(key).await?;
+			}
+			Ok(())
+		})
+	}
+
+	/// Aborts a multipart upload, discarding any uploaded parts.
+	fn abort_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		Box::pin(async move {
+			check_multipart(self, key, upload).await?;
+			for key in staged_multipart(self, upload).await? {
+				self.delete(&key).await?;
+			}
+			Ok(())
+		})
+	}
 }
 
 impl ObjectStore for Arc<dyn ObjectStore> {
@@ -260,4 +523,113 @@ impl ObjectStore for Arc<dyn ObjectStore> {
 	) -> Pin<Box<dyn Future<Output = Result<Vec<ObjectMeta>, String>> + Send + 'a>> {
 		(**self).list(opts)
 	}
+
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Range<u64>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		(**self).get_range(key, range)
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<Range<u64>>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		(**self).get_stream(key, range)
+	}
+
+	fn put_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		stream: ByteStream,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		(**self).put_stream(key, stream)
+	}
+
+	fn create_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>> {
+		(**self).create_multipart(key)
+	}
+
+	fn put_part<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+		part: u32,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		(**self).put_part(key, upload, part, data)
+	}
+
+	fn complete_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		(**self).complete_multipart(key, upload)
+	}
+
+	fn abort_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		(**self).abort_multipart(key, upload)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::buc::store::memory::MemoryStore;
+
+	#[test]
+	fn test_is_internal_key() {
+		assert!(is_internal_key(&ObjectKey::new("/.metadata/data.txt")));
+		assert!(is_internal_key(&ObjectKey::new("//./.multipart/upload/1")));
+		assert!(is_internal_key(&ObjectKey::new("/x/../.metadata/data.txt")));
+		assert!(is_internal_key(&ObjectKey::new("/../../.metadata/data.txt")));
+		assert!(!is_internal_key(&ObjectKey::new("/x/.metadata/data.txt")));
+		assert!(!is_internal_key(&ObjectKey::new("/.metadata/../data.txt")));
+	}
+
+	#[test]
+	fn test_byte_range() {
+		assert_eq!(ByteRange::parse_header("bytes=0-9"), Some(ByteRange::Bounded(0, 9)));
+		assert_eq!(ByteRange::parse_header("bytes=10-"), Some(ByteRange::From(10)));
+		assert_eq!(ByteRange::parse_header("bytes=-5"), Some(ByteRange::Suffix(5)));
+		assert_eq!(ByteRange::parse_header("bytes=9-0"), None);
+		assert_eq!(ByteRange::parse_header("bytes=0-1,4-5"), None);
+		assert_eq!(ByteRange::parse_header("items=0-1"), None);
+		assert_eq!(ByteRange::Bounded(0, 9).resolve(5), Some(0..5));
+		assert_eq!(ByteRange::From(2).resolve(5), Some(2..5));
+		assert_eq!(ByteRange::Suffix(10).resolve(5), Some(0..5));
+		assert_eq!(ByteRange::From(5).resolve(5), None);
+	}
+
+	#[tokio::test]
+	async fn test_default_multipart() {
+		let store = MemoryStore::new();
+		let key = ObjectKey::new("/video.mp4");
+		let upload = store.create_multipart(&key).await.unwrap();
+		// Parts are combined in order of their part numbers
+		store.put_part(&key, &upload, 2, Bytes::from_static(b"world")).await.unwrap();
+		store.put_part(&key, &upload, 1, Bytes::from_static(b"hello ")).await.unwrap();
+		// An upload can only be used for the key it was created for
+		let other = ObjectKey::new("/other.mp4");
+		store.put_part(&other, &upload, 3, Bytes::new()).await.unwrap_err();
+		store.complete_multipart(&key, &upload).await.unwrap();
+		assert_eq!(store.get(&key).await.unwrap(), Some(Bytes::from_static(b"hello world")));
+		assert_eq!(
+			store.get_range(&key, 6..100).await.unwrap(),
+			Some(Bytes::from_static(b"world"))
+		);
+		// The staged parts are removed once the upload is complete
+		assert_eq!(store.list(&ListOptions::default()).await.unwrap().len(), 1);
+		store.complete_multipart(&key, &upload).await.unwrap_err();
+	}
 }
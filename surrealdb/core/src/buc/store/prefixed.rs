@@ -4,11 +4,12 @@
 //! allowing multiple logical buckets to share a single physical storage backend.
 
 use std::future::Future;
+use std::ops::Range;
 use std::pin::Pin;
 
 use bytes::Bytes;
 
-use super::{ListOptions, ObjectKey, ObjectMeta, ObjectStore};
+use super::{ByteStream, ListOptions, ObjectKey, ObjectMeta, ObjectStore};
 
 /// A wrapper that adds a prefix to all keys in an underlying [`ObjectStore`].
 ///
@@ -181,4 +182,75 @@ impl<T: ObjectStore> ObjectStore for PrefixedStore<T> {
 			Ok(mapped_objects)
 		})
 	}
+
+	fn get_range<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Range<u64>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<Bytes>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.get_range(&full_key, range).await })
+	}
+
+	fn get_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		range: Option<Range<u64>>,
+	) -> Pin<Box<dyn Future<Output = Result<Option<ByteStream>, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.get_stream(&full_key, range).await })
+	}
+
+	fn put_stream<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		stream: ByteStream,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.put_stream(&full_key, stream).await })
+	}
+
+	fn create_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+	) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.create_multipart(&full_key).await })
+	}
+
+	fn put_part<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+		part: u32,
+		data: Bytes,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.put_part(&full_key, upload, part, data).await })
+	}
+
+	fn complete_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.complete_multipart(&full_key, upload).await })
+	}
+
+	fn abort_multipart<'a>(
+		&'a self,
+		key: &'a ObjectKey,
+		upload: &'a str,
+	) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+		let full_key = self.prefix.join(key);
+
+		Box::pin(async move { self.store.abort_multipart(&full_key, upload).await })
+	}
 }
@@ -81,6 +81,7 @@ pub fn database_basic() -> DatabaseDefinition {
 		strict: false,
 		comment: None,
 		changefeed: None,
+		history: None,
 	}
 }
 
@@ -96,6 +97,7 @@ pub fn database_with_changefeed() -> DatabaseDefinition {
 			expiry: Duration::from_secs(3600),
 			store_diff: true,
 		}),
+		history: None,
 	}
 }
 
@@ -108,6 +110,7 @@ pub fn database_strict() -> DatabaseDefinition {
 		strict: true,
 		comment: Some("Strict mode database".to_string()),
 		changefeed: None,
+		history: None,
 	}
 }
 
@@ -133,6 +136,8 @@ pub fn table_basic() -> TableDefinition {
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
 		cache_indexes_ts: UuidExt::nil(),
+		history: None,
+		tier: None,
 	}
 }
 
@@ -165,6 +170,8 @@ pub fn table_with_view() -> TableDefinition {
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
 		cache_indexes_ts: UuidExt::nil(),
+		history: None,
+		tier: None,
 	}
 }
 
@@ -189,6 +196,8 @@ pub fn table_schemafull() -> TableDefinition {
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
 		cache_indexes_ts: UuidExt::nil(),
+		history: None,
+		tier: None,
 	}
 }
 
@@ -221,6 +230,8 @@ pub fn table_relation() -> TableDefinition {
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
 		cache_indexes_ts: UuidExt::nil(),
+		history: None,
+		tier: None,
 	}
 }
 
@@ -246,6 +257,8 @@ pub fn table_with_materialized_view() -> TableDefinition {
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
 		cache_indexes_ts: UuidExt::nil(),
+		history: None,
+		tier: None,
 	}
 }
 
@@ -267,6 +280,8 @@ pub fn table_any_type() -> TableDefinition {
 		cache_events_ts: UuidExt::nil(),
 		cache_tables_ts: UuidExt::nil(),
 		cache_indexes_ts: UuidExt::nil(),
+		history: None,
+		tier: None,
 	}
 }
 
@@ -358,6 +373,7 @@ pub fn access_bearer() -> AccessDefinition {
 		token_duration: Some(Duration::from_secs(900)),
 		session_duration: Some(Duration::from_secs(86400)),
 		comment: Some("API access".to_string()),
+		limit: None,
 	}
 }
 
@@ -384,6 +400,7 @@ pub fn access_with_authenticate() -> AccessDefinition {
 		token_duration: Some(Duration::from_secs(3600)),
 		session_duration: None,
 		comment: None,
+		limit: None,
 	}
 }
 
@@ -430,6 +447,7 @@ pub fn access_record() -> AccessDefinition {
 		token_duration: Some(Duration::from_secs(900)),
 		session_duration: Some(Duration::from_secs(86400)),
 		comment: Some("User record access".to_string()),
+		limit: None,
 	}
 }
 
@@ -449,6 +467,7 @@ pub fn access_jwt_jwks() -> AccessDefinition {
 		token_duration: None,
 		session_duration: Some(Duration::from_secs(3600)),
 		comment: Some("External JWT verification via JWKS".to_string()),
+		limit: None,
 	}
 }
 
@@ -476,6 +495,7 @@ pub fn access_bearer_refresh() -> AccessDefinition {
 		token_duration: Some(Duration::from_secs(300)),
 		session_duration: None,
 		comment: None,
+		limit: None,
 	}
 }
 
@@ -586,6 +606,9 @@ pub fn api_basic() -> ApiDefinition {
 			methods: vec![ApiMethod::Get],
 			action: Expr::Literal(Literal::String("SELECT * FROM users".to_string())),
 			config: ApiConfigDefinition::default(),
+			params: None,
+			query: None,
+			body: None,
 		}],
 		fallback: None,
 		config: ApiConfigDefinition::default(),
@@ -604,11 +627,17 @@ pub fn api_with_middleware() -> ApiDefinition {
 				methods: vec![ApiMethod::Get, ApiMethod::Post],
 				action: Expr::Literal(Literal::String("SELECT * FROM orders".to_string())),
 				config: ApiConfigDefinition::default(),
+				params: None,
+				query: None,
+				body: None,
 			},
 			ApiActionDefinition {
 				methods: vec![ApiMethod::Delete],
 				action: Expr::Literal(Literal::String("DELETE FROM orders".to_string())),
 				config: ApiConfigDefinition::default(),
+				params: None,
+				query: None,
+				body: None,
 			},
 		],
 		fallback: Some(Expr::Literal(Literal::String("RETURN 404".to_string()))),
@@ -643,11 +672,17 @@ pub fn api_with_auth_limit() -> ApiDefinition {
 						"$auth.role = 'admin'".to_string(),
 					))),
 				},
+				params: None,
+				query: None,
+				body: None,
 			},
 			ApiActionDefinition {
 				methods: vec![ApiMethod::Delete, ApiMethod::Trace],
 				action: Expr::Literal(Literal::String("RETURN { status: 'ok' }".to_string())),
 				config: ApiConfigDefinition::default(),
+				params: None,
+				query: None,
+				body: None,
 			},
 		],
 		fallback: None,
@@ -673,6 +708,7 @@ pub fn bucket_basic() -> BucketDefinition {
 		backend: None,
 		comment: None,
 		permissions: Permission::Full,
+		lifecycle: vec![],
 	}
 }
 
@@ -685,6 +721,7 @@ pub fn bucket_readonly() -> BucketDefinition {
 		backend: Some("s3://bucket/archives".to_string()),
 		comment: Some("Read-only archive storage".to_string()),
 		permissions: Permission::None,
+		lifecycle: vec![],
 	}
 }
 
@@ -729,6 +766,8 @@ pub fn config_graphql_full() -> ConfigDefinition {
 		depth_limit: Some(10),
 		complexity_limit: Some(1000),
 		introspection: GraphQLIntrospectionConfig::None,
+		federation: GraphQLFederationConfig::None,
+		persisted_queries: BTreeMap::new(),
 	})
 }
 
@@ -1125,6 +1164,8 @@ pub fn user_basic() -> UserDefinition {
 		session_duration: None,
 		comment: None,
 		base: Base::Root,
+		totp: None,
+		limit: None,
 	}
 }
 
@@ -1139,6 +1180,8 @@ pub fn user_with_durations() -> UserDefinition {
 		session_duration: Some(Duration::from_secs(86400)),
 		comment: Some("API service account".to_string()),
 		base: Base::Ns,
+		totp: None,
+		limit: None,
 	}
 }
 
@@ -1153,6 +1196,8 @@ pub fn user_db_base() -> UserDefinition {
 		session_duration: None,
 		comment: Some("Database-level user".to_string()),
 		base: Base::Db,
+		totp: None,
+		limit: None,
 	}
 }
 
@@ -1400,6 +1445,9 @@ pub fn api_action_basic() -> ApiActionDefinition {
 		methods: vec![ApiMethod::Get],
 		action: Expr::Literal(Literal::String("SELECT * FROM users".to_string())),
 		config: ApiConfigDefinition::default(),
+		params: None,
+		query: None,
+		body: None,
 	}
 }
 
@@ -1409,6 +1457,9 @@ pub fn api_action_multi_method() -> ApiActionDefinition {
 		methods: vec![ApiMethod::Get, ApiMethod::Post, ApiMethod::Put],
 		action: Expr::Literal(Literal::String("CREATE users CONTENT $body".to_string())),
 		config: ApiConfigDefinition::default(),
+		params: None,
+		query: None,
+		body: None,
 	}
 }
 
@@ -0,0 +1,26 @@
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::kvs::impl_kv_value_revisioned;
+use crate::val::Value;
+
+/// The progress of a consumer group reading the changefeed of a table.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ConsumerGroupDefinition {
+	/// The name of the consumer group
+	pub name: String,
+	/// The versionstamp of the last change set acknowledged by the group
+	pub offset: u64,
+}
+
+impl_kv_value_revisioned!(ConsumerGroupDefinition);
+
+impl InfoStructure for ConsumerGroupDefinition {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"name".to_string() => self.name.into(),
+			"offset".to_string() => Value::from(self.offset),
+		})
+	}
+}
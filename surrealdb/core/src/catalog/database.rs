@@ -1,4 +1,5 @@
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 
 use revision::{DeserializeRevisioned, Revisioned, SerializeRevisioned, revisioned};
 use serde::{Deserialize, Serialize};
@@ -67,7 +68,7 @@ impl From<u32> for DatabaseId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct DatabaseDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -76,6 +77,8 @@ pub struct DatabaseDefinition {
 	pub(crate) comment: Option<String>,
 	pub(crate) changefeed: Option<ChangeFeed>,
 	pub(crate) strict: bool,
+	#[revision(start = 2)]
+	pub(crate) history: Option<Duration>,
 }
 impl_kv_value_revisioned!(DatabaseDefinition);
 
@@ -89,6 +92,7 @@ impl DatabaseDefinition {
 				.map(|v| Expr::Literal(Literal::String(v)))
 				.unwrap_or(Expr::Literal(Literal::None)),
 			changefeed: self.changefeed.map(|v| v.into()),
+			history: self.history.map(|v| v.into()),
 			..Default::default()
 		}
 	}
@@ -105,6 +109,7 @@ impl InfoStructure for DatabaseDefinition {
 		Value::from(map! {
 			"name".to_string() => self.name.into(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
+			"history".to_string(), if let Some(v) = self.history => crate::val::Duration(v).into(),
 			"id".to_string() => self.database_id.0.into(),
 		})
 	}
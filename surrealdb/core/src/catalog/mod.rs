@@ -9,6 +9,7 @@
 mod access;
 pub(crate) mod aggregation;
 mod auth;
+mod consumer;
 mod database;
 mod module;
 mod namespace;
@@ -25,14 +26,15 @@ mod compat;
 mod test;
 
 pub(crate) use access::*;
+pub(crate) use consumer::*;
 pub(crate) use database::*;
 pub(crate) use module::*;
 pub(crate) use namespace::*;
 pub(crate) use record::*;
-pub use schema::ApiMethod;
 pub(crate) use schema::{
 	ApiDefinition, Distance, FullTextParams, HnswParams, Scoring, VectorType, *,
 };
+pub use schema::{ApiMethod, RateLimit};
 pub(crate) use subscription::*;
 pub(crate) use table::*;
 pub(crate) use view::*;
@@ -4,6 +4,7 @@ use std::time::Duration;
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
+use crate::catalog::schema::RateLimit;
 use crate::catalog::schema::base::Base;
 use crate::expr::Expr;
 use crate::expr::statements::info::InfoStructure;
@@ -118,6 +119,15 @@ impl InfoStructure for JwtAccess {
 				JwtAccessVerify::Jwks(v) => Value::from(map!{
 					"url".to_string() => v.url.into(),
 				}),
+				JwtAccessVerify::Oidc(v) => Value::from(map!{
+					"openid".to_string() => v.issuer.into(),
+					"audience".to_string(), if let Some(v) = v.audience => v.into(),
+					"roles".to_string(), if let Some(v) = v.roles => v.into(),
+					"introspect".to_string(), if let Some(v) = v.introspect => Value::from(map!{
+						"client".to_string() => v.client.into(),
+						"secret".to_string() => "[REDACTED]".into(),
+					}),
+				}),
 				JwtAccessVerify::Key(v) => {
 					if v.alg.is_symmetric(){
 						Value::from(map!{
@@ -140,11 +150,13 @@ impl InfoStructure for JwtAccess {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 pub enum JwtAccessVerify {
 	Key(JwtAccessVerifyKey),
 	Jwks(JwtAccessVerifyJwks),
+	#[revision(start = 2)]
+	Oidc(JwtAccessVerifyOidc),
 }
 
 #[revisioned(revision = 1)]
@@ -160,6 +172,30 @@ pub struct JwtAccessVerifyJwks {
 	pub url: String,
 }
 
+/// Verifies tokens issued by an OpenID Connect provider, whose signing keys
+/// and endpoints are found through discovery from the issuer URL.
+#[revisioned(revision = 1)]
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub struct JwtAccessVerifyOidc {
+	/// The issuer URL, which must match the `iss` claim of verified tokens
+	pub issuer: String,
+	/// The audience which must be present in the `aud` claim, if any
+	pub audience: Option<String>,
+	/// The path to the claim containing the roles to grant, if any
+	pub roles: Option<String>,
+	/// The client credentials used for token introspection, if enabled
+	pub introspect: Option<JwtAccessIntrospect>,
+}
+
+/// The client credentials used to authenticate against an RFC 7662 token
+/// introspection endpoint.
+#[revisioned(revision = 1)]
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub struct JwtAccessIntrospect {
+	pub client: String,
+	pub secret: String,
+}
+
 #[revisioned(revision = 1)]
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 pub struct JwtAccessIssue {
@@ -218,7 +254,7 @@ impl ToSql for Algorithm {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct AccessDefinition {
 	pub(crate) name: String,
@@ -229,6 +265,9 @@ pub struct AccessDefinition {
 	pub(crate) token_duration: Option<Duration>,
 	pub(crate) session_duration: Option<Duration>,
 	pub(crate) comment: Option<String>,
+	/// The request limits which apply to sessions authenticated with this access method
+	#[revision(start = 2)]
+	pub(crate) limit: Option<RateLimit>,
 }
 impl_kv_value_revisioned!(AccessDefinition);
 
@@ -274,6 +313,7 @@ impl AccessDefinition {
 				.map(|c| sql::Expr::Literal(sql::Literal::String(c)))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
 			base: sql::Base::from(crate::expr::Base::from(self.base.clone())),
+			limit: self.limit.clone().map(Into::into),
 		}
 	}
 }
@@ -318,6 +358,13 @@ impl JwtAccessVerify {
 				Self::Key(k)
 			}
 			Self::Jwks(j) => Self::Jwks(j),
+			Self::Oidc(mut o) => {
+				// Redact client secrets
+				if let Some(i) = o.introspect.as_mut() {
+					i.secret = "[REDACTED]".to_string();
+				}
+				Self::Oidc(o)
+			}
 		}
 	}
 }
@@ -353,6 +400,7 @@ impl InfoStructure for AccessDefinition {
 				"token".to_string(), if self.access_type.can_issue_tokens() => self.token_duration.map(Value::from).unwrap_or(Value::None),
 			}),
 			"kind".to_string() => self.access_type.structure(),
+			"limit".to_string(), if let Some(v) = self.limit => v.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
@@ -375,16 +423,6 @@ impl From<AccessType> for crate::expr::AccessType {
 	}
 }
 
-impl From<crate::expr::AccessType> for AccessType {
-	fn from(v: crate::expr::AccessType) -> Self {
-		match v {
-			crate::expr::AccessType::Record(v) => AccessType::Record(v.into()),
-			crate::expr::AccessType::Jwt(v) => AccessType::Jwt(v.into()),
-			crate::expr::AccessType::Bearer(v) => AccessType::Bearer(v.into()),
-		}
-	}
-}
-
 impl From<RecordAccess> for crate::expr::RecordAccess {
 	fn from(v: RecordAccess) -> Self {
 		Self {
@@ -396,17 +434,6 @@ impl From<RecordAccess> for crate::expr::RecordAccess {
 	}
 }
 
-impl From<crate::expr::RecordAccess> for RecordAccess {
-	fn from(v: crate::expr::RecordAccess) -> Self {
-		Self {
-			signup: v.signup,
-			signin: v.signin,
-			jwt: v.jwt.into(),
-			bearer: v.bearer.map(|b| b.into()),
-		}
-	}
-}
-
 impl From<JwtAccess> for crate::expr::JwtAccess {
 	fn from(v: JwtAccess) -> Self {
 		Self {
@@ -416,29 +443,12 @@ impl From<JwtAccess> for crate::expr::JwtAccess {
 	}
 }
 
-impl From<crate::expr::JwtAccess> for JwtAccess {
-	fn from(v: crate::expr::JwtAccess) -> Self {
-		Self {
-			verify: v.verify.into(),
-			issue: v.issue.map(|i| i.into()),
-		}
-	}
-}
-
 impl From<JwtAccessVerify> for crate::expr::access_type::JwtAccessVerify {
 	fn from(v: JwtAccessVerify) -> Self {
 		match v {
 			JwtAccessVerify::Key(k) => Self::Key(k.into()),
 			JwtAccessVerify::Jwks(j) => Self::Jwks(j.into()),
-		}
-	}
-}
-
-impl From<crate::expr::access_type::JwtAccessVerify> for JwtAccessVerify {
-	fn from(v: crate::expr::access_type::JwtAccessVerify) -> Self {
-		match v {
-			crate::expr::access_type::JwtAccessVerify::Key(k) => JwtAccessVerify::Key(k.into()),
-			crate::expr::access_type::JwtAccessVerify::Jwks(j) => JwtAccessVerify::Jwks(j.into()),
+			JwtAccessVerify::Oidc(o) => Self::Oidc(o.into()),
 		}
 	}
 }
@@ -452,18 +462,6 @@ impl From<JwtAccessVerifyKey> for crate::expr::access_type::JwtAccessVerifyKey {
 	}
 }
 
-impl From<crate::expr::access_type::JwtAccessVerifyKey> for JwtAccessVerifyKey {
-	fn from(v: crate::expr::access_type::JwtAccessVerifyKey) -> Self {
-		Self {
-			alg: v.alg.into(),
-			key: match v.key {
-				crate::expr::Expr::Literal(crate::expr::Literal::String(s)) => s,
-				_ => v.key.to_sql(),
-			},
-		}
-	}
-}
-
 impl From<JwtAccessVerifyJwks> for crate::expr::access_type::JwtAccessVerifyJwks {
 	fn from(v: JwtAccessVerifyJwks) -> Self {
 		Self {
@@ -472,13 +470,17 @@ impl From<JwtAccessVerifyJwks> for crate::expr::access_type::JwtAccessVerifyJwks
 	}
 }
 
-impl From<crate::expr::access_type::JwtAccessVerifyJwks> for JwtAccessVerifyJwks {
-	fn from(v: crate::expr::access_type::JwtAccessVerifyJwks) -> Self {
+impl From<JwtAccessVerifyOidc> for crate::expr::access_type::JwtAccessVerifyOidc {
+	fn from(v: JwtAccessVerifyOidc) -> Self {
+		let lit = |s: String| crate::expr::Expr::Literal(crate::expr::Literal::String(s));
 		Self {
-			url: match v.url {
-				crate::expr::Expr::Literal(crate::expr::Literal::String(s)) => s,
-				_ => v.url.to_sql(),
-			},
+			issuer: lit(v.issuer),
+			audience: v.audience.map(lit),
+			roles: v.roles.map(lit),
+			introspect: v.introspect.map(|i| crate::expr::access_type::JwtAccessIntrospect {
+				client: lit(i.client),
+				secret: lit(i.secret),
+			}),
 		}
 	}
 }
@@ -514,16 +516,6 @@ impl From<BearerAccess> for crate::expr::access_type::BearerAccess {
 	}
 }
 
-impl From<crate::expr::access_type::BearerAccess> for BearerAccess {
-	fn from(v: crate::expr::access_type::BearerAccess) -> Self {
-		Self {
-			kind: v.kind.into(),
-			subject: v.subject.into(),
-			jwt: v.jwt.into(),
-		}
-	}
-}
-
 impl From<BearerAccessType> for crate::expr::access_type::BearerAccessType {
 	fn from(v: BearerAccessType) -> Self {
 		match v {
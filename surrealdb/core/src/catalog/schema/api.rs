@@ -6,7 +6,7 @@ use surrealdb_types::{SqlFormat, SurrealValue, ToSql, write_sql};
 use crate::api::path::Path;
 use crate::catalog::Permission;
 use crate::catalog::auth::AuthLimit;
-use crate::expr::Expr;
+use crate::expr::{Expr, Kind};
 use crate::expr::statements::info::InfoStructure;
 use crate::fmt::Fmt;
 use crate::kvs::impl_kv_value_revisioned;
@@ -150,12 +150,21 @@ impl InfoStructure for ApiMethod {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct ApiActionDefinition {
 	pub methods: Vec<ApiMethod>,
 	pub action: Expr,
 	pub config: ApiConfigDefinition,
+	/// The expected type of the path parameters.
+	#[revision(start = 2)]
+	pub params: Option<Kind>,
+	/// The expected type of the query parameters.
+	#[revision(start = 2)]
+	pub query: Option<Kind>,
+	/// The expected type of the request body.
+	#[revision(start = 2)]
+	pub body: Option<Kind>,
 }
 
 impl_kv_value_revisioned!(ApiActionDefinition);
@@ -166,6 +175,9 @@ impl ApiActionDefinition {
 			methods: self.methods.clone(),
 			action: self.action.clone().into(),
 			config: self.config.to_sql_config(),
+			params: self.params.clone().map(Into::into),
+			query: self.query.clone().map(Into::into),
+			body: self.body.clone().map(Into::into),
 		}
 	}
 }
@@ -176,6 +188,9 @@ impl InfoStructure for ApiActionDefinition {
 			"methods" => Value::from(self.methods.into_iter().map(InfoStructure::structure).collect::<Vec<Value>>()),
 			"action" => Value::from(self.action.to_sql()),
 			"config" => self.config.structure(),
+			"params", if let Some(v) = self.params => v.structure(),
+			"query", if let Some(v) = self.query => v.structure(),
+			"body", if let Some(v) = self.body => v.structure(),
 		))
 	}
 }
@@ -1,6 +1,9 @@
+use std::time::Duration;
+
 use revision::revisioned;
 use serde::{Deserialize, Serialize};
 use surrealdb_types::{SqlFormat, ToSql};
+use url::Url;
 
 use crate::catalog::Permission;
 use crate::expr::statements::info::InfoStructure;
@@ -14,7 +17,63 @@ use crate::val::Value;
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct BucketId(pub u32);
 
+/// The query options of a backend URL which contain secrets
+const SECRET_OPTIONS: &[&str] = &["secret_access_key", "session_token"];
+
+/// Redacts the credentials which are embedded in a backend URL, so that they
+/// are not revealed when the bucket definition is displayed.
+fn redact_backend(backend: &str) -> String {
+	let Ok(mut url) = Url::parse(backend) else {
+		return backend.to_string();
+	};
+	let password = url.password().is_some();
+	let options = url.query_pairs().any(|(k, _)| SECRET_OPTIONS.contains(&k.as_ref()));
+	// Leave URLs without credentials exactly as they were defined
+	if !password && !options {
+		return backend.to_string();
+	}
+	if password {
+		// Only fails for URLs which can not have credentials
+		let _ = url.set_password(Some("REDACTED"));
+	}
+	if options {
+		let pairs = url
+			.query_pairs()
+			.map(|(k, v)| {
+				if SECRET_OPTIONS.contains(&k.as_ref()) {
+					(k.into_owned(), "REDACTED".to_string())
+				} else {
+					(k.into_owned(), v.into_owned())
+				}
+			})
+			.collect::<Vec<_>>();
+		url.query_pairs_mut().clear().extend_pairs(pairs);
+	}
+	url.to_string()
+}
+
+/// A rule which removes files from a bucket once they reach a certain age.
+///
+/// Lifecycle rules are enforced periodically by a background task.
 #[revisioned(revision = 1)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct BucketLifecycleRule {
+	/// The age after which files are removed
+	pub expire: Duration,
+	/// Only apply the rule to files with keys starting with this prefix
+	pub prefix: Option<String>,
+}
+
+impl InfoStructure for BucketLifecycleRule {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"expire".to_string() => Value::from(self.expire),
+			"prefix".to_string(), if let Some(v) = self.prefix => Value::from(v),
+		})
+	}
+}
+
+#[revisioned(revision = 2)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct BucketDefinition {
 	pub(crate) id: Option<BucketId>,
@@ -23,6 +82,8 @@ pub struct BucketDefinition {
 	pub(crate) permissions: Permission,
 	pub(crate) readonly: bool,
 	pub(crate) comment: Option<String>,
+	#[revision(start = 2)]
+	pub(crate) lifecycle: Vec<BucketLifecycleRule>,
 }
 impl_kv_value_revisioned!(BucketDefinition);
 
@@ -31,9 +92,13 @@ impl BucketDefinition {
 		DefineBucketStatement {
 			kind: DefineKind::Default,
 			name: sql::Expr::Idiom(sql::Idiom::field(self.name.clone())),
-			backend: self.backend.clone().map(|v| sql::Expr::Literal(sql::Literal::String(v))),
+			backend: self
+				.backend
+				.as_deref()
+				.map(|v| sql::Expr::Literal(sql::Literal::String(redact_backend(v)))),
 			permissions: self.permissions.clone().into(),
 			readonly: self.readonly,
+			lifecycle: self.lifecycle.iter().cloned().map(Into::into).collect(),
 			comment: self
 				.comment
 				.clone()
@@ -48,8 +113,9 @@ impl InfoStructure for BucketDefinition {
 		Value::from(map! {
 			"name".to_string() => self.name.into(),
 			"permissions".to_string() => self.permissions.structure(),
-			"backend".to_string(), if let Some(backend) = self.backend => Value::String(backend),
+			"backend".to_string(), if let Some(backend) = self.backend => Value::String(redact_backend(&backend)),
 			"readonly".to_string() => self.readonly.into(),
+			"lifecycle".to_string(), if !self.lifecycle.is_empty() => Value::from(self.lifecycle.into_iter().map(InfoStructure::structure).collect::<Vec<Value>>()),
 			"comment".to_string(), if let Some(comment) = self.comment => comment.into(),
 		})
 	}
@@ -60,3 +126,24 @@ impl ToSql for BucketDefinition {
 		self.to_sql_definition().fmt_sql(f, fmt)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_redact_backend() {
+		assert_eq!(redact_backend("memory"), "memory");
+		assert_eq!(redact_backend("file:///data/files"), "file:///data/files");
+		assert_eq!(
+			redact_backend(
+				"s3://media/videos?region=eu-west-2&access_key_id=AKID&secret_access_key=SECRET&session_token=TOKEN"
+			),
+			"s3://media/videos?region=eu-west-2&access_key_id=AKID&secret_access_key=REDACTED&session_token=REDACTED"
+		);
+		assert_eq!(
+			redact_backend("s3://user:pass@media/videos"),
+			"s3://user:REDACTED@media/videos"
+		);
+	}
+}
@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use revision::revisioned;
+use sha2::{Digest, Sha256};
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use crate::catalog::ApiConfigDefinition;
@@ -38,6 +41,22 @@ impl ConfigDefinition {
 		}
 	}
 
+	/// Format the config for `INFO`, which identifies persisted GraphQL queries
+	/// by their hash rather than showing the full query documents.
+	pub fn to_info_sql(&self) -> String {
+		match self {
+			ConfigDefinition::GraphQL(v) if !v.persisted_queries.is_empty() => {
+				let hashes = v.persisted_queries.keys().map(|h| (h.clone(), h.clone())).collect();
+				ConfigDefinition::GraphQL(GraphQLConfig {
+					persisted_queries: hashes,
+					..v.clone()
+				})
+				.to_sql()
+			}
+			_ => self.to_sql(),
+		}
+	}
+
 	pub fn try_as_api(&self) -> Result<&ApiConfigDefinition> {
 		match self {
 			ConfigDefinition::Api(a) => Ok(a),
@@ -76,7 +95,7 @@ impl InfoStructure for ConfigDefinition {
 	}
 }
 
-#[revisioned(revision = 3)]
+#[revisioned(revision = 5)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct GraphQLConfig {
 	pub tables: GraphQLTablesConfig,
@@ -91,6 +110,19 @@ pub struct GraphQLConfig {
 	/// Defaults to `Auto` (introspection enabled).
 	#[revision(start = 3)]
 	pub introspection: GraphQLIntrospectionConfig,
+	/// Controls whether the schema is exposed as an Apollo Federation subgraph.
+	/// Defaults to `None` (federation disabled).
+	#[revision(start = 4)]
+	pub federation: GraphQLFederationConfig,
+	/// The persisted queries which clients are allowed to execute, keyed by the
+	/// hex encoded SHA-256 hash of the query document. Empty means any query is allowed.
+	#[revision(start = 5)]
+	pub persisted_queries: BTreeMap<String, String>,
+}
+
+/// Compute the hash which identifies a persisted GraphQL query document.
+pub(crate) fn persisted_query_hash(query: &str) -> String {
+	hex::encode(Sha256::digest(query.as_bytes()))
 }
 
 impl InfoStructure for GraphQLConfig {
@@ -101,6 +133,8 @@ impl InfoStructure for GraphQLConfig {
 			"depth_limit", if let Some(d) = self.depth_limit => Value::from(d as i64),
 			"complexity_limit", if let Some(c) = self.complexity_limit => Value::from(c as i64),
 			"introspection", if let GraphQLIntrospectionConfig::None = self.introspection => Value::None,
+			"federation", if let GraphQLFederationConfig::Auto = self.federation => Value::String("AUTO".into()),
+			"persisted", if !self.persisted_queries.is_empty() => Value::Array(self.persisted_queries.into_keys().map(Value::from).collect()),
 		))
 	}
 }
@@ -172,6 +206,23 @@ impl InfoStructure for GraphQLIntrospectionConfig {
 	}
 }
 
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum GraphQLFederationConfig {
+	#[default]
+	None,
+	Auto,
+}
+
+impl InfoStructure for GraphQLFederationConfig {
+	fn structure(self) -> Value {
+		match self {
+			GraphQLFederationConfig::None => Value::None,
+			GraphQLFederationConfig::Auto => Value::String("AUTO".into()),
+		}
+	}
+}
+
 #[revisioned(revision = 1)]
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 pub struct DefaultConfig {
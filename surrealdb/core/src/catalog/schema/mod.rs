@@ -14,6 +14,7 @@ mod index;
 mod ml;
 mod module;
 mod param;
+mod rate_limit;
 mod sequence;
 mod user;
 use std::fmt::{Display, Formatter};
@@ -30,6 +31,7 @@ pub use index::*;
 pub use ml::*;
 pub use module::*;
 pub(crate) use param::*;
+pub use rate_limit::*;
 pub use sequence::*;
 pub use user::*;
 
@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use revision::revisioned;
+
+use crate::expr::statements::info::InfoStructure;
+use crate::val::Value;
+
+/// Limits applied to the requests made by a single authenticated identity.
+///
+/// These limits are not enforced by the datastore itself, but by the network
+/// layer which serves the HTTP and RPC endpoints.
+#[revisioned(revision = 1)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RateLimit {
+	/// The maximum number of requests which can be made per second
+	pub rate: Option<u32>,
+	/// The maximum number of requests which can be processed at once
+	pub concurrency: Option<u32>,
+	/// The maximum duration that a set of statements can run for
+	pub timeout: Option<Duration>,
+}
+
+impl RateLimit {
+	/// Returns these limits, falling back to the specified defaults for any
+	/// limit which has not been set.
+	pub fn or(&self, defaults: &RateLimit) -> RateLimit {
+		RateLimit {
+			rate: self.rate.or(defaults.rate),
+			concurrency: self.concurrency.or(defaults.concurrency),
+			timeout: self.timeout.or(defaults.timeout),
+		}
+	}
+}
+
+impl InfoStructure for RateLimit {
+	fn structure(self) -> Value {
+		Value::from(map! {
+			"rate".to_string(), if let Some(v) = self.rate => Value::from(v),
+			"concurrency".to_string(), if let Some(v) = self.concurrency => Value::from(v),
+			"timeout".to_string(), if let Some(v) = self.timeout => Value::from(v),
+		})
+	}
+}
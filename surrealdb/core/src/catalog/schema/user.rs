@@ -3,13 +3,14 @@ use std::time::Duration;
 use revision::revisioned;
 use surrealdb_types::{SqlFormat, ToSql};
 
+use crate::catalog::RateLimit;
 use crate::catalog::base::Base;
 use crate::expr::statements::info::InfoStructure;
 use crate::kvs::impl_kv_value_revisioned;
 use crate::sql;
 use crate::val::{Array, Value};
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct UserDefinition {
 	pub name: String,
@@ -22,6 +23,12 @@ pub struct UserDefinition {
 	pub session_duration: Option<Duration>,
 	pub comment: Option<String>,
 	pub base: Base,
+	/// The base32 encoded TOTP secret which must be used as a second factor when signing in
+	#[revision(start = 2)]
+	pub totp: Option<String>,
+	/// The request limits which apply to sessions authenticated as this user
+	#[revision(start = 3)]
+	pub limit: Option<RateLimit>,
 }
 
 impl UserDefinition {
@@ -53,6 +60,9 @@ impl UserDefinition {
 				.clone()
 				.map(|c| sql::Expr::Literal(sql::Literal::String(c)))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
+			// Redact the shared secret, which can be used to generate one-time passwords
+			totp: self.totp.as_ref().map(|_| "[REDACTED]".to_string()),
+			limit: self.limit.clone().map(Into::into),
 		}
 	}
 }
@@ -73,6 +83,8 @@ impl InfoStructure for UserDefinition {
 				"token".to_string() => self.token_duration.map(Value::from).unwrap_or(Value::None),
 				"session".to_string() => self.token_duration.map(Value::from).unwrap_or(Value::None),
 			}),
+			"totp".to_string(), if self.totp.is_some() => Value::Bool(true),
+			"limit".to_string(), if let Some(v) = self.limit => v.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 		})
 	}
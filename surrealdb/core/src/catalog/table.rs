@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use revision::{DeserializeRevisioned, Revisioned, SerializeRevisioned, revisioned};
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use uuid::Uuid;
@@ -40,7 +42,7 @@ impl DeserializeRevisioned for TableId {
 	}
 }
 
-#[revisioned(revision = 1)]
+#[revisioned(revision = 3)]
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct TableDefinition {
 	pub(crate) namespace_id: NamespaceId,
@@ -63,6 +65,12 @@ pub struct TableDefinition {
 	pub(crate) cache_tables_ts: Uuid,
 	/// The last time that a DEFINE INDEX was added to this table
 	pub(crate) cache_indexes_ts: Uuid,
+	/// How long the history of records in this table is retained
+	#[revision(start = 2)]
+	pub(crate) history: Option<Duration>,
+	/// The storage tier which stores the records of this table
+	#[revision(start = 3)]
+	pub(crate) tier: Option<String>,
 }
 
 impl_kv_value_revisioned!(TableDefinition);
@@ -91,6 +99,8 @@ impl TableDefinition {
 			cache_events_ts: now,
 			cache_tables_ts: now,
 			cache_indexes_ts: now,
+			history: None,
+			tier: None,
 		}
 	}
 
@@ -118,6 +128,8 @@ impl TableDefinition {
 				.map(|v| sql::Expr::Literal(sql::Literal::String(v)))
 				.unwrap_or(sql::Expr::Literal(sql::Literal::None)),
 			table_type: self.table_type.clone().into(),
+			history: self.history.map(|v| v.into()),
+			tier: self.tier.clone(),
 			..Default::default()
 		}
 	}
@@ -138,6 +150,8 @@ impl InfoStructure for TableDefinition {
 			"kind".to_string() => self.table_type.structure(),
 			"view".to_string(), if let Some(v) = self.view => v.structure(),
 			"changefeed".to_string(), if let Some(v) = self.changefeed => v.structure(),
+			"history".to_string(), if let Some(v) = self.history => crate::val::Duration(v).into(),
+			"tier".to_string(), if let Some(v) = self.tier => v.into(),
 			"permissions".to_string() => self.permissions.structure(),
 			"comment".to_string(), if let Some(v) = self.comment => v.into(),
 			"id".to_string() => self.table_id.0.into(),
@@ -36,7 +36,8 @@ use crate::val::{Datetime, TableName, Value};
 		expiry: Duration::from_secs(123),
 		store_diff: false,
 	}),
-}, 25)]
+	history: None,
+}, 26)]
 #[case::table(TableDefinition {
 	namespace_id: NamespaceId(123),
 	database_id: DatabaseId(456),
@@ -64,7 +65,9 @@ use crate::val::{Datetime, TableName, Value};
 	cache_events_ts: Uuid::default(),
 	cache_tables_ts: Uuid::default(),
 	cache_indexes_ts: Uuid::default(),
-}, 149)]
+	history: None,
+	tier: None,
+}, 151)]
 #[case::subscription(SubscriptionDefinition {
 	id: Uuid::default(),
 	node: Uuid::default(),
@@ -101,7 +104,8 @@ use crate::val::{Datetime, TableName, Value};
 	token_duration: Some(Duration::from_secs(123)),
 	session_duration: Some(Duration::from_secs(123)),
 	comment: Some("comment".to_string()),
-}, 61)]
+	limit: None,
+}, 62)]
 #[case::access(AccessGrant {
 	id: "access".to_string(),
 	ac: "access".to_string(),
@@ -128,6 +132,9 @@ use crate::val::{Datetime, TableName, Value};
 			methods: vec![ApiMethod::Get],
 			action: Expr::Literal(Literal::String("action".to_string())),
 			config: ApiConfigDefinition::default(),
+			params: None,
+			query: None,
+			body: None,
 		},
 	],
 	fallback: None,
@@ -142,7 +149,7 @@ use crate::val::{Datetime, TableName, Value};
 	},
 	comment: None,
 	auth_limit: AuthLimit::default(),
-}, 48)]
+}, 51)]
 #[case::bucket(BucketDefinition {
 	id: Some(BucketId(123)),
 	readonly: false,
@@ -150,14 +157,17 @@ use crate::val::{Datetime, TableName, Value};
 	backend: Some("backend".to_string()),
 	comment: Some("comment".to_string()),
 	permissions: Permission::Full,
-}, 32)]
+	lifecycle: vec![],
+}, 33)]
 #[case::config(ConfigDefinition::GraphQL(GraphQLConfig {
 	tables: GraphQLTablesConfig::default(),
 	functions: GraphQLFunctionsConfig::default(),
 	depth_limit: None,
 	complexity_limit: None,
 	introspection: GraphQLIntrospectionConfig::default(),
-}), 11)]
+	federation: GraphQLFederationConfig::default(),
+	persisted_queries: BTreeMap::new(),
+}), 14)]
 #[case::event(EventDefinition {
 	name: "test".to_string(),
 	target_table: TableName::from("test"),
@@ -227,6 +237,10 @@ use crate::val::{Datetime, TableName, Value};
 	start: 123,
 	timeout: Some(Duration::from_secs(123)),
 }, 15)]
+#[case::consumer_group(ConsumerGroupDefinition {
+	name: "group".to_string(),
+	offset: 123,
+}, 8)]
 #[case::version(MajorVersion::from(1), 2)]
 #[case::user(UserDefinition {
 	name: "tobie".to_string(),
@@ -237,7 +251,9 @@ use crate::val::{Datetime, TableName, Value};
 	session_duration: Some(Duration::from_secs(123)),
 	comment: Some("comment".to_string()),
 	base: crate::catalog::schema::base::Base::Root,
-}, 40)]
+	totp: None,
+	limit: None,
+}, 42)]
 #[case::record(Record::new(Value::Bool(true)), 5)]
 fn test_serialize_deserialize<T>(#[case] original: T, #[case] expected_encoded_size: usize)
 where
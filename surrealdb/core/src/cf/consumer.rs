@@ -0,0 +1,136 @@
+use anyhow::{Result, ensure};
+
+use crate::catalog::{ConsumerGroupDefinition, DatabaseId, NamespaceId, TableDefinition};
+use crate::cf::ChangeSet;
+use crate::err::Error;
+use crate::expr::statements::show::ShowSince;
+use crate::key::table::co;
+use crate::kvs::{KVValue, Transaction};
+use crate::val::TableName;
+
+// Returns the versionstamp of the last change set which a consumer group
+// has acknowledged for a table, or 0 if the group has not acknowledged
+// any change sets yet.
+pub async fn offset(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	group: &str,
+) -> Result<u64> {
+	let key = co::new(ns, db, tb, group);
+	match tx.get(&key, None).await? {
+		Some(v) => Ok(v.offset),
+		None => Ok(0),
+	}
+}
+
+// Returns the lowest versionstamp which any consumer group of the tables in
+// a database has acknowledged, or `None` if no consumer group has
+// acknowledged any change sets yet.
+//
+// Changefeed garbage collection keeps the change sets after this
+// versionstamp, so that they can still be delivered to the consumer group.
+pub async fn min_offset(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tbs: &[TableDefinition],
+) -> Result<Option<u64>> {
+	let mut min: Option<u64> = None;
+	for tb in tbs {
+		let beg = co::prefix(ns, db, &tb.name)?;
+		let end = co::suffix(ns, db, &tb.name)?;
+		for (_, v) in tx.getr(beg..end, None).await? {
+			let group = ConsumerGroupDefinition::kv_decode_value(v)?;
+			min = Some(min.map_or(group.offset, |m| m.min(group.offset)));
+		}
+	}
+	Ok(min)
+}
+
+// Registers a consumer group on a table, if it is not registered yet.
+//
+// A registered consumer group starts from the first change set in the
+// changefeed, and changefeed garbage collection keeps the change sets which
+// it has not acknowledged, until the consumer group is removed.
+pub async fn register(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	group: &str,
+) -> Result<()> {
+	let key = co::new(ns, db, tb, group);
+	if !tx.exists(&key, None).await? {
+		let val = ConsumerGroupDefinition {
+			name: group.to_owned(),
+			offset: 0,
+		};
+		tx.set(&key, &val, None).await?;
+	}
+	Ok(())
+}
+
+// Removes a consumer group from a table, so that changefeed garbage
+// collection no longer keeps the change sets which it has not acknowledged.
+pub async fn remove(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	group: &str,
+) -> Result<()> {
+	let key = co::new(ns, db, tb, group);
+	ensure!(
+		tx.exists(&key, None).await?,
+		Error::ConsumerGroupNotFound {
+			name: group.to_owned(),
+		}
+	);
+	tx.del(&key).await
+}
+
+// Acknowledges all change sets of a table up to and including a versionstamp
+// for a consumer group.
+//
+// Offsets only move forward, so acknowledging a change set which is older
+// than the stored offset has no effect. This allows consumers to acknowledge
+// change sets concurrently, or more than once.
+pub async fn commit(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	group: &str,
+	versionstamp: u64,
+) -> Result<()> {
+	let key = co::new(ns, db, tb, group);
+	let current = offset(tx, ns, db, tb, group).await?;
+	if versionstamp > current {
+		let val = ConsumerGroupDefinition {
+			name: group.to_owned(),
+			offset: versionstamp,
+		};
+		tx.set(&key, &val, None).await?;
+	}
+	Ok(())
+}
+
+// Reads the change sets of a table which were written after a versionstamp.
+//
+// Change sets are returned in versionstamp order, so a consumer can pass the
+// versionstamp of the last returned change set to read the next batch.
+pub async fn read(
+	tx: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+	after: u64,
+	limit: u32,
+) -> Result<Vec<ChangeSet>> {
+	let start = after.checked_add(1).ok_or_else(|| Error::Query {
+		message: format!("Invalid versionstamp `{after}`, no changes can follow it"),
+	})?;
+	super::read(tx, ns, db, Some(tb), ShowSince::Versionstamp(start), Some(limit)).await
+}
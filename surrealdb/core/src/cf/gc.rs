@@ -50,7 +50,20 @@ pub async fn gc_all_at(lh: &LeaseHandler, tx: &Transaction) -> Result<()> {
 
 			let ts = tx.timestamp().await?;
 			// Calculate the changefeed watermark cutoff time
-			let watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
+			let mut watermark_ts = ts.sub_checked(cf_expiry).unwrap_or_else(|| ts_impl.earliest());
+			// Keep the change sets which a consumer group has not acknowledged
+			let offset =
+				super::consumer::min_offset(tx, db.namespace_id, db.database_id, tbs.as_ref())
+					.await?;
+			if let Some(offset) = offset {
+				let unacked = offset
+					.checked_add(1)
+					.and_then(|v| ts_impl.create_from_versionstamp(v as u128))
+					.unwrap_or_else(|| ts_impl.earliest());
+				if unacked.as_versionstamp() < watermark_ts.as_versionstamp() {
+					watermark_ts = unacked;
+				}
+			}
 			// Garbage collect all entries older than the watermark
 			gc_range(tx, db.namespace_id, db.database_id, &watermark_ts, &ts_impl).await?;
 			// Possibly renew the lease
@@ -1,3 +1,4 @@
+pub(crate) mod consumer;
 pub(crate) mod gc;
 pub(crate) mod mutations;
 pub(crate) mod reader;
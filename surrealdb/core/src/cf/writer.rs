@@ -351,6 +351,7 @@ mod tests {
 			}),
 			comment: None,
 			strict: false,
+			history: None,
 		};
 		let mut tb_def = TableDefinition::new(
 			namespace_id,
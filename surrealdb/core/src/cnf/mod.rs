@@ -97,6 +97,16 @@ pub static COUNT_BATCH_SIZE: LazyLock<u32> =
 pub static INDEXING_BATCH_SIZE: LazyLock<u32> =
 	lazy_env_parse!("SURREAL_INDEXING_BATCH_SIZE", u32, 250);
 
+/// The maximum number of change sets delivered at once to change data capture
+/// consumers (default: 100)
+pub static CHANGE_STREAM_BATCH_SIZE: LazyLock<u32> =
+	lazy_env_parse!("SURREAL_CHANGE_STREAM_BATCH_SIZE", u32, 100);
+
+/// The interval in milliseconds at which change data capture streams check
+/// the changefeed for new change sets, once they have caught up (default: 100)
+pub static CHANGE_STREAM_POLL_INTERVAL_MS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_CHANGE_STREAM_POLL_INTERVAL_MS", u64, 100);
+
 /// The number of batches each operator buffers ahead of downstream demand.
 /// Set to 0 to disable operator-level pipeline buffering.
 /// (default: 2)
@@ -143,6 +153,11 @@ pub static HTTP_IDLE_TIMEOUT_SECS: LazyLock<u64> =
 pub static HTTP_CONNECT_TIMEOUT_SECS: LazyLock<u64> =
 	lazy_env_parse!("SURREAL_HTTP_CONNECT_TIMEOUT_SECS", u64, 30);
 
+/// The timeout for receiving data from an S3 bucket backend (default: 60
+/// seconds)
+pub static BUCKET_REQUEST_TIMEOUT_SECS: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_BUCKET_REQUEST_TIMEOUT_SECS", u64, 60);
+
 /// Forward all authentication errors to the client. Do not use in production
 /// (default: false)
 pub static INSECURE_FORWARD_ACCESS_ERRORS: LazyLock<bool> =
@@ -190,6 +205,16 @@ pub static GLOBAL_BUCKET: LazyLock<Option<String>> =
 pub static GLOBAL_BUCKET_ENFORCED: LazyLock<bool> =
 	lazy_env_parse!("SURREAL_GLOBAL_BUCKET_ENFORCED", bool, false);
 
+/// The secret used to sign presigned file URLs (default: randomly generated at startup)
+///
+/// This must be set to the same value on every node of a cluster for presigned URLs to be
+/// accepted by any node, and for them to remain valid across restarts.
+pub static FILE_PRESIGN_SECRET: LazyLock<Vec<u8>> = LazyLock::new(|| {
+	std::env::var("SURREAL_FILE_PRESIGN_SECRET")
+		.map(String::into_bytes)
+		.unwrap_or_else(|_| rand::random::<[u8; 32]>().to_vec())
+});
+
 /// Specify the USER-AGENT string used by HTTP requests
 pub static SURREALDB_USER_AGENT: LazyLock<String> =
 	LazyLock::new(|| std::env::var("SURREAL_USER_AGENT").unwrap_or("SurrealDB".to_string()));
@@ -745,6 +745,10 @@ impl Context {
 		if session.redact_volatile_explain_attrs {
 			self.redact_volatile_explain_attrs = true;
 		}
+		// Limit the time that the session queries can run for
+		if let Some(timeout) = session.timeout {
+			self.add_timeout(timeout)?;
+		}
 		if !session.variables.is_empty() {
 			self.attach_variables(session.variables.clone().into())?;
 		}
@@ -368,6 +368,7 @@ pub enum RouteTarget {
 	Health,
 	Export,
 	Import,
+	Checkpoint,
 	Rpc,
 	Version,
 	Sync,
@@ -378,6 +379,7 @@ pub enum RouteTarget {
 	Ml,
 	GraphQL,
 	Api,
+	Files,
 }
 
 // impl display
@@ -387,6 +389,7 @@ impl fmt::Display for RouteTarget {
 			RouteTarget::Health => write!(f, "health"),
 			RouteTarget::Export => write!(f, "export"),
 			RouteTarget::Import => write!(f, "import"),
+			RouteTarget::Checkpoint => write!(f, "checkpoint"),
 			RouteTarget::Rpc => write!(f, "rpc"),
 			RouteTarget::Version => write!(f, "version"),
 			RouteTarget::Sync => write!(f, "sync"),
@@ -397,6 +400,7 @@ impl fmt::Display for RouteTarget {
 			RouteTarget::Ml => write!(f, "ml"),
 			RouteTarget::GraphQL => write!(f, "graphql"),
 			RouteTarget::Api => write!(f, "api"),
+			RouteTarget::Files => write!(f, "files"),
 		}
 	}
 }
@@ -425,6 +429,7 @@ impl std::str::FromStr for RouteTarget {
 			"health" => Ok(RouteTarget::Health),
 			"export" => Ok(RouteTarget::Export),
 			"import" => Ok(RouteTarget::Import),
+			"checkpoint" => Ok(RouteTarget::Checkpoint),
 			"rpc" => Ok(RouteTarget::Rpc),
 			"version" => Ok(RouteTarget::Version),
 			"sync" => Ok(RouteTarget::Sync),
@@ -435,6 +440,7 @@ impl std::str::FromStr for RouteTarget {
 			"ml" => Ok(RouteTarget::Ml),
 			"graphql" => Ok(RouteTarget::GraphQL),
 			"api" => Ok(RouteTarget::Api),
+			"files" => Ok(RouteTarget::Files),
 			_ => Err(ParseRouteTargetError),
 		}
 	}
@@ -1,6 +1,7 @@
 use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::Utc;
 use surrealdb_types::ToSql;
@@ -35,6 +36,8 @@ pub struct Session {
 	pub rd: Option<PublicValue>,
 	/// The current expiration time of the session
 	pub exp: Option<i64>,
+	/// The maximum duration that the queries in this session can run for
+	pub timeout: Option<Duration>,
 	/// The variables set
 	pub variables: PublicVariables,
 	/// Strategy for the new streaming planner/executor.
@@ -194,6 +197,7 @@ impl Session {
 			tk: None,
 			rd: Some(rid),
 			exp: None,
+			timeout: None,
 			variables: Default::default(),
 			new_planner_strategy: NewPlannerStrategy::default(),
 			redact_volatile_explain_attrs: false,
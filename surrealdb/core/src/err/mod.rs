@@ -312,6 +312,12 @@ pub(crate) enum Error {
 		name: TableName,
 	},
 
+	/// The requested storage tier is not configured
+	#[error("The storage tier '{name}' does not exist")]
+	TierNotFound {
+		name: String,
+	},
+
 	/// The requested api does not exist
 	#[error("The api '{value}' does not exist")]
 	ApNotFound {
@@ -748,6 +754,14 @@ pub(crate) enum Error {
 	#[error("The password did not verify")]
 	InvalidPass,
 
+	/// The user requires a one-time password, but none was provided
+	#[error("A one-time password is required to authenticate this user")]
+	MissingTotp,
+
+	/// The shared secret for a one-time password is not valid
+	#[error("Invalid TOTP secret: {0}")]
+	InvalidTotpSecret(String),
+
 	/// There was an error with authentication
 	///
 	/// This error hides different kinds of errors directly related to
@@ -862,6 +876,24 @@ pub(crate) enum Error {
 		name: String,
 	},
 
+	/// The storage tier of an existing table can not be changed
+	#[error("The storage tier of the table '{name}' can not be changed")]
+	TbTierChanged {
+		name: String,
+	},
+
+	/// Change data capture requires a changefeed on the table
+	#[error("The table '{name}' does not have a changefeed")]
+	TbChangefeedMissing {
+		name: TableName,
+	},
+
+	/// The requested consumer group does not exist
+	#[error("The consumer group '{name}' does not exist")]
+	ConsumerGroupNotFound {
+		name: String,
+	},
+
 	/// The requested namespace token already exists
 	#[error("The namespace token '{name}' already exists")]
 	#[allow(dead_code)]
@@ -1043,6 +1075,10 @@ pub(crate) enum Error {
 	#[error("The underlying datastore does not support versioned queries")]
 	UnsupportedVersionedQueries,
 
+	/// The datastore can not remove history older than a retention period
+	#[error("The underlying datastore does not support a history retention period")]
+	UnsupportedHistoryRetention,
+
 	/// There was an invalid storage version stored in the database
 	#[error("There was an invalid storage version stored in the database")]
 	InvalidStorageVersion,
@@ -1167,6 +1203,18 @@ pub(crate) enum Error {
 	#[error("Operation for bucket `{0}` failed: {1}")]
 	ObjectStoreFailure(String, String),
 
+	#[error("The contents of file `{key}` in bucket `{bucket}` do not match its checksum")]
+	FileChecksumMismatch {
+		bucket: String,
+		key: String,
+	},
+
+	#[error("The key `{key}` in bucket `{bucket}` is reserved for internal use")]
+	FileKeyReserved {
+		bucket: String,
+		key: String,
+	},
+
 	/// The `COMPUTED` clause cannot be used with other clauses altering or
 	/// working with the value
 	#[error("Cannot use the `{0}` keyword with `COMPUTED`.")]
@@ -29,6 +29,7 @@ pub fn into_types_error(error: Error) -> TypesError {
 		MissingUserOrPass => TypesError::not_allowed(message, AuthError::MissingUserOrPass),
 		NoSigninTarget => TypesError::not_allowed(message, AuthError::NoSigninTarget),
 		InvalidPass => TypesError::not_allowed(message, AuthError::InvalidPass),
+		MissingTotp => TypesError::not_allowed(message, AuthError::MissingTotp),
 		TokenMakingFailed => TypesError::not_allowed(message, AuthError::TokenMakingFailed),
 		IamError(iam_err) => match iam_err {
 			IamErrorKind::InvalidRole(name) => TypesError::not_allowed(
@@ -160,6 +161,9 @@ pub fn into_types_error(error: Error) -> TypesError {
 				name: name.into_string(),
 			},
 		),
+		TierNotFound {
+			..
+		} => TypesError::not_found(message, None),
 		IdNotFound {
 			rid,
 		} => TypesError::not_found(
@@ -12,6 +12,10 @@ define_pure_function!(CryptoMd5, "crypto::md5", (value: Any) -> String, crate::f
 define_pure_function!(CryptoSha1, "crypto::sha1", (value: Any) -> String, crate::fnc::crypto::sha1);
 define_pure_function!(CryptoSha256, "crypto::sha256", (value: Any) -> String, crate::fnc::crypto::sha256);
 define_pure_function!(CryptoSha512, "crypto::sha512", (value: Any) -> String, crate::fnc::crypto::sha512);
+define_pure_function!(CryptoTotpGenerate, "crypto::totp::generate", (secret: String, ?time: Datetime) -> String, crate::fnc::crypto::totp::generate);
+define_pure_function!(CryptoTotpSecret, "crypto::totp::secret", () -> String, crate::fnc::crypto::totp::secret);
+define_pure_function!(CryptoTotpUri, "crypto::totp::uri", (secret: String, account: String, ?issuer: String) -> String, crate::fnc::crypto::totp::uri);
+define_pure_function!(CryptoTotpVerify, "crypto::totp::verify", (secret: String, code: String, ?time: Datetime) -> Bool, crate::fnc::crypto::totp::verify);
 
 pub fn register(registry: &mut FunctionRegistry) {
 	register_functions!(
@@ -22,5 +26,9 @@ pub fn register(registry: &mut FunctionRegistry) {
 		CryptoSha1,
 		CryptoSha256,
 		CryptoSha512,
+		CryptoTotpGenerate,
+		CryptoTotpSecret,
+		CryptoTotpUri,
+		CryptoTotpVerify,
 	);
 }
@@ -3,20 +3,24 @@
 //! These provide file storage functionality for working with buckets.
 //! Note: File functions require the experimental "files" capability to be enabled.
 
+use std::ops::Range;
 use std::sync::Arc;
 
 use anyhow::{Result, bail, ensure};
 
 use crate::buc::BucketOperation;
-use crate::buc::store::{ListOptions, ObjectKey, ObjectStore};
+use crate::buc::metadata::FileMetadata;
+use crate::buc::presign::PresignedFile;
+use crate::buc::store::{ListOptions, ObjectKey, ObjectStore, is_internal_key};
 use crate::catalog::providers::BucketProvider;
 use crate::catalog::{BucketDefinition, Permission};
 use crate::dbs::capabilities::ExperimentalTarget;
 use crate::err::Error;
 use crate::exec::function::FunctionRegistry;
 use crate::exec::physical_expr::EvalContext;
-use crate::fnc::args::FromArgs;
-use crate::val::{Bytes, File, Object, Value};
+use crate::fnc::args::{FromArgs, Optional};
+use crate::fnc::file::{byte_range, part_number, presign_expiry};
+use crate::val::{Bytes, Duration, File, Object, Value};
 use crate::{define_async_function, define_pure_function, register_functions};
 
 // =========================================================================
@@ -78,12 +82,28 @@ impl<'a> StreamingBucketOps<'a> {
 		Ok(())
 	}
 
-	/// Check permissions for an operation.
+	/// Check permissions for an operation on a file, and its target file for
+	/// copy and rename operations.
 	///
 	/// For Permission::Specific, we currently don't support the full expression
 	/// evaluation in the streaming executor (requires Stk). In that case, we
 	/// fall back to checking based on role only.
-	fn check_permission(&self, op: BucketOperation) -> Result<()> {
+	fn check_permission(
+		&self,
+		op: BucketOperation,
+		key: Option<&ObjectKey>,
+		target: Option<&ObjectKey>,
+	) -> Result<()> {
+		// Multipart uploads and metadata can not be accessed directly
+		for key in key.into_iter().chain(target) {
+			ensure!(
+				!is_internal_key(key),
+				Error::FileKeyReserved {
+					bucket: self.bucket.name.clone(),
+					key: key.to_string(),
+				}
+			);
+		}
 		// Check if we should check permissions (uses Options::check_perms like fnc::file)
 		if self.opt.check_perms(op.into())? {
 			// Guest and Record users are not allowed to list files in buckets
@@ -118,37 +138,80 @@ impl<'a> StreamingBucketOps<'a> {
 		Ok(())
 	}
 
-	/// Put a file into the bucket.
-	async fn put(&self, key: &ObjectKey, value: Value) -> Result<()> {
+	/// Put a file, and its metadata, into the bucket.
+	async fn put(&self, key: &ObjectKey, value: Value, meta: FileMetadata) -> Result<()> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
-		self.check_permission(BucketOperation::Put)?;
+		self.check_permission(BucketOperation::Put, Some(key), None)?;
 
+		let meta = meta.with_checksum(&payload);
+		meta.write_pending(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 		self.store
 			.put(key, payload)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		meta.write(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
 
-	/// Put a file into the bucket if it doesn't exist.
-	async fn put_if_not_exists(&self, key: &ObjectKey, value: Value) -> Result<()> {
+	/// Put a file, and its metadata, into the bucket if it doesn't exist.
+	async fn put_if_not_exists(
+		&self,
+		key: &ObjectKey,
+		value: Value,
+		meta: FileMetadata,
+	) -> Result<()> {
 		let payload = accept_payload(value)?;
 		self.require_writeable()?;
-		self.check_permission(BucketOperation::Put)?;
+		self.check_permission(BucketOperation::Put, Some(key), None)?;
+
+		// Leave the metadata of an existing file untouched
+		if self.exists_raw(key).await? {
+			return Ok(());
+		}
 
+		let meta = meta.with_checksum(&payload);
+		meta.write_pending(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 		self.store
 			.put_if_not_exists(key, payload)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		meta.write(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
 
+	/// Get the metadata stored alongside a file in the bucket.
+	async fn metadata(&self, key: &ObjectKey) -> Result<Option<FileMetadata>> {
+		self.check_permission(BucketOperation::Head, Some(key), None)?;
+
+		FileMetadata::read(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))
+			.map_err(anyhow::Error::new)
+	}
+
+	/// Check whether a file exists, without checking permissions.
+	async fn exists_raw(&self, key: &ObjectKey) -> Result<bool> {
+		self.store
+			.exists(key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))
+			.map_err(anyhow::Error::new)
+	}
+
 	/// Get a file from the bucket.
 	async fn get(&self, key: &ObjectKey) -> Result<Option<Bytes>> {
-		self.check_permission(BucketOperation::Get)?;
+		self.check_permission(BucketOperation::Get, Some(key), None)?;
 
 		let bytes = match self
 			.store
@@ -160,31 +223,169 @@ impl<'a> StreamingBucketOps<'a> {
 			None => return Ok(None),
 		};
 
+		// Check the contents against the stored checksum
+		let meta = FileMetadata::read(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		if let Some(meta) = meta {
+			ensure!(
+				meta.verify(&bytes),
+				Error::FileChecksumMismatch {
+					bucket: self.bucket.name.clone(),
+					key: key.to_string(),
+				}
+			);
+		}
+
 		Ok(Some(Bytes(bytes)))
 	}
 
+	/// Get a byte range of a file from the bucket.
+	async fn get_range(&self, key: &ObjectKey, range: Range<u64>) -> Result<Option<Bytes>> {
+		self.check_permission(BucketOperation::Get, Some(key), None)?;
+
+		let bytes = self
+			.store
+			.get_range(key, range)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(bytes.map(Bytes))
+	}
+
+	/// Start a multipart upload to a file in the bucket, holding its metadata
+	/// until the upload is completed.
+	async fn create_multipart(&self, key: &ObjectKey, meta: FileMetadata) -> Result<String> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None)?;
+
+		let upload = self
+			.store
+			.create_multipart(key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		meta.write(self.store.as_ref(), &FileMetadata::upload(&upload))
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(upload)
+	}
+
+	/// Upload a part of a multipart upload.
+	async fn put_part(&self, key: &ObjectKey, upload: &str, part: u32, value: Value) -> Result<()> {
+		let payload = accept_payload(value)?;
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None)?;
+
+		self.store
+			.put_part(key, upload, part, payload)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
+	/// Complete a multipart upload.
+	async fn complete_multipart(&self, key: &ObjectKey, upload: &str) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None)?;
+
+		let store = self.store.as_ref();
+		let pending = FileMetadata::upload(upload);
+		let meta = FileMetadata::read(store, &pending)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?
+			.unwrap_or_else(FileMetadata::default);
+		meta.write_pending(store, key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		store
+			.complete_multipart(key, upload)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		// The parts were combined by the store, so the checksum is taken from the file
+		let meta = FileMetadata {
+			checksum: FileMetadata::checksum_stored(store, key)
+				.await
+				.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?,
+			..meta
+		};
+		meta.write(store, key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(store, &pending)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
+	/// Abort a multipart upload.
+	async fn abort_multipart(&self, key: &ObjectKey, upload: &str) -> Result<()> {
+		self.require_writeable()?;
+		self.check_permission(BucketOperation::Put, Some(key), None)?;
+
+		self.store
+			.abort_multipart(key, upload)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), &FileMetadata::upload(upload))
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+
+		Ok(())
+	}
+
 	/// Get file metadata from the bucket.
 	async fn head(&self, key: &ObjectKey) -> Result<Option<Value>> {
-		self.check_permission(BucketOperation::Head)?;
+		self.check_permission(BucketOperation::Head, Some(key), None)?;
 
-		let meta = self
+		let Some(meta) = self
 			.store
 			.head(key)
 			.await
-			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?
+		else {
+			return Ok(None);
+		};
+
+		let mut res = meta.into_value(self.bucket.name.clone());
+		FileMetadata::read(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?
+			.unwrap_or_default()
+			.extend_value(&mut res);
+
+		Ok(Some(res))
+	}
+
+	/// Create a presigned URL path for a file in the bucket.
+	fn presign(&self, key: &ObjectKey, expiry: std::time::Duration) -> Result<String> {
+		self.check_permission(BucketOperation::Get, Some(key), None)?;
+
+		let (ns, db) = self.opt.ns_db()?;
+		let file = PresignedFile {
+			ns,
+			db,
+			bucket: &self.bucket.name,
+			key: key.as_str(),
+		};
 
-		Ok(meta.map(|m| m.into_value(self.bucket.name.clone())))
+		Ok(file.url(expiry))
 	}
 
 	/// Delete a file from the bucket.
 	async fn delete(&self, key: &ObjectKey) -> Result<()> {
 		self.require_writeable()?;
-		self.check_permission(BucketOperation::Delete)?;
+		self.check_permission(BucketOperation::Delete, Some(key), None)?;
 
 		self.store
 			.delete(key)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), key)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -192,12 +393,15 @@ impl<'a> StreamingBucketOps<'a> {
 	/// Copy a file within the bucket.
 	async fn copy(&self, src: &ObjectKey, dst: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
-		self.check_permission(BucketOperation::Copy)?;
+		self.check_permission(BucketOperation::Copy, Some(src), Some(&dst))?;
 
 		self.store
 			.copy(src, &dst)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), src, &dst)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -205,12 +409,20 @@ impl<'a> StreamingBucketOps<'a> {
 	/// Copy a file if destination doesn't exist.
 	async fn copy_if_not_exists(&self, src: &ObjectKey, dst: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
-		self.check_permission(BucketOperation::Copy)?;
+		self.check_permission(BucketOperation::Copy, Some(src), Some(&dst))?;
+
+		// Leave the metadata of an existing file untouched
+		if self.exists_raw(&dst).await? {
+			return Ok(());
+		}
 
 		self.store
 			.copy_if_not_exists(src, &dst)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), src, &dst)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -218,12 +430,18 @@ impl<'a> StreamingBucketOps<'a> {
 	/// Rename a file within the bucket.
 	async fn rename(&self, src: &ObjectKey, dst: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
-		self.check_permission(BucketOperation::Rename)?;
+		self.check_permission(BucketOperation::Rename, Some(src), Some(&dst))?;
 
 		self.store
 			.rename(src, &dst)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), src, &dst)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), src)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
@@ -231,19 +449,30 @@ impl<'a> StreamingBucketOps<'a> {
 	/// Rename a file if destination doesn't exist.
 	async fn rename_if_not_exists(&self, src: &ObjectKey, dst: ObjectKey) -> Result<()> {
 		self.require_writeable()?;
-		self.check_permission(BucketOperation::Rename)?;
+		self.check_permission(BucketOperation::Rename, Some(src), Some(&dst))?;
+
+		// Leave the metadata of an existing file untouched
+		if self.exists_raw(&dst).await? {
+			return Ok(());
+		}
 
 		self.store
 			.rename_if_not_exists(src, &dst)
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::copy(self.store.as_ref(), src, &dst)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
+		FileMetadata::remove(self.store.as_ref(), src)
+			.await
+			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
 		Ok(())
 	}
 
 	/// Check if a file exists.
 	async fn exists(&self, key: &ObjectKey) -> Result<bool> {
-		self.check_permission(BucketOperation::Exists)?;
+		self.check_permission(BucketOperation::Exists, Some(key), None)?;
 
 		self.store
 			.exists(key)
@@ -254,7 +483,7 @@ impl<'a> StreamingBucketOps<'a> {
 
 	/// List files in the bucket.
 	async fn list(&self, opts: &ListOptions) -> Result<Vec<Value>> {
-		self.check_permission(BucketOperation::List)?;
+		self.check_permission(BucketOperation::List, None, None)?;
 
 		let items = self
 			.store
@@ -262,7 +491,12 @@ impl<'a> StreamingBucketOps<'a> {
 			.await
 			.map_err(|e| Error::ObjectStoreFailure(self.bucket.name.clone(), e))?;
 
-		Ok(items.into_iter().map(|m| m.into_value(self.bucket.name.clone())).collect())
+		// Hide any multipart uploads and metadata which are stored within the bucket
+		Ok(items
+			.into_iter()
+			.filter(|m| !is_internal_key(&m.key))
+			.map(|m| m.into_value(self.bucket.name.clone()))
+			.collect())
 	}
 }
 
@@ -300,9 +534,11 @@ fn value_to_file(value: Value) -> Result<DestinationFile> {
 // =========================================================================
 
 async fn file_put_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
-	let (file, value): (File, Value) = FromArgs::from_args("file::put", args)?;
+	let (file, value, Optional(opts)): (File, Value, Optional<Object>) =
+		FromArgs::from_args("file::put", args)?;
+	let meta = opts.map(FileMetadata::try_from).transpose()?.unwrap_or_default();
 	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
-	ops.put(&ObjectKey::new(file.key), value).await?;
+	ops.put(&ObjectKey::new(file.key), value, meta).await?;
 	Ok(Value::None)
 }
 
@@ -311,9 +547,11 @@ async fn file_put_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value>
 // =========================================================================
 
 async fn file_put_if_not_exists_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
-	let (file, value): (File, Value) = FromArgs::from_args("file::put_if_not_exists", args)?;
+	let (file, value, Optional(opts)): (File, Value, Optional<Object>) =
+		FromArgs::from_args("file::put_if_not_exists", args)?;
+	let meta = opts.map(FileMetadata::try_from).transpose()?.unwrap_or_default();
 	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
-	ops.put_if_not_exists(&ObjectKey::new(file.key), value).await?;
+	ops.put_if_not_exists(&ObjectKey::new(file.key), value, meta).await?;
 	Ok(Value::None)
 }
 
@@ -328,6 +566,32 @@ async fn file_get_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value>
 	Ok(res.map(Value::Bytes).unwrap_or_default())
 }
 
+// =========================================================================
+// file::get_range
+// =========================================================================
+
+async fn file_get_range_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, start, Optional(end)): (File, i64, Optional<i64>) =
+		FromArgs::from_args("file::get_range", args)?;
+	let range = byte_range("file::get_range", start, end)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	let res = ops.get_range(&ObjectKey::new(file.key), range).await?;
+	Ok(res.map(Value::Bytes).unwrap_or_default())
+}
+
+// =========================================================================
+// file::presign
+// =========================================================================
+
+async fn file_presign_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, Optional(expiry)): (File, Optional<Duration>) =
+		FromArgs::from_args("file::presign", args)?;
+	let expiry = presign_expiry("file::presign", expiry)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	let res = ops.presign(&ObjectKey::new(file.key), expiry)?;
+	Ok(res.into())
+}
+
 // =========================================================================
 // file::head
 // =========================================================================
@@ -363,18 +627,20 @@ async fn file_copy_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value
 		ops.copy(&ObjectKey::new(src.key), ObjectKey::new(dst.key)).await?;
 	} else {
 		// Cross-bucket copy
-		let data = {
+		let (data, meta) = {
 			let src_ops = StreamingBucketOps::new(ctx, &src.bucket).await?;
-			let Some(data) = src_ops.get(&ObjectKey::new(src.key)).await? else {
+			let src_key = ObjectKey::new(src.key);
+			let Some(data) = src_ops.get(&src_key).await? else {
 				return Err(anyhow::anyhow!("Source file does not exist"));
 			};
-			data
+			let meta = src_ops.metadata(&src_key).await?.unwrap_or_default();
+			(data, meta)
 		};
 
 		let dst_bucket =
 			dst.bucket.ok_or_else(|| anyhow::anyhow!("destination bucket must be set"))?;
 		let dst_ops = StreamingBucketOps::new(ctx, &dst_bucket).await?;
-		dst_ops.put(&ObjectKey::new(dst.key), data.into()).await?;
+		dst_ops.put(&ObjectKey::new(dst.key), data.into(), meta).await?;
 	}
 
 	Ok(Value::None)
@@ -393,18 +659,20 @@ async fn file_copy_if_not_exists_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -
 		ops.copy_if_not_exists(&ObjectKey::new(src.key), ObjectKey::new(dst.key)).await?;
 	} else {
 		// Cross-bucket copy
-		let data = {
+		let (data, meta) = {
 			let src_ops = StreamingBucketOps::new(ctx, &src.bucket).await?;
-			let Some(data) = src_ops.get(&ObjectKey::new(src.key)).await? else {
+			let src_key = ObjectKey::new(src.key);
+			let Some(data) = src_ops.get(&src_key).await? else {
 				return Err(anyhow::anyhow!("Source file does not exist"));
 			};
-			data
+			let meta = src_ops.metadata(&src_key).await?.unwrap_or_default();
+			(data, meta)
 		};
 
 		let dst_bucket =
 			dst.bucket.ok_or_else(|| anyhow::anyhow!("destination bucket must be set"))?;
 		let dst_ops = StreamingBucketOps::new(ctx, &dst_bucket).await?;
-		dst_ops.put_if_not_exists(&ObjectKey::new(dst.key), data.into()).await?;
+		dst_ops.put_if_not_exists(&ObjectKey::new(dst.key), data.into(), meta).await?;
 	}
 
 	Ok(Value::None)
@@ -448,8 +716,6 @@ async fn file_exists_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Val
 // =========================================================================
 
 async fn file_list_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
-	use crate::fnc::args::Optional;
-
 	let (bucket, Optional(opts)): (String, Optional<Object>) =
 		FromArgs::from_args("file::list", args)?;
 	let ops = StreamingBucketOps::new(ctx, &bucket).await?;
@@ -458,6 +724,54 @@ async fn file_list_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value
 	Ok(items.into())
 }
 
+// =========================================================================
+// file::multipart::create
+// =========================================================================
+
+async fn file_multipart_create_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, Optional(opts)): (File, Optional<Object>) =
+		FromArgs::from_args("file::multipart::create", args)?;
+	let meta = opts.map(FileMetadata::try_from).transpose()?.unwrap_or_default();
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	let upload = ops.create_multipart(&ObjectKey::new(file.key), meta).await?;
+	Ok(Value::from(upload))
+}
+
+// =========================================================================
+// file::multipart::put
+// =========================================================================
+
+async fn file_multipart_put_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, upload, part, value): (File, String, i64, Value) =
+		FromArgs::from_args("file::multipart::put", args)?;
+	let part = part_number("file::multipart::put", part)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	ops.put_part(&ObjectKey::new(file.key), &upload, part, value).await?;
+	Ok(Value::None)
+}
+
+// =========================================================================
+// file::multipart::complete
+// =========================================================================
+
+async fn file_multipart_complete_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, upload): (File, String) = FromArgs::from_args("file::multipart::complete", args)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	ops.complete_multipart(&ObjectKey::new(file.key), &upload).await?;
+	Ok(Value::None)
+}
+
+// =========================================================================
+// file::multipart::abort
+// =========================================================================
+
+async fn file_multipart_abort_impl(ctx: &EvalContext<'_>, args: Vec<Value>) -> Result<Value> {
+	let (file, upload): (File, String) = FromArgs::from_args("file::multipart::abort", args)?;
+	let ops = StreamingBucketOps::new(ctx, &file.bucket).await?;
+	ops.abort_multipart(&ObjectKey::new(file.key), &upload).await?;
+	Ok(Value::None)
+}
+
 // =========================================================================
 // file::bucket (pure function)
 // =========================================================================
@@ -480,9 +794,11 @@ fn file_key_impl((file,): (File,)) -> Result<Value> {
 
 // Note: We use `Any` for file arguments in the macro signature because Kind::File takes
 // parameters (bucket types). The actual type checking happens via FromArgs::from_args.
-define_async_function!(FilePut, "file::put", (file: Any, value: Any) -> Any, file_put_impl);
-define_async_function!(FilePutIfNotExists, "file::put_if_not_exists", (file: Any, value: Any) -> Any, file_put_if_not_exists_impl);
+define_async_function!(FilePut, "file::put", (file: Any, value: Any, ?opts: Object) -> Any, file_put_impl);
+define_async_function!(FilePutIfNotExists, "file::put_if_not_exists", (file: Any, value: Any, ?opts: Object) -> Any, file_put_if_not_exists_impl);
 define_async_function!(FileGet, "file::get", (file: Any) -> Any, file_get_impl);
+define_async_function!(FileGetRange, "file::get_range", (file: Any, start: Int, ?end: Int) -> Any, file_get_range_impl);
+define_async_function!(FilePresign, "file::presign", (file: Any, ?expiry: Duration) -> String, file_presign_impl);
 define_async_function!(FileHead, "file::head", (file: Any) -> Any, file_head_impl);
 define_async_function!(FileDelete, "file::delete", (file: Any) -> Any, file_delete_impl);
 define_async_function!(FileCopy, "file::copy", (src: Any, dst: Any) -> Any, file_copy_impl);
@@ -491,6 +807,10 @@ define_async_function!(FileRename, "file::rename", (file: Any, target: String) -
 define_async_function!(FileRenameIfNotExists, "file::rename_if_not_exists", (file: Any, target: String) -> Any, file_rename_if_not_exists_impl);
 define_async_function!(FileExists, "file::exists", (file: Any) -> Any, file_exists_impl);
 define_async_function!(FileList, "file::list", (bucket: String, ?opts: Object) -> Any, file_list_impl);
+define_async_function!(FileMultipartAbort, "file::multipart::abort", (file: Any, upload: String) -> Any, file_multipart_abort_impl);
+define_async_function!(FileMultipartComplete, "file::multipart::complete", (file: Any, upload: String) -> Any, file_multipart_complete_impl);
+define_async_function!(FileMultipartCreate, "file::multipart::create", (file: Any, ?opts: Object) -> Any, file_multipart_create_impl);
+define_async_function!(FileMultipartPut, "file::multipart::put", (file: Any, upload: String, part: Int, value: Any) -> Any, file_multipart_put_impl);
 
 define_pure_function!(FileBucket, "file::bucket", (file: Any) -> Any, file_bucket_impl);
 define_pure_function!(FileKey, "file::key", (file: Any) -> Any, file_key_impl);
@@ -505,6 +825,8 @@ pub fn register(registry: &mut FunctionRegistry) {
 		FilePut,
 		FilePutIfNotExists,
 		FileGet,
+		FileGetRange,
+		FilePresign,
 		FileHead,
 		FileDelete,
 		FileCopy,
@@ -513,6 +835,10 @@ pub fn register(registry: &mut FunctionRegistry) {
 		FileRenameIfNotExists,
 		FileExists,
 		FileList,
+		FileMultipartAbort,
+		FileMultipartComplete,
+		FileMultipartCreate,
+		FileMultipartPut,
 		FileBucket,
 		FileKey,
 	);
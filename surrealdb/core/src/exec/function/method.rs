@@ -669,6 +669,8 @@ pub fn build_method_registry(funcs: &FunctionRegistry) -> MethodRegistry {
 	m.register_typed("put", ValueKind::File, get(funcs, "file::put"));
 	m.register_typed("put_if_not_exists", ValueKind::File, get(funcs, "file::put_if_not_exists"));
 	m.register_typed("get", ValueKind::File, get(funcs, "file::get"));
+	m.register_typed("get_range", ValueKind::File, get(funcs, "file::get_range"));
+	m.register_typed("presign", ValueKind::File, get(funcs, "file::presign"));
 	m.register_typed("head", ValueKind::File, get(funcs, "file::head"));
 	m.register_typed("delete", ValueKind::File, get(funcs, "file::delete"));
 	m.register_typed("copy", ValueKind::File, get(funcs, "file::copy"));
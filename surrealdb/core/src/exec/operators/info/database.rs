@@ -1,6 +1,6 @@
 //! Database INFO operator - returns database-level metadata.
 //!
-//! Implements INFO FOR DB [STRUCTURE] [VERSION timestamp] which returns information about:
+//! Implements INFO FOR DB [VERSION timestamp] [STRUCTURE] [STATS] which returns information about:
 //! - Database accesses
 //! - APIs
 //! - Analyzers
@@ -13,6 +13,7 @@
 //! - Users
 //! - Configs
 //! - Sequences
+//! - Storage statistics, when requested
 
 use std::sync::Arc;
 
@@ -30,7 +31,7 @@ use crate::exec::{
 	AccessMode, CardinalityHint, ExecOperator, FlowResult, OperatorMetrics, ValueBatch,
 	ValueBatchStream,
 };
-use crate::expr::statements::info::InfoStructure;
+use crate::expr::statements::info::{InfoStructure, database_stats};
 use crate::iam::{Action, ResourceKind};
 use crate::val::{Datetime, Object, Value};
 
@@ -44,14 +45,21 @@ pub struct DatabaseInfoPlan {
 	pub structured: bool,
 	/// Optional version timestamp to filter schema by
 	pub version: Option<Arc<dyn PhysicalExpr>>,
+	/// Whether to include storage statistics
+	pub stats: bool,
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
 
 impl DatabaseInfoPlan {
-	pub(crate) fn new(structured: bool, version: Option<Arc<dyn PhysicalExpr>>) -> Self {
+	pub(crate) fn new(
+		structured: bool,
+		version: Option<Arc<dyn PhysicalExpr>>,
+		stats: bool,
+	) -> Self {
 		Self {
 			structured,
 			version,
+			stats,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -69,6 +77,9 @@ impl ExecOperator for DatabaseInfoPlan {
 		if self.version.is_some() {
 			attrs.push(("version".to_string(), "<expr>".to_string()));
 		}
+		if self.stats {
+			attrs.push(("stats".to_string(), "true".to_string()));
+		}
 		attrs
 	}
 
@@ -104,10 +115,11 @@ impl ExecOperator for DatabaseInfoPlan {
 	fn execute(&self, ctx: &ExecutionContext) -> FlowResult<ValueBatchStream> {
 		let structured = self.structured;
 		let version = self.version.clone();
+		let stats = self.stats;
 		let ctx = ctx.clone();
 
 		Ok(Box::pin(stream::once(async move {
-			let value = execute_database_info(&ctx, structured, version.as_deref()).await?;
+			let value = execute_database_info(&ctx, structured, version.as_deref(), stats).await?;
 			Ok(ValueBatch {
 				values: vec![value],
 			})
@@ -123,6 +135,7 @@ async fn execute_database_info(
 	ctx: &ExecutionContext,
 	structured: bool,
 	version: Option<&dyn PhysicalExpr>,
+	stats: bool,
 ) -> crate::expr::FlowResult<Value> {
 	// Check permissions
 	let root = ctx.root();
@@ -157,6 +170,13 @@ async fn execute_database_info(
 	// Get the transaction
 	let txn = ctx.txn();
 
+	// Estimate the storage used
+	let stats = if stats {
+		Some(database_stats(&txn, ns, db).await?)
+	} else {
+		None
+	};
+
 	// Create the result set
 	if structured {
 		let object = map! {
@@ -172,6 +192,7 @@ async fn execute_database_info(
 			"users".to_string() => process(txn.all_db_users(ns, db).await?),
 			"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
 			"sequences".to_string() => process(txn.all_db_sequences(ns, db).await?),
+			"stats".to_string(), if let Some(v) = stats => v,
 		};
 		Ok(Value::Object(Object(object)))
 	} else {
@@ -249,7 +270,7 @@ async fn execute_database_info(
 			"configs".to_string() => {
 				let mut out = Object::default();
 				for v in txn.all_db_configs(ns, db).await?.iter() {
-					out.insert(v.name(), v.to_sql().into());
+					out.insert(v.name(), v.to_info_sql().into());
 				}
 				out.into()
 			},
@@ -260,6 +281,7 @@ async fn execute_database_info(
 				}
 				out.into()
 			},
+			"stats".to_string(), if let Some(v) = stats => v,
 		};
 		Ok(Value::Object(Object(object)))
 	}
@@ -1,11 +1,12 @@
 //! Table INFO operator - returns table-level metadata.
 //!
-//! Implements INFO FOR TABLE name [STRUCTURE] [VERSION timestamp] which returns information about:
+//! Implements INFO FOR TABLE name [VERSION timestamp] [STRUCTURE] [STATS] which returns information about:
 //! - Events
 //! - Fields
 //! - Indexes
 //! - Live queries
 //! - Views (tables that reference this table)
+//! - Storage statistics, when requested
 
 use std::sync::Arc;
 
@@ -20,7 +21,7 @@ use crate::exec::{
 	AccessMode, CardinalityHint, ExecOperator, FlowResult, OperatorMetrics, ValueBatch,
 	ValueBatchStream,
 };
-use crate::expr::statements::info::InfoStructure;
+use crate::expr::statements::info::{InfoStructure, table_stats};
 use crate::iam::{Action, ResourceKind};
 use crate::val::{Datetime, Object, TableName, Value};
 
@@ -36,6 +37,8 @@ pub struct TableInfoPlan {
 	pub structured: bool,
 	/// Optional version timestamp to filter schema by
 	pub version: Option<Arc<dyn PhysicalExpr>>,
+	/// Whether to include storage statistics
+	pub stats: bool,
 	pub(crate) metrics: Arc<OperatorMetrics>,
 }
 
@@ -44,11 +47,13 @@ impl TableInfoPlan {
 		table: Arc<dyn PhysicalExpr>,
 		structured: bool,
 		version: Option<Arc<dyn PhysicalExpr>>,
+		stats: bool,
 	) -> Self {
 		Self {
 			table,
 			structured,
 			version,
+			stats,
 			metrics: Arc::new(OperatorMetrics::new()),
 		}
 	}
@@ -69,6 +74,9 @@ impl ExecOperator for TableInfoPlan {
 		if self.version.is_some() {
 			attrs.push(("version".to_string(), "<expr>".to_string()));
 		}
+		if self.stats {
+			attrs.push(("stats".to_string(), "true".to_string()));
+		}
 		attrs
 	}
 
@@ -107,10 +115,12 @@ impl ExecOperator for TableInfoPlan {
 		let table = self.table.clone();
 		let structured = self.structured;
 		let version = self.version.clone();
+		let stats = self.stats;
 		let ctx = ctx.clone();
 
 		Ok(Box::pin(stream::once(async move {
-			let value = execute_table_info(&ctx, &*table, structured, version.as_deref()).await?;
+			let value =
+				execute_table_info(&ctx, &*table, structured, version.as_deref(), stats).await?;
 			Ok(ValueBatch {
 				values: vec![value],
 			})
@@ -127,6 +137,7 @@ async fn execute_table_info(
 	table_expr: &dyn PhysicalExpr,
 	structured: bool,
 	version: Option<&dyn PhysicalExpr>,
+	stats: bool,
 ) -> crate::expr::FlowResult<Value> {
 	// Check permissions
 	let root = ctx.root();
@@ -165,6 +176,13 @@ async fn execute_table_info(
 	// Get the transaction
 	let txn = ctx.txn();
 
+	// Estimate the storage used
+	let stats = if stats {
+		Some(table_stats(&txn, ns, db, &tb).await?)
+	} else {
+		None
+	};
+
 	// Create the result set
 	if structured {
 		Ok(Value::from(map! {
@@ -173,6 +191,7 @@ async fn execute_table_info(
 			"indexes".to_string() => process(txn.all_tb_indexes(ns, db, &tb).await?),
 			"lives".to_string() => process(txn.all_tb_lives(ns, db, &tb).await?),
 			"tables".to_string() => process(txn.all_tb_views(ns, db, &tb).await?),
+			"stats".to_string(), if let Some(v) = stats => v,
 		}))
 	} else {
 		Ok(Value::from(map! {
@@ -211,6 +230,7 @@ async fn execute_table_info(
 				}
 				out.into()
 			},
+			"stats".to_string(), if let Some(v) = stats => v,
 		}))
 	}
 }
@@ -333,7 +333,7 @@ fn info_stmt_required_context(info: &InfoStatement) -> ContextLevel {
 	match info {
 		InfoStatement::Root(_) => ContextLevel::Root,
 		InfoStatement::Ns(_) => ContextLevel::Namespace,
-		InfoStatement::Db(_, _) | InfoStatement::Tb(_, _, _) | InfoStatement::Index(_, _, _) => {
+		InfoStatement::Db(..) | InfoStatement::Tb(..) | InfoStatement::Index(..) => {
 			ContextLevel::Database
 		}
 		InfoStatement::User(user_expr, base, _) => {
@@ -994,20 +994,21 @@ impl<'ctx> Planner<'ctx> {
 			InfoStatement::Ns(structured) => {
 				Ok(Arc::new(NamespaceInfoPlan::new(structured)) as Arc<dyn ExecOperator>)
 			}
-			InfoStatement::Db(structured, version) => {
+			InfoStatement::Db(structured, version, stats) => {
 				let version = match version {
 					Some(v) => Some(Box::pin(self.physical_expr(v)).await?),
 					None => None,
 				};
-				Ok(Arc::new(DatabaseInfoPlan::new(structured, version)) as Arc<dyn ExecOperator>)
+				Ok(Arc::new(DatabaseInfoPlan::new(structured, version, stats))
+					as Arc<dyn ExecOperator>)
 			}
-			InfoStatement::Tb(table, structured, version) => {
+			InfoStatement::Tb(table, structured, version, stats) => {
 				let table = self.physical_expr_as_name(table).await?;
 				let version = match version {
 					Some(v) => Some(Box::pin(self.physical_expr(v)).await?),
 					None => None,
 				};
-				Ok(Arc::new(TableInfoPlan::new(table, structured, version))
+				Ok(Arc::new(TableInfoPlan::new(table, structured, version, stats))
 					as Arc<dyn ExecOperator>)
 			}
 			InfoStatement::User(user, base, structured) => {
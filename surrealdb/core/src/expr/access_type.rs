@@ -99,6 +99,7 @@ impl Default for JwtAccessIssue {
 pub(crate) enum JwtAccessVerify {
 	Key(JwtAccessVerifyKey),
 	Jwks(JwtAccessVerifyJwks),
+	Oidc(JwtAccessVerifyOidc),
 }
 
 impl Default for JwtAccessVerify {
@@ -130,6 +131,20 @@ pub(crate) struct JwtAccessVerifyJwks {
 	pub(crate) url: Expr,
 }
 
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub(crate) struct JwtAccessVerifyOidc {
+	pub(crate) issuer: Expr,
+	pub(crate) audience: Option<Expr>,
+	pub(crate) roles: Option<Expr>,
+	pub(crate) introspect: Option<JwtAccessIntrospect>,
+}
+
+#[derive(Debug, Hash, Clone, Eq, PartialEq)]
+pub(crate) struct JwtAccessIntrospect {
+	pub(crate) client: Expr,
+	pub(crate) secret: Expr,
+}
+
 #[derive(Debug, Hash, Clone, Eq, PartialEq)]
 pub(crate) struct RecordAccess {
 	pub signup: Option<Expr>,
@@ -116,6 +116,9 @@ impl AlterApiStatement {
 						methods: action.methods.clone(),
 						action: action.action.clone(),
 						config: action.config.compute(stk, ctx, opt, doc).await?,
+						params: action.params.clone(),
+						query: action.query.clone(),
+						body: action.body.clone(),
 					});
 				}
 				AlterApiClause::DropAction {
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use reblessive::tree::Stk;
 use surrealdb_types::{SqlFormat, ToSql};
 
@@ -17,12 +19,16 @@ use crate::val::{Duration, Value};
 /// - `query_timeout`: set/drop a global query timeout which is enforced across queries. The value
 ///   is evaluated as a `Duration` expression at runtime.
 /// - `compact`: runs a storage compaction across the entire datastore.
+/// - `checkpoint`: writes a consistent on-disk copy of the datastore to a directory. The value is
+///   evaluated as a string path at runtime.
 pub(crate) struct AlterSystemStatement {
 	/// Global query timeout alteration. `Set` evaluates an expression to a
 	/// `Duration`; `Drop` clears the timeout; `None` leaves it unchanged.
 	pub query_timeout: AlterKind<Expr>,
 	/// When true, triggers a datastore-wide compaction.
 	pub compact: bool,
+	/// The directory to write a datastore checkpoint to, if requested.
+	pub checkpoint: Option<Expr>,
 }
 
 impl AlterSystemStatement {
@@ -39,6 +45,15 @@ impl AlterSystemStatement {
 		if self.compact {
 			ctx.tx().compact::<Key>(None).await?;
 		}
+		// Are we creating a checkpoint?
+		if let Some(path) = &self.checkpoint {
+			let path = stk
+				.run(|stk| path.compute(stk, ctx, opt, doc))
+				.await
+				.catch_return()?
+				.coerce_to::<String>()?;
+			ctx.tx().checkpoint(Path::new(&path)).await?;
+		}
 		match &self.query_timeout {
 			AlterKind::None => {}
 			AlterKind::Set(timeout) => {
@@ -12,7 +12,7 @@ use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::Base;
-use crate::iam::{Action, ResourceKind};
+use crate::iam::{Action, ResourceKind, totp};
 use crate::val::Value;
 
 #[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
@@ -24,6 +24,7 @@ pub(crate) struct AlterUserStatement {
 	pub roles: AlterKind<Vec<String>>,
 	pub token_duration: AlterKind<Option<Duration>>,
 	pub session_duration: AlterKind<Option<Duration>>,
+	pub totp: AlterKind<String>,
 	pub comment: AlterKind<String>,
 }
 
@@ -31,6 +32,10 @@ impl AlterUserStatement {
 	#[instrument(level = "trace", name = "AlterUserStatement::compute", skip_all)]
 	pub(crate) async fn compute(&self, ctx: &FrozenContext, opt: &Options) -> Result<Value> {
 		opt.is_allowed(Action::Edit, ResourceKind::Actor, &self.base)?;
+		// Ensure that the TOTP secret is valid
+		if let AlterKind::Set(ref secret) = self.totp {
+			totp::decode_secret(secret)?;
+		}
 
 		match self.base {
 			Base::Root => self.compute_root(ctx).await,
@@ -58,6 +63,11 @@ impl AlterUserStatement {
 			AlterKind::Drop => user.session_duration = None,
 			AlterKind::None => {}
 		}
+		match self.totp {
+			AlterKind::Set(ref v) => user.totp = Some(v.clone()),
+			AlterKind::Drop => user.totp = None,
+			AlterKind::None => {}
+		}
 		match self.comment {
 			AlterKind::Set(ref v) => user.comment = Some(v.clone()),
 			AlterKind::Drop => user.comment = None,
@@ -13,8 +13,8 @@ use crate::doc::CursorDoc;
 use crate::err::Error;
 use crate::expr::access::AccessDuration;
 use crate::expr::access_type::{
-	BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccessIssue, JwtAccessVerify,
-	JwtAccessVerifyJwks, JwtAccessVerifyKey,
+	BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccessIntrospect, JwtAccessIssue,
+	JwtAccessVerify, JwtAccessVerifyJwks, JwtAccessVerifyKey, JwtAccessVerifyOidc,
 };
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{
@@ -31,6 +31,7 @@ pub(crate) struct DefineAccessStatement {
 	pub access_type: AccessType,
 	pub authenticate: Option<Expr>,
 	pub duration: AccessDuration,
+	pub limit: Option<catalog::RateLimit>,
 	pub comment: Expr,
 }
 
@@ -43,6 +44,7 @@ impl Default for DefineAccessStatement {
 			access_type: AccessType::default(),
 			authenticate: None,
 			duration: AccessDuration::default(),
+			limit: None,
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -87,6 +89,17 @@ impl DefineAccessStatement {
 							url: Expr::Literal(Literal::String(j.url.clone())),
 						})
 					}
+					catalog::JwtAccessVerify::Oidc(o) => {
+						JwtAccessVerify::Oidc(JwtAccessVerifyOidc {
+							issuer: Expr::Literal(Literal::String(o.issuer.clone())),
+							audience: o.audience.clone().map(|x| Expr::Literal(Literal::String(x))),
+							roles: o.roles.clone().map(|x| Expr::Literal(Literal::String(x))),
+							introspect: o.introspect.as_ref().map(|x| JwtAccessIntrospect {
+								client: Expr::Literal(Literal::String(x.client.clone())),
+								secret: Expr::Literal(Literal::String(x.secret.clone())),
+							}),
+						})
+					}
 				},
 				issue: access.issue.as_ref().map(|x| JwtAccessIssue {
 					alg: convert_algorithm(&x.alg),
@@ -127,6 +140,7 @@ impl DefineAccessStatement {
 					.map(|v| Expr::Literal(Literal::Duration(val::Duration(v))))
 					.unwrap_or(Expr::Literal(Literal::None)),
 			},
+			limit: def.limit.clone(),
 			comment: def
 				.comment
 				.clone()
@@ -202,6 +216,21 @@ impl DefineAccessStatement {
 							.cast_to()?,
 					})
 				}
+				JwtAccessVerify::Oidc(o) => {
+					catalog::JwtAccessVerify::Oidc(catalog::JwtAccessVerifyOidc {
+						issuer: stk
+							.run(|stk| o.issuer.compute(stk, ctx, opt, doc))
+							.await
+							.catch_return()?
+							.cast_to()?,
+						audience: map_opt!(x as &o.audience => stk.run(|stk| x.compute(stk, ctx, opt, doc)).await.catch_return()?.cast_to()?),
+						roles: map_opt!(x as &o.roles => stk.run(|stk| x.compute(stk, ctx, opt, doc)).await.catch_return()?.cast_to()?),
+						introspect: map_opt!(x as &o.introspect => catalog::JwtAccessIntrospect {
+							client: stk.run(|stk| x.client.compute(stk, ctx, opt, doc)).await.catch_return()?.cast_to()?,
+							secret: stk.run(|stk| x.secret.compute(stk, ctx, opt, doc)).await.catch_return()?.cast_to()?,
+						}),
+					})
+				}
 			};
 
 			let issue = map_opt!(x as &access.issue => catalog::JwtAccessIssue {
@@ -280,6 +309,7 @@ impl DefineAccessStatement {
 			token_duration,
 			session_duration,
 			comment,
+			limit: self.limit.clone(),
 			authenticate: self.authenticate.clone(),
 			access_type: match &self.access_type {
 				AccessType::Record(record_access) => {
@@ -409,6 +439,11 @@ impl DefineAccessStatement {
 			{
 				v.key = Expr::Literal(Literal::String("[REDACTED]".to_string()));
 			}
+			if let JwtAccessVerify::Oidc(ref mut v) = acc.verify
+				&& let Some(ref mut i) = v.introspect
+			{
+				i.secret = Expr::Literal(Literal::String("[REDACTED]".to_string()));
+			}
 			if let Some(ref mut s) = acc.issue {
 				s.key = Expr::Literal(Literal::String("[REDACTED]".to_string()));
 			}
@@ -11,7 +11,7 @@ use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
-use crate::expr::{Base, Expr, FlowResultExt as _, Value};
+use crate::expr::{Base, Expr, FlowResultExt as _, Kind, Value};
 use crate::iam::{Action, AuthLimit, ResourceKind};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -67,6 +67,9 @@ impl DefineApiStatement {
 				methods: action.methods.clone(),
 				action: action.action.clone(),
 				config: action.config.compute(stk, ctx, opt, doc).await?,
+				params: action.params.clone(),
+				query: action.query.clone(),
+				body: action.body.clone(),
 			});
 		}
 
@@ -96,6 +99,9 @@ pub(crate) struct ApiAction {
 	pub methods: Vec<ApiMethod>,
 	pub action: Expr,
 	pub config: ApiConfig,
+	pub params: Option<Kind>,
+	pub query: Option<Kind>,
+	pub body: Option<Kind>,
 }
 
 impl ToSql for ApiAction {
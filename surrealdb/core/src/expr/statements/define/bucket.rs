@@ -3,7 +3,7 @@ use reblessive::tree::Stk;
 
 use super::{CursorDoc, DefineKind};
 use crate::catalog::providers::BucketProvider;
-use crate::catalog::{BucketDefinition, Permission};
+use crate::catalog::{BucketDefinition, BucketLifecycleRule, Permission};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::err::Error;
@@ -19,6 +19,7 @@ pub(crate) struct DefineBucketStatement {
 	pub backend: Option<Expr>,
 	pub permissions: Permission,
 	pub readonly: bool,
+	pub lifecycle: Vec<BucketLifecycleRule>,
 	pub comment: Expr,
 }
 
@@ -30,6 +31,7 @@ impl Default for DefineBucketStatement {
 			backend: None,
 			permissions: Permission::default(),
 			readonly: false,
+			lifecycle: Vec::new(),
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -79,6 +81,11 @@ impl DefineBucketStatement {
 			None
 		};
 
+		// Check that the backend only connects to allowed network targets
+		if let Some(backend) = &backend {
+			crate::buc::check_backend_net(ctx, backend).await?;
+		}
+
 		// Create and cache a new backend
 		if let Some(buckets) = ctx.get_buckets() {
 			buckets.new_backend(ns, db, &name, self.readonly, backend.as_deref()).await?;
@@ -101,6 +108,7 @@ impl DefineBucketStatement {
 			backend,
 			permissions: self.permissions.clone(),
 			readonly: self.readonly,
+			lifecycle: self.lifecycle.clone(),
 			comment,
 		};
 		txn.set(&key, &ap, None).await?;
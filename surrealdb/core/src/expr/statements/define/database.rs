@@ -1,4 +1,6 @@
-use anyhow::{Result, bail};
+use std::time;
+
+use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 
 use super::DefineKind;
@@ -23,6 +25,7 @@ pub(crate) struct DefineDatabaseStatement {
 	pub strict: bool,
 	pub comment: Expr,
 	pub changefeed: Option<ChangeFeed>,
+	pub history: Option<time::Duration>,
 }
 
 impl Default for DefineDatabaseStatement {
@@ -33,6 +36,7 @@ impl Default for DefineDatabaseStatement {
 			name: Expr::Literal(Literal::None),
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
+			history: None,
 			strict: false,
 		}
 	}
@@ -58,6 +62,12 @@ impl DefineDatabaseStatement {
 		let txn = ctx.tx();
 		let nsv = txn.get_or_add_ns(Some(ctx), ns).await?;
 
+		// Check that the history of the database can be removed
+		ensure!(
+			self.history.is_none() || txn.can_prune_versions(),
+			Error::UnsupportedHistoryRetention
+		);
+
 		// Process the name
 		let name = expr_to_ident(stk, ctx, opt, doc, &self.name, "database name").await?;
 
@@ -95,6 +105,7 @@ impl DefineDatabaseStatement {
 			name: name.clone(),
 			comment,
 			changefeed: self.changefeed,
+			history: self.history,
 			strict: self.strict,
 		};
 		txn.put_db(&nsv.name, db_def).await?;
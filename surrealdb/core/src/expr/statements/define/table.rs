@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time;
 
-use anyhow::{Result, bail};
+use anyhow::{Result, bail, ensure};
 use reblessive::tree::Stk;
 use uuid::Uuid;
 
@@ -41,6 +42,8 @@ pub(crate) struct DefineTableStatement {
 	pub view: Option<View>,
 	pub permissions: Permissions,
 	pub changefeed: Option<ChangeFeed>,
+	pub history: Option<time::Duration>,
+	pub tier: Option<String>,
 	pub comment: Expr,
 	pub table_type: TableType,
 }
@@ -56,6 +59,8 @@ impl Default for DefineTableStatement {
 			view: None,
 			permissions: Permissions::default(),
 			changefeed: None,
+			history: None,
+			tier: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
 		}
@@ -87,6 +92,22 @@ impl DefineTableStatement {
 		let ns = txn.expect_ns_by_name(ns_name).await?;
 		let db = txn.expect_db_by_name(ns_name, db_name).await?;
 
+		// Check that the history of the table can be removed
+		ensure!(
+			self.history.is_none() || txn.can_prune_versions(),
+			Error::UnsupportedHistoryRetention
+		);
+
+		// Check that the storage tier exists
+		if let Some(tier) = &self.tier {
+			ensure!(
+				txn.has_tier(tier),
+				Error::TierNotFound {
+					name: tier.clone(),
+				}
+			);
+		}
+
 		// Check if the definition exists
 		let table_id = if let Some(tb) = txn.get_tb(ns.namespace_id, db.database_id, &name).await? {
 			match self.kind {
@@ -100,9 +121,21 @@ impl DefineTableStatement {
 				DefineKind::Overwrite => {}
 				DefineKind::IfNotExists => return Ok(Value::None),
 			}
+			// The records of the table are stored in its tier
+			ensure!(
+				tb.tier == self.tier,
+				Error::TbTierChanged {
+					name: name.clone().into_string(),
+				}
+			);
 
 			tb.table_id
 		} else {
+			// Store the records of the new table in its tier
+			if let Some(tier) = &self.tier {
+				let key = crate::key::table::all::new(ns.namespace_id, db.database_id, &name);
+				txn.set_tier(&key, Some(tier)).await?;
+			}
 			txn.get_next_tb_id(Some(ctx), ns.namespace_id, db.database_id).await?
 		};
 
@@ -126,6 +159,8 @@ impl DefineTableStatement {
 			permissions: self.permissions.clone(),
 			comment,
 			changefeed: self.changefeed,
+			history: self.history,
+			tier: self.tier.clone(),
 
 			cache_fields_ts: cache_ts,
 			cache_events_ts: cache_ts,
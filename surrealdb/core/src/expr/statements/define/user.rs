@@ -17,7 +17,7 @@ use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::user::UserDuration;
 use crate::expr::{Base, Expr, FlowResultExt, Idiom, Literal};
-use crate::iam::{Action, ResourceKind};
+use crate::iam::{Action, ResourceKind, totp};
 use crate::val::{self, Duration, Value};
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -29,6 +29,8 @@ pub(crate) struct DefineUserStatement {
 	pub code: String,
 	pub roles: Vec<String>,
 	pub duration: UserDuration,
+	pub totp: Option<String>,
+	pub limit: Option<catalog::RateLimit>,
 	pub comment: Expr,
 }
 
@@ -42,6 +44,8 @@ impl Default for DefineUserStatement {
 			code: String::new(),
 			roles: vec![],
 			duration: UserDuration::default(),
+			totp: None,
+			limit: None,
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -64,6 +68,8 @@ impl DefineUserStatement {
 				.collect::<String>(),
 			roles: vec![role],
 			duration: UserDuration::default(),
+			totp: None,
+			limit: None,
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -94,6 +100,11 @@ impl DefineUserStatement {
 			.catch_return()?
 			.cast_to()?;
 
+		// Ensure that the TOTP secret is valid
+		if let Some(ref secret) = self.totp {
+			totp::decode_secret(secret)?;
+		}
+
 		Ok(UserDefinition {
 			name: expr_to_ident(stk, ctx, opt, doc, &self.name, "user name").await?,
 			hash: self.hash.clone(),
@@ -103,6 +114,8 @@ impl DefineUserStatement {
 			session_duration,
 			comment,
 			base: self.base.into(),
+			totp: self.totp.clone(),
+			limit: self.limit.clone(),
 		})
 	}
 
@@ -124,6 +137,8 @@ impl DefineUserStatement {
 					.map(|x| Expr::Literal(Literal::Duration(val::Duration(x))))
 					.unwrap_or(Expr::Literal(Literal::None)),
 			},
+			totp: def.totp.clone(),
+			limit: def.limit.clone(),
 			comment: def
 				.comment
 				.as_ref()
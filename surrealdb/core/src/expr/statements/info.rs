@@ -1,13 +1,16 @@
+use std::ops::Range;
 use std::sync::Arc;
 
 use anyhow::Result;
 use reblessive::tree::Stk;
 use surrealdb_types::ToSql;
 
+use crate::api::openapi;
 use crate::catalog::providers::{
 	ApiProvider, AuthorisationProvider, BucketProvider, DatabaseProvider, NamespaceProvider,
 	NodeProvider, RootProvider, TableProvider, UserProvider,
 };
+use crate::catalog::{DatabaseId, NamespaceId};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
 use crate::doc::CursorDoc;
@@ -15,6 +18,8 @@ use crate::err::Error;
 use crate::expr::parameterize::expr_to_ident;
 use crate::expr::{Base, Expr, FlowResultExt};
 use crate::iam::{Action, ResourceKind};
+use crate::key::{change, record};
+use crate::kvs::{KVKey, Transaction, util};
 use crate::sys::INFORMATION;
 use crate::val::{Datetime, Object, TableName, Value};
 
@@ -26,9 +31,9 @@ pub(crate) enum InfoStatement {
 
 	Ns(bool),
 
-	Db(bool, Option<Expr>),
+	Db(bool, Option<Expr>, bool),
 
-	Tb(Expr, bool, Option<Expr>),
+	Tb(Expr, bool, Option<Expr>, bool),
 
 	User(Expr, Option<Base>, bool),
 
@@ -148,7 +153,7 @@ impl InfoStatement {
 					Ok(Value::Object(Object(object)))
 				}
 			}
-			InfoStatement::Db(structured, version) => {
+			InfoStatement::Db(structured, version, stats) => {
 				// Allowed to run?
 				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
 				// Get the NS and DB
@@ -166,11 +171,25 @@ impl InfoStatement {
 				};
 				// Get the transaction
 				let txn = ctx.tx();
+				// Estimate the storage used
+				let stats = if *stats {
+					Some(database_stats(&txn, ns, db).await?)
+				} else {
+					None
+				};
 				// Create the result set
 				let res = if *structured {
+					// Describe the defined APIs as an OpenAPI document
+					let apis = txn.all_db_apis(ns, db).await?;
+					let openapi = if apis.is_empty() {
+						None
+					} else {
+						Some(openapi::generate(&txn, opt.ns()?, opt.db()?).await?)
+					};
 					let object = map! {
 						"accesses".to_string() => process(txn.all_db_accesses(ns, db).await?),
-						"apis".to_string() => process(txn.all_db_apis(ns, db).await?),
+						"apis".to_string() => process(apis),
+						"openapi".to_string(), if let Some(v) = openapi => v,
 						"analyzers".to_string() => process(txn.all_db_analyzers(ns, db).await?),
 						"buckets".to_string() => process(txn.all_db_buckets(ns, db).await?),
 						"functions".to_string() => process(txn.all_db_functions(ns, db).await?),
@@ -181,6 +200,7 @@ impl InfoStatement {
 						"users".to_string() => process(txn.all_db_users(ns, db).await?),
 						"configs".to_string() => process(txn.all_db_configs(ns, db).await?),
 						"sequences".to_string() => process(txn.all_db_sequences(ns, db).await?),
+						"stats".to_string(), if let Some(v) = stats => v,
 					};
 					Value::Object(Object(object))
 				} else {
@@ -258,7 +278,7 @@ impl InfoStatement {
 						"configs".to_string() => {
 							let mut out = Object::default();
 							for v in txn.all_db_configs(ns, db).await?.iter() {
-								out.insert(v.name(), v.to_sql().into());
+								out.insert(v.name(), v.to_info_sql().into());
 							}
 							out.into()
 						},
@@ -269,12 +289,13 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"stats".to_string(), if let Some(v) = stats => v,
 					};
 					Value::Object(Object(object))
 				};
 				Ok(res)
 			}
-			InfoStatement::Tb(tb, structured, version) => {
+			InfoStatement::Tb(tb, structured, version, stats) => {
 				// Allowed to run?
 				opt.is_allowed(Action::View, ResourceKind::Any, &Base::Db)?;
 				// Get the NS and DB
@@ -294,6 +315,12 @@ impl InfoStatement {
 				};
 				// Get the transaction
 				let txn = ctx.tx();
+				// Estimate the storage used
+				let stats = if *stats {
+					Some(table_stats(&txn, ns, db, &tb).await?)
+				} else {
+					None
+				};
 				// Create the result set
 				Ok(if *structured {
 					Value::from(map! {
@@ -302,6 +329,7 @@ impl InfoStatement {
 						"indexes".to_string() => process(txn.all_tb_indexes(ns, db, &tb).await?),
 						"lives".to_string() => process(txn.all_tb_lives(ns, db, &tb).await?),
 						"tables".to_string() => process(txn.all_tb_views(ns, db, &tb).await?),
+						"stats".to_string(), if let Some(v) = stats => v,
 					})
 				} else {
 					Value::from(map! {
@@ -340,6 +368,7 @@ impl InfoStatement {
 							}
 							out.into()
 						},
+						"stats".to_string(), if let Some(v) = stats => v,
 					})
 				})
 			}
@@ -429,6 +458,63 @@ where
 	Value::Array(a.iter().cloned().map(InfoStructure::structure).collect())
 }
 
+/// Estimate the storage used by the tables and change feeds in a database.
+pub(crate) async fn database_stats(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+) -> Result<Value> {
+	// Change feeds are stored for the whole database
+	let beg = change::prefix(ns, db).encode_key()?;
+	let end = change::suffix(ns, db).encode_key()?;
+	let changefeed = size(txn, beg..end).await?;
+	// Estimate the storage used by each table
+	let mut tables = Object::default();
+	for tb in txn.all_tb(ns, db, None).await?.iter() {
+		let rng = util::to_prefix_range(crate::key::table::all::new(ns, db, &tb.name))?;
+		tables.insert(tb.name.clone().into_string(), size(txn, rng).await?);
+	}
+	Ok(Value::from(map! {
+		"changefeed".to_string() => changefeed,
+		"tables".to_string() => tables.into(),
+	}))
+}
+
+/// Estimate the storage used by a table, its records, and its indexes.
+pub(crate) async fn table_stats(
+	txn: &Transaction,
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &TableName,
+) -> Result<Value> {
+	// Estimate the storage used by the whole table
+	let total = size(txn, util::to_prefix_range(crate::key::table::all::new(ns, db, tb))?).await?;
+	// Estimate the storage used by the table records
+	let records = size(txn, record::prefix(ns, db, tb)?..record::suffix(ns, db, tb)?).await?;
+	// Estimate the storage used by each index
+	let mut indexes = Object::default();
+	for ix in txn.all_tb_indexes(ns, db, tb).await?.iter() {
+		let rng = util::to_prefix_range(crate::key::index::all::new(ns, db, tb, ix.index_id))?;
+		indexes.insert(ix.name.clone(), size(txn, rng).await?);
+	}
+	Ok(Value::from(map! {
+		"indexes".to_string() => indexes.into(),
+		"records".to_string() => records,
+		"total".to_string() => total,
+	}))
+}
+
+/// Estimate the storage used by a range of keys, if the storage engine supports it.
+async fn size(txn: &Transaction, rng: Range<Vec<u8>>) -> Result<Value> {
+	Ok(match txn.size(rng).await? {
+		Some(v) => Value::from(map! {
+			"bytes".to_string() => v.bytes.into(),
+			"keys".to_string() => v.keys.into(),
+		}),
+		None => Value::None,
+	})
+}
+
 async fn system() -> Value {
 	let info = INFORMATION.lock().await;
 	Value::from(map! {
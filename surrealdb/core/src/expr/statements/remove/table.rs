@@ -101,6 +101,10 @@ impl RemoveTableStatement {
 		} else {
 			txn.delp(&key).await?
 		};
+		// Remove the storage tier of the table
+		if tb.tier.is_some() {
+			txn.set_tier(&key, None).await?;
+		}
 		// Check if this is a foreign table
 		if let Some(view) = &tb.view {
 			let (ViewDefinition::Materialized {
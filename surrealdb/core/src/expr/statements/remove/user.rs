@@ -63,6 +63,9 @@ impl RemoveUserStatement {
 				// Process the statement
 				let key = crate::key::root::us::new(&us.name);
 				txn.del(&key).await?;
+				// Delete the last used one-time password
+				let key = crate::key::root::ot::new(&us.name);
+				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
@@ -90,6 +93,9 @@ impl RemoveUserStatement {
 				// Delete the definition
 				let key = crate::key::namespace::us::new(ns, &us.name);
 				txn.del(&key).await?;
+				// Delete the last used one-time password
+				let key = crate::key::namespace::ot::new(ns, &us.name);
+				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
@@ -118,6 +124,9 @@ impl RemoveUserStatement {
 				// Delete the definition
 				let key = crate::key::database::us::new(ns, db, &us.name);
 				txn.del(&key).await?;
+				// Delete the last used one-time password
+				let key = crate::key::database::ot::new(ns, db, &us.name);
+				txn.del(&key).await?;
 				// Clear the cache
 				txn.clear_cache();
 				// Ok all good
@@ -793,12 +793,12 @@ implement_visitor! {
 		match i{
 			InfoStatement::Root(_) |
 			InfoStatement::Ns(_) => {}
-			InfoStatement::Db(_, expr) => {
+			InfoStatement::Db(_, expr, _) => {
 				if let Some(e) = expr.as_ref(){
 					this.visit_expr(e)?;
 				}
 			},
-			InfoStatement::Tb(expr, _, expr1) => {
+			InfoStatement::Tb(expr, _, expr1, _) => {
 				this.visit_expr(expr)?;
 				if let Some(e) = expr1.as_ref(){
 					this.visit_expr(e)?;
@@ -1030,6 +1030,19 @@ implement_visitor! {
 			JwtAccessVerify::Jwks(ref j) => {
 				this.visit_expr(&j.url)?
 			},
+			JwtAccessVerify::Oidc(ref o) => {
+				this.visit_expr(&o.issuer)?;
+				if let Some(a) = o.audience.as_ref(){
+					this.visit_expr(a)?
+				}
+				if let Some(r) = o.roles.as_ref(){
+					this.visit_expr(r)?
+				}
+				if let Some(i) = o.introspect.as_ref(){
+					this.visit_expr(&i.client)?;
+					this.visit_expr(&i.secret)?;
+				}
+			},
 		}
 
 		if let Some(i) = j.issue.as_ref(){
@@ -2303,12 +2316,12 @@ implement_visitor_mut! {
 		match i{
 			InfoStatement::Root(_) |
 			InfoStatement::Ns(_) => {}
-			InfoStatement::Db(_, expr) => {
+			InfoStatement::Db(_, expr, _) => {
 				if let Some(e) = expr.as_mut(){
 					this.visit_mut_expr(e)?;
 				}
 			},
-			InfoStatement::Tb(expr, _, expr1) => {
+			InfoStatement::Tb(expr, _, expr1, _) => {
 				this.visit_mut_expr(expr)?;
 				if let Some(e) = expr1.as_mut(){
 					this.visit_mut_expr(e)?;
@@ -2536,6 +2549,19 @@ implement_visitor_mut! {
 			JwtAccessVerify::Jwks(ref mut j) => {
 				this.visit_mut_expr(&mut j.url)?
 			},
+			JwtAccessVerify::Oidc(ref mut o) => {
+				this.visit_mut_expr(&mut o.issuer)?;
+				if let Some(a) = o.audience.as_mut(){
+					this.visit_mut_expr(a)?
+				}
+				if let Some(r) = o.roles.as_mut(){
+					this.visit_mut_expr(r)?
+				}
+				if let Some(i) = o.introspect.as_mut(){
+					this.visit_mut_expr(&mut i.client)?;
+					this.visit_mut_expr(&mut i.secret)?;
+				}
+			},
 		}
 
 		if let Some(i) = j.issue.as_mut(){
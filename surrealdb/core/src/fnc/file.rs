@@ -1,32 +1,44 @@
+use std::ops::Range;
+
 use anyhow::{Context, Result};
 use reblessive::tree::Stk;
 
 use super::CursorDoc;
 use super::args::Optional;
 use crate::buc::BucketController;
-use crate::buc::store::ObjectKey;
+use crate::buc::metadata::FileMetadata;
+use crate::buc::presign::{DEFAULT_PRESIGN_EXPIRY, MAX_PRESIGN_EXPIRY};
+use crate::buc::store::{MAX_MULTIPART_PARTS, ObjectKey};
 use crate::ctx::FrozenContext;
 use crate::dbs::Options;
-use crate::val::{File, Object, Value};
+use crate::err::Error;
+use crate::val::{Duration, File, Object, Value};
 
 /// Put a file into a bucket.
+///
+/// The options can specify the `content_type` of the file, and custom
+/// `metadata` which is stored alongside the file.
 pub async fn put(
 	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
-	(file, value): (File, Value),
+	(file, value, Optional(opts)): (File, Value, Optional<Object>),
 ) -> Result<Value> {
+	let meta = opts.map(FileMetadata::try_from).transpose()?.unwrap_or_default();
 	let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
-	controller.put(&ObjectKey::new(file.key), value).await?;
+	controller.put(&ObjectKey::new(file.key), value, meta).await?;
 
 	Ok(Value::None)
 }
 
 /// Put a file into a bucket if it does not exist.
+///
+/// Accepts the same options as [`put`].
 pub async fn put_if_not_exists(
 	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
-	(file, value): (File, Value),
+	(file, value, Optional(opts)): (File, Value, Optional<Object>),
 ) -> Result<Value> {
+	let meta = opts.map(FileMetadata::try_from).transpose()?.unwrap_or_default();
 	let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
-	controller.put_if_not_exists(&ObjectKey::new(file.key), value).await?;
+	controller.put_if_not_exists(&ObjectKey::new(file.key), value, meta).await?;
 
 	Ok(Value::None)
 }
@@ -41,6 +53,34 @@ pub async fn get(
 	Ok(res.map(Value::Bytes).unwrap_or_default())
 }
 
+/// Get a byte range of a file from a bucket.
+///
+/// The end of the range is exclusive, and defaults to the end of the file.
+/// Returns None if the file doesn't exist.
+pub async fn get_range(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(file, start, Optional(end)): (File, i64, Optional<i64>),
+) -> Result<Value> {
+	let range = byte_range("file::get_range", start, end)?;
+	let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+	let res = controller.get_range(&ObjectKey::new(file.key), range).await?;
+	Ok(res.map(Value::Bytes).unwrap_or_default())
+}
+
+/// Create a presigned URL path for a file, which grants read access to the
+/// file without authentication until the URL expires.
+///
+/// The expiry defaults to 1 hour, and can be at most 7 days.
+pub async fn presign(
+	(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+	(file, Optional(expiry)): (File, Optional<Duration>),
+) -> Result<Value> {
+	let expiry = presign_expiry("file::presign", expiry)?;
+	let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+	let res = controller.presign(&ObjectKey::new(file.key), expiry).await?;
+	Ok(res.into())
+}
+
 /// Get the metadata of a file from a bucket.
 ///
 /// Returns None if the file doesn't exist.
@@ -49,8 +89,13 @@ pub async fn head(
 	(file,): (File,),
 ) -> Result<Value> {
 	let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
-	let res = controller.head(&ObjectKey::new(file.key)).await?;
-	Ok(res.map(|v| v.into_value(file.bucket)).unwrap_or_default())
+	let key = ObjectKey::new(file.key);
+	let Some(res) = controller.head(&key).await? else {
+		return Ok(Value::None);
+	};
+	let mut res = res.into_value(file.bucket);
+	controller.metadata(&key).await?.unwrap_or_default().extend_value(&mut res);
+	Ok(res)
 }
 
 /// Delete a file.
@@ -79,13 +124,14 @@ pub async fn copy(
 		let dst_key = ObjectKey::new(dst.key);
 		controller.copy(&src_key, dst_key).await?;
 	} else {
-		let data = {
+		let (data, meta) = {
 			let mut src_controller = BucketController::new(stk, ctx, opt, doc, &src.bucket).await?;
 			let src_key = ObjectKey::new(src.key);
 			let Some(data) = src_controller.get(&src_key).await? else {
 				return Err(anyhow::anyhow!("Source file does not exist"));
 			};
-			data
+			let meta = src_controller.metadata(&src_key).await?.unwrap_or_default();
+			(data, meta)
 		};
 
 		let DestinationFile {
@@ -96,7 +142,7 @@ pub async fn copy(
 
 		let dst_key = ObjectKey::new(key);
 		let mut dst_controller = BucketController::new(stk, ctx, opt, doc, &dst_bucket).await?;
-		dst_controller.put(&dst_key, data.into()).await?;
+		dst_controller.put(&dst_key, data.into(), meta).await?;
 	}
 
 	Ok(Value::None)
@@ -118,13 +164,14 @@ pub async fn copy_if_not_exists(
 		let dst_key = ObjectKey::new(dst.key);
 		controller.copy_if_not_exists(&src_key, dst_key).await?;
 	} else {
-		let data = {
+		let (data, meta) = {
 			let mut src_controller = BucketController::new(stk, ctx, opt, doc, &src.bucket).await?;
 			let src_key = ObjectKey::new(src.key);
 			let Some(data) = src_controller.get(&src_key).await? else {
 				return Err(anyhow::anyhow!("Source file does not exist"));
 			};
-			data
+			let meta = src_controller.metadata(&src_key).await?.unwrap_or_default();
+			(data, meta)
 		};
 
 		let DestinationFile {
@@ -135,7 +182,7 @@ pub async fn copy_if_not_exists(
 
 		let dst_key = ObjectKey::new(key);
 		let mut dst_controller = BucketController::new(stk, ctx, opt, doc, &dst_bucket).await?;
-		dst_controller.put_if_not_exists(&dst_key, data.into()).await?;
+		dst_controller.put_if_not_exists(&dst_key, data.into(), meta).await?;
 	}
 
 	Ok(Value::None)
@@ -194,6 +241,71 @@ pub async fn list(
 	Ok(res)
 }
 
+pub mod multipart {
+	use anyhow::Result;
+	use reblessive::tree::Stk;
+
+	use super::{CursorDoc, part_number};
+	use crate::buc::BucketController;
+	use crate::buc::metadata::FileMetadata;
+	use crate::buc::store::ObjectKey;
+	use crate::ctx::FrozenContext;
+	use crate::dbs::Options;
+	use crate::fnc::args::Optional;
+	use crate::val::{File, Object, Value};
+
+	/// Start a multipart upload to a file, returning the id of the upload.
+	///
+	/// Accepts the same options as [`put`](super::put), which are stored
+	/// alongside the file once the upload is completed.
+	pub async fn create(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file, Optional(opts)): (File, Optional<Object>),
+	) -> Result<Value> {
+		let meta = opts.map(FileMetadata::try_from).transpose()?.unwrap_or_default();
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		let upload = controller.create_multipart(&ObjectKey::new(file.key), meta).await?;
+
+		Ok(Value::from(upload))
+	}
+
+	/// Upload a part of a multipart upload.
+	///
+	/// Parts are combined in the order of their part numbers when the upload is completed.
+	pub async fn put(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file, upload, part, value): (File, String, i64, Value),
+	) -> Result<Value> {
+		let part = part_number("file::multipart::put", part)?;
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		controller.put_part(&ObjectKey::new(file.key), &upload, part, value).await?;
+
+		Ok(Value::None)
+	}
+
+	/// Complete a multipart upload, storing the combined parts in the file.
+	pub async fn complete(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file, upload): (File, String),
+	) -> Result<Value> {
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		controller.complete_multipart(&ObjectKey::new(file.key), &upload).await?;
+
+		Ok(Value::None)
+	}
+
+	/// Abort a multipart upload, discarding any uploaded parts.
+	pub async fn abort(
+		(stk, ctx, opt, doc): (&mut Stk, &FrozenContext, &Options, Option<&CursorDoc>),
+		(file, upload): (File, String),
+	) -> Result<Value> {
+		let mut controller = BucketController::new(stk, ctx, opt, doc, &file.bucket).await?;
+		controller.abort_multipart(&ObjectKey::new(file.key), &upload).await?;
+
+		Ok(Value::None)
+	}
+}
+
 pub fn bucket((file,): (File,)) -> Result<Value> {
 	Ok(file.bucket.into())
 }
@@ -202,6 +314,50 @@ pub fn key((file,): (File,)) -> Result<Value> {
 	Ok(file.key.into())
 }
 
+/// Converts the arguments of a ranged read into a byte range.
+pub(crate) fn byte_range(name: &str, start: i64, end: Option<i64>) -> Result<Range<u64>> {
+	let start = u64::try_from(start).map_err(|_| Error::InvalidFunctionArguments {
+		name: name.to_owned(),
+		message: "The start of the range must not be negative".to_string(),
+	})?;
+	let end = match end {
+		Some(end) => u64::try_from(end).map_err(|_| Error::InvalidFunctionArguments {
+			name: name.to_owned(),
+			message: "The end of the range must not be negative".to_string(),
+		})?,
+		None => u64::MAX,
+	};
+	Ok(start..end)
+}
+
+/// Validates the expiry of a presigned URL, falling back to the default expiry.
+pub(crate) fn presign_expiry(name: &str, expiry: Option<Duration>) -> Result<std::time::Duration> {
+	match expiry {
+		None => Ok(DEFAULT_PRESIGN_EXPIRY),
+		Some(Duration(expiry)) if !expiry.is_zero() && expiry <= MAX_PRESIGN_EXPIRY => Ok(expiry),
+		Some(_) => Err(Error::InvalidFunctionArguments {
+			name: name.to_owned(),
+			message: format!(
+				"The expiry must be greater than 0 and at most {}",
+				Duration(MAX_PRESIGN_EXPIRY)
+			),
+		}
+		.into()),
+	}
+}
+
+/// Validates the part number of a multipart upload.
+pub(crate) fn part_number(name: &str, part: i64) -> Result<u32> {
+	match u32::try_from(part) {
+		Ok(part) if (1..=MAX_MULTIPART_PARTS).contains(&part) => Ok(part),
+		_ => Err(Error::InvalidFunctionArguments {
+			name: name.to_owned(),
+			message: format!("The part number must be between 1 and {MAX_MULTIPART_PARTS}"),
+		}
+		.into()),
+	}
+}
+
 /// The only difference between a file and a destination file is that a destination file
 /// can be relative (no bucket).
 struct DestinationFile {
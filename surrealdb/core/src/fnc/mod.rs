@@ -68,6 +68,8 @@ pub async fn run(
 		|| name.eq("file::put")
 		|| name.eq("file::put_if_not_exists")
 		|| name.eq("file::get")
+		|| name.eq("file::get_range")
+		|| name.eq("file::presign")
 		|| name.eq("file::head")
 		|| name.eq("file::delete")
 		|| name.eq("file::exists")
@@ -76,6 +78,10 @@ pub async fn run(
 		|| name.eq("file::rename")
 		|| name.eq("file::rename_if_not_exists")
 		|| name.eq("file::list")
+		|| name.eq("file::multipart::abort")
+		|| name.eq("file::multipart::complete")
+		|| name.eq("file::multipart::create")
+		|| name.eq("file::multipart::put")
 		|| name.eq("record::exists")
 		|| name.eq("record::is_edge")
 		|| name.eq("set::all")
@@ -217,6 +223,10 @@ pub fn synchronous(
 		"crypto::sha1" => crypto::sha1,
 		"crypto::sha256" => crypto::sha256,
 		"crypto::sha512" => crypto::sha512,
+		"crypto::totp::generate" => crypto::totp::generate,
+		"crypto::totp::secret" => crypto::totp::secret,
+		"crypto::totp::uri" => crypto::totp::uri,
+		"crypto::totp::verify" => crypto::totp::verify,
 		//
 		"duration::days" => duration::days,
 		"duration::hours" => duration::hours,
@@ -604,6 +614,8 @@ pub async fn asynchronous(
 		exp(Files) "file::put" => file::put((stk, ctx, opt, doc)).await,
 		exp(Files) "file::put_if_not_exists" => file::put_if_not_exists((stk, ctx, opt, doc)).await,
 		exp(Files) "file::get" => file::get((stk, ctx, opt, doc)).await,
+		exp(Files) "file::get_range" => file::get_range((stk, ctx, opt, doc)).await,
+		exp(Files) "file::presign" => file::presign((stk, ctx, opt, doc)).await,
 		exp(Files) "file::head" => file::head((stk, ctx, opt, doc)).await,
 		exp(Files) "file::delete" => file::delete((stk, ctx, opt, doc)).await,
 		exp(Files) "file::copy" => file::copy((stk, ctx, opt, doc)).await,
@@ -612,6 +624,10 @@ pub async fn asynchronous(
 		exp(Files) "file::rename_if_not_exists" => file::rename_if_not_exists((stk, ctx, opt, doc)).await,
 		exp(Files) "file::exists" => file::exists((stk, ctx, opt, doc)).await,
 		exp(Files) "file::list" => file::list((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::abort" => file::multipart::abort((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::complete" => file::multipart::complete((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::create" => file::multipart::create((stk, ctx, opt, doc)).await,
+		exp(Files) "file::multipart::put" => file::multipart::put((stk, ctx, opt, doc)).await,
 		//
 		"http::head" => http::head(ctx).await,
 		"http::get" => http::get(ctx).await,
@@ -1508,6 +1524,8 @@ pub async fn idiom(
 				exp(Files) "put" => file::put((stk, ctx, opt, doc)).await,
 				exp(Files) "put_if_not_exists" => file::put_if_not_exists((stk, ctx, opt, doc)).await,
 				exp(Files) "get" => file::get((stk, ctx, opt, doc)).await,
+				exp(Files) "get_range" => file::get_range((stk, ctx, opt, doc)).await,
+				exp(Files) "presign" => file::presign((stk, ctx, opt, doc)).await,
 				exp(Files) "head" => file::head((stk, ctx, opt, doc)).await,
 				exp(Files) "delete" => file::delete((stk, ctx, opt, doc)).await,
 				exp(Files) "copy" => file::copy((stk, ctx, opt, doc)).await,
@@ -5,6 +5,7 @@ mod argon2;
 mod bcrypt;
 mod pbkdf2;
 mod scrypt;
+mod totp;
 
 pub struct Package;
 
@@ -20,5 +21,6 @@ impl_module_def!(
 	"argon2" => (argon2::Package),
 	"bcrypt" => (bcrypt::Package),
 	"pbkdf2" => (pbkdf2::Package),
-	"scrypt" => (scrypt::Package)
+	"scrypt" => (scrypt::Package),
+	"totp" => (totp::Package)
 );
@@ -3,6 +3,8 @@ use js::prelude::Async;
 use super::{fut, run};
 use crate::fnc::script::modules::impl_module_def;
 
+mod multipart;
+
 pub struct Package;
 
 impl_module_def!(
@@ -15,6 +17,8 @@ impl_module_def!(
 	"put" => fut Async,
 	"put_if_not_exists" => fut Async,
 	"get" => fut Async,
+	"get_range" => fut Async,
+	"presign" => fut Async,
 	"head" => fut Async,
 	"delete" => fut Async,
 	"copy" => fut Async,
@@ -22,5 +26,6 @@ impl_module_def!(
 	"rename" => fut Async,
 	"rename_if_not_exists" => fut Async,
 	"exists" => fut Async,
-	"list" => fut Async
+	"list" => fut Async,
+	"multipart" => (multipart::Package)
 );
@@ -0,0 +1,15 @@
+use js::prelude::Async;
+
+use super::fut;
+use crate::fnc::script::modules::impl_module_def;
+
+pub struct Package;
+
+impl_module_def!(
+	Package,
+	"file::multipart",
+	"abort" => fut Async,
+	"complete" => fut Async,
+	"create" => fut Async,
+	"put" => fut Async
+);
@@ -0,0 +1,321 @@
+//! GraphQL aggregate queries for tables.
+//!
+//! For each exposed table (e.g. `person`), an `_aggregate_person` Query field
+//! is generated which computes aggregates over the records matching a filter:
+//!
+//! ```graphql
+//! _aggregate_person(filter: _filter_person, version: String) {
+//!     count
+//!     sum { age }
+//!     min { age, created }
+//!     max { age, created }
+//! }
+//! ```
+//!
+//! `sum` covers the numeric fields of the table, while `min` and `max` cover
+//! the numeric and datetime fields. The nested aggregate objects are only
+//! generated when the table has fields they apply to. All aggregates are
+//! computed in a single `SELECT ... GROUP ALL` query.
+
+use std::sync::Arc;
+
+use async_graphql::dynamic::{
+	Field, FieldFuture, FieldValue, InputValue, Object, ResolverContext, Type, TypeRef,
+};
+use surrealdb_types::ToSql;
+
+use super::error::{GqlError, internal_error};
+use super::schema::{kind_to_type, sql_value_to_gql_value_with_kind, unwrap_type};
+use super::tables::{execute_select, filter_name_from_table, parse_filter_arg, parse_version_arg};
+use crate::catalog::{FieldDefinition, TableDefinition};
+use crate::dbs::Session;
+use crate::expr::field::{Field as SelectField, Selector};
+use crate::expr::statements::SelectStatement;
+use crate::expr::{Cond, Expr, Fields, Function, FunctionCall, Groups, Idiom, Kind, Literal};
+use crate::kvs::Datastore;
+use crate::val::{Datetime, Number, Object as SurObject, TableName, Value};
+
+/// The aggregates which are computed for each applicable field.
+#[derive(Clone, Copy)]
+enum Aggregate {
+	Sum,
+	Min,
+	Max,
+}
+
+impl Aggregate {
+	/// The name of the aggregate field on the aggregate Object type.
+	fn name(self) -> &'static str {
+		match self {
+			Aggregate::Sum => "sum",
+			Aggregate::Min => "min",
+			Aggregate::Max => "max",
+		}
+	}
+
+	/// The function used to compute the aggregate for a field of the given kind,
+	/// if the aggregate applies to the field.
+	fn function(self, kind: &Kind) -> Option<&'static str> {
+		match (self, kind) {
+			(Aggregate::Sum, Kind::Int | Kind::Float | Kind::Number | Kind::Decimal) => {
+				Some("math::sum")
+			}
+			(Aggregate::Min, Kind::Int | Kind::Float | Kind::Number | Kind::Decimal) => {
+				Some("math::min")
+			}
+			(Aggregate::Max, Kind::Int | Kind::Float | Kind::Number | Kind::Decimal) => {
+				Some("math::max")
+			}
+			(Aggregate::Min, Kind::Datetime) => Some("time::min"),
+			(Aggregate::Max, Kind::Datetime) => Some("time::max"),
+			_ => None,
+		}
+	}
+}
+
+const AGGREGATES: [Aggregate; 3] = [Aggregate::Sum, Aggregate::Min, Aggregate::Max];
+
+/// A top-level field of a table which can be aggregated.
+struct AggregateField {
+	name: String,
+	/// The field kind, with any `option<...>` wrapper removed
+	kind: Kind,
+}
+
+/// Collect the top-level fields of a table which can be aggregated.
+fn aggregate_fields(fds: &[FieldDefinition]) -> Vec<AggregateField> {
+	fds.iter()
+		.filter(|fd| fd.name.0.len() == 1 && !fd.name.is_id())
+		.filter_map(|fd| {
+			let kind = match fd.field_kind.as_ref()? {
+				// Normalise `option<T>` down to the inner kind
+				Kind::Either(ks) => {
+					let mut non_none = ks.iter().filter(|k| !matches!(k, Kind::None | Kind::Null));
+					match (non_none.next(), non_none.next()) {
+						(Some(k), None) => k.clone(),
+						_ => return None,
+					}
+				}
+				k => k.clone(),
+			};
+			AGGREGATES.iter().any(|a| a.function(&kind).is_some()).then(|| AggregateField {
+				name: fd.name.to_sql(),
+				kind,
+			})
+		})
+		.collect()
+}
+
+/// Get the record count from a computed aggregates object.
+fn record_count(obj: &SurObject) -> i64 {
+	match obj.get("count") {
+		Some(Value::Number(Number::Int(n))) => *n,
+		_ => 0,
+	}
+}
+
+/// Count the records in a table matching a condition.
+pub(super) async fn count_records(
+	kvs: &Datastore,
+	sess: &Session,
+	tb: TableName,
+	cond: Option<Cond>,
+	version: &Option<Datetime>,
+) -> Result<i64, GqlError> {
+	let obj = compute_aggregates(kvs, sess, tb, cond, version, &[]).await?;
+	Ok(record_count(&obj))
+}
+
+/// Compute the count, and the aggregates of the specified fields, of the
+/// records in a table matching a condition.
+///
+/// Returns an object with a `count` field, and an object for each aggregate
+/// keyed by field name.
+async fn compute_aggregates(
+	kvs: &Datastore,
+	sess: &Session,
+	tb: TableName,
+	cond: Option<Cond>,
+	version: &Option<Datetime>,
+	fields: &[AggregateField],
+) -> Result<SurObject, GqlError> {
+	let selector = |function: &str, arguments: Vec<Expr>, alias: String| {
+		SelectField::Single(Selector {
+			expr: Expr::FunctionCall(Box::new(FunctionCall {
+				receiver: Function::Normal(function.to_string()),
+				arguments,
+			})),
+			alias: Some(Idiom::field(alias)),
+		})
+	};
+	// Each aggregate is selected under a positional alias, so that field
+	// names never need to be escaped
+	let mut selectors = vec![selector("count", vec![], "count".to_string())];
+	for aggregate in AGGREGATES {
+		for (i, field) in fields.iter().enumerate() {
+			if let Some(function) = aggregate.function(&field.kind) {
+				selectors.push(selector(
+					function,
+					vec![Expr::Idiom(Idiom::field(field.name.clone()))],
+					format!("{}_{i}", aggregate.name()),
+				));
+			}
+		}
+	}
+
+	let stmt = SelectStatement {
+		what: vec![Expr::Table(tb)],
+		fields: Fields::Select(selectors),
+		cond,
+		group: Some(Groups(vec![])),
+		version: match version {
+			Some(dt) => Expr::Literal(Literal::Datetime(dt.clone())),
+			None => Expr::Literal(Literal::None),
+		},
+		timeout: Expr::Literal(Literal::None),
+		omit: vec![],
+		only: false,
+		with: None,
+		split: None,
+		order: None,
+		limit: None,
+		start: None,
+		fetch: None,
+		explain: None,
+		tempfiles: false,
+	};
+
+	// Grouping an empty table returns no rows at all
+	let mut row = match execute_select(kvs, sess, stmt).await? {
+		Value::Array(a) => match a.0.into_iter().next() {
+			Some(Value::Object(obj)) => obj,
+			Some(v) => {
+				error!("Expected object in aggregate result, found: {v:?}");
+				return Err(internal_error("Expected object in aggregate result"));
+			}
+			None => SurObject::default(),
+		},
+		v => {
+			error!("Found top level value, in result which should be array: {v:?}");
+			return Err(internal_error("Unexpected result type from aggregate query"));
+		}
+	};
+
+	// Rebuild the aggregates as objects keyed by field name
+	let mut out = SurObject::default();
+	out.insert("count".to_string(), row.remove("count").unwrap_or(Value::from(0)));
+	for aggregate in AGGREGATES {
+		let mut values = SurObject::default();
+		for (i, field) in fields.iter().enumerate() {
+			if let Some(v) = row.remove(&format!("{}_{i}", aggregate.name())) {
+				values.insert(field.name.clone(), v);
+			}
+		}
+		out.insert(aggregate.name().to_string(), Value::Object(values));
+	}
+	Ok(out)
+}
+
+/// Build the `_aggregate_<table>` query field.
+pub(super) fn make_table_aggregate_field(
+	tb: &TableDefinition,
+	fds: Arc<[FieldDefinition]>,
+	kvs: Arc<Datastore>,
+) -> Field {
+	let tb_name = tb.name.clone();
+	let aggregate_name = format!("_aggregate_{tb_name}");
+	let table_filter_name = filter_name_from_table(&tb_name);
+
+	Field::new(&aggregate_name, TypeRef::named_nn(&aggregate_name), move |ctx| {
+		let tb_name = tb_name.clone();
+		let fds = fds.clone();
+		let kvs = kvs.clone();
+		FieldFuture::new(async move {
+			let sess = ctx.data::<Arc<Session>>()?;
+			let args = ctx.args.as_index_map();
+			let version = parse_version_arg(args)?;
+			let cond = parse_filter_arg(args, &fds, tb_name.as_str())?;
+			let fields = aggregate_fields(&fds);
+			let obj = compute_aggregates(&kvs, sess, tb_name, cond, &version, &fields).await?;
+			Ok(Some(FieldValue::owned_any(obj)))
+		})
+	})
+	.description(format!(
+		"Generated from table `{}`\nallows computing aggregates over a table with filters",
+		tb.name
+	))
+	.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)))
+}
+
+/// Build the aggregate Object types for a table, pushing the nested
+/// per-aggregate types onto `types`.
+pub(super) fn make_aggregate_type(
+	tb: &TableDefinition,
+	fds: &[FieldDefinition],
+	types: &mut Vec<Type>,
+) -> Result<Object, GqlError> {
+	let tb_name = tb.name.clone().into_string();
+	let fields = aggregate_fields(fds);
+
+	let mut obj = Object::new(format!("_aggregate_{tb_name}"))
+		.description(format!("Generated from `{tb_name}` aggregates over the matching records"))
+		.field(Field::new("count", TypeRef::named_nn(TypeRef::INT), |ctx| {
+			FieldFuture::new(async move {
+				let obj = ctx.parent_value.try_downcast_ref::<SurObject>()?;
+				Ok(Some(FieldValue::value(record_count(obj))))
+			})
+		}));
+
+	for aggregate in AGGREGATES {
+		let name = aggregate.name();
+		let type_name = format!("_aggregate_{name}_{tb_name}");
+		let mut ty = Object::new(&type_name);
+		let mut has_fields = false;
+		for field in fields.iter().filter(|f| aggregate.function(&f.kind).is_some()) {
+			// Aggregates are empty when no records match, so are always nullable
+			let fd_type = unwrap_type(kind_to_type(field.kind.clone(), types, false)?);
+			ty = ty.field(Field::new(&field.name, fd_type, make_value_resolver(field)));
+			has_fields = true;
+		}
+		if !has_fields {
+			continue;
+		}
+		types.push(Type::Object(ty));
+		obj = obj.field(Field::new(name, TypeRef::named_nn(&type_name), move |ctx| {
+			FieldFuture::new(async move {
+				let obj = ctx.parent_value.try_downcast_ref::<SurObject>()?;
+				match obj.get(name) {
+					Some(Value::Object(values)) => Ok(Some(FieldValue::owned_any(values.clone()))),
+					_ => Ok(Some(FieldValue::owned_any(SurObject::default()))),
+				}
+			})
+		}));
+	}
+
+	Ok(obj)
+}
+
+/// Create a resolver which extracts an aggregated value from its parent object.
+fn make_value_resolver(
+	field: &AggregateField,
+) -> impl for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static {
+	let name = field.name.clone();
+	let kind = field.kind.clone();
+	move |ctx| {
+		let name = name.clone();
+		let kind = kind.clone();
+		FieldFuture::new(async move {
+			let obj = ctx.parent_value.try_downcast_ref::<SurObject>()?;
+			match obj.get(&name) {
+				None | Some(Value::None | Value::Null) => Ok(None),
+				Some(v) => {
+					let out = sql_value_to_gql_value_with_kind(v.clone(), Some(&kind), None)
+						.map_err(async_graphql::Error::from)?;
+					Ok(Some(FieldValue::value(out)))
+				}
+			}
+		})
+	}
+}
@@ -0,0 +1,472 @@
+//! Relay-style cursor connections for GraphQL table queries.
+//!
+//! For each exposed table (e.g. `person`), a `_connection_person` Query field
+//! is generated which follows the [Relay cursor connections
+//! specification](https://relay.dev/graphql/connections.htm):
+//!
+//! ```graphql
+//! _connection_person(first: Int, after: String, last: Int, before: String,
+//!                    order: _order_person, filter: _filter_person,
+//!                    version: String): _connection_person!
+//! ```
+//!
+//! ## Cursors
+//!
+//! Pagination is keyset based, so pages remain stable when records are
+//! inserted or removed between requests. A cursor encodes the values of the
+//! ordered fields of a record, followed by its record ID which is always used
+//! as the final tie-breaker. The values are serialised as CBOR and base64
+//! encoded, so cursors are opaque to clients. Cursors are never parsed as
+//! SurrealQL, and are rejected unless they decode exactly to the values of the
+//! current ordering, ending with a record ID of the table. Cursors come from
+//! clients, so their length is limited, and they are decoded with a format
+//! which never allocates more than the size of its input.
+
+use std::sync::Arc;
+
+use async_graphql::dynamic::indexmap::IndexMap;
+use async_graphql::dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, TypeRef};
+use async_graphql::{Name, Value as GqlValue};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+use super::error::{GqlError, internal_error, resolver_error};
+use super::tables::{
+	CachedRecord, execute_select, filter_name_from_table, parse_filter_arg, parse_order_arg,
+	parse_version_arg, select_all_from_table,
+};
+use crate::catalog::{FieldDefinition, TableDefinition};
+use crate::dbs::Session;
+use crate::expr::order::{OrderList, Ordering};
+use crate::expr::part::Part;
+use crate::expr::{self, BinaryOperator, Cond, Expr, Idiom, Limit, Literal};
+use crate::kvs::Datastore;
+use crate::rpc::format::cbor;
+use crate::sql::expression::convert_public_value_to_internal;
+use crate::val::{Array as SurArray, Datetime, TableName, Value, convert_value_to_public_value};
+
+/// The name of the shared page info type.
+pub(super) const PAGE_INFO_TYPE: &str = "_page_info";
+
+/// The maximum length of a cursor accepted from a client.
+const MAX_CURSOR_LEN: usize = 16 * 1024;
+
+/// A page of records returned from a connection query.
+#[derive(Clone, Debug)]
+struct Connection {
+	edges: Vec<Edge>,
+	page_info: PageInfo,
+	/// The table, filter and version used to count the total number of records
+	tb: TableName,
+	cond: Option<Cond>,
+	version: Option<Datetime>,
+}
+
+/// A single record within a connection, together with its cursor.
+#[derive(Clone, Debug)]
+struct Edge {
+	cursor: String,
+	node: CachedRecord,
+}
+
+/// Information about the position of a page within a connection.
+#[derive(Clone, Debug, Default)]
+struct PageInfo {
+	has_next_page: bool,
+	has_previous_page: bool,
+	start_cursor: Option<String>,
+	end_cursor: Option<String>,
+}
+
+/// The ordering of a connection, as a list of field idioms and whether each
+/// field is sorted in ascending order. Always ends with the `id` field.
+type KeyOrder = Vec<(Idiom, bool)>;
+
+/// Check whether an ordered field is the record ID.
+fn is_id_field(idiom: &Idiom) -> bool {
+	matches!(idiom.0.as_slice(), [Part::Field(name)] if name == "id")
+}
+
+/// Get the value of an ordered field, which may be nested, from a record.
+fn order_field_value(idiom: &Idiom, record: &CachedRecord) -> Value {
+	if is_id_field(idiom) {
+		return Value::RecordId(record.rid.clone());
+	}
+	match idiom.0.split_first() {
+		Some((Part::Field(name), rest)) => match record.data.get(name.as_str()) {
+			Some(value) => value.pick(rest),
+			None => Value::None,
+		},
+		_ => Value::None,
+	}
+}
+
+/// Build the key ordering from the optional `order` argument, appending the
+/// record ID as a tie-breaker so that every record has a unique position.
+fn key_order(ordering: Option<Ordering>) -> KeyOrder {
+	let mut keys: KeyOrder = match ordering {
+		Some(Ordering::Order(OrderList(orders))) => {
+			orders.into_iter().map(|o| (o.value, o.direction)).collect()
+		}
+		_ => Vec::new(),
+	};
+	// Ordering by the record ID is already unique, so any later fields are redundant
+	if let Some(pos) = keys.iter().position(|(k, _)| is_id_field(k)) {
+		keys.truncate(pos + 1);
+	} else {
+		keys.push((Idiom::field("id".to_string()), true));
+	}
+	keys
+}
+
+/// Build the `ORDER BY` clause for the key ordering, optionally reversed.
+fn key_ordering(keys: &KeyOrder, reverse: bool) -> Ordering {
+	let orders = keys
+		.iter()
+		.map(|(field, asc)| expr::Order {
+			value: field.clone(),
+			direction: *asc != reverse,
+			..Default::default()
+		})
+		.collect();
+	Ordering::Order(OrderList(orders))
+}
+
+/// Encode the cursor for a record, from the values of its ordered fields.
+fn encode_cursor(keys: &KeyOrder, record: &CachedRecord) -> Result<String, GqlError> {
+	let values: SurArray = keys.iter().map(|(field, _)| order_field_value(field, record)).collect();
+	let bytes = convert_value_to_public_value(Value::Array(values))
+		.and_then(cbor::encode)
+		.map_err(|e| internal_error(format!("Failed to encode cursor: {e}")))?;
+	Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+/// Decode a cursor into the values of the ordered fields it was created from.
+///
+/// The cursor must decode to exactly one value for each ordered field, and
+/// must end with the ID of a record in the table.
+fn decode_cursor(tb: &TableName, keys: &KeyOrder, cursor: &str) -> Result<Vec<Value>, GqlError> {
+	if cursor.len() > MAX_CURSOR_LEN {
+		return Err(resolver_error("Invalid cursor: the cursor is too long"));
+	}
+	let invalid = || resolver_error(format!("Invalid cursor: {cursor}"));
+	let bytes = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+	let values = match cbor::decode(&bytes).map(convert_public_value_to_internal) {
+		Ok(Value::Array(values)) if values.len() == keys.len() => values,
+		_ => return Err(invalid()),
+	};
+	match values.last() {
+		Some(Value::RecordId(rid)) if rid.table == *tb => Ok(values.0),
+		_ => Err(invalid()),
+	}
+}
+
+/// Build the condition selecting the records positioned after a cursor
+/// (or before it, when `after` is false) in the key ordering.
+///
+/// For keys `(a, b, id)` this produces the lexicographic comparison
+/// `a > $a OR (a = $a AND b > $b) OR (a = $a AND b = $b AND id > $id)`,
+/// with the comparison reversed for descending keys.
+fn cursor_cond(keys: &KeyOrder, values: Vec<Value>, after: bool) -> Expr {
+	let field = |idiom: &Idiom| Box::new(Expr::Idiom(idiom.clone()));
+	let mut equal: Option<Expr> = None;
+	let mut cond: Option<Expr> = None;
+	for ((name, asc), value) in keys.iter().zip(values) {
+		let op = if *asc == after {
+			BinaryOperator::MoreThan
+		} else {
+			BinaryOperator::LessThan
+		};
+		let compare = Expr::Binary {
+			left: field(name),
+			op,
+			right: Box::new(value.clone().into_literal()),
+		};
+		let branch = match &equal {
+			Some(eq) => Expr::Binary {
+				left: Box::new(eq.clone()),
+				op: BinaryOperator::And,
+				right: Box::new(compare),
+			},
+			None => compare,
+		};
+		cond = Some(match cond {
+			Some(c) => Expr::Binary {
+				left: Box::new(c),
+				op: BinaryOperator::Or,
+				right: Box::new(branch),
+			},
+			None => branch,
+		});
+		let same = Expr::Binary {
+			left: field(name),
+			op: BinaryOperator::Equal,
+			right: Box::new(value.into_literal()),
+		};
+		equal = Some(match equal {
+			Some(eq) => Expr::Binary {
+				left: Box::new(eq),
+				op: BinaryOperator::And,
+				right: Box::new(same),
+			},
+			None => same,
+		});
+	}
+	cond.unwrap_or(Expr::Literal(Literal::Bool(true)))
+}
+
+/// Combine two optional conditions with `AND`.
+fn and_cond(left: Option<Expr>, right: Expr) -> Expr {
+	match left {
+		Some(left) => Expr::Binary {
+			left: Box::new(left),
+			op: BinaryOperator::And,
+			right: Box::new(right),
+		},
+		None => right,
+	}
+}
+
+/// Parse a non-negative page size argument.
+fn parse_count_arg(args: &IndexMap<Name, GqlValue>, name: &str) -> Result<Option<i64>, GqlError> {
+	match args.get(name) {
+		Some(GqlValue::Number(n)) => match n.as_i64() {
+			Some(n) if n >= 0 => Ok(Some(n)),
+			_ => Err(resolver_error(format!("`{name}` must be a non-negative integer"))),
+		},
+		Some(GqlValue::Null) | None => Ok(None),
+		Some(_) => Err(resolver_error(format!("`{name}` must be an integer"))),
+	}
+}
+
+/// Parse an optional cursor argument.
+fn parse_cursor_arg(
+	args: &IndexMap<Name, GqlValue>,
+	name: &str,
+	tb: &TableName,
+	keys: &KeyOrder,
+) -> Result<Option<Vec<Value>>, GqlError> {
+	match args.get(name) {
+		Some(GqlValue::String(s)) => decode_cursor(tb, keys, s).map(Some),
+		Some(GqlValue::Null) | None => Ok(None),
+		Some(_) => Err(resolver_error(format!("`{name}` must be a cursor string"))),
+	}
+}
+
+/// Fetch a page of records from a table.
+async fn fetch_connection(
+	kvs: &Datastore,
+	sess: &Session,
+	tb: TableName,
+	fds: &[FieldDefinition],
+	args: &IndexMap<Name, GqlValue>,
+) -> Result<Connection, GqlError> {
+	let version = parse_version_arg(args)?;
+	let filter = parse_filter_arg(args, fds, tb.as_str())?;
+	let keys = key_order(parse_order_arg(args)?);
+	let first = parse_count_arg(args, "first")?;
+	let last = parse_count_arg(args, "last")?;
+	if first.is_some() && last.is_some() {
+		return Err(resolver_error("`first` and `last` cannot be used together"));
+	}
+	let after = parse_cursor_arg(args, "after", &tb, &keys)?;
+	let before = parse_cursor_arg(args, "before", &tb, &keys)?;
+
+	// Restrict the records to those between the cursors
+	let mut cond = filter.clone().map(|c| c.0);
+	if let Some(values) = after {
+		cond = Some(and_cond(cond, cursor_cond(&keys, values, true)));
+	}
+	if let Some(values) = before {
+		cond = Some(and_cond(cond, cursor_cond(&keys, values, false)));
+	}
+
+	// Paginating backwards reads the records in reverse, from the end of the range
+	let backward = last.is_some();
+	let size = first.or(last);
+	// Fetch one extra record to determine whether there are more pages
+	let limit = size.map(|n| Limit(Expr::Literal(Literal::Integer(n.saturating_add(1)))));
+	let stmt = select_all_from_table(
+		Expr::Table(tb.clone()),
+		cond.map(Cond),
+		Some(key_ordering(&keys, backward)),
+		limit,
+		None,
+		&version,
+	);
+	let mut records = match execute_select(kvs, sess, stmt).await? {
+		Value::Array(a) => {
+			a.0.into_iter()
+				.map(|v| match v {
+					Value::Object(obj) => match obj.get("id") {
+						Some(Value::RecordId(rid)) => Ok(CachedRecord {
+							rid: rid.clone(),
+							version: version.clone(),
+							data: obj,
+						}),
+						_ => Err(internal_error("Record missing 'id' field")),
+					},
+					_ => Err(internal_error("Expected object in result")),
+				})
+				.collect::<Result<Vec<_>, _>>()?
+		}
+		v => {
+			error!("Found top level value, in result which should be array: {v:?}");
+			return Err(internal_error("Unexpected result type from connection query"));
+		}
+	};
+
+	let has_more = size.is_some_and(|n| records.len() as i64 > n);
+	if let Some(n) = size {
+		records.truncate(n as usize);
+	}
+	if backward {
+		records.reverse();
+	}
+
+	let edges: Vec<Edge> = records
+		.into_iter()
+		.map(|node| {
+			Ok(Edge {
+				cursor: encode_cursor(&keys, &node)?,
+				node,
+			})
+		})
+		.collect::<Result<_, GqlError>>()?;
+	let page_info = PageInfo {
+		has_next_page: !backward && has_more,
+		has_previous_page: backward && has_more,
+		start_cursor: edges.first().map(|e| e.cursor.clone()),
+		end_cursor: edges.last().map(|e| e.cursor.clone()),
+	};
+
+	Ok(Connection {
+		edges,
+		page_info,
+		tb,
+		cond: filter,
+		version,
+	})
+}
+
+/// Build the `_connection_<table>` query field.
+pub(super) fn make_table_connection_field(
+	tb: &TableDefinition,
+	fds: Arc<[FieldDefinition]>,
+	kvs: Arc<Datastore>,
+) -> Field {
+	let tb_name = tb.name.clone();
+	let connection_name = format!("_connection_{tb_name}");
+	let table_order_name = format!("_order_{tb_name}");
+	let table_filter_name = filter_name_from_table(&tb_name);
+
+	Field::new(&connection_name, TypeRef::named_nn(&connection_name), move |ctx| {
+		let tb_name = tb_name.clone();
+		let fds = fds.clone();
+		let kvs = kvs.clone();
+		FieldFuture::new(async move {
+			let sess = ctx.data::<Arc<Session>>()?;
+			let args = ctx.args.as_index_map();
+			let connection = fetch_connection(&kvs, sess, tb_name, &fds, args).await?;
+			Ok(Some(FieldValue::owned_any(connection)))
+		})
+	})
+	.description(format!(
+		"Generated from table `{}`\nallows paginating through a table with cursors",
+		tb.name
+	))
+	.argument(InputValue::new("first", TypeRef::named(TypeRef::INT)))
+	.argument(InputValue::new("after", TypeRef::named(TypeRef::STRING)))
+	.argument(InputValue::new("last", TypeRef::named(TypeRef::INT)))
+	.argument(InputValue::new("before", TypeRef::named(TypeRef::STRING)))
+	.argument(InputValue::new("order", TypeRef::named(&table_order_name)))
+	.argument(InputValue::new("filter", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("where", TypeRef::named(&table_filter_name)))
+	.argument(InputValue::new("version", TypeRef::named(TypeRef::STRING)))
+}
+
+/// Build the connection and edge Object types for a table.
+pub(super) fn make_connection_types(tb: &TableDefinition) -> (Object, Object) {
+	let tb_name = tb.name.clone().into_string();
+	let connection_name = format!("_connection_{tb_name}");
+	let edge_name = format!("_edge_{tb_name}");
+
+	let connection = Object::new(&connection_name)
+		.description(format!("Generated from `{tb_name}` a page of records with cursors"))
+		.field(Field::new("edges", TypeRef::named_nn_list_nn(&edge_name), |ctx| {
+			FieldFuture::new(async move {
+				let connection = ctx.parent_value.try_downcast_ref::<Connection>()?;
+				let edges = connection.edges.iter().cloned().map(FieldValue::owned_any);
+				Ok(Some(FieldValue::list(edges)))
+			})
+		}))
+		.field(Field::new("pageInfo", TypeRef::named_nn(PAGE_INFO_TYPE), |ctx| {
+			FieldFuture::new(async move {
+				let connection = ctx.parent_value.try_downcast_ref::<Connection>()?;
+				Ok(Some(FieldValue::owned_any(connection.page_info.clone())))
+			})
+		}))
+		.field(Field::new("totalCount", TypeRef::named_nn(TypeRef::INT), |ctx| {
+			FieldFuture::new(async move {
+				let connection = ctx.parent_value.try_downcast_ref::<Connection>()?;
+				let kvs = ctx.data::<Arc<Datastore>>()?;
+				let sess = ctx.data::<Arc<Session>>()?;
+				let count = super::aggregates::count_records(
+					kvs,
+					sess,
+					connection.tb.clone(),
+					connection.cond.clone(),
+					&connection.version,
+				)
+				.await?;
+				Ok(Some(FieldValue::value(count)))
+			})
+		}));
+
+	let edge = Object::new(&edge_name)
+		.description(format!("Generated from `{tb_name}` a record with its cursor"))
+		.field(Field::new("cursor", TypeRef::named_nn(TypeRef::STRING), |ctx| {
+			FieldFuture::new(async move {
+				let edge = ctx.parent_value.try_downcast_ref::<Edge>()?;
+				Ok(Some(FieldValue::value(edge.cursor.clone())))
+			})
+		}))
+		.field(Field::new("node", TypeRef::named_nn(&tb_name), |ctx| {
+			FieldFuture::new(async move {
+				let edge = ctx.parent_value.try_downcast_ref::<Edge>()?;
+				Ok(Some(FieldValue::owned_any(edge.node.clone())))
+			})
+		}));
+
+	(connection, edge)
+}
+
+/// Build the shared page info Object type.
+pub(super) fn make_page_info_type() -> Object {
+	fn page_info_field(name: &str, ty: TypeRef, get: fn(&PageInfo) -> GqlValue) -> Field {
+		Field::new(name, ty, move |ctx| {
+			FieldFuture::new(async move {
+				let page_info = ctx.parent_value.try_downcast_ref::<PageInfo>()?;
+				match get(page_info) {
+					GqlValue::Null => Ok(None),
+					v => Ok(Some(FieldValue::value(v))),
+				}
+			})
+		})
+	}
+
+	Object::new(PAGE_INFO_TYPE)
+		.description("Information about the position of a page within a connection")
+		.field(page_info_field("hasNextPage", TypeRef::named_nn(TypeRef::BOOLEAN), |p| {
+			GqlValue::Boolean(p.has_next_page)
+		}))
+		.field(page_info_field("hasPreviousPage", TypeRef::named_nn(TypeRef::BOOLEAN), |p| {
+			GqlValue::Boolean(p.has_previous_page)
+		}))
+		.field(page_info_field("startCursor", TypeRef::named(TypeRef::STRING), |p| {
+			p.start_cursor.clone().map(GqlValue::String).unwrap_or(GqlValue::Null)
+		}))
+		.field(page_info_field("endCursor", TypeRef::named(TypeRef::STRING), |p| {
+			p.end_cursor.clone().map(GqlValue::String).unwrap_or(GqlValue::Null)
+		}))
+}
@@ -0,0 +1,129 @@
+//! Apollo Federation v2 subgraph support.
+//!
+//! When the GraphQL config enables federation (`DEFINE CONFIG GRAPHQL ...
+//! FEDERATION AUTO`), the generated schema can be registered as a subgraph of
+//! a federated supergraph:
+//!
+//! - Each exposed table Object type is marked as an entity with `@key(fields: "id")`, so other
+//!   subgraphs can reference records by their record ID.
+//! - The `_service { sdl }` field is added to the Query type, returning the subgraph SDL with the
+//!   federation directives.
+//! - The `_entities(representations: [_Any!]!)` field is added to the Query type, resolving entity
+//!   references from the gateway to records.
+//!
+//! Entity representations are resolved with the session of the request, so
+//! table and field permissions apply as they do for any other query. The
+//! records of each table are fetched with a single query, and the number of
+//! representations in a request is limited.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_graphql::dynamic::{FieldFuture, FieldValue, ResolverContext, Type};
+
+use super::error::resolver_error;
+use super::tables::{CachedRecord, execute_select, select_all_from_records};
+use crate::dbs::Session;
+use crate::kvs::Datastore;
+use crate::val::{RecordId, TableName, Value};
+
+/// The key fields of every table entity.
+const ENTITY_KEY: &str = "id";
+
+/// The maximum number of entity representations resolved in one request.
+const MAX_REPRESENTATIONS: usize = 1_000;
+
+/// Mark the Object types of the given tables as federation entities.
+pub(super) fn with_entity_keys(types: Vec<Type>, tables: &HashSet<String>) -> Vec<Type> {
+	types
+		.into_iter()
+		.map(|ty| match ty {
+			Type::Object(obj) if tables.contains(obj.type_name()) => {
+				Type::Object(obj.key(ENTITY_KEY))
+			}
+			ty => ty,
+		})
+		.collect()
+}
+
+/// Parse the record ID of an entity representation.
+///
+/// The `id` field of a table type is the full record ID (e.g. `person:alice`),
+/// but a bare key is also accepted, as with the `_get_<table>` query field.
+fn parse_entity_id(tb: &str, id: &str) -> RecordId {
+	if let Ok(rid) = crate::syn::record_id(id) {
+		let rid: RecordId = rid.into();
+		if rid.table.as_str() == tb {
+			return rid;
+		}
+	}
+	match crate::syn::record_id(&format!("{tb}:{id}")) {
+		Ok(rid) => rid.into(),
+		Err(_) => RecordId::new(TableName::from(tb), id.to_string()),
+	}
+}
+
+/// Create the resolver for the `_entities` field, which fetches the record
+/// referenced by each entity representation.
+///
+/// Records which do not exist, or which the session can not select, resolve
+/// to `null` as required by the federation specification.
+pub(super) fn entity_resolver(
+	kvs: Arc<Datastore>,
+	tables: Arc<HashSet<String>>,
+) -> impl for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static {
+	move |ctx| {
+		let kvs = kvs.clone();
+		let tables = tables.clone();
+		FieldFuture::new(async move {
+			let sess = ctx.data::<Arc<Session>>()?;
+			let representations = ctx.args.try_get("representations")?.list()?;
+			if representations.len() > MAX_REPRESENTATIONS {
+				return Err(resolver_error(format!(
+					"Too many entity representations: at most {MAX_REPRESENTATIONS} can be resolved at once"
+				))
+				.into());
+			}
+			// Parse every representation, grouping the record IDs by table
+			let mut refs = Vec::with_capacity(representations.len());
+			let mut grouped: HashMap<String, Vec<RecordId>> = HashMap::new();
+			for representation in representations.iter() {
+				let representation = representation.object()?;
+				let tb = representation.try_get("__typename")?.string()?;
+				if !tables.contains(tb) {
+					return Err(resolver_error(format!("Unknown entity type: {tb}")).into());
+				}
+				let id = representation.try_get(ENTITY_KEY)?.string()?;
+				let rid = parse_entity_id(tb, id);
+				grouped.entry(tb.to_string()).or_default().push(rid.clone());
+				refs.push((tb.to_string(), rid));
+			}
+			// Fetch the records of each table with a single query
+			let mut records = HashMap::with_capacity(refs.len());
+			for (_, rids) in grouped {
+				let stmt = select_all_from_records(&rids);
+				if let Value::Array(found) = execute_select(&kvs, sess, stmt).await? {
+					for record in found {
+						if let Value::Object(obj) = record
+							&& let Some(Value::RecordId(rid)) = obj.get("id")
+						{
+							records.insert(rid.clone(), obj);
+						}
+					}
+				}
+			}
+			// Return the entities in the order of the representations
+			let entities = refs.into_iter().map(|(tb, rid)| match records.get(&rid) {
+				Some(obj) => FieldValue::owned_any(CachedRecord {
+					rid,
+					version: None,
+					data: obj.clone(),
+				})
+				.with_type(tb),
+				None => FieldValue::NULL,
+			});
+
+			Ok(Some(FieldValue::list(entities)))
+		})
+	}
+}
@@ -14,11 +14,19 @@
 //! - **Table queries** ([`tables`]) -- generates Query root fields and Object types for each
 //!   exposed table, including field resolvers, filter/order types, nested objects, and relation
 //!   fields.
+//! - **Connections** ([`connections`]) -- generates Relay-style cursor connections for each
+//!   exposed table, with `first`/`after`/`last`/`before` pagination.
+//! - **Aggregates** ([`aggregates`]) -- generates `count`, `sum`, `min` and `max` aggregate
+//!   queries for each exposed table.
 //! - **Mutations** ([`mutations`]) -- generates Mutation root fields (create, update, upsert,
 //!   delete -- single and bulk) with corresponding input types.
+//! - **Federation** ([`federation`]) -- marks table types as Apollo Federation entities and
+//!   resolves entity references when the schema is served as a subgraph.
 //! - **Functions** ([`functions`]) -- exposes user-defined database functions as Query fields.
 //! - **Authentication** ([`auth`]) -- generates `signIn` / `signUp` mutations from database access
 //!   definitions.
+//! - **Persisted queries** ([`persisted`]) -- restricts execution to the persisted queries allowed
+//!   by the GraphQL configuration.
 //! - **Relations** ([`relations`]) -- discovers relation tables and provides data structures for
 //!   relation field generation.
 //! - **Caching** ([`cache`]) -- caches generated schemas keyed by namespace, database, and GraphQL
@@ -36,12 +44,16 @@
 //! because `async_graphql` and the HTTP serving stack are not compatible with WASM.
 #![cfg(not(target_family = "wasm"))]
 
+mod aggregates;
 mod auth;
 pub mod cache;
+mod connections;
 pub mod error;
 mod ext;
+mod federation;
 mod functions;
 mod mutations;
+mod persisted;
 mod relations;
 pub mod schema;
 mod subscriptions;
@@ -1,12 +1,19 @@
 //! GraphQL mutation generation.
 //!
-//! Generates CREATE, UPDATE, UPSERT, DELETE mutations (single and bulk) for each
-//! table in the schema, along with the corresponding input types.
+//! Generates CREATE, UPDATE, MERGE, UPSERT, DELETE mutations (single and bulk)
+//! for each table in the schema, along with the corresponding input types.
 //!
-//! - Single mutations: `create{Table}`, `update{Table}`, `upsert{Table}`, `delete{Table}`
-//! - Bulk mutations: `createMany{Table}`, `updateMany{Table}`, `upsertMany{Table}`,
-//!   `deleteMany{Table}`
-//! - For relation tables, `create{Table}` uses RELATE instead of CREATE
+//! - Single mutations: `create{Table}`, `update{Table}`, `merge{Table}`, `upsert{Table}`,
+//!   `delete{Table}`
+//! - Bulk mutations: `createMany{Table}`, `updateMany{Table}`, `mergeMany{Table}`,
+//!   `upsertMany{Table}`, `deleteMany{Table}`
+//! - For relation tables, `create{Table}` uses RELATE instead of CREATE, and a
+//!   `relate{Table}(in, out, id, data)` mutation is also generated
+//!
+//! Both `update` and `merge` mutations deep-merge the provided data into the
+//! existing records, so fields which are not provided are left untouched.
+//! Relation mutations check the `in` and `out` records against the `FROM` and
+//! `TO` tables of the relation definition.
 
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -138,17 +145,28 @@ fn kind_to_input_type_ref(
 	kind_to_type_with_enum_prefix(kind, types, true, enum_scope)
 }
 
-/// Generate Create/Update/Upsert input types for a table and return their names.
+/// The names of the input types generated for a table.
+struct InputTypeNames {
+	create: String,
+	update: String,
+	upsert: String,
+	/// Only generated for relation tables.
+	relate: Option<String>,
+}
+
+/// Generate Create/Update/Upsert (and, for relation tables, Relate) input types
+/// for a table and return their names.
 fn generate_input_types(
 	tb_name: &str,
 	fds: &[FieldDefinition],
 	is_relation: bool,
 	types: &mut Vec<Type>,
-) -> Result<(String, String, String), GqlError> {
+) -> Result<InputTypeNames, GqlError> {
 	let cap_name = capitalize_first(tb_name);
 	let create_name = format!("Create{cap_name}Input");
 	let update_name = format!("Update{cap_name}Input");
 	let upsert_name = format!("Upsert{cap_name}Input");
+	let relate_name = format!("Relate{cap_name}Input");
 
 	let mut create_input = InputObject::new(&create_name)
 		.description(format!("Input for creating a `{tb_name}` record"));
@@ -156,6 +174,10 @@ fn generate_input_types(
 		.description(format!("Input for updating a `{tb_name}` record"));
 	let mut upsert_input = InputObject::new(&upsert_name)
 		.description(format!("Input for upserting a `{tb_name}` record"));
+	// The `in`, `out` and `id` of a relate mutation are passed as arguments
+	let mut relate_input = InputObject::new(&relate_name)
+		.description(format!("Input for relating records through a `{tb_name}` record"));
+	let mut has_relate_fields = false;
 
 	// Add optional `id` field for create and upsert
 	create_input = create_input.field(InputValue::new("id", TypeRef::named(TypeRef::ID)));
@@ -193,7 +215,11 @@ fn generate_input_types(
 		let enum_scope = format!("{}_{}", tb_name, fd_name);
 		let create_type = kind_to_input_type_ref(kind.clone(), types, Some(&enum_scope))?;
 		create_input = create_input.field(InputValue::new(&fd_name, create_type.clone()));
-		upsert_input = upsert_input.field(InputValue::new(&fd_name, create_type));
+		upsert_input = upsert_input.field(InputValue::new(&fd_name, create_type.clone()));
+		if is_relation {
+			relate_input = relate_input.field(InputValue::new(&fd_name, create_type));
+			has_relate_fields = true;
+		}
 
 		// For update: all fields are optional (strip NonNull)
 		let update_type =
@@ -205,7 +231,21 @@ fn generate_input_types(
 	types.push(Type::InputObject(update_input));
 	types.push(Type::InputObject(upsert_input));
 
-	Ok((create_name, update_name, upsert_name))
+	// Input objects must have at least one field, so the relate input is only
+	// generated when the relation table defines fields of its own
+	let relate = if is_relation && has_relate_fields {
+		types.push(Type::InputObject(relate_input));
+		Some(relate_name)
+	} else {
+		None
+	};
+
+	Ok(InputTypeNames {
+		create: create_name,
+		update: update_name,
+		upsert: upsert_name,
+		relate,
+	})
 }
 
 /// Shared context for generating mutation fields for a single table.
@@ -221,6 +261,12 @@ struct MutationTableContext {
 	tb_name: TableName,
 	/// Whether the table is a relation table.
 	is_relation: bool,
+	/// For relation tables, the tables the relation may originate from.
+	/// Empty if any table is allowed.
+	from_tables: Arc<[String]>,
+	/// For relation tables, the tables the relation may point to.
+	/// Empty if any table is allowed.
+	to_tables: Arc<[String]>,
 	/// Field definitions for this table.
 	fds: Arc<[FieldDefinition]>,
 	/// The datastore.
@@ -240,13 +286,16 @@ pub async fn process_mutations(
 	for tb in tbs.iter() {
 		let tb_name = tb.name.clone();
 		let tb_name_str = tb_name.clone().into_string();
-		let is_relation = matches!(tb.table_type, TableType::Relation(_));
+		let (is_relation, from_tables, to_tables): (bool, Arc<[String]>, Arc<[String]>) =
+			match &tb.table_type {
+				TableType::Relation(rel) => (true, rel.from.clone().into(), rel.to.clone().into()),
+				_ => (false, Arc::from([]), Arc::from([])),
+			};
 
 		let fds = schema_ctx.tx.all_tb_fields(schema_ctx.ns, schema_ctx.db, &tb.name, None).await?;
 
 		// Generate input types
-		let (create_input_name, update_input_name, upsert_input_name) =
-			generate_input_types(&tb_name_str, &fds, is_relation, types)?;
+		let inputs = generate_input_types(&tb_name_str, &fds, is_relation, types)?;
 
 		let ctx = MutationTableContext {
 			cap_name: capitalize_first(&tb_name_str),
@@ -254,20 +303,27 @@ pub async fn process_mutations(
 			tb_name_str,
 			tb_name,
 			is_relation,
+			from_tables,
+			to_tables,
 			fds,
 			kvs: schema_ctx.datastore.clone(),
 		};
 
 		// --- Single-record mutations ---
-		mutation = add_create_field(mutation, &ctx, &create_input_name);
-		mutation = add_update_field(mutation, &ctx, &update_input_name);
-		mutation = add_upsert_field(mutation, &ctx, &upsert_input_name);
+		mutation = add_create_field(mutation, &ctx, &inputs.create);
+		mutation = add_update_field(mutation, &ctx, &inputs.update, UpdateMode::Update);
+		mutation = add_update_field(mutation, &ctx, &inputs.update, UpdateMode::Merge);
+		mutation = add_upsert_field(mutation, &ctx, &inputs.upsert);
 		mutation = add_delete_field(mutation, &ctx);
+		if is_relation {
+			mutation = add_relate_field(mutation, &ctx, inputs.relate.as_deref());
+		}
 
 		// --- Bulk mutations ---
-		mutation = add_create_many_field(mutation, &ctx, &create_input_name);
-		mutation = add_update_many_field(mutation, &ctx, &update_input_name);
-		mutation = add_upsert_many_field(mutation, &ctx, &upsert_input_name);
+		mutation = add_create_many_field(mutation, &ctx, &inputs.create);
+		mutation = add_update_many_field(mutation, &ctx, &inputs.update, UpdateMode::Update);
+		mutation = add_update_many_field(mutation, &ctx, &inputs.update, UpdateMode::Merge);
+		mutation = add_upsert_many_field(mutation, &ctx, &inputs.upsert);
 		mutation = add_delete_many_field(mutation, &ctx);
 	}
 
@@ -283,6 +339,7 @@ fn add_create_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 	let kvs = tc.kvs.clone();
 	let tb_name = tc.tb_name.clone();
 	let is_relation = tc.is_relation;
+	let endpoints = RelationEndpoints::from(tc);
 	mutation.field(
 		Field::new(
 			format!("create{}", tc.cap_name),
@@ -291,6 +348,7 @@ fn add_create_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 				let fds = fds.clone();
 				let kvs = kvs.clone();
 				let tb_name = tb_name.clone();
+				let endpoints = endpoints.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
 					let args = ctx.args.as_index_map();
@@ -298,7 +356,10 @@ fn add_create_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 					let id_opt = data_obj.get("id").and_then(GqlValueUtils::as_string);
 
 					if is_relation {
-						execute_relate_create(&kvs, sess, &tb_name, data_obj, &fds, id_opt).await
+						execute_relate_create(
+							&kvs, sess, &tb_name, data_obj, &fds, id_opt, &endpoints,
+						)
+						.await
 					} else {
 						execute_normal_create(&kvs, sess, &tb_name, data_obj, &fds, id_opt).await
 					}
@@ -310,13 +371,18 @@ fn add_create_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 	)
 }
 
-fn add_update_field(mutation: Object, tc: &MutationTableContext, input_name: &str) -> Object {
+fn add_update_field(
+	mutation: Object,
+	tc: &MutationTableContext,
+	input_name: &str,
+	mode: UpdateMode,
+) -> Object {
 	let fds = tc.fds.clone();
 	let kvs = tc.kvs.clone();
 	let tb_name = tc.tb_name.clone();
 	mutation.field(
 		Field::new(
-			format!("update{}", tc.cap_name),
+			format!("{}{}", mode.prefix(), tc.cap_name),
 			TypeRef::named(tc.tb_name_str.as_str()),
 			move |ctx| {
 				let fds = fds.clone();
@@ -332,16 +398,10 @@ fn add_update_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 					let content =
 						gql_input_to_sql_object(data_obj, &fds, &["id"], tb_name.as_str())?;
 
-					let data = if content.0.is_empty() {
-						None
-					} else {
-						Some(Data::MergeExpression(Value::Object(content).into_literal()))
-					};
-
 					let stmt = UpdateStatement {
 						only: true,
 						what: vec![Value::RecordId(rid).into_literal()],
-						data,
+						data: merge_data(content),
 						cond: None,
 						output: None,
 						timeout: Expr::Literal(Literal::None),
@@ -357,7 +417,14 @@ fn add_update_field(mutation: Object, tc: &MutationTableContext, input_name: &st
 				})
 			},
 		)
-		.description(format!("Update an existing `{}` record", tc.tb_name_str))
+		.description(match mode {
+			UpdateMode::Update => {
+				format!("Update an existing `{}` record", tc.tb_name_str)
+			}
+			UpdateMode::Merge => {
+				format!("Merge data into an existing `{}` record", tc.tb_name_str)
+			}
+		})
 		.argument(InputValue::new("id", TypeRef::named_nn(TypeRef::ID)))
 		.argument(InputValue::new("data", TypeRef::named_nn(input_name))),
 	)
@@ -456,6 +523,62 @@ fn add_delete_field(mutation: Object, tc: &MutationTableContext) -> Object {
 	)
 }
 
+fn add_relate_field(
+	mutation: Object,
+	tc: &MutationTableContext,
+	input_name: Option<&str>,
+) -> Object {
+	let fds = tc.fds.clone();
+	let kvs = tc.kvs.clone();
+	let tb_name = tc.tb_name.clone();
+	let endpoints = RelationEndpoints::from(tc);
+	let field = Field::new(
+		format!("relate{}", tc.cap_name),
+		TypeRef::named(tc.tb_name_str.as_str()),
+		move |ctx| {
+			let fds = fds.clone();
+			let kvs = kvs.clone();
+			let tb_name = tb_name.clone();
+			let endpoints = endpoints.clone();
+			FieldFuture::new(async move {
+				let sess = ctx.data::<Arc<Session>>()?;
+				let args = ctx.args.as_index_map();
+				let in_str = args
+					.get("in")
+					.and_then(GqlValueUtils::as_string)
+					.ok_or_else(|| resolver_error("Missing required 'in' argument"))?;
+				let out_str = args
+					.get("out")
+					.and_then(GqlValueUtils::as_string)
+					.ok_or_else(|| resolver_error("Missing required 'out' argument"))?;
+				let id_opt = args.get("id").and_then(GqlValueUtils::as_string);
+				let content = match args.get("data") {
+					Some(GqlValue::Object(data_obj)) => gql_input_to_sql_object(
+						data_obj,
+						&fds,
+						&["id", "in", "out"],
+						tb_name.as_str(),
+					)?,
+					_ => SurObject::default(),
+				};
+
+				execute_relate(&kvs, sess, &tb_name, &in_str, &out_str, content, id_opt, &endpoints)
+					.await
+			})
+		},
+	)
+	.description(format!("Relate two records through a new `{}` record", tc.tb_name_str))
+	.argument(InputValue::new("in", TypeRef::named_nn(TypeRef::ID)))
+	.argument(InputValue::new("out", TypeRef::named_nn(TypeRef::ID)))
+	.argument(InputValue::new("id", TypeRef::named(TypeRef::ID)));
+	match input_name {
+		Some(input_name) => {
+			mutation.field(field.argument(InputValue::new("data", TypeRef::named(input_name))))
+		}
+		None => mutation.field(field),
+	}
+}
+
 // ---------------------------------------------------------------------------
 // Bulk mutation field builders
 // ---------------------------------------------------------------------------
@@ -465,6 +588,7 @@ fn add_create_many_field(mutation: Object, tc: &MutationTableContext, input_name
 	let kvs = tc.kvs.clone();
 	let tb_name = tc.tb_name.clone();
 	let is_relation = tc.is_relation;
+	let endpoints = RelationEndpoints::from(tc);
 	mutation.field(
 		Field::new(
 			format!("createMany{}", tc.cap_name),
@@ -473,6 +597,7 @@ fn add_create_many_field(mutation: Object, tc: &MutationTableContext, input_name
 				let fds = fds.clone();
 				let kvs = kvs.clone();
 				let tb_name = tb_name.clone();
+				let endpoints = endpoints.clone();
 				FieldFuture::new(async move {
 					let sess = ctx.data::<Arc<Session>>()?;
 					let args = ctx.args.as_index_map();
@@ -490,8 +615,10 @@ fn add_create_many_field(mutation: Object, tc: &MutationTableContext, input_name
 						let id_opt = data_obj.get("id").and_then(GqlValueUtils::as_string);
 
 						let res = if is_relation {
-							execute_relate_create(&kvs, sess, &tb_name, data_obj, &fds, id_opt)
-								.await
+							execute_relate_create(
+								&kvs, sess, &tb_name, data_obj, &fds, id_opt, &endpoints,
+							)
+							.await
 						} else {
 							execute_normal_create(&kvs, sess, &tb_name, data_obj, &fds, id_opt)
 								.await
@@ -517,13 +644,18 @@ fn add_create_many_field(mutation: Object, tc: &MutationTableContext, input_name
 	)
 }
 
-fn add_update_many_field(mutation: Object, tc: &MutationTableContext, input_name: &str) -> Object {
+fn add_update_many_field(
+	mutation: Object,
+	tc: &MutationTableContext,
+	input_name: &str,
+	mode: UpdateMode,
+) -> Object {
 	let fds = tc.fds.clone();
 	let kvs = tc.kvs.clone();
 	let tb_name = tc.tb_name.clone();
 	mutation.field(
 		Field::new(
-			format!("updateMany{}", tc.cap_name),
+			format!("{}Many{}", mode.prefix(), tc.cap_name),
 			TypeRef::named_nn_list_nn(tc.tb_name_str.as_str()),
 			move |ctx| {
 				let fds = fds.clone();
@@ -536,18 +668,13 @@ fn add_update_many_field(mutation: Object, tc: &MutationTableContext, input_name
 					let data_obj = get_data_object(args)?;
 					let content =
 						gql_input_to_sql_object(data_obj, &fds, &["id"], tb_name.as_str())?;
-					let data = if content.0.is_empty() {
-						None
-					} else {
-						Some(Data::MergeExpression(Value::Object(content).into_literal()))
-					};
 
 					let cond = parse_where_arg(args, &fds, tb_name.as_str())?;
 
 					let stmt = UpdateStatement {
 						only: false,
 						what: vec![Expr::Table(tb_name)],
-						data,
+						data: merge_data(content),
 						cond,
 						output: None,
 						timeout: Expr::Literal(Literal::None),
@@ -563,7 +690,14 @@ fn add_update_many_field(mutation: Object, tc: &MutationTableContext, input_name
 				})
 			},
 		)
-		.description(format!("Update multiple `{}` records matching a filter", tc.tb_name_str))
+		.description(match mode {
+			UpdateMode::Update => {
+				format!("Update multiple `{}` records matching a filter", tc.tb_name_str)
+			}
+			UpdateMode::Merge => {
+				format!("Merge data into multiple `{}` records matching a filter", tc.tb_name_str)
+			}
+		})
 		.argument(InputValue::new("where", TypeRef::named(tc.table_filter_name.as_str())))
 		.argument(InputValue::new("data", TypeRef::named_nn(input_name))),
 	)
@@ -674,6 +808,76 @@ fn add_delete_many_field(mutation: Object, tc: &MutationTableContext) -> Object
 // Shared helpers for mutation resolvers
 // ---------------------------------------------------------------------------
 
+/// The name under which an update mutation is generated. Both modes MERGE the
+/// provided data into the existing records.
+#[derive(Clone, Copy)]
+enum UpdateMode {
+	/// The `update{Table}` and `updateMany{Table}` mutations.
+	Update,
+	/// The `merge{Table}` and `mergeMany{Table}` mutations.
+	Merge,
+}
+
+impl UpdateMode {
+	/// The prefix of the generated mutation field names.
+	fn prefix(self) -> &'static str {
+		match self {
+			UpdateMode::Update => "update",
+			UpdateMode::Merge => "merge",
+		}
+	}
+}
+
+/// Build the MERGE data clause of an UPDATE statement from the input content.
+fn merge_data(content: SurObject) -> Option<Data> {
+	if content.0.is_empty() {
+		None
+	} else {
+		Some(Data::MergeExpression(Value::Object(content).into_literal()))
+	}
+}
+
+/// The tables a relation table may connect, from its catalog definition.
+#[derive(Clone)]
+struct RelationEndpoints {
+	from_tables: Arc<[String]>,
+	to_tables: Arc<[String]>,
+}
+
+impl From<&MutationTableContext> for RelationEndpoints {
+	fn from(tc: &MutationTableContext) -> Self {
+		Self {
+			from_tables: tc.from_tables.clone(),
+			to_tables: tc.to_tables.clone(),
+		}
+	}
+}
+
+impl RelationEndpoints {
+	/// Check that the `in` and `out` records belong to the tables allowed by
+	/// the relation definition.
+	fn check(&self, tb_name: &TableName, from: &RecordId, to: &RecordId) -> Result<(), GqlError> {
+		let allowed = |tables: &[String], rid: &RecordId| {
+			tables.is_empty() || tables.iter().any(|t| t.as_str() == rid.table.as_str())
+		};
+		if !allowed(&self.from_tables, from) {
+			return Err(resolver_error(format!(
+				"Relation `{tb_name}` can not originate from `{}`, expected one of: {}",
+				from.to_sql(),
+				self.from_tables.join(", ")
+			)));
+		}
+		if !allowed(&self.to_tables, to) {
+			return Err(resolver_error(format!(
+				"Relation `{tb_name}` can not point to `{}`, expected one of: {}",
+				to.to_sql(),
+				self.to_tables.join(", ")
+			)));
+		}
+		Ok(())
+	}
+}
+
 /// Extract the required `data` argument as an object from the args map.
 fn get_data_object(args: &IndexMap<Name, GqlValue>) -> Result<&IndexMap<Name, GqlValue>, GqlError> {
 	args.get("data")
@@ -741,7 +945,8 @@ async fn execute_normal_create(
 	extract_single_record(res)
 }
 
-/// Execute a RELATE for a relation table creation.
+/// Execute a RELATE for a relation table creation, reading the `in` and `out`
+/// records from the input data.
 async fn execute_relate_create(
 	kvs: &Arc<Datastore>,
 	sess: &Arc<Session>,
@@ -749,6 +954,7 @@ async fn execute_relate_create(
 	data_obj: &IndexMap<Name, GqlValue>,
 	fds: &[FieldDefinition],
 	id_opt: Option<String>,
+	endpoints: &RelationEndpoints,
 ) -> Result<Option<FieldValue<'static>>, async_graphql::Error> {
 	let in_str = data_obj
 		.get("in")
@@ -759,11 +965,27 @@ async fn execute_relate_create(
 		.and_then(GqlValueUtils::as_string)
 		.ok_or_else(|| resolver_error("Relation create requires 'out' field"))?;
 
-	let from_rid = parse_full_record_id(&in_str)?;
-	let to_rid = parse_full_record_id(&out_str)?;
-
 	let content = gql_input_to_sql_object(data_obj, fds, &["id", "in", "out"], tb_name.as_str())?;
 
+	execute_relate(kvs, sess, tb_name, &in_str, &out_str, content, id_opt, endpoints).await
+}
+
+/// Execute a RELATE between two records through a relation table.
+#[allow(clippy::too_many_arguments)]
+async fn execute_relate(
+	kvs: &Arc<Datastore>,
+	sess: &Arc<Session>,
+	tb_name: &TableName,
+	in_str: &str,
+	out_str: &str,
+	content: SurObject,
+	id_opt: Option<String>,
+	endpoints: &RelationEndpoints,
+) -> Result<Option<FieldValue<'static>>, async_graphql::Error> {
+	let from_rid = parse_full_record_id(in_str)?;
+	let to_rid = parse_full_record_id(out_str)?;
+	endpoints.check(tb_name, &from_rid, &to_rid)?;
+
 	let through = match id_opt {
 		Some(id_str) => {
 			let rid = parse_record_id(tb_name, &id_str)?;
@@ -0,0 +1,92 @@
+//! Persisted query allow-list for the GraphQL endpoint.
+//!
+//! When the GraphQL config defines persisted queries
+//! (`DEFINE CONFIG GRAPHQL ... PERSISTED ["query { ... }"]`), only those query
+//! documents can be executed. Clients either send the full query document, or
+//! identify it by its SHA-256 hash using the Apollo persisted query protocol:
+//!
+//! ```json
+//! { "extensions": { "persistedQuery": { "version": 1, "sha256Hash": "<hash>" } } }
+//! ```
+//!
+//! Any other query is rejected before it is parsed or executed, which locks
+//! down the GraphQL surface of public-facing deployments to a known set of
+//! operations.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_graphql::extensions::{
+	Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest,
+};
+use async_graphql::{Request, ServerError, ServerResult, Value as GqlValue};
+
+use crate::catalog::persisted_query_hash;
+
+/// Schema extension which only allows persisted queries to be executed.
+pub(super) struct PersistedQueries {
+	/// The persisted query documents, keyed by their hash
+	queries: Arc<BTreeMap<String, String>>,
+}
+
+impl PersistedQueries {
+	/// Create the extension from the persisted query documents, keyed by their hash.
+	pub(super) fn new(queries: &BTreeMap<String, String>) -> Self {
+		Self {
+			queries: Arc::new(queries.clone()),
+		}
+	}
+}
+
+impl ExtensionFactory for PersistedQueries {
+	fn create(&self) -> Arc<dyn Extension> {
+		Arc::new(PersistedQueriesExtension {
+			queries: self.queries.clone(),
+		})
+	}
+}
+
+struct PersistedQueriesExtension {
+	queries: Arc<BTreeMap<String, String>>,
+}
+
+#[async_trait::async_trait]
+impl Extension for PersistedQueriesExtension {
+	async fn prepare_request(
+		&self,
+		ctx: &ExtensionContext<'_>,
+		mut request: Request,
+		next: NextPrepareRequest<'_>,
+	) -> ServerResult<Request> {
+		let hash = match request.extensions.get("persistedQuery") {
+			Some(GqlValue::Object(persisted)) => match persisted.get("sha256Hash") {
+				Some(GqlValue::String(hash)) => {
+					// A query sent alongside its hash must match the hash
+					if !request.query.is_empty() && persisted_query_hash(&request.query) != *hash {
+						return Err(ServerError::new(
+							"Provided sha256Hash does not match query",
+							None,
+						));
+					}
+					hash.clone()
+				}
+				_ => return Err(ServerError::new("Invalid persisted query extension", None)),
+			},
+			Some(_) => return Err(ServerError::new("Invalid persisted query extension", None)),
+			None => persisted_query_hash(&request.query),
+		};
+		match self.queries.get(&hash) {
+			Some(query) => request.query.clone_from(query),
+			None if request.extensions.contains_key("persistedQuery") => {
+				return Err(ServerError::new("PersistedQueryNotFound", None));
+			}
+			None => {
+				return Err(ServerError::new(
+					"Only persisted queries are allowed by the GraphQL configuration",
+					None,
+				));
+			}
+		}
+		next.run(ctx, request).await
+	}
+}
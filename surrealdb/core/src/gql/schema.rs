@@ -18,7 +18,7 @@
 //! - **Custom scalars** -- registers scalars like `uuid`, `decimal`, `datetime`, `duration`,
 //!   `bytes`, `object`, `any`, `JSON`, and `null`.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 
 use async_graphql::dynamic::indexmap::IndexMap;
@@ -38,15 +38,17 @@ use super::error::{GqlError, resolver_error};
 use super::ext::ValidatorExt;
 use crate::catalog::providers::{AuthorisationProvider, DatabaseProvider, TableProvider};
 use crate::catalog::{
-	DatabaseId, GraphQLConfig, GraphQLFunctionsConfig, GraphQLIntrospectionConfig,
-	GraphQLTablesConfig, NamespaceId,
+	DatabaseId, GraphQLConfig, GraphQLFederationConfig, GraphQLFunctionsConfig,
+	GraphQLIntrospectionConfig, GraphQLTablesConfig, NamespaceId,
 };
 use crate::dbs::Session;
 use crate::expr::kind::{GeometryKind, KindLiteral};
 use crate::expr::{Expr, Kind, Literal};
 use crate::gql::error::{internal_error, schema_error, type_error};
+use crate::gql::federation::{entity_resolver, with_entity_keys};
 use crate::gql::functions::process_fns;
 use crate::gql::mutations::process_mutations;
+use crate::gql::persisted::PersistedQueries;
 use crate::gql::relations::collect_relations;
 use crate::gql::subscriptions::process_subscriptions;
 use crate::gql::tables::process_tbs;
@@ -82,7 +84,8 @@ pub(crate) struct SchemaContext<'a> {
 ///   unions, and interfaces.
 ///
 /// The `gql_config` controls which tables and functions are exposed, and
-/// sets depth/complexity limits and introspection behaviour.
+/// sets depth/complexity limits, introspection, federation and persisted
+/// query behaviour.
 pub async fn generate_schema(
 	datastore: &Arc<Datastore>,
 	session: &Session,
@@ -230,6 +233,20 @@ pub async fn generate_schema(
 	if matches!(gql_config.introspection, GraphQLIntrospectionConfig::None) {
 		schema = schema.disable_introspection();
 	}
+	// Only allow persisted queries when an allow-list is configured
+	if !gql_config.persisted_queries.is_empty() {
+		schema = schema.extension(PersistedQueries::new(&gql_config.persisted_queries));
+	}
+	// Expose the schema as a federation subgraph when configured to do so
+	if matches!(gql_config.federation, GraphQLFederationConfig::Auto) {
+		let entities: HashSet<String> =
+			tbs.iter().flat_map(|tbs| tbs.iter()).map(|tb| tb.name.clone().into_string()).collect();
+		types = with_entity_keys(types, &entities);
+		schema = schema.enable_federation();
+		if !entities.is_empty() {
+			schema = schema.entity_resolver(entity_resolver(kvs.clone(), Arc::new(entities)));
+		}
+	}
 
 	if let Some(mutation) = mutation_obj {
 		schema = schema.register(mutation);
@@ -9,6 +9,10 @@
 //!
 //! - `person(limit, start, order, filter/where, version)` -- list query returning `[person!]!`
 //! - `_get_person(id, version)` -- single-record fetch returning `person`
+//! - `_connection_person(first, after, last, before, order, filter/where, version)` -- cursor
+//!   paginated query returning `_connection_person!` (see [`super::connections`])
+//! - `_aggregate_person(filter/where, version)` -- aggregate query returning `_aggregate_person!`
+//!   (see [`super::aggregates`])
 //!
 //! A generic `_get(id, version)` field is also added to fetch any record by
 //! its full ID string (e.g. `"person:alice"`).
@@ -50,6 +54,8 @@ use async_graphql::dynamic::{
 use async_graphql::{Name, Value as GqlValue};
 use surrealdb_types::ToSql;
 
+use super::aggregates::{make_aggregate_type, make_table_aggregate_field};
+use super::connections::{make_connection_types, make_page_info_type, make_table_connection_field};
 use super::error::{GqlError, resolver_error};
 use super::relations::{RelationDirection, RelationInfo};
 use super::schema::{
@@ -135,7 +141,9 @@ fn version_to_expr(version: &Option<Datetime>) -> Expr {
 
 /// Parse the optional `version` argument from GraphQL query arguments.
 /// Expects an ISO 8601 / RFC 3339 datetime string (e.g. `"2024-06-01T00:00:00Z"`).
-fn parse_version_arg(args: &IndexMap<Name, GqlValue>) -> Result<Option<Datetime>, GqlError> {
+pub(super) fn parse_version_arg(
+	args: &IndexMap<Name, GqlValue>,
+) -> Result<Option<Datetime>, GqlError> {
 	match args.get("version") {
 		Some(GqlValue::String(s)) => {
 			let dt = crate::syn::datetime(s)
@@ -169,7 +177,9 @@ fn parse_limit_arg(args: &IndexMap<Name, GqlValue>) -> Option<Limit> {
 /// ```
 /// Each node has exactly one of `asc` or `desc` (an enum value naming the
 /// field) and an optional `then` link to the next ordering criterion.
-fn parse_order_arg(args: &IndexMap<Name, GqlValue>) -> Result<Option<Ordering>, GqlError> {
+pub(super) fn parse_order_arg(
+	args: &IndexMap<Name, GqlValue>,
+) -> Result<Option<Ordering>, GqlError> {
 	let order = args.get("order");
 	match order {
 		Some(GqlValue::Object(o)) => {
@@ -232,9 +242,12 @@ pub(crate) fn parse_filter_arg(
 
 /// Build a `SELECT * FROM ONLY <record_id>` statement with an optional version.
 ///
-/// Used by `_get_`, `_get`, and record-link dereferencing to fetch a single
+/// Used by `_get_`, `_get` and record-link dereferencing to fetch a single
 /// record's full data for caching.
-fn select_all_from_record(rid: &RecordId, version: &Option<Datetime>) -> SelectStatement {
+pub(super) fn select_all_from_record(
+	rid: &RecordId,
+	version: &Option<Datetime>,
+) -> SelectStatement {
 	SelectStatement {
 		what: vec![Value::RecordId(rid.clone()).into_literal()],
 		fields: Fields::all(),
@@ -255,6 +268,17 @@ fn select_all_from_record(rid: &RecordId, version: &Option<Datetime>) -> SelectS
 	}
 }
 
+/// Build a `SELECT * FROM <record_id>, ...` statement for a set of records.
+///
+/// Used by federation entity resolution to fetch the records referenced by
+/// the entity representations of a table in a single query.
+pub(super) fn select_all_from_records(rids: &[RecordId]) -> SelectStatement {
+	SelectStatement {
+		what: rids.iter().map(|rid| Value::RecordId(rid.clone()).into_literal()).collect(),
+		..select_all_from_table(Expr::Literal(Literal::None), None, None, None, None, &None)
+	}
+}
+
 /// Build a `SELECT VALUE <field> FROM ONLY <record_id>` statement with an
 /// optional version.
 ///
@@ -292,7 +316,7 @@ fn select_field_from_record(
 /// ordering, pagination, and versioning.
 ///
 /// Used by the table list query and relation field resolvers.
-fn select_all_from_table(
+pub(super) fn select_all_from_table(
 	what: Expr,
 	cond: Option<Cond>,
 	order: Option<Ordering>,
@@ -321,7 +345,7 @@ fn select_all_from_table(
 }
 
 /// Execute a `SelectStatement` via `LogicalPlan` and return the result.
-async fn execute_select(
+pub(super) async fn execute_select(
 	ds: &Datastore,
 	sess: &Session,
 	stmt: SelectStatement,
@@ -1114,6 +1138,8 @@ pub async fn process_tbs(
 		// Add query root fields for this table
 		query = query.field(make_table_list_field(tb, fds.clone(), ctx.datastore.clone()));
 		query = query.field(make_table_get_field(tb, ctx.datastore.clone()));
+		query = query.field(make_table_connection_field(tb, fds.clone(), ctx.datastore.clone()));
+		query = query.field(make_table_aggregate_field(tb, fds.clone(), ctx.datastore.clone()));
 
 		// Build and register the table's type system
 		let tt = build_table_type(
@@ -1128,8 +1154,18 @@ pub async fn process_tbs(
 		types.push(tt.order.into());
 		types.push(Type::Enum(tt.orderable));
 		types.push(Type::InputObject(tt.filter));
+
+		// Register the connection and aggregate types for this table
+		let (connection, edge) = make_connection_types(tb);
+		types.push(Type::Object(connection));
+		types.push(Type::Object(edge));
+		let aggregate = make_aggregate_type(tb, &fds, types)?;
+		types.push(Type::Object(aggregate));
 	}
 
+	// Register the page info type shared by all table connections
+	types.push(Type::Object(make_page_info_type()));
+
 	// Add generic _get query field for fetching any record by full ID
 	query = query.field(make_generic_get_field(ctx.datastore.clone()));
 
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tokio::sync::RwLock;
 
+use crate::catalog;
 use crate::dbs::capabilities::NetTarget;
 use crate::err::Error;
 use crate::kvs::Datastore;
@@ -24,6 +25,23 @@ pub(crate) struct JwksCacheEntry {
 	time: DateTime<Utc>,
 }
 
+pub(crate) type OidcCache = HashMap<String, OidcCacheEntry>;
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct OidcCacheEntry {
+	provider: OidcProvider,
+	time: DateTime<Utc>,
+}
+
+// The subset of the OpenID Provider metadata which is used to verify tokens
+// Source: https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderMetadata
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct OidcProvider {
+	pub(crate) issuer: String,
+	pub(crate) jwks_uri: String,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub(crate) introspection_endpoint: Option<String>,
+}
+
 #[cfg(test)]
 static CACHE_EXPIRATION: LazyLock<chrono::Duration> = LazyLock::new(|| Duration::seconds(1));
 #[cfg(not(test))]
@@ -229,6 +247,186 @@ pub(super) async fn config(
 	}
 }
 
+// Generates a verification configuration for a token issued by an OpenID
+// Connect provider The provider metadata is found through discovery from the
+// issuer URL, and the JWKS object it references is used to find the key The
+// issuer claim is always validated, and the audience claim is validated when
+// an audience has been defined for the access method
+// Source: https://openid.net/specs/openid-connect-discovery-1_0.html
+pub(super) async fn oidc_config(
+	kvs: &Datastore,
+	kid: &str,
+	oidc: &catalog::JwtAccessVerifyOidc,
+	token_alg: jsonwebtoken::Algorithm,
+) -> Result<(DecodingKey, Validation)> {
+	// Discover the provider metadata from the issuer
+	let provider = discover(kvs, &oidc.issuer).await?;
+	// Retrieve the verification configuration from the provider JWKS object
+	let (dec, mut val) = config(kvs, kid, &provider.jwks_uri, token_alg).await?;
+	// Tokens must have been issued by the provider
+	val.set_issuer(&[&provider.issuer]);
+	val.required_spec_claims.insert("iss".to_string());
+	// Tokens must be intended for the audience, if defined
+	if let Some(aud) = &oidc.audience {
+		val.validate_aud = true;
+		val.set_audience(&[aud]);
+		val.required_spec_claims.insert("aud".to_string());
+	}
+
+	Ok((dec, val))
+}
+
+// Retrieves the metadata of an OpenID Connect provider either from the local
+// cache or from the well-known discovery location of the issuer Validates that
+// the issuer in the metadata matches the issuer which was defined
+pub(super) async fn discover(kvs: &Datastore, issuer: &str) -> Result<OidcProvider> {
+	// Retrieve OpenID Connect provider cache
+	let cache = kvs.oidc_cache();
+	let key = cache_key_from_url(issuer);
+	// Check that the cached provider metadata has not expired yet
+	if let Some(entry) = cache.read().await.get(&key)
+		&& Utc::now().signed_duration_since(entry.time) < *CACHE_EXPIRATION
+	{
+		trace!("Successfully fetched OpenID Connect provider metadata from local cache");
+		return Ok(entry.provider.clone());
+	}
+	// The metadata is found relative to the issuer URL
+	// Source: https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderConfig
+	let url = format!("{}/.well-known/openid-configuration", issuer.trim_end_matches('/'));
+	// Check that the datastore capabilities allow connections to the URL host
+	if let Err(err) = check_capabilities_url(kvs, &url) {
+		warn!("Network access to OpenID Connect discovery location is not allowed: '{}'", err);
+		bail!(Error::InvalidAuth); // Return opaque error
+	}
+	let res = match send(Client::new().get(&url)).await {
+		Ok(res) => res,
+		Err(err) => {
+			warn!("Failed to fetch OpenID Connect provider metadata: '{}'", err);
+			bail!(Error::InvalidAuth); // Return opaque error
+		}
+	};
+	if !res.status().is_success() {
+		warn!(
+			"Unsuccessful HTTP status code received when fetching OpenID Connect provider metadata: '{:?}'",
+			res.status()
+		);
+		bail!(Error::InvalidAuth); // Return opaque error
+	}
+	let provider = match serde_json::from_slice::<OidcProvider>(&res.bytes().await?) {
+		Ok(provider) => provider,
+		Err(err) => {
+			warn!("Failed to parse malformed OpenID Connect provider metadata: '{}'", err);
+			bail!(Error::InvalidAuth); // Return opaque error
+		}
+	};
+	// The issuer in the metadata must be identical to the one used for discovery
+	// Source: https://openid.net/specs/openid-connect-discovery-1_0.html#ProviderConfigurationValidation
+	if provider.issuer.trim_end_matches('/') != issuer.trim_end_matches('/') {
+		warn!(
+			"Issuer in OpenID Connect provider metadata '{}' does not match the defined issuer '{}'",
+			provider.issuer, issuer
+		);
+		bail!(Error::InvalidAuth); // Return opaque error
+	}
+	// Cache the provider metadata by its issuer
+	let entry = OidcCacheEntry {
+		provider: provider.clone(),
+		time: Utc::now(),
+	};
+	cache.write().await.insert(key, entry);
+	trace!("Successfully added OpenID Connect provider metadata to local cache");
+
+	Ok(provider)
+}
+
+// Queries the token introspection endpoint of an OpenID Connect provider to
+// determine the state of a token, which is usually opaque Returns the claims
+// of the token only if it is active, and if its issuer and audience (when
+// present in the response) are valid for the access method
+// Source: https://datatracker.ietf.org/doc/html/rfc7662
+pub(super) async fn introspect(
+	kvs: &Datastore,
+	oidc: &catalog::JwtAccessVerifyOidc,
+	token: &str,
+) -> Result<serde_json::Map<String, serde_json::Value>> {
+	// Check that introspection has been enabled for the access method
+	let Some(credentials) = &oidc.introspect else {
+		debug!("Token introspection has not been enabled for the access method");
+		bail!(Error::InvalidAuth); // Return opaque error
+	};
+	// Discover the provider metadata from the issuer
+	let provider = discover(kvs, &oidc.issuer).await?;
+	let Some(url) = &provider.introspection_endpoint else {
+		warn!("The OpenID Connect provider does not advertise an introspection endpoint");
+		bail!(Error::InvalidAuth); // Return opaque error
+	};
+	// Check that the datastore capabilities allow connections to the URL host
+	if let Err(err) = check_capabilities_url(kvs, url) {
+		warn!("Network access to token introspection endpoint is not allowed: '{}'", err);
+		bail!(Error::InvalidAuth); // Return opaque error
+	}
+	// The token is sent as a form parameter, authenticating as the client
+	// Source: https://datatracker.ietf.org/doc/html/rfc7662#section-2.1
+	let body = url::form_urlencoded::Serializer::new(String::new())
+		.append_pair("token", token)
+		.append_pair("token_type_hint", "access_token")
+		.finish();
+	let req = Client::new()
+		.post(url)
+		.basic_auth(&credentials.client, Some(&credentials.secret))
+		.header(reqwest::header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+		.header(reqwest::header::ACCEPT, "application/json")
+		.body(body);
+	let res = match send(req).await {
+		Ok(res) => res,
+		Err(err) => {
+			warn!("Failed to query token introspection endpoint: '{}'", err);
+			bail!(Error::InvalidAuth); // Return opaque error
+		}
+	};
+	if !res.status().is_success() {
+		warn!(
+			"Unsuccessful HTTP status code received from token introspection endpoint: '{:?}'",
+			res.status()
+		);
+		bail!(Error::InvalidAuth); // Return opaque error
+	}
+	let claims = match serde_json::from_slice::<serde_json::Value>(&res.bytes().await?) {
+		Ok(serde_json::Value::Object(claims)) => claims,
+		_ => {
+			warn!("Failed to parse malformed token introspection response");
+			bail!(Error::InvalidAuth); // Return opaque error
+		}
+	};
+	// The token is only valid if it is active
+	// Source: https://datatracker.ietf.org/doc/html/rfc7662#section-2.2
+	if claims.get("active").and_then(serde_json::Value::as_bool) != Some(true) {
+		debug!("Token introspection determined that the token is not active");
+		bail!(Error::InvalidAuth); // Return opaque error
+	}
+	// The token must have been issued by the provider
+	if let Some(iss) = claims.get("iss")
+		&& iss.as_str() != Some(provider.issuer.as_str())
+	{
+		debug!("Token introspection returned an unexpected issuer: '{}'", iss);
+		bail!(Error::InvalidAuth); // Return opaque error
+	}
+	// The token must be intended for the audience, if defined
+	if let Some(aud) = &oidc.audience {
+		let valid = match claims.get("aud") {
+			Some(serde_json::Value::String(v)) => v == aud,
+			Some(serde_json::Value::Array(v)) => v.iter().any(|v| v.as_str() == Some(aud)),
+			_ => false,
+		};
+		if !valid {
+			debug!("Token introspection returned an unexpected audience");
+			bail!(Error::InvalidAuth); // Return opaque error
+		}
+	}
+
+	Ok(claims)
+}
+
 // Checks if network access to a remote location is allowed by the datastore
 // capabilities Attempts to find a relevant JWK object inside a JWKS object
 // fetched from the remote location
@@ -299,11 +497,8 @@ fn check_capabilities_url(kvs: &Datastore, url: &str) -> Result<()> {
 	Ok(())
 }
 
-// Attempts to fetch a JWKS object from a remote location and stores it in the
-// cache if successful
-async fn fetch_jwks_from_url(cache: &Arc<RwLock<JwksCache>>, url: &str) -> Result<JwkSet> {
-	let client = Client::new();
-	let req = client.get(url);
+// Sends a request to a remote location, applying the remote timeout
+async fn send(req: reqwest::RequestBuilder) -> Result<reqwest::Response> {
 	// Add a User-Agent header so that WAF rules don't reject the request
 	#[cfg(not(target_family = "wasm"))]
 	let req = req.header(reqwest::header::USER_AGENT, &*crate::cnf::SURREALDB_USER_AGENT);
@@ -311,6 +506,13 @@ async fn fetch_jwks_from_url(cache: &Arc<RwLock<JwksCache>>, url: &str) -> Resul
 	let res = req.timeout((*REMOTE_TIMEOUT).to_std().expect("valid duration")).send().await?;
 	#[cfg(target_family = "wasm")]
 	let res = req.send().await?;
+	Ok(res)
+}
+
+// Attempts to fetch a JWKS object from a remote location and stores it in the
+// cache if successful
+async fn fetch_jwks_from_url(cache: &Arc<RwLock<JwksCache>>, url: &str) -> Result<JwkSet> {
+	let res = send(Client::new().get(url)).await?;
 	if !res.status().is_success() {
 		warn!(
 			"Unsuccessful HTTP status code received when fetching JWKS object from remote location: '{:?}'",
@@ -959,4 +1161,171 @@ mod tests {
 			"Remote request was not aborted immediately after timeout"
 		);
 	}
+
+	fn oidc(issuer: &str) -> catalog::JwtAccessVerifyOidc {
+		catalog::JwtAccessVerifyOidc {
+			issuer: issuer.to_string(),
+			audience: None,
+			roles: None,
+			introspect: None,
+		}
+	}
+
+	async fn mount_discovery(mock_server: &MockServer, base_path: &str, issuer: &str) {
+		let response = ResponseTemplate::new(200).set_body_json(serde_json::json!({
+			"issuer": issuer,
+			"jwks_uri": format!("{}/{base_path}/jwks.json", mock_server.uri()),
+			"introspection_endpoint": format!("{}/{base_path}/introspect", mock_server.uri()),
+		}));
+		Mock::given(method("GET"))
+			.and(path(format!("{base_path}/.well-known/openid-configuration")))
+			.respond_with(response)
+			.expect(1)
+			.mount(mock_server)
+			.await;
+	}
+
+	#[tokio::test]
+	async fn test_oidc_discovery() {
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+		let base_path = random_path();
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{base_path}", mock_server.uri());
+		mount_discovery(&mock_server, &base_path, &issuer).await;
+		Mock::given(method("GET"))
+			.and(path(format!("{base_path}/jwks.json")))
+			.respond_with(ResponseTemplate::new(200).set_body_json(DEFAULT_JWKS.clone()))
+			.expect(1)
+			.mount(&mock_server)
+			.await;
+
+		// Get token configuration through discovery
+		let (_, val) =
+			oidc_config(&ds, "test_1", &oidc(&issuer), jsonwebtoken::Algorithm::RS256).await.unwrap();
+		assert_eq!(val.iss, Some([issuer.clone()].into()));
+		assert!(val.required_spec_claims.contains("iss"));
+		assert!(!val.validate_aud, "Audience should not be validated when not defined");
+
+		// Get token configuration with an audience from the local cache
+		let mut with_aud = oidc(&issuer);
+		with_aud.audience = Some("surrealdb".to_string());
+		let (_, val) =
+			oidc_config(&ds, "test_2", &with_aud, jsonwebtoken::Algorithm::RS256).await.unwrap();
+		assert!(val.validate_aud, "Audience should be validated when defined");
+		assert_eq!(val.aud, Some(["surrealdb".to_string()].into()));
+		assert!(val.required_spec_claims.contains("aud"));
+	}
+
+	#[tokio::test]
+	async fn test_oidc_discovery_issuer_mismatch() {
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+		let base_path = random_path();
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{base_path}", mock_server.uri());
+		mount_discovery(&mock_server, &base_path, "https://attacker.example.com").await;
+
+		// Discovery must fail when the metadata is for a different issuer
+		let res = discover(&ds, &issuer).await;
+		assert!(res.is_err(), "Unexpected success discovering metadata for a different issuer");
+	}
+
+	#[tokio::test]
+	async fn test_oidc_discovery_capabilities_default() {
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(Capabilities::default());
+		let base_path = random_path();
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{base_path}", mock_server.uri());
+		Mock::given(method("GET"))
+			.and(path(format!("{base_path}/.well-known/openid-configuration")))
+			.respond_with(ResponseTemplate::new(200))
+			.expect(0)
+			.mount(&mock_server)
+			.await;
+
+		// Discovery must not reach unallowed remote locations
+		let res = discover(&ds, &issuer).await;
+		assert!(res.is_err(), "Unexpected success discovering metadata from unallowed location");
+	}
+
+	#[tokio::test]
+	async fn test_introspection() {
+		use wiremock::matchers::{basic_auth, body_string_contains};
+
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+		let base_path = random_path();
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{base_path}", mock_server.uri());
+		mount_discovery(&mock_server, &base_path, &issuer).await;
+		let introspect_path = format!("{base_path}/introspect");
+		Mock::given(method("POST"))
+			.and(path(&introspect_path))
+			.and(basic_auth("surrealdb", "secret"))
+			.and(body_string_contains("token=active"))
+			.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+				"active": true,
+				"iss": issuer,
+				"aud": ["surrealdb", "other"],
+				"sub": "tobie",
+			})))
+			.mount(&mock_server)
+			.await;
+		Mock::given(method("POST"))
+			.and(path(&introspect_path))
+			.and(basic_auth("surrealdb", "secret"))
+			.and(body_string_contains("token=inactive"))
+			.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+				"active": false,
+			})))
+			.mount(&mock_server)
+			.await;
+		Mock::given(method("POST"))
+			.and(path(&introspect_path))
+			.respond_with(ResponseTemplate::new(401))
+			.mount(&mock_server)
+			.await;
+
+		let mut config = oidc(&issuer);
+		// Introspection must be enabled for the access method
+		let res = introspect(&ds, &config, "active").await;
+		assert!(res.is_err(), "Unexpected success introspecting without client credentials");
+
+		config.introspect = Some(catalog::JwtAccessIntrospect {
+			client: "surrealdb".to_string(),
+			secret: "secret".to_string(),
+		});
+		// Active tokens return their claims
+		let claims = introspect(&ds, &config, "active").await.unwrap();
+		assert_eq!(claims.get("sub"), Some(&serde_json::json!("tobie")));
+		// Inactive tokens are rejected
+		let res = introspect(&ds, &config, "inactive").await;
+		assert!(res.is_err(), "Unexpected success introspecting an inactive token");
+
+		// Tokens must be intended for the audience
+		config.audience = Some("surrealdb".to_string());
+		assert!(introspect(&ds, &config, "active").await.is_ok());
+		config.audience = Some("unknown".to_string());
+		let res = introspect(&ds, &config, "active").await;
+		assert!(res.is_err(), "Unexpected success introspecting a token for another audience");
+
+		// Invalid client credentials are rejected by the provider
+		config.audience = None;
+		config.introspect = Some(catalog::JwtAccessIntrospect {
+			client: "surrealdb".to_string(),
+			secret: "invalid".to_string(),
+		});
+		let res = introspect(&ds, &config, "active").await;
+		assert!(res.is_err(), "Unexpected success introspecting with invalid client credentials");
+	}
 }
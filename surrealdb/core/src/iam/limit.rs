@@ -0,0 +1,64 @@
+use anyhow::Result;
+
+use crate::catalog::RateLimit;
+use crate::catalog::providers::{
+	AuthorisationProvider, DatabaseProvider, NamespaceProvider, UserProvider,
+};
+use crate::dbs::Session;
+use crate::iam::Level;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, Transaction};
+
+/// Fetches the rate limits defined on the access method or system user which
+/// was used to authenticate the session. Anonymous sessions, and sessions
+/// authenticated by a definition without a `LIMIT` clause, return `None`.
+pub async fn rate_limit(kvs: &Datastore, session: &Session) -> Result<Option<RateLimit>> {
+	// Anonymous sessions have no definition
+	if session.au.is_anon() {
+		return Ok(None);
+	}
+	// Create a new readonly transaction
+	let tx = kvs.transaction(Read, Optimistic).await?;
+	// Fetch the limits from the relevant definition
+	let limit = catch!(tx, fetch(&tx, session).await);
+	// Close the transaction
+	tx.cancel().await?;
+	// Return the limits
+	Ok(limit)
+}
+
+async fn fetch(tx: &Transaction, session: &Session) -> Result<Option<RateLimit>> {
+	let id = session.au.id();
+	match (session.au.level(), session.ac.as_deref()) {
+		// Sessions authenticated with an access method
+		(Level::Root, Some(ac)) => Ok(tx.get_root_access(ac).await?.and_then(|v| v.limit.clone())),
+		(Level::Namespace(ns), Some(ac)) => match tx.get_ns_by_name(ns).await? {
+			Some(ns) => Ok(tx.get_ns_access(ns.namespace_id, ac).await?.and_then(|v| v.limit.clone())),
+			None => Ok(None),
+		},
+		(Level::Database(ns, db) | Level::Record(ns, db, _), Some(ac)) => {
+			match tx.get_db_by_name(ns, db).await? {
+				Some(db) => Ok(tx
+					.get_db_access(db.namespace_id, db.database_id, ac)
+					.await?
+					.and_then(|v| v.limit.clone())),
+				None => Ok(None),
+			}
+		}
+		// Sessions authenticated with system user credentials
+		(Level::Root, None) => Ok(tx.get_root_user(id).await?.and_then(|v| v.limit.clone())),
+		(Level::Namespace(ns), None) => match tx.get_ns_by_name(ns).await? {
+			Some(ns) => Ok(tx.get_ns_user(ns.namespace_id, id).await?.and_then(|v| v.limit.clone())),
+			None => Ok(None),
+		},
+		(Level::Database(ns, db), None) => match tx.get_db_by_name(ns, db).await? {
+			Some(db) => Ok(tx
+				.get_db_user(db.namespace_id, db.database_id, id)
+				.await?
+				.and_then(|v| v.limit.clone())),
+			None => Ok(None),
+		},
+		_ => Ok(None),
+	}
+}
@@ -10,12 +10,14 @@ pub mod clear;
 pub mod entities;
 pub(crate) mod file;
 pub mod issue;
+pub mod limit;
 #[cfg(feature = "jwks")]
 pub mod jwks;
 pub mod reset;
 pub mod signin;
 pub mod signup;
 pub mod token;
+pub mod totp;
 pub mod verify;
 
 pub use self::auth::*;
@@ -14,7 +14,9 @@ use super::access::{
 	authenticate_generic, authenticate_record, create_refresh_token_record,
 	revoke_refresh_token_record,
 };
-use super::verify::{verify_db_creds, verify_ns_creds, verify_root_creds};
+#[cfg(feature = "jwks")]
+use super::verify::token_roles;
+use super::verify::{verify_db_creds, verify_ns_creds, verify_root_creds, verify_totp};
 use super::{Actor, Level, Role};
 use crate::catalog;
 use crate::catalog::providers::{
@@ -123,8 +125,9 @@ pub async fn signin(
 					let db = db.into_string()?;
 					let user = user.into_string()?;
 					let pass = pass.into_string()?;
+					let totp = vars.get("totp").cloned().map(PublicValue::into_string).transpose()?;
 					// Attempt to signin to database
-					super::signin::db_user(kvs, session, ns, db, user, pass).await
+					super::signin::db_user(kvs, session, ns, db, user, pass, totp).await
 				}
 				_ => Err(anyhow::Error::new(Error::MissingUserOrPass)),
 			}
@@ -150,8 +153,9 @@ pub async fn signin(
 					let ns = ns.into_string()?;
 					let user = user.into_string()?;
 					let pass = pass.into_string()?;
+					let totp = vars.get("totp").cloned().map(PublicValue::into_string).transpose()?;
 					// Attempt to signin to namespace
-					super::signin::ns_user(kvs, session, ns, user, pass).await
+					super::signin::ns_user(kvs, session, ns, user, pass, totp).await
 				}
 				_ => Err(anyhow::Error::new(Error::MissingUserOrPass)),
 			}
@@ -168,8 +172,9 @@ pub async fn signin(
 					// Process the provided values
 					let user = user.into_string()?;
 					let pass = pass.into_string()?;
+					let totp = vars.get("totp").cloned().map(PublicValue::into_string).transpose()?;
 					// Attempt to signin to root
-					super::signin::root_user(kvs, session, user, pass).await
+					super::signin::root_user(kvs, session, user, pass, totp).await
 				}
 				_ => Err(anyhow::Error::new(Error::MissingUserOrPass)),
 			}
@@ -267,8 +272,8 @@ pub async fn db_access(
 	// Check the provided access method exists
 
 	// Check the access method type
-	// All access method types are supported except for JWT
-	// The equivalent of signing in with JWT is to authenticate it
+	// JWT access methods only support signin with tokens which need introspection
+	// The equivalent of signing in with a JWT is to authenticate it
 	match av.access_type.clone() {
 		catalog::AccessType::Record(at) => {
 			// Check if the record access method supports issuing tokens
@@ -446,7 +451,15 @@ pub async fn db_access(
 
 			signin_bearer(kvs, session, Some(&ns_def), Some(&db_def), av, &at, key).await
 		}
-		_ => Err(anyhow::Error::new(Error::AccessMethodMismatch)),
+		catalog::AccessType::Jwt(at) => {
+			// Extract the token to be introspected from the provided variables.
+			let token = match vars.get("token") {
+				Some(token) => token.clone().into_string()?,
+				None => bail!(Error::AccessMethodMismatch),
+			};
+
+			signin_introspect(kvs, session, Some(&ns), Some(&db), av, &at, token).await
+		}
 	}
 }
 
@@ -468,9 +481,12 @@ pub async fn db_user(
 	db: String,
 	user: String,
 	pass: String,
+	totp: Option<String>,
 ) -> Result<Token> {
 	match verify_db_creds(kvs, &ns, &db, &user, &pass).await {
 		Ok(u) => {
+			// Verify the second authentication factor
+			verify_totp(kvs, Some(&ns), Some(&db), &u, totp.as_deref()).await?;
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -547,6 +563,15 @@ pub async fn ns_access(
 
 			signin_bearer(kvs, session, Some(&ns_def), None, av, &at, key).await
 		}
+		catalog::AccessType::Jwt(at) => {
+			// Extract the token to be introspected from the provided variables.
+			let token = match vars.get("token") {
+				Some(token) => token.clone().into_string()?,
+				None => bail!(Error::AccessMethodMismatch),
+			};
+
+			signin_introspect(kvs, session, Some(&ns), None, av, &at, token).await
+		}
 		_ => Err(anyhow::Error::new(Error::AccessMethodMismatch)),
 	}
 }
@@ -557,9 +582,12 @@ pub async fn ns_user(
 	ns: String,
 	user: String,
 	pass: String,
+	totp: Option<String>,
 ) -> Result<Token> {
 	match verify_ns_creds(kvs, &ns, &user, &pass).await {
 		Ok(u) => {
+			// Verify the second authentication factor
+			verify_totp(kvs, Some(&ns), None, &u, totp.as_deref()).await?;
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -611,9 +639,12 @@ pub async fn root_user(
 	session: &mut Session,
 	user: String,
 	pass: String,
+	totp: Option<String>,
 ) -> Result<Token> {
 	match verify_root_creds(kvs, &user, &pass).await {
 		Ok(u) => {
+			// Verify the second authentication factor
+			verify_totp(kvs, None, None, &u, totp.as_deref()).await?;
 			// Create the authentication key
 			let key = EncodingKey::from_secret(u.code.as_ref());
 			// Create the authentication claim
@@ -683,10 +714,121 @@ pub async fn root_access(
 
 			signin_bearer(kvs, session, None, None, av, &at, key).await
 		}
+		catalog::AccessType::Jwt(at) => {
+			// Extract the token to be introspected from the provided variables.
+			let token = match vars.get("token") {
+				Some(token) => token.clone().into_string()?,
+				None => bail!(Error::AccessMethodMismatch),
+			};
+
+			signin_introspect(kvs, session, None, None, av, &at, token).await
+		}
 		_ => Err(anyhow::Error::new(Error::AccessMethodMismatch)),
 	}
 }
 
+/// Authenticates using a token which is validated through introspection.
+///
+/// This function handles signin with JWT access methods which verify tokens
+/// from an OpenID Connect provider and have token introspection enabled. This
+/// allows the use of opaque access tokens, which can not be verified locally.
+///
+/// # Parameters
+///
+/// - `kvs`: The datastore instance for database operations
+/// - `session`: The current session context to be updated with authentication state
+/// - `ns`: Optional namespace for scoped authentication
+/// - `db`: Optional database for scoped authentication
+/// - `av`: The access method definition
+/// - `at`: The JWT access configuration
+/// - `token`: The token to introspect
+///
+/// # Returns
+///
+/// Returns the introspected token, as the access method does not issue tokens.
+/// The session is authenticated with the claims returned by introspection, so
+/// the token needs to be introspected again when used with a new session.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The access method does not have token introspection enabled
+/// - The introspection endpoint can not be reached or the token is not active
+/// - The token was not issued for the access method issuer or audience
+/// - The token has expired or the AUTHENTICATE clause fails
+#[cfg_attr(not(feature = "jwks"), allow(unused_variables))]
+async fn signin_introspect(
+	kvs: &Datastore,
+	session: &mut Session,
+	ns: Option<&str>,
+	db: Option<&str>,
+	av: Arc<catalog::AccessDefinition>,
+	at: &catalog::JwtAccess,
+	token: String,
+) -> Result<Token> {
+	// Check if the access method supports token introspection.
+	let oidc = match &at.verify {
+		catalog::JwtAccessVerify::Oidc(oidc) if oidc.introspect.is_some() => oidc,
+		_ => bail!(Error::AccessMethodMismatch),
+	};
+	#[cfg(not(feature = "jwks"))]
+	bail!(Error::AccessMethodMismatch);
+	#[cfg(feature = "jwks")]
+	{
+		// Introspect the token with the provider
+		let claims = super::jwks::introspect(kvs, oidc, &token).await?;
+		let claims = match serde_json::from_value::<Claims>(serde_json::Value::Object(claims)) {
+			Ok(claims) => claims,
+			Err(err) => {
+				debug!("Failed to parse token introspection claims: {err}");
+				bail!(Error::InvalidAuth);
+			}
+		};
+		// Check if the token has expired
+		if let Some(exp) = claims.exp
+			&& exp < Utc::now().timestamp()
+		{
+			debug!("Token introspection returned an 'exp' claim containing a past time");
+			bail!(Error::ExpiredToken);
+		}
+		let value = Value::from(claims.clone().into_claims_object());
+		// AUTHENTICATE clause
+		if let Some(au) = &av.authenticate {
+			// Setup the system session for executing the clause
+			let mut sess = Session::editor();
+			sess.ns = ns.map(str::to_owned);
+			sess.db = db.map(str::to_owned);
+			sess.tk = Some(
+				crate::val::convert_value_to_public_value(value.clone())
+					.expect("claims conversion should succeed"),
+			);
+			sess.ip.clone_from(&session.ip);
+			sess.or.clone_from(&session.or);
+			authenticate_generic(kvs, &sess, au).await?;
+		}
+		// Parse the roles
+		let roles = token_roles(&at.verify, &claims, &value)?;
+		let level = match (ns, db) {
+			(Some(ns), Some(db)) => Level::Database(ns.to_owned(), db.to_owned()),
+			(Some(ns), None) => Level::Namespace(ns.to_owned()),
+			_ => Level::Root,
+		};
+		// Log the success
+		debug!("Signed in with access method `{}` using token introspection", av.name);
+		// Set the session
+		session.tk = Some(
+			crate::val::convert_value_to_public_value(value)
+				.expect("value conversion should succeed"),
+		);
+		session.ns = ns.map(str::to_owned);
+		session.db = db.map(str::to_owned);
+		session.ac = Some(av.name.clone());
+		session.exp = expiration(av.session_duration)?;
+		session.au = Arc::new(Auth::new(Actor::new(av.name.clone(), roles, level)));
+		Ok(Token::Access(token))
+	}
+}
+
 /// Authenticates a user using bearer token authentication (refresh token flow).
 ///
 /// This function handles authentication for refresh tokens, which are long-lived
@@ -1868,8 +2010,14 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 
 				let res = match level.level {
 					"ROOT" => {
-						root_user(&ds, &mut sess, "user".to_string(), case.password.to_string())
-							.await
+						root_user(
+							&ds,
+							&mut sess,
+							"user".to_string(),
+							case.password.to_string(),
+							None,
+						)
+						.await
 					}
 					"NS" => {
 						ns_user(
@@ -1878,6 +2026,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 							level.ns.unwrap().to_string(),
 							"user".to_string(),
 							case.password.to_string(),
+							None,
 						)
 						.await
 					}
@@ -1889,6 +2038,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 							level.db.unwrap().to_string(),
 							"user".to_string(),
 							case.password.to_string(),
+							None,
 						)
 						.await
 					}
@@ -1977,6 +2127,246 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 		}
 	}
 
+	#[tokio::test]
+	async fn test_signin_user_with_totp() {
+		const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+		let test_levels = vec![
+			TestLevel {
+				level: "ROOT",
+				ns: None,
+				db: None,
+			},
+			TestLevel {
+				level: "NS",
+				ns: Some("test"),
+				db: None,
+			},
+			TestLevel {
+				level: "DB",
+				ns: Some("test"),
+				db: Some("test"),
+			},
+		];
+
+		for level in &test_levels {
+			let ds = Datastore::new("memory").await.unwrap();
+			let sess = Session::owner().with_ns("test").with_db("test");
+			ds.execute(
+				&format!("DEFINE USER user ON {} PASSWORD 'pass' TOTP '{SECRET}'", level.level),
+				&sess,
+				None,
+			)
+			.await
+			.unwrap();
+
+			let vars = |totp: Option<String>| {
+				let mut vars = PublicVariables::new();
+				if let Some(ns) = level.ns {
+					vars.insert("NS", ns.to_string());
+				}
+				if let Some(db) = level.db {
+					vars.insert("DB", db.to_string());
+				}
+				vars.insert("user", "user".to_string());
+				vars.insert("pass", "pass".to_string());
+				if let Some(totp) = totp {
+					vars.insert("totp", totp);
+				}
+				vars
+			};
+
+			// Signin without a one-time password
+			let mut sess = Session::default();
+			let res = signin(&ds, &mut sess, vars(None)).await;
+			match res.unwrap_err().downcast().expect("Unexpected error kind") {
+				Error::MissingTotp => {}
+				e => panic!("Unexpected error, expected MissingTotp found {e}"),
+			}
+			assert!(sess.au.is_anon());
+
+			// Signin with an invalid one-time password
+			let mut sess = Session::default();
+			let code = iam::totp::generate(SECRET, Utc::now().timestamp() - 3600).unwrap();
+			let res = signin(&ds, &mut sess, vars(Some(code))).await;
+			match res.unwrap_err().downcast().expect("Unexpected error kind") {
+				Error::InvalidAuth => {}
+				e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+			}
+			assert!(sess.au.is_anon());
+
+			// Signin with a valid one-time password
+			let mut sess = Session::default();
+			let code = iam::totp::generate(SECRET, Utc::now().timestamp()).unwrap();
+			let res = signin(&ds, &mut sess, vars(Some(code.clone()))).await;
+			assert!(res.is_ok(), "Failed to signin: {:?}", res);
+			assert_eq!(sess.au.id(), "user");
+			assert_eq!(sess.au.level().ns(), level.ns);
+			assert_eq!(sess.au.level().db(), level.db);
+
+			// Signin again with the same one-time password
+			let mut sess = Session::default();
+			let res = signin(&ds, &mut sess, vars(Some(code))).await;
+			match res.unwrap_err().downcast().expect("Unexpected error kind") {
+				Error::InvalidAuth => {}
+				e => panic!("Unexpected error, expected InvalidAuth found {e}"),
+			}
+			assert!(sess.au.is_anon());
+
+			// Basic authentication can not provide a second factor
+			let mut sess = Session::default();
+			let res =
+				super::super::verify::basic(&ds, &mut sess, "user", "pass", level.ns, level.db)
+					.await;
+			assert!(res.is_err(), "Unexpected successful basic authentication");
+			assert!(sess.au.is_anon());
+		}
+	}
+
+	#[cfg(feature = "jwks")]
+	#[tokio::test]
+	async fn test_signin_jwt_with_introspection() {
+		use rand::Rng;
+		use rand::distributions::Alphanumeric;
+		use wiremock::matchers::{basic_auth, body_string_contains, method, path};
+		use wiremock::{Mock, MockServer, ResponseTemplate};
+
+		use crate::dbs::capabilities::{Capabilities, NetTarget, Targets};
+
+		async fn access(
+			ds: &Datastore,
+			level: &TestLevel,
+			ac: &str,
+			token: &str,
+		) -> (Session, Result<Token>) {
+			let mut sess = Session::default();
+			let mut vars = PublicVariables::new();
+			vars.insert("token", token.to_string());
+			let ac = ac.to_string();
+			let res = match level.level {
+				"DB" => {
+					let (ns, db) = (level.ns.unwrap().to_string(), level.db.unwrap().to_string());
+					db_access(ds, &mut sess, ns, db, ac, vars).await
+				}
+				"NS" => ns_access(ds, &mut sess, level.ns.unwrap().to_string(), ac, vars).await,
+				"ROOT" => root_access(ds, &mut sess, ac, vars).await,
+				_ => panic!("Unsupported level"),
+			};
+			(sess, res)
+		}
+
+		let test_levels = vec![
+			TestLevel {
+				level: "ROOT",
+				ns: None,
+				db: None,
+			},
+			TestLevel {
+				level: "NS",
+				ns: Some("test"),
+				db: None,
+			},
+			TestLevel {
+				level: "DB",
+				ns: Some("test"),
+				db: Some("test"),
+			},
+		];
+
+		for level in &test_levels {
+			// Use unique path to prevent accidental cache reuse
+			let base_path: String =
+				rand::thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+			// Mock identity provider serving its metadata and introspection endpoint
+			let mock_server = MockServer::start().await;
+			let issuer = format!("{}/{base_path}", mock_server.uri());
+			Mock::given(method("GET"))
+				.and(path(format!("{base_path}/.well-known/openid-configuration")))
+				.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+					"issuer": issuer,
+					"jwks_uri": format!("{issuer}/jwks.json"),
+					"introspection_endpoint": format!("{issuer}/introspect"),
+				})))
+				.mount(&mock_server)
+				.await;
+			Mock::given(method("POST"))
+				.and(path(format!("{base_path}/introspect")))
+				.and(basic_auth("surrealdb", "secret"))
+				.and(body_string_contains("token=active"))
+				.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+					"active": true,
+					"iss": issuer,
+					"aud": "surrealdb",
+					"sub": "tobie",
+					"exp": (Utc::now() + Duration::hours(1)).timestamp(),
+					"scope": "openid owner",
+				})))
+				.mount(&mock_server)
+				.await;
+			Mock::given(method("POST"))
+				.and(path(format!("{base_path}/introspect")))
+				.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+					"active": false,
+				})))
+				.mount(&mock_server)
+				.await;
+
+			// We allow requests to the local server acting as identity provider
+			let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+				Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+					[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+				)),
+			);
+			let sess = Session::owner().with_ns("test").with_db("test");
+			ds.execute(
+				&format!(
+					r#"
+					DEFINE ACCESS idp ON {} TYPE JWT
+						OPENID '{issuer}' AUDIENCE 'surrealdb' ROLES 'scope'
+						INTROSPECT CLIENT 'surrealdb' SECRET 'secret'
+						AUTHENTICATE {{
+							IF $token.sub != "tobie" {{ THROW "Unexpected subject" }};
+						}};
+					DEFINE ACCESS key ON {} TYPE JWT ALGORITHM HS512 KEY 'secret';
+					"#,
+					level.level, level.level
+				),
+				&sess,
+				None,
+			)
+			.await
+			.unwrap();
+
+			// Signin with an active token
+			let (sess, res) = access(&ds, level, "idp", "active").await;
+			match res {
+				Ok(Token::Access(token)) => assert_eq!(token, "active"),
+				res => panic!("Failed to signin with active token: {:?}", res),
+			}
+			assert_eq!(sess.ns, level.ns.map(|s| s.to_string()));
+			assert_eq!(sess.db, level.db.map(|s| s.to_string()));
+			assert_eq!(sess.ac, Some("idp".to_string()));
+			assert_eq!(sess.au.id(), "idp");
+			assert_eq!(sess.au.level().ns(), level.ns);
+			assert_eq!(sess.au.level().db(), level.db);
+			assert!(sess.au.has_role(Role::Owner), "Auth user expected to have Owner role");
+			assert!(!sess.au.has_role(Role::Viewer), "Auth user expected to not have Viewer role");
+
+			// Signin with an inactive token
+			let (sess, res) = access(&ds, level, "idp", "inactive").await;
+			assert!(res.is_err(), "Unexpected successful signin: {:?}", res);
+			assert!(sess.au.is_anon());
+
+			// Signin with an access method without introspection
+			let (sess, res) = access(&ds, level, "key", "active").await;
+			match res.unwrap_err().downcast().expect("Unexpected error kind") {
+				Error::AccessMethodMismatch => {}
+				e => panic!("Unexpected error, expected AccessMethodMismatch found {e}"),
+			}
+			assert!(sess.au.is_anon());
+		}
+	}
+
 	#[tokio::test]
 	async fn test_signin_record_and_authenticate_clause() {
 		// Test with correct credentials
@@ -4237,6 +4627,8 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 				roles: vec!["nonexistent".to_owned()],
 				session_duration: Expr::Literal(Literal::None),
 				token_duration: Expr::Literal(Literal::None),
+				totp: None,
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			};
 
@@ -4257,7 +4649,9 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 
 			// Sign in using the newly defined user.
 			let res = match level.level {
-				"ROOT" => root_user(&ds, &mut sess, "user".to_string(), "pass".to_string()).await,
+				"ROOT" => {
+					root_user(&ds, &mut sess, "user".to_string(), "pass".to_string(), None).await
+				}
 				"NS" => {
 					ns_user(
 						&ds,
@@ -4265,6 +4659,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 						level.ns.unwrap().to_string(),
 						"user".to_string(),
 						"pass".to_string(),
+						None,
 					)
 					.await
 				}
@@ -4276,6 +4671,7 @@ dn/RsYEONbwQSjIfMPkvxF+8HQ==
 						level.db.unwrap().to_string(),
 						"user".to_string(),
 						"pass".to_string(),
+						None,
 					)
 					.await
 				}
@@ -0,0 +1,198 @@
+//! Time-based one-time passwords (RFC 6238) used as a second
+//! authentication factor for system users, and exposed to SurrealQL
+//! through the `crypto::totp::*` functions.
+
+use anyhow::Result;
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use rand::rngs::OsRng;
+use sha1::Sha1;
+use subtle::ConstantTimeEq;
+
+use crate::err::Error;
+
+/// The number of random bytes in a generated shared secret (160 bits, as
+/// recommended by RFC 4226)
+const SECRET_LENGTH: usize = 20;
+/// The minimum number of bytes accepted in a shared secret (128 bits)
+const SECRET_MIN_LENGTH: usize = 16;
+/// The length of a time step in seconds
+pub const STEP: i64 = 30;
+/// The number of digits in a generated code
+pub const DIGITS: usize = 6;
+/// The number of time steps either side of the current one which are
+/// accepted, to allow for clock drift between the server and the device
+pub const SKEW: i64 = 1;
+
+/// Generates a new random shared secret, encoded as unpadded base32 so that
+/// it can be entered into, or encoded as a QR code for, authenticator apps.
+pub fn secret() -> String {
+	let mut bytes = [0u8; SECRET_LENGTH];
+	OsRng.fill_bytes(&mut bytes);
+	BASE32_NOPAD.encode(&bytes)
+}
+
+/// Decodes a base32 encoded shared secret, ignoring case, whitespace and
+/// trailing padding.
+pub fn decode_secret(secret: &str) -> Result<Vec<u8>> {
+	let secret = secret
+		.chars()
+		.filter(|c| !c.is_whitespace() && *c != '=')
+		.map(|c| c.to_ascii_uppercase())
+		.collect::<String>();
+	match BASE32_NOPAD.decode(secret.as_bytes()) {
+		Ok(v) if v.len() >= SECRET_MIN_LENGTH => Ok(v),
+		Ok(_) => Err(anyhow::Error::new(Error::InvalidTotpSecret(format!(
+			"the secret must be at least {SECRET_MIN_LENGTH} bytes long"
+		)))),
+		Err(_) => Err(anyhow::Error::new(Error::InvalidTotpSecret(
+			"the secret must be a base32 encoded string".to_string(),
+		))),
+	}
+}
+
+/// Computes the code for the time step which contains the specified unix
+/// timestamp (in seconds).
+pub fn generate(secret: &str, timestamp: i64) -> Result<String> {
+	let key = decode_secret(secret)?;
+	Ok(hotp(&key, timestamp.div_euclid(STEP)))
+}
+
+/// Checks whether a code is valid for the specified unix timestamp (in
+/// seconds), accepting codes from adjacent time steps within [`SKEW`].
+pub fn verify(secret: &str, code: &str, timestamp: i64) -> Result<bool> {
+	Ok(verify_after(secret, code, timestamp, None)?.is_some())
+}
+
+/// Checks whether a code is valid for the specified unix timestamp (in
+/// seconds), and returns the time step which it is valid for. Codes for the
+/// time step `after`, or for any earlier time step, are rejected, so that a
+/// code can only be used once (RFC 6238 section 5.2).
+pub fn verify_after(
+	secret: &str,
+	code: &str,
+	timestamp: i64,
+	after: Option<u64>,
+) -> Result<Option<u64>> {
+	let key = decode_secret(secret)?;
+	let code = code.trim();
+	// Codes which are malformed can never match
+	if code.len() != DIGITS || !code.bytes().all(|b| b.is_ascii_digit()) {
+		return Ok(None);
+	}
+	// Check every step within the window, without returning early
+	let step = timestamp.div_euclid(STEP);
+	let mut valid = None;
+	for counter in (step - SKEW)..=(step + SKEW) {
+		let matched: bool = hotp(&key, counter).as_bytes().ct_eq(code.as_bytes()).into();
+		// Time steps before the unix epoch can not be recorded
+		let Ok(counter) = u64::try_from(counter) else {
+			continue;
+		};
+		if matched && after.is_none_or(|after| counter > after) {
+			valid = Some(counter);
+		}
+	}
+	Ok(valid)
+}
+
+/// Builds an `otpauth://` provisioning URI which authenticator apps can
+/// import, usually by scanning it as a QR code.
+pub fn uri(secret: &str, account: &str, issuer: Option<&str>) -> Result<String> {
+	// Ensure that the secret is valid
+	decode_secret(secret)?;
+	// Encode the label and parameters
+	let encode = |v: &str| url::form_urlencoded::byte_serialize(v.as_bytes()).collect::<String>();
+	let secret = secret.to_ascii_uppercase();
+	let mut uri = match issuer {
+		Some(issuer) => format!("otpauth://totp/{}:{}", encode(issuer), encode(account)),
+		None => format!("otpauth://totp/{}", encode(account)),
+	};
+	uri.push_str(&format!("?secret={secret}&algorithm=SHA1&digits={DIGITS}&period={STEP}"));
+	if let Some(issuer) = issuer {
+		uri.push_str(&format!("&issuer={}", encode(issuer)));
+	}
+	Ok(uri)
+}
+
+/// Computes an HMAC-based one-time password (RFC 4226) for a counter.
+fn hotp(key: &[u8], counter: i64) -> String {
+	// HMAC accepts keys of any length
+	let mut mac = Hmac::<Sha1>::new_from_slice(key).expect("HMAC can take a key of any size");
+	mac.update(&(counter as u64).to_be_bytes());
+	let hash = mac.finalize().into_bytes();
+	// Dynamically truncate the hash
+	let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+	let binary = u32::from_be_bytes([
+		hash[offset] & 0x7f,
+		hash[offset + 1],
+		hash[offset + 2],
+		hash[offset + 3],
+	]);
+	format!("{:0width$}", binary % 10u32.pow(DIGITS as u32), width = DIGITS)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// The SHA1 test vectors from RFC 6238 Appendix B, truncated to 6 digits
+	const RFC_SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+	#[test]
+	fn rfc6238_test_vectors() {
+		for (time, code) in [
+			(59, "287082"),
+			(1111111109, "081804"),
+			(1111111111, "050471"),
+			(1234567890, "005924"),
+			(2000000000, "279037"),
+			(20000000000, "353130"),
+		] {
+			assert_eq!(generate(RFC_SECRET, time).unwrap(), code, "time {time}");
+		}
+	}
+
+	#[test]
+	fn verify_accepts_adjacent_steps() {
+		let code = generate(RFC_SECRET, 1111111109).unwrap();
+		assert!(verify(RFC_SECRET, &code, 1111111109).unwrap());
+		assert!(verify(RFC_SECRET, &code, 1111111109 + STEP).unwrap());
+		assert!(verify(RFC_SECRET, &code, 1111111109 - STEP).unwrap());
+		assert!(!verify(RFC_SECRET, &code, 1111111109 + 3 * STEP).unwrap());
+		assert!(!verify(RFC_SECRET, "12345", 1111111109).unwrap());
+		assert!(!verify(RFC_SECRET, "abcdef", 1111111109).unwrap());
+	}
+
+	#[test]
+	fn verify_rejects_used_steps() {
+		let code = generate(RFC_SECRET, 1111111109).unwrap();
+		let step = (1111111109 / STEP) as u64;
+		assert_eq!(verify_after(RFC_SECRET, &code, 1111111109, None).unwrap(), Some(step));
+		assert_eq!(
+			verify_after(RFC_SECRET, &code, 1111111109, Some(step - 1)).unwrap(),
+			Some(step)
+		);
+		assert_eq!(verify_after(RFC_SECRET, &code, 1111111109, Some(step)).unwrap(), None);
+		assert_eq!(verify_after(RFC_SECRET, &code, 1111111109 + STEP, Some(step)).unwrap(), None);
+	}
+
+	#[test]
+	fn secrets_are_valid() {
+		let secret = secret();
+		assert_eq!(decode_secret(&secret).unwrap().len(), SECRET_LENGTH);
+		assert_eq!(decode_secret(&secret.to_lowercase()).unwrap().len(), SECRET_LENGTH);
+		assert!(decode_secret("not base32!").is_err());
+		assert!(decode_secret("GEZDGNBV").is_err());
+	}
+
+	#[test]
+	fn provisioning_uri() {
+		let uri = uri(RFC_SECRET, "tobie@surrealdb.com", Some("Surreal DB")).unwrap();
+		assert_eq!(
+			uri,
+			"otpauth://totp/Surreal+DB:tobie%40surrealdb.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&algorithm=SHA1&digits=6&period=30&issuer=Surreal+DB"
+		);
+	}
+}
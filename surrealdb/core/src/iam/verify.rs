@@ -18,9 +18,9 @@ use crate::iam::issue::expiration;
 use crate::iam::jwks;
 use crate::iam::token::Claims;
 use crate::iam::{self, Actor, Auth, Level, Role};
-use crate::kvs::Datastore;
 use crate::kvs::LockType::*;
 use crate::kvs::TransactionType::*;
+use crate::kvs::{Datastore, KVKey, Transaction};
 use crate::{catalog, syn};
 
 /// Returns the decoding key as wel as the method by which to verify the key against
@@ -87,6 +87,116 @@ fn decode_claims_unverified(token: &str) -> Result<TokenData<Claims>> {
 	Ok(data)
 }
 
+/// Returns the decoding key and validation used to verify a token with the
+/// verification method of an access method
+#[cfg_attr(not(feature = "jwks"), allow(unused_variables))]
+async fn verify_config(
+	kvs: &Datastore,
+	verify: &catalog::JwtAccessVerify,
+	header: &jsonwebtoken::Header,
+) -> Result<(DecodingKey, Validation)> {
+	match verify {
+		catalog::JwtAccessVerify::Key(key) => decode_key(key.alg, key.key.as_bytes()),
+		#[cfg(feature = "jwks")]
+		catalog::JwtAccessVerify::Jwks(jwks) => {
+			jwks::config(kvs, token_kid(header)?, &jwks.url, header.alg).await
+		}
+		#[cfg(feature = "jwks")]
+		catalog::JwtAccessVerify::Oidc(oidc) => {
+			jwks::oidc_config(kvs, token_kid(header)?, oidc, header.alg).await
+		}
+		#[cfg(not(feature = "jwks"))]
+		_ => bail!(Error::AccessMethodMismatch),
+	}
+}
+
+/// Returns the key identifier from the header of a token
+#[cfg(feature = "jwks")]
+fn token_kid(header: &jsonwebtoken::Header) -> Result<&str> {
+	match &header.kid {
+		Some(kid) => Ok(kid),
+		None => bail!(Error::InvalidFunctionArguments {
+			name: "token".to_string(),
+			message: "Missing token header 'kid'".to_string(),
+		}),
+	}
+}
+
+/// Returns the roles granted by the claims of a verified token
+///
+/// By default, roles are read from the SurrealDB roles claim. An OpenID
+/// Connect access method can instead define the path to the claim containing
+/// the roles, in which case roles not recognised by SurrealDB are ignored.
+pub(super) fn token_roles(
+	verify: &catalog::JwtAccessVerify,
+	claims: &Claims,
+	value: &crate::val::Value,
+) -> Result<Vec<Role>> {
+	let roles = match verify {
+		catalog::JwtAccessVerify::Oidc(catalog::JwtAccessVerifyOidc {
+			roles: Some(path),
+			..
+		}) => claim_at_path(value, path)
+			.map(|v| {
+				let names: Vec<&str> = match v {
+					// Roles can be provided as an array of strings
+					crate::val::Value::Array(v) => v
+						.iter()
+						.filter_map(|v| match v {
+							crate::val::Value::String(v) => Some(v.as_str()),
+							_ => None,
+						})
+						.collect(),
+					// Roles can be provided as a space-delimited string
+					crate::val::Value::String(v) => v.split_whitespace().collect(),
+					_ => Vec::new(),
+				};
+				names
+					.into_iter()
+					.filter_map(|r| match Role::from_str(r) {
+						Ok(role) => Some(role),
+						Err(_) => {
+							trace!("Ignoring unrecognised role '{r}' in token claim '{path}'");
+							None
+						}
+					})
+					.collect::<Vec<_>>()
+			})
+			.filter(|v| !v.is_empty()),
+		_ => match &claims.roles {
+			// If roles are provided, parse them
+			Some(roles) => Some(
+				roles
+					.iter()
+					.map(|r| -> Result<Role> {
+						Role::from_str(r.as_str())
+							.map_err(Error::IamError)
+							.map_err(anyhow::Error::new)
+					})
+					.collect::<Result<Vec<_>, _>>()?,
+			),
+			None => None,
+		},
+	};
+	// If no role is provided, grant the viewer role
+	Ok(roles.unwrap_or_else(|| vec![Role::Viewer]))
+}
+
+/// Finds a claim by its path, where nested claims are separated by dots
+///
+/// Claim names which themselves contain dots, such as namespaced URLs, are
+/// matched in full before the path is split.
+fn claim_at_path<'a>(value: &'a crate::val::Value, path: &str) -> Option<&'a crate::val::Value> {
+	let crate::val::Value::Object(obj) = value else {
+		return None;
+	};
+	if let Some(v) = obj.get(path) {
+		return Some(v);
+	}
+	let (head, rest) = path.split_once('.')?;
+	claim_at_path(obj.get(head)?, rest)
+}
+
 pub async fn basic(
 	kvs: &Datastore,
 	session: &mut Session,
@@ -102,6 +212,8 @@ pub async fn basic(
 		// DB signin
 		(Some(ns), Some(db)) => match verify_db_creds(kvs, ns, db, user, pass).await {
 			Ok(u) => {
+				// Basic authentication can not provide a second factor
+				verify_totp(kvs, Some(ns), Some(db), &u, None).await?;
 				debug!("Authenticated as database user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
 				let au = Auth::new(Actor::from_role_names(
@@ -118,6 +230,8 @@ pub async fn basic(
 		// NS signin
 		(Some(ns), None) => match verify_ns_creds(kvs, ns, user, pass).await {
 			Ok(u) => {
+				// Basic authentication can not provide a second factor
+				verify_totp(kvs, Some(ns), None, &u, None).await?;
 				debug!("Authenticated as namespace user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
 				let au = Auth::new(Actor::from_role_names(
@@ -134,6 +248,8 @@ pub async fn basic(
 		// Root signin
 		(None, None) => match verify_root_creds(kvs, user, pass).await {
 			Ok(u) => {
+				// Basic authentication can not provide a second factor
+				verify_totp(kvs, None, None, &u, None).await?;
 				debug!("Authenticated as root user '{}'", user);
 				session.exp = expiration(u.session_duration)?;
 				let au = Auth::new(Actor::from_role_names(u.name.clone(), &u.roles, Level::Root)?);
@@ -221,24 +337,9 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			tx.cancel().await?;
 			// Obtain the configuration to verify the token based on the access method
 			let cf = match &de.access_type {
-				catalog::AccessType::Record(at) => match &at.jwt.verify {
-					catalog::JwtAccessVerify::Key(key) => {
-						iam::verify::decode_key(key.alg, key.key.as_bytes())
-					}
-					#[cfg(feature = "jwks")]
-					catalog::JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							Err(anyhow::Error::new(Error::InvalidFunctionArguments {
-								name: "token".to_string(),
-								message: "Missing token header 'kid'".to_string(),
-							}))
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => bail!(Error::AccessMethodMismatch),
-				}?,
+				catalog::AccessType::Record(at) => {
+					verify_config(kvs, &at.jwt.verify, &token_data.header).await?
+				}
 				_ => bail!(Error::AccessMethodMismatch),
 			};
 			// Verify the token
@@ -333,24 +434,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 					jwt,
 					..
 				}) => {
-					let cf = match &jwt.verify {
-						catalog::JwtAccessVerify::Key(key) => {
-							decode_key(key.alg, key.key.as_bytes())
-						}
-						#[cfg(feature = "jwks")]
-						catalog::JwtAccessVerify::Jwks(jwks) => {
-							if let Some(kid) = token_data.header.kid {
-								jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-							} else {
-								Err(anyhow::Error::new(Error::InvalidFunctionArguments {
-									name: "token".to_string(),
-									message: "Missing token header 'kid'".to_string(),
-								}))
-							}
-						}
-						#[cfg(not(feature = "jwks"))]
-						_ => bail!(Error::AccessMethodMismatch),
-					}?;
+					let cf = verify_config(kvs, &jwt.verify, &token_data.header).await?;
 					// Verify the token
 					verify_token(token, &cf.0, &cf.1)?;
 					// AUTHENTICATE clause
@@ -368,19 +452,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 						authenticate_generic(kvs, &sess, au).await?;
 					}
 					// Parse the roles
-					let roles = match &token_data.claims.roles {
-						// If no role is provided, grant the viewer role
-						None => vec![Role::Viewer],
-						// If roles are provided, parse them
-						Some(roles) => roles
-							.iter()
-							.map(|r| -> Result<Role> {
-								Role::from_str(r.as_str())
-									.map_err(Error::IamError)
-									.map_err(anyhow::Error::new)
-							})
-							.collect::<Result<Vec<_>, _>>()?,
-					};
+					let roles = token_roles(&jwt.verify, &token_data.claims, &value)?;
 					// Log the success
 					debug!("Authenticated to database `{}` with access method `{}`", db, ac);
 					// Set the session
@@ -405,24 +477,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				catalog::AccessType::Record(at) => match &de.authenticate {
 					Some(au) => {
 						trace!("Access method `{}` is record access with AUTHENTICATE clause", ac);
-						let cf = match &at.jwt.verify {
-							catalog::JwtAccessVerify::Key(key) => {
-								decode_key(key.alg, key.key.as_bytes())
-							}
-							#[cfg(feature = "jwks")]
-							catalog::JwtAccessVerify::Jwks(jwks) => {
-								if let Some(kid) = token_data.header.kid {
-									jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-								} else {
-									Err(anyhow::Error::new(Error::InvalidFunctionArguments {
-										name: "token".to_string(),
-										message: "Missing token header 'kid'".to_string(),
-									}))
-								}
-							}
-							#[cfg(not(feature = "jwks"))]
-							_ => bail!(Error::AccessMethodMismatch),
-						}?;
+						let cf = verify_config(kvs, &at.jwt.verify, &token_data.header).await?;
 
 						// Verify the token
 						verify_token(token, &cf.0, &cf.1)?;
@@ -563,29 +618,15 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			};
 
 			// Obtain the configuration to verify the token based on the access method
-			let cf = match &de.access_type {
+			let jwt = match &de.access_type {
 				catalog::AccessType::Jwt(jwt)
 				| catalog::AccessType::Bearer(catalog::BearerAccess {
 					jwt,
 					..
-				}) => match &jwt.verify {
-					catalog::JwtAccessVerify::Key(key) => decode_key(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					catalog::JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							bail!(Error::InvalidFunctionArguments {
-								name: "token".to_string(),
-								message: "Missing token header 'kid'".to_string()
-							})
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => bail!(Error::AccessMethodMismatch),
-				},
+				}) => jwt,
 				_ => bail!(Error::AccessMethodMismatch),
-			}?;
+			};
+			let cf = verify_config(kvs, &jwt.verify, &token_data.header).await?;
 			// Verify the token
 			verify_token(token, &cf.0, &cf.1)?;
 			// AUTHENTICATE clause
@@ -603,19 +644,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				authenticate_generic(kvs, &sess, au).await?;
 			}
 			// Parse the roles
-			let roles = match &token_data.claims.roles {
-				// If no role is provided, grant the viewer role
-				None => vec![Role::Viewer],
-				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role> {
-						Role::from_str(r.as_str())
-							.map_err(Error::IamError)
-							.map_err(anyhow::Error::new)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
-			};
+			let roles = token_roles(&jwt.verify, &token_data.claims, &value)?;
 			// Log the success
 			debug!("Authenticated to namespace `{}` with access method `{}`", ns, ac);
 			// Set the session
@@ -715,29 +744,15 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 			};
 
 			// Obtain the configuration to verify the token based on the access method
-			let cf = match &de.access_type {
+			let jwt = match &de.access_type {
 				catalog::AccessType::Jwt(jwt)
 				| catalog::AccessType::Bearer(catalog::BearerAccess {
 					jwt,
 					..
-				}) => match &jwt.verify {
-					catalog::JwtAccessVerify::Key(key) => decode_key(key.alg, key.key.as_bytes()),
-					#[cfg(feature = "jwks")]
-					catalog::JwtAccessVerify::Jwks(jwks) => {
-						if let Some(kid) = token_data.header.kid {
-							jwks::config(kvs, &kid, &jwks.url, token_data.header.alg).await
-						} else {
-							bail!(Error::InvalidFunctionArguments {
-								name: "token".to_string(),
-								message: "Missing token header 'kid'".to_string()
-							})
-						}
-					}
-					#[cfg(not(feature = "jwks"))]
-					_ => bail!(Error::AccessMethodMismatch),
-				},
+				}) => jwt,
 				_ => bail!(Error::AccessMethodMismatch),
-			}?;
+			};
+			let cf = verify_config(kvs, &jwt.verify, &token_data.header).await?;
 			// Verify the token
 			verify_token(token, &cf.0, &cf.1)?;
 			// AUTHENTICATE clause
@@ -755,19 +770,7 @@ pub async fn token(kvs: &Datastore, session: &mut Session, token: &str) -> Resul
 				authenticate_generic(kvs, &sess, au).await?;
 			}
 			// Parse the roles
-			let roles = match &token_data.claims.roles {
-				// If no role is provided, grant the viewer role
-				None => vec![Role::Viewer],
-				// If roles are provided, parse them
-				Some(roles) => roles
-					.iter()
-					.map(|r| -> Result<Role> {
-						Role::from_str(r.as_str())
-							.map_err(Error::IamError)
-							.map_err(anyhow::Error::new)
-					})
-					.collect::<Result<Vec<_>, _>>()?,
-			};
+			let roles = token_roles(&jwt.verify, &token_data.claims, &value)?;
 			// Log the success
 			debug!("Authenticated to root with access method `{}`", ac);
 			// Set the session
@@ -942,6 +945,79 @@ pub async fn verify_db_creds(
 	Ok(user)
 }
 
+/// Verifies the one-time password for a system user which has enrolled in
+/// multi-factor authentication. Users without a TOTP secret always pass.
+///
+/// The time step of each accepted one-time password is stored for the user,
+/// so that a one-time password can not be used a second time (RFC 6238
+/// section 5.2).
+pub async fn verify_totp(
+	ds: &Datastore,
+	ns: Option<&str>,
+	db: Option<&str>,
+	user: &catalog::UserDefinition,
+	code: Option<&str>,
+) -> Result<()> {
+	// Check if the user requires a second factor
+	let Some(secret) = &user.totp else {
+		return Ok(());
+	};
+	// Ensure that a one-time password was provided
+	let Some(code) = code else {
+		bail!(Error::MissingTotp);
+	};
+	// Verify and record the one-time password for the user
+	let tx = ds.transaction(Write, Optimistic).await?;
+	let res = run!(
+		tx,
+		async {
+			match (ns, db) {
+				(Some(ns), Some(db)) => {
+					let db = tx.expect_db_by_name(ns, db).await?;
+					let key =
+						crate::key::database::ot::new(db.namespace_id, db.database_id, &user.name);
+					accept_totp(&tx, &key, secret, code).await
+				}
+				(Some(ns), None) => {
+					let ns = tx.expect_ns_by_name(ns).await?;
+					let key = crate::key::namespace::ot::new(ns.namespace_id, &user.name);
+					accept_totp(&tx, &key, secret, code).await
+				}
+				(None, None) => {
+					let key = crate::key::root::ot::new(&user.name);
+					accept_totp(&tx, &key, secret, code).await
+				}
+				(None, Some(_)) => Err(anyhow::Error::new(Error::InvalidAuth)),
+			}
+		}
+		.await
+	);
+	match res {
+		Ok(true) => Ok(()),
+		Ok(false) => Err(anyhow::Error::new(Error::InvalidAuth)),
+		Err(e) => {
+			debug!("Failed to verify one-time password for user `{}`: {e}", user.name);
+			Err(anyhow::Error::new(Error::InvalidAuth))
+		}
+	}
+}
+
+/// Checks a one-time password against the last time step used by a user,
+/// and stores its time step if it is accepted
+async fn accept_totp<K>(tx: &Transaction, key: &K, secret: &str, code: &str) -> Result<bool>
+where
+	K: KVKey<ValueType = u64>,
+{
+	let last = tx.get(key, None).await?;
+	match iam::totp::verify_after(secret, code, Utc::now().timestamp(), last)? {
+		Some(step) => {
+			tx.set(key, &step, None).await?;
+			Ok(true)
+		}
+		None => Ok(false),
+	}
+}
+
 fn verify_pass(pass: &str, hash: &str) -> Result<()> {
 	// Compute the hash and verify the password
 	let hash =
@@ -1144,6 +1220,8 @@ mod tests {
 				roles: vec!["nonexistent".to_owned()],
 				token_duration: Expr::Literal(Literal::None),
 				session_duration: Expr::Literal(Literal::None),
+				totp: None,
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			};
 
@@ -1640,6 +1718,206 @@ mod tests {
 		}
 	}
 
+	#[cfg(feature = "jwks")]
+	#[tokio::test]
+	async fn test_token_db_oidc() {
+		use std::collections::HashMap;
+
+		use base64::Engine;
+		use base64::engine::general_purpose::STANDARD_NO_PAD;
+		use jsonwebtoken::jwk::{Jwk, JwkSet};
+		use rand::Rng;
+		use rand::distributions::Alphanumeric;
+		use wiremock::matchers::{method, path};
+		use wiremock::{Mock, MockServer, ResponseTemplate};
+
+		use crate::dbs::capabilities::{Capabilities, NetTarget, Targets};
+
+		// Use unique path to prevent accidental cache reuse
+		let base_path: String =
+			rand::thread_rng().sample_iter(&Alphanumeric).take(8).map(char::from).collect();
+
+		// Key identifier used in both JWT and JWK
+		let kid = "test_kid";
+		// Secret used to both sign and verify with HMAC
+		let secret = "jwt_secret";
+
+		// JWKS object with single JWK object providing the HS512 secret used to verify
+		let jwks = JwkSet {
+			keys: vec![Jwk {
+				common: jsonwebtoken::jwk::CommonParameters {
+					public_key_use: None,
+					key_operations: None,
+					key_algorithm: Some(jsonwebtoken::jwk::KeyAlgorithm::HS512),
+					key_id: Some(kid.to_string()),
+					x509_url: None,
+					x509_chain: None,
+					x509_sha1_fingerprint: None,
+					x509_sha256_fingerprint: None,
+				},
+				algorithm: jsonwebtoken::jwk::AlgorithmParameters::OctetKey(
+					jsonwebtoken::jwk::OctetKeyParameters {
+						key_type: jsonwebtoken::jwk::OctetKeyType::Octet,
+						value: STANDARD_NO_PAD.encode(secret),
+					},
+				),
+			}],
+		};
+
+		// Mock identity provider serving its metadata and JWKS object
+		let mock_server = MockServer::start().await;
+		let issuer = format!("{}/{base_path}", mock_server.uri());
+		Mock::given(method("GET"))
+			.and(path(format!("{base_path}/.well-known/openid-configuration")))
+			.respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+				"issuer": issuer,
+				"jwks_uri": format!("{issuer}/jwks.json"),
+			})))
+			.expect(1)
+			.mount(&mock_server)
+			.await;
+		Mock::given(method("GET"))
+			.and(path(format!("{base_path}/jwks.json")))
+			.respond_with(ResponseTemplate::new(200).set_body_json(jwks))
+			.expect(1)
+			.mount(&mock_server)
+			.await;
+
+		// We allow requests to the local server acting as identity provider
+		let ds = Datastore::new("memory").await.unwrap().with_capabilities(
+			Capabilities::default().with_network_targets(Targets::<NetTarget>::Some(
+				[NetTarget::from_str("127.0.0.1").unwrap()].into(),
+			)),
+		);
+
+		let sess = Session::owner().with_ns("test").with_db("test");
+		ds.execute(
+			format!(
+				r#"
+			DEFINE ACCESS idp ON DATABASE TYPE JWT
+				OPENID '{issuer}' AUDIENCE 'surrealdb' ROLES 'realm_access.roles';
+			"#
+			)
+			.as_str(),
+			&sess,
+			None,
+		)
+		.await
+		.unwrap();
+
+		// Use custom JWT header that includes the key identifier
+		let header_with_kid = jsonwebtoken::Header {
+			kid: Some(kid.to_string()),
+			alg: jsonwebtoken::Algorithm::HS512,
+			..jsonwebtoken::Header::default()
+		};
+
+		// Sign the JWT with the same secret specified in the JWK
+		let key = EncodingKey::from_secret(secret.as_ref());
+		let claims = Claims {
+			iss: Some(issuer.clone()),
+			iat: Some(Utc::now().timestamp()),
+			nbf: Some(Utc::now().timestamp()),
+			aud: Some(Audience::Multiple(vec!["account".to_string(), "surrealdb".to_string()])),
+			exp: Some((Utc::now() + Duration::hours(1)).timestamp()),
+			ns: Some("test".to_string()),
+			db: Some("test".to_string()),
+			ac: Some("idp".to_string()),
+			custom_claims: Some(HashMap::from([(
+				"realm_access".to_string(),
+				serde_json::json!({ "roles": ["offline_access", "editor"] }),
+			)])),
+			..Claims::default()
+		};
+
+		//
+		// Test with roles mapped from the defined claim
+		//
+		{
+			// Create the token
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			// Signin with the token
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert_eq!(sess.ns, Some("test".to_string()));
+			assert_eq!(sess.db, Some("test".to_string()));
+			assert_eq!(sess.ac, Some("idp".to_string()));
+			assert_eq!(sess.au.id(), "idp");
+			assert!(sess.au.is_db());
+			assert!(!sess.au.has_role(Role::Viewer), "Auth user expected to not have Viewer role");
+			assert!(sess.au.has_role(Role::Editor), "Auth user expected to have Editor role");
+			assert!(!sess.au.has_role(Role::Owner), "Auth user expected to not have Owner role");
+		}
+
+		//
+		// Test without the roles claim
+		//
+		{
+			// Prepare the claims object
+			let mut claims = claims.clone();
+			claims.custom_claims = None;
+			// Create the token
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			// Signin with the token
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_ok(), "Failed to signin with token: {:?}", res);
+			assert!(sess.au.has_role(Role::Viewer), "Auth user expected to have Viewer role");
+			assert!(!sess.au.has_role(Role::Editor), "Auth user expected to not have Editor role");
+		}
+
+		//
+		// Test with a different issuer
+		//
+		{
+			// Prepare the claims object
+			let mut claims = claims.clone();
+			claims.iss = Some("https://attacker.example.com".to_string());
+			// Create the token
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			// Signin with the token
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		//
+		// Test with a different audience
+		//
+		{
+			// Prepare the claims object
+			let mut claims = claims.clone();
+			claims.aud = Some(Audience::Single("account".to_string()));
+			// Create the token
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			// Signin with the token
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+
+		//
+		// Test without an audience
+		//
+		{
+			// Prepare the claims object
+			let mut claims = claims.clone();
+			claims.aud = None;
+			// Create the token
+			let enc = encode(&header_with_kid, &claims, &key).unwrap();
+			// Signin with the token
+			let mut sess = Session::default();
+			let res = token(&ds, &mut sess, &enc).await;
+
+			assert!(res.is_err(), "Unexpected success signing in with token: {:?}", res);
+		}
+	}
+
 	#[test]
 	fn test_verify_pass() {
 		let salt = SaltString::generate(&mut rand::thread_rng());
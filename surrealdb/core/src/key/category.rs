@@ -29,6 +29,8 @@ pub enum Category {
 	Namespace,
 	/// crate::key::root::us                 /!us{us}
 	User,
+	/// crate::key::root::ot                 /!ot{us}
+	UserTotp,
 	/// crate::key::root::tl                 /!tl{tl}
 	TaskLease,
 	/// crate::key::root::cg                 /!cg{ty}
@@ -74,6 +76,8 @@ pub enum Category {
 	NamespaceAccessGrant,
 	/// crate::key::namespace::us            /*{ns}!us{us}
 	NamespaceUser,
+	/// crate::key::namespace::ot            /*{ns}!ot{us}
+	NamespaceUserTotp,
 	///
 	/// ------------------------------
 	///
@@ -103,6 +107,8 @@ pub enum Category {
 	DatabaseTimestamp,
 	/// crate::key::database::us             /*{ns}*{db}!us{us}
 	DatabaseUser,
+	/// crate::key::database::ot             /*{ns}*{db}!ot{us}
+	DatabaseUserTotp,
 	/// crate::key::database::vs             /*{ns}*{db}!vs
 	DatabaseVersionstamp,
 	/// crate::key::database::cg             /*{ns}*{db}!cg{ty}
@@ -114,6 +120,8 @@ pub enum Category {
 	///
 	/// crate::key::table::all               /*{ns}*{db}*{tb}
 	TableRoot,
+	/// crate::key::table::co                /*{ns}*{db}*{tb}!co{co}
+	TableConsumerGroup,
 	/// crate::key::table::ev                /*{ns}*{db}*{tb}!ev{ev}
 	TableEvent,
 	/// crate::key::table::fd                /*{ns}*{db}*{tb}!fd{fd}
@@ -219,6 +227,7 @@ impl Display for Category {
 			Self::NamespaceIdentifierState => "NamespaceIdentifierState",
 			Self::Namespace => "Namespace",
 			Self::User => "User",
+			Self::UserTotp => "UserTotp",
 			Self::NodeRoot => "NodeRoot",
 			Self::NodeLiveQuery => "NodeLiveQuery",
 			Self::NamespaceRoot => "NamespaceRoot",
@@ -229,6 +238,7 @@ impl Display for Category {
 			Self::NamespaceAccessRoot => "NamespaceAccessRoot",
 			Self::NamespaceAccessGrant => "NamespaceAccessGrant",
 			Self::NamespaceUser => "NamespaceUser",
+			Self::NamespaceUserTotp => "NamespaceUserTotp",
 			Self::DatabaseRoot => "DatabaseRoot",
 			Self::DatabaseAccess => "DatabaseAccess",
 			Self::DatabaseAccessRoot => "DatabaseAccessRoot",
@@ -244,6 +254,7 @@ impl Display for Category {
 			Self::DatabaseTableIdentifierState => "DatabaseTableIdentifierState",
 			Self::DatabaseTimestamp => "DatabaseTimestamp",
 			Self::DatabaseUser => "DatabaseUser",
+			Self::DatabaseUserTotp => "DatabaseUserTotp",
 			Self::DatabaseVersionstamp => "DatabaseVersionstamp",
 			Self::DatabaseSequence => "DatabaseSequence",
 			Self::DatabaseConfig => "DatabaseConfig",
@@ -253,6 +264,7 @@ impl Display for Category {
 			Self::TableView => "TableView",
 			Self::IndexDefinition => "IndexDefinition",
 			Self::TableLiveQuery => "TableLiveQuery",
+			Self::TableConsumerGroup => "TableConsumerGroup",
 			Self::IndexRoot => "IndexRoot",
 			Self::IndexTermDocList => "IndexTermDocList",
 			Self::IndexBTreeNode => "IndexBTreeNode",
@@ -132,7 +132,6 @@ pub fn prefix_ts(ns: NamespaceId, db: DatabaseId, ts: &[u8]) -> DatabaseChangeFe
 }
 
 /// Returns the prefix for the whole database change feeds
-#[expect(unused)]
 pub fn prefix(ns: NamespaceId, db: DatabaseId) -> DatabaseChangeFeedRange {
 	DatabaseChangeFeedRange::new_prefix(ns, db)
 }
@@ -8,6 +8,7 @@ pub mod cg;
 pub mod fc;
 pub mod md;
 pub mod ml;
+pub mod ot;
 pub mod pa;
 pub mod sq;
 pub mod tb;
@@ -0,0 +1,62 @@
+//! Stores the last time step of a one-time password used by a database user
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct OtpKey<'key> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	pub db: DatabaseId,
+	_c: u8,
+	_d: u8,
+	_e: u8,
+	pub user: Cow<'key, str>,
+}
+
+impl_kv_key_storekey!(OtpKey<'_> => u64);
+
+pub fn new(ns: NamespaceId, db: DatabaseId, user: &str) -> OtpKey<'_> {
+	OtpKey::new(ns, db, user)
+}
+
+impl Categorise for OtpKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::DatabaseUserTotp
+	}
+}
+
+impl<'a> OtpKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'*',
+			db,
+			_c: b'!',
+			_d: b'o',
+			_e: b't',
+			user: Cow::Borrowed(user),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = OtpKey::new(NamespaceId(1), DatabaseId(2), "testuser");
+		let enc = OtpKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02!ottestuser\0");
+	}
+}
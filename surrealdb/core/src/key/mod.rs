@@ -26,6 +26,7 @@
 //! crate::key::root::ni                 /!ni
 //! crate::key::root::ns                 /!ns{ns} -> NamespaceDefinition
 //! crate::key::root::us                 /!us{us}
+//! crate::key::root::ot                 /!ot{us}
 //! crate::key::root::tl                 /!tl{tl}
 //! crate::key::root::cg                 /!cg{ty}
 //!
@@ -41,6 +42,7 @@
 //! crate::key::namespace::di            /+{ns}!di
 //! crate::key::namespace::lg            /*{ns}!lg{lg}
 //! crate::key::namespace::us            /*{ns}!us{us}
+//! crate::key::namespace::ot            /*{ns}!ot{us}
 //!
 //! crate::key::namespace::access::all   /*{ns}&{ac}
 //! crate::key::namespace::access::gr    /*{ns}&{ac}!gr{gr}
@@ -58,6 +60,7 @@
 //! crate::key::database::ti             /+{ns}*{db}!ti
 //! crate::key::database::ts             /*{ns}*{db}!ts{ts}
 //! crate::key::database::us             /*{ns}*{db}!us{us_name}
+//! crate::key::database::ot             /*{ns}*{db}!ot{us_name}
 //! crate::key::database::vs             /*{ns}*{db}!vs
 //! crate::key::database::cg             /*{ns}*{db}!cg{ty}
 //!
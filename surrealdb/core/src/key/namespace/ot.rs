@@ -0,0 +1,58 @@
+//! Stores the last time step of a one-time password used by a namespace user
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::NamespaceId;
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Ot<'a> {
+	__: u8,
+	_a: u8,
+	pub ns: NamespaceId,
+	_b: u8,
+	_c: u8,
+	_d: u8,
+	pub user: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Ot<'_> => u64);
+
+pub fn new(ns: NamespaceId, user: &str) -> Ot<'_> {
+	Ot::new(ns, user)
+}
+
+impl Categorise for Ot<'_> {
+	fn categorise(&self) -> Category {
+		Category::NamespaceUserTotp
+	}
+}
+
+impl<'a> Ot<'a> {
+	pub fn new(ns: NamespaceId, user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'*',
+			ns,
+			_b: b'!',
+			_c: b'o',
+			_d: b't',
+			user: Cow::Borrowed(user),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = Ot::new(NamespaceId(1), "testuser");
+		let enc = Ot::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01!ottestuser\0");
+	}
+}
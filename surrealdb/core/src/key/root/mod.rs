@@ -7,6 +7,7 @@ pub mod nd;
 pub mod nh;
 pub mod ni;
 pub mod ns;
+pub mod ot;
 pub mod root_config;
 pub mod tl;
 pub mod us;
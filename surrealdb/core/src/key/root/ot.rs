@@ -0,0 +1,53 @@
+//! Stores the last time step of a one-time password used by a root user
+use std::borrow::Cow;
+
+use storekey::{BorrowDecode, Encode};
+
+use crate::key::category::{Categorise, Category};
+use crate::kvs::impl_kv_key_storekey;
+
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+pub(crate) struct Ot<'a> {
+	__: u8,
+	_a: u8,
+	_b: u8,
+	_c: u8,
+	pub user: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(Ot<'_> => u64);
+
+pub fn new(user: &str) -> Ot<'_> {
+	Ot::new(user)
+}
+
+impl Categorise for Ot<'_> {
+	fn categorise(&self) -> Category {
+		Category::UserTotp
+	}
+}
+
+impl<'a> Ot<'a> {
+	pub fn new(user: &'a str) -> Self {
+		Self {
+			__: b'/',
+			_a: b'!',
+			_b: b'o',
+			_c: b't',
+			user: Cow::Borrowed(user),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::kvs::KVKey;
+
+	#[test]
+	fn key() {
+		let val = Ot::new("testuser");
+		let enc = Ot::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/!ottestuser\x00");
+	}
+}
@@ -0,0 +1,91 @@
+//! Stores the changefeed offset of a consumer group on the table
+use std::borrow::Cow;
+
+use anyhow::Result;
+use storekey::{BorrowDecode, Encode};
+
+use crate::catalog::{ConsumerGroupDefinition, DatabaseId, NamespaceId};
+use crate::key::category::{Categorise, Category};
+use crate::key::table::all::TableRoot;
+use crate::kvs::{KVKey, impl_kv_key_storekey};
+use crate::val::TableName;
+
+/// Key structure for storing the progress of a change data capture consumer
+/// group.
+///
+/// The value is the versionstamp of the last change set which the consumer
+/// group has acknowledged, so that a consumer which reconnects resumes from
+/// the first change set which was not acknowledged.
+#[derive(Clone, Debug, Eq, PartialEq, PartialOrd, Encode, BorrowDecode)]
+#[storekey(format = "()")]
+pub(crate) struct ConsumerGroupKey<'a> {
+	table_root: TableRoot<'a>,
+	_d: u8,
+	_e: u8,
+	_f: u8,
+	pub group: Cow<'a, str>,
+}
+
+impl_kv_key_storekey!(ConsumerGroupKey<'_> => ConsumerGroupDefinition);
+
+pub fn new<'a>(
+	ns: NamespaceId,
+	db: DatabaseId,
+	tb: &'a TableName,
+	group: &'a str,
+) -> ConsumerGroupKey<'a> {
+	ConsumerGroupKey::new(ns, db, tb, group)
+}
+
+pub fn prefix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!co\x00");
+	Ok(k)
+}
+
+pub fn suffix(ns: NamespaceId, db: DatabaseId, tb: &TableName) -> Result<Vec<u8>> {
+	let mut k = super::all::new(ns, db, tb).encode_key()?;
+	k.extend_from_slice(b"!co\xff");
+	Ok(k)
+}
+
+impl Categorise for ConsumerGroupKey<'_> {
+	fn categorise(&self) -> Category {
+		Category::TableConsumerGroup
+	}
+}
+
+impl<'a> ConsumerGroupKey<'a> {
+	pub fn new(ns: NamespaceId, db: DatabaseId, tb: &'a TableName, group: &'a str) -> Self {
+		Self {
+			table_root: TableRoot::new(ns, db, tb),
+			_d: b'!',
+			_e: b'c',
+			_f: b'o',
+			group: Cow::Borrowed(group),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn key() {
+		let tb = TableName::from("testtb");
+		let val = ConsumerGroupKey::new(NamespaceId(1), DatabaseId(2), &tb, "testgroup");
+		let enc = ConsumerGroupKey::encode_key(&val).unwrap();
+		assert_eq!(enc, b"/*\x00\x00\x00\x01*\x00\x00\x00\x02*testtb\0!cotestgroup\0");
+	}
+
+	#[test]
+	fn range() {
+		let tb = TableName::from("testtb");
+		let val = ConsumerGroupKey::new(NamespaceId(1), DatabaseId(2), &tb, "testgroup");
+		let enc = ConsumerGroupKey::encode_key(&val).unwrap();
+		let beg = prefix(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		let end = suffix(NamespaceId(1), DatabaseId(2), &tb).unwrap();
+		assert!((beg..end).contains(&enc));
+	}
+}
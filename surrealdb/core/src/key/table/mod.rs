@@ -1,4 +1,5 @@
 pub mod all;
+pub mod co;
 pub mod ev;
 pub mod fd;
 pub mod ft;
@@ -2,6 +2,7 @@
 #![warn(clippy::missing_docs_in_private_items)]
 
 use std::ops::Range;
+use std::path::Path;
 
 use anyhow::bail;
 
@@ -32,6 +33,15 @@ impl From<u32> for ScanLimit {
 	}
 }
 
+/// An estimate of the storage used by a range of keys
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SizeEstimate {
+	/// The approximate number of bytes stored on disk
+	pub bytes: u64,
+	/// The approximate number of keys
+	pub keys: u64,
+}
+
 pub mod requirements {
 	//! This module defines the trait requirements for a transaction.
 	//!
@@ -70,6 +80,20 @@ pub mod requirements {
 ///
 /// All keys and values are represented as byte arrays, encoding is handled
 /// by [`super::tr::Transactor`].
+///
+/// Storage engines outside of this crate can implement this trait, and be
+/// registered with [`super::engine::register`]. Implementations are expected
+/// to provide snapshot isolation, and to follow these conventions:
+/// - operations on a cancelled or committed transaction return
+///   [`Error::TransactionFinished`]
+/// - writes in a read-only transaction return [`Error::TransactionReadonly`]
+/// - `put` on an existing key returns [`Error::TransactionKeyAlreadyExists`]
+/// - conditional writes which fail their check return
+///   [`Error::TransactionConditionNotMet`]
+/// - conflicting commits return [`Error::TransactionConflict`], so that they
+///   can be retried
+/// - versioned operations which are not supported return
+///   [`Error::UnsupportedVersionedQueries`]
 #[allow(dead_code, reason = "Not used when none of the storage backends are enabled.")]
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
 #[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
@@ -364,6 +388,54 @@ pub trait Transactable: requirements::TransactionRequirements {
 		Ok(len)
 	}
 
+	/// Estimate the storage used by a range of keys in the datastore.
+	///
+	/// Storage engines which can cheaply estimate the size of a key range
+	/// should override this function. The default implementation returns
+	/// `None`, as no estimate is available.
+	async fn size(&self, _rng: Range<Key>) -> Result<Option<SizeEstimate>> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// No estimate is available
+		Ok(None)
+	}
+
+	/// Check whether the history of a range of keys can be removed.
+	///
+	/// Storage engines with native versioning retain history for a single
+	/// period across the whole datastore, so by default the history of a
+	/// range of keys can not be removed.
+	fn can_prune_versions(&self) -> bool {
+		false
+	}
+
+	/// Remove the history of a range of keys which is older than a version.
+	///
+	/// Versioned reads at or after the `before` version are not affected.
+	/// Once roughly `limit` history entries have been checked, this returns
+	/// the key from which pruning should continue, in a new transaction.
+	/// This is only supported when [`Transactable::can_prune_versions`]
+	/// returns `true`, so the default implementation returns an error.
+	async fn prune_versions(
+		&self,
+		_rng: Range<Key>,
+		_before: u64,
+		_limit: u32,
+	) -> Result<Option<Key>> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// History is retained by the storage engine
+		Err(Error::UnsupportedHistoryRetention)
+	}
+
 	// --------------------------------------------------
 	// Batch functions
 	// --------------------------------------------------
@@ -491,4 +563,34 @@ pub trait Transactable: requirements::TransactionRequirements {
 	async fn compact(&self, _range: Option<Range<Key>>) -> anyhow::Result<()> {
 		bail!(Error::CompactionNotSupported)
 	}
+
+	/// Check whether a storage tier is available to store tables.
+	///
+	/// Storage tiers are provided by the tiered storage wrapper, so by
+	/// default no storage tiers are available.
+	fn has_tier(&self, _name: &str) -> bool {
+		false
+	}
+
+	/// Store the keys with a prefix in a storage tier.
+	///
+	/// When `tier` is `None` the keys are stored in the primary datastore.
+	/// Storage engines without storage tiers store all keys themselves, so
+	/// the default implementation does nothing.
+	async fn set_tier(&self, _prefix: Key, _tier: Option<String>) -> Result<()> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// All keys are stored in this datastore
+		Ok(())
+	}
+
+	/// Create a consistent on-disk copy of the whole datastore at a path.
+	///
+	/// The checkpoint can be opened directly as a new datastore. Storage
+	/// engines which are not backed by local files do not support this.
+	async fn checkpoint(&self, _path: &Path) -> anyhow::Result<()> {
+		bail!(Error::CheckpointNotSupported)
+	}
 }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::sync::LazyLock;
 use std::time::Duration;
@@ -18,7 +18,8 @@ static SURREAL_DATASTORE_SYNC_DATA: LazyLock<Option<String>> =
 
 /// Whether MVCC versioning is enabled (default: false).
 /// This is an alternative to the `versioned` query parameter.
-/// Only used by the 'memory' and 'surrealkv' engines.
+/// The 'memory' and 'surrealkv' engines version keys natively, and the
+/// other engines store the history of keys in a separate keyspace.
 /// Accepts: "true", "false", "1", "0".
 static SURREAL_DATASTORE_VERSIONED: LazyLock<Option<String>> =
 	lazy_env_parse!("SURREAL_DATASTORE_VERSIONED", Option<String>);
@@ -67,6 +68,38 @@ pub fn parse_query_params(query: &str) -> HashMap<String, String> {
 		.collect()
 }
 
+/// Check whether MVCC versioning is enabled, for engines without their own
+/// configuration (query param > env var > default).
+pub fn versioned_from_params(params: &HashMap<String, String>) -> bool {
+	match params.get("versioned").map(String::as_str).or(SURREAL_DATASTORE_VERSIONED.as_deref()) {
+		Some(v) => v.eq_ignore_ascii_case("true") || v == "1",
+		None => false,
+	}
+}
+
+/// The prefix of the query parameters which configure storage tiers.
+const TIER_PARAM: &str = "tier.";
+
+/// Extract the storage tiers from parsed query parameters, as a map of tier
+/// names to datastore paths (e.g. `tier.cold=rocksdb:///mnt/hdd/cold.db`).
+pub fn tiers_from_params(params: &HashMap<String, String>) -> BTreeMap<String, String> {
+	params
+		.iter()
+		.filter_map(|(k, v)| Some((k.strip_prefix(TIER_PARAM)?.to_string(), v.clone())))
+		.filter(|(k, _)| !k.is_empty())
+		.collect()
+}
+
+/// Remove the storage tier parameters from a query string, leaving the
+/// parameters which configure the primary datastore.
+pub fn without_tiers(query: &str) -> String {
+	query
+		.split('&')
+		.filter(|s| !s.is_empty() && !s.to_lowercase().starts_with(TIER_PARAM))
+		.collect::<Vec<_>>()
+		.join("&")
+}
+
 // --------------------------------------------------
 // SurrealMX configuration
 // --------------------------------------------------
@@ -529,6 +562,17 @@ mod tests {
 		assert_eq!(&params["sync"], "every");
 	}
 
+	#[test]
+	fn test_tiers_from_params() {
+		let query = "sync=never&tier.cold=rocksdb:///mnt/hdd/cold.db&TIER.archive=surrealkv://a";
+		let params = parse_query_params(query);
+		let tiers = tiers_from_params(&params);
+		assert_eq!(tiers.len(), 2);
+		assert_eq!(tiers["cold"], "rocksdb:///mnt/hdd/cold.db");
+		assert_eq!(tiers["archive"], "surrealkv://a");
+		assert_eq!(without_tiers(query), "sync=never");
+	}
+
 	#[test]
 	fn test_sync_mode_parsing() {
 		assert_eq!(parse_sync_mode("never").unwrap(), SyncMode::Never);
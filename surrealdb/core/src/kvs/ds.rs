@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, VecDeque};
 #[cfg(not(target_family = "wasm"))]
 use std::collections::HashMap;
 #[cfg(target_family = "wasm")]
@@ -5,9 +6,11 @@ use std::collections::HashSet;
 #[cfg(not(target_family = "wasm"))]
 use std::collections::hash_map::Entry;
 use std::fmt::{self, Display};
+use std::ops::Range;
+use std::path::Path;
 #[cfg(storage)]
 use std::path::PathBuf;
-use std::pin::pin;
+use std::pin::{Pin, pin};
 use std::sync::Arc;
 use std::task::{Poll, ready};
 use std::time::Duration;
@@ -17,6 +20,7 @@ use anyhow::bail;
 use anyhow::{Context as _, Result, ensure};
 use async_channel::{Receiver, Sender};
 use bytes::{Bytes, BytesMut};
+use chrono::Utc;
 use futures::{Future, Stream};
 use rand::{Rng, thread_rng};
 use reblessive::TreeStack;
@@ -28,26 +32,34 @@ use tokio::sync::Notify;
 use tokio::sync::RwLock;
 use tokio::time::{Instant, sleep};
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, instrument, trace, warn};
+use tracing::{debug, error, instrument, trace, warn};
 use uuid::Uuid;
 
 use super::api::Transactable;
+use super::encryption::{EncryptedBuilder, EncryptionConfig};
 use super::tr::Transactor;
 use super::tx::Transaction;
 use super::version::MajorVersion;
 use super::{Key, Val, export};
 use crate::api::err::ApiError;
 use crate::api::invocation::process_api_request;
+use crate::api::openapi;
 use crate::api::request::ApiRequest;
 use crate::api::response::ApiResponse;
-use crate::buc::BucketStoreProvider;
+use crate::buc::lifecycle;
 use crate::buc::manager::BucketsManager;
+use crate::buc::metadata::FileMetadata;
+use crate::buc::store::{ByteRange, ByteStream, ObjectKey};
+use crate::buc::{BucketController, BucketStoreProvider, FileContent, FileStream};
 use crate::catalog::providers::{
-	ApiProvider, CatalogProvider, DatabaseProvider, NamespaceProvider, NodeProvider, TableProvider,
-	UserProvider,
+	ApiProvider, BucketProvider, CatalogProvider, DatabaseProvider, NamespaceProvider,
+	NodeProvider, TableProvider, UserProvider,
 };
-use crate::catalog::{ApiDefinition, Index, NodeLiveQuery, SubscriptionDefinition};
-use crate::cnf::NORMAL_FETCH_SIZE;
+use crate::catalog::{
+	ApiDefinition, DatabaseDefinition, Index, NodeLiveQuery, SubscriptionDefinition,
+};
+use crate::cf::ChangeSet;
+use crate::cnf::{CHANGE_STREAM_BATCH_SIZE, CHANGE_STREAM_POLL_INTERVAL_MS, NORMAL_FETCH_SIZE};
 use crate::cnf::dynamic::DynamicConfiguration;
 use crate::ctx::Context;
 #[cfg(feature = "jwks")]
@@ -63,8 +75,8 @@ use crate::expr::model::get_model_path;
 use crate::expr::statements::{DefineModelStatement, DefineStatement, DefineUserStatement};
 use crate::expr::{Base, Expr, FlowResultExt as _, Literal, LogicalPlan, TopLevelExpr};
 #[cfg(feature = "jwks")]
-use crate::iam::jwks::JwksCache;
-use crate::iam::{Action, Auth, Error as IamError, Resource, ResourceKind, Role};
+use crate::iam::jwks::{JwksCache, OidcCache};
+use crate::iam::{Action, Auth, Error as IamError, Level, Resource, ResourceKind, Role};
 use crate::idx::IndexKeyBase;
 use crate::idx::index::IndexOperation;
 use crate::idx::trees::store::IndexStores;
@@ -80,13 +92,13 @@ use crate::kvs::index::IndexBuilder;
 use crate::kvs::sequences::Sequences;
 use crate::kvs::slowlog::SlowLog;
 use crate::kvs::tasklease::{LeaseHandler, TaskLeaseType};
-use crate::kvs::{KVValue, LockType, TransactionType};
+use crate::kvs::{KVKey, KVValue, LockType, TransactionType};
 use crate::sql::Ast;
 #[cfg(feature = "surrealism")]
 use crate::surrealism::cache::SurrealismCache;
 use crate::syn::parser::{ParserSettings, StatementStream};
 use crate::types::{PublicNotification, PublicValue, PublicVariables};
-use crate::val::convert_value_to_public_value;
+use crate::val::{TableName, convert_value_to_public_value};
 use crate::{CommunityComposer, syn};
 
 const TARGET: &str = "surrealdb::core::kvs::ds";
@@ -99,6 +111,13 @@ const LQ_CHANNEL_SIZE: usize = 15_000;
 /// credentials for the first time
 const INITIAL_USER_ROLE: &str = "owner";
 
+/// A stream of the change sets of a table, for a change data capture consumer.
+#[cfg(target_family = "wasm")]
+pub type ChangeStream = Pin<Box<dyn Stream<Item = Result<PublicValue>>>>;
+/// A stream of the change sets of a table, for a change data capture consumer.
+#[cfg(not(target_family = "wasm"))]
+pub type ChangeStream = Pin<Box<dyn Stream<Item = Result<PublicValue>> + Send>>;
+
 /// The underlying datastore instance which stores the dataset.
 pub struct Datastore {
 	transaction_factory: TransactionFactory,
@@ -126,6 +145,9 @@ pub struct Datastore {
 	#[cfg(feature = "jwks")]
 	// The JWKS object cache
 	jwks_cache: Arc<RwLock<JwksCache>>,
+	#[cfg(feature = "jwks")]
+	// The OpenID Connect provider metadata cache
+	oidc_cache: Arc<RwLock<OidcCache>>,
 	#[cfg(storage)]
 	// The temporary directory
 	temporary_directory: Option<Arc<PathBuf>>,
@@ -274,6 +296,9 @@ pub trait TransactionBuilder: TransactionBuilderRequirements {
 ///
 /// The `path_valid` helper is used by the CLI to validate the path early and
 /// provide better error messages before starting the runtime.
+///
+/// To add a storage engine alongside the built-in engines, register it with
+/// [`super::engine::register`] instead of implementing this trait.
 pub trait TransactionBuilderFactory: TransactionBuilderFactoryRequirements {
 	/// Create a new transaction builder for the datastore.
 	///
@@ -317,6 +342,8 @@ pub enum DatastoreFlavor {
 	TiKV(super::tikv::Datastore),
 	#[cfg(feature = "kv-surrealkv")]
 	SurrealKV(super::surrealkv::Datastore),
+	#[cfg(feature = "kv-redb")]
+	Redb(super::redb::Datastore),
 }
 
 impl TransactionBuilderFactoryRequirements for CommunityComposer {}
@@ -328,7 +355,7 @@ impl TransactionBuilderFactory for CommunityComposer {
 	async fn new_transaction_builder(
 		&self,
 		path: &str,
-		_canceller: CancellationToken,
+		canceller: CancellationToken,
 	) -> Result<Box<dyn TransactionBuilder>> {
 		// Extract query parameters from the path before scheme extraction
 		let (raw_path, query_string) = match path.split_once('?') {
@@ -339,6 +366,32 @@ impl TransactionBuilderFactory for CommunityComposer {
 		// Parse any query parameters into a map
 		let params = query_string.map(super::config::parse_query_params).unwrap_or_default();
 
+		// Store tables in secondary storage engines if storage tiers are configured
+		let tiers = super::config::tiers_from_params(&params);
+		if !tiers.is_empty() {
+			// Tiered commits are coordinated within this process, which other nodes can not see
+			if raw_path.starts_with("tikv") || tiers.values().any(|p| p.starts_with("tikv")) {
+				bail!(Error::Kvs(crate::kvs::Error::Datastore(
+					"Storage tiers can not be used with the distributed `tikv` storage engine"
+						.to_owned()
+				)));
+			}
+			let query = super::config::without_tiers(query_string.unwrap_or_default());
+			let path = match query.is_empty() {
+				true => raw_path.to_owned(),
+				false => format!("{raw_path}?{query}"),
+			};
+			let primary = self.new_transaction_builder(&path, canceller.clone()).await?;
+			let mut builders = BTreeMap::new();
+			for (name, path) in tiers {
+				info!(target: TARGET, "Starting storage tier {name}");
+				let builder = self.new_transaction_builder(&path, canceller.clone()).await?;
+				builders.insert(name, builder);
+			}
+			let tiered = super::tiered::TieredBuilder::new(primary, builders).await?;
+			return Ok(Box::new(tiered));
+		}
+
 		// Extract the scheme and path components
 		let (flavour, path) = match raw_path.split_once("://").or_else(|| raw_path.split_once(':'))
 		{
@@ -391,12 +444,13 @@ impl TransactionBuilderFactory for CommunityComposer {
 					// Parse RocksDB-specific configuration from query parameters
 					let config =
 						super::config::RocksDbConfig::from_params(&params).map_err(Error::Kvs)?;
+					let versioned = config.versioned;
 					// Initialise the storage engine
 					let v = super::rocksdb::Datastore::new(&path, config)
 						.await
 						.map(DatastoreFlavor::RocksDB)?;
 					info!(target: TARGET, "Started {flavour} kvs store");
-					Ok(Box::<DatastoreFlavor>::new(v))
+					Ok(with_history(v, versioned))
 				}
 				#[cfg(not(feature = "kv-rocksdb"))]
 				bail!(Error::Kvs(crate::kvs::Error::Datastore("Cannot connect to the `rocksdb` storage engine as it is not enabled in this build of SurrealDB".to_owned())));
@@ -420,6 +474,20 @@ impl TransactionBuilderFactory for CommunityComposer {
 				#[cfg(not(feature = "kv-surrealkv"))]
 				bail!(Error::Kvs(crate::kvs::Error::Datastore("Cannot connect to the `surrealkv` storage engine as it is not enabled in this build of SurrealDB".to_owned())));
 			}
+			// Initiate a redb database
+			(flavour @ "redb", path) => {
+				#[cfg(feature = "kv-redb")]
+				{
+					// Create a new blocking threadpool
+					super::threadpool::initialise();
+					// Initialise the storage engine
+					let v = super::redb::Datastore::new(&path).await.map(DatastoreFlavor::Redb)?;
+					info!(target: TARGET, "Started {flavour} kvs store");
+					Ok(with_history(v, super::config::versioned_from_params(&params)))
+				}
+				#[cfg(not(feature = "kv-redb"))]
+				bail!(Error::Kvs(crate::kvs::Error::Datastore("Cannot connect to the `redb` storage engine as it is not enabled in this build of SurrealDB".to_owned())));
+			}
 			// Initiate an IndxDB database
 			(flavour @ "indxdb", path) => {
 				#[cfg(feature = "kv-indxdb")]
@@ -427,7 +495,7 @@ impl TransactionBuilderFactory for CommunityComposer {
 					let v =
 						super::indxdb::Datastore::new(&path).await.map(DatastoreFlavor::IndxDB)?;
 					info!(target: TARGET, "Started {flavour} kvs store");
-					Ok(Box::<DatastoreFlavor>::new(v))
+					Ok(with_history(v, super::config::versioned_from_params(&params)))
 				}
 				#[cfg(not(feature = "kv-indxdb"))]
 				bail!(Error::Kvs(crate::kvs::Error::Datastore("Cannot connect to the `indxdb` storage engine as it is not enabled in this build of SurrealDB".to_owned())));
@@ -438,13 +506,19 @@ impl TransactionBuilderFactory for CommunityComposer {
 				{
 					let v = super::tikv::Datastore::new(&path).await.map(DatastoreFlavor::TiKV)?;
 					info!(target: TARGET, "Started {flavour} kvs store");
-					Ok(Box::<DatastoreFlavor>::new(v))
+					Ok(with_history(v, super::config::versioned_from_params(&params)))
 				}
 				#[cfg(not(feature = "kv-tikv"))]
 				bail!(Error::Kvs(crate::kvs::Error::Datastore("Cannot connect to the `tikv` storage engine as it is not enabled in this build of SurrealDB".to_owned())));
 			}
-			// The datastore path is not valid
 			(flavour, path) => {
+				// Initiate a registered storage engine
+				if let Some(engine) = super::engine::get(flavour) {
+					let v = engine.open(&path, &params, canceller).await?;
+					info!(target: TARGET, "Started {flavour} kvs store");
+					return Ok(v);
+				}
+				// The datastore path is not valid
 				info!(target: TARGET, "Unable to load the specified datastore {flavour}{path}");
 				bail!(Error::Kvs(crate::kvs::Error::Datastore(
 					"Unable to load the specified datastore".into()
@@ -462,13 +536,33 @@ impl TransactionBuilderFactory for CommunityComposer {
 			v_s if v_s.starts_with("file:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("rocksdb:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("surrealkv:") => Ok(v.to_string()),
+			v_s if v_s.starts_with("redb:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("mem:") => Ok(v.to_string()),
 			v_s if v_s.starts_with("tikv:") => Ok(v.to_string()),
+			v_s if v_s.split_once(':').is_some_and(|(s, _)| super::engine::is_registered(s)) => {
+				Ok(v.to_string())
+			}
 			_ => bail!("Provide a valid database path parameter"),
 		}
 	}
 }
 
+/// Store the history of keys for a storage engine without native versioning
+#[cfg(any(
+	feature = "kv-rocksdb",
+	feature = "kv-tikv",
+	feature = "kv-redb",
+	feature = "kv-indxdb"
+))]
+fn with_history(v: DatastoreFlavor, versioned: bool) -> Box<dyn TransactionBuilder> {
+	if versioned {
+		info!(target: TARGET, "Storing the version history of keys");
+		Box::new(super::history::HistoryBuilder::new(Box::new(v)))
+	} else {
+		Box::new(v)
+	}
+}
+
 impl TransactionBuilderRequirements for DatastoreFlavor {}
 
 #[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
@@ -513,6 +607,11 @@ impl TransactionBuilder for DatastoreFlavor {
 				let tx = v.transaction(write, lock).await?;
 				(tx, true)
 			}
+			#[cfg(feature = "kv-redb")]
+			Self::Redb(v) => {
+				let tx = v.transaction(write, lock).await?;
+				(tx, true)
+			}
 			_ => unreachable!(),
 		})
 	}
@@ -551,6 +650,8 @@ impl TransactionBuilder for DatastoreFlavor {
 			Self::TiKV(v) => Ok(v.shutdown().await?),
 			#[cfg(feature = "kv-surrealkv")]
 			Self::SurrealKV(v) => Ok(v.shutdown().await?),
+			#[cfg(feature = "kv-redb")]
+			Self::Redb(v) => Ok(v.shutdown().await?),
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
 		}
@@ -571,6 +672,8 @@ impl Display for DatastoreFlavor {
 			Self::TiKV(_) => write!(f, "tikv"),
 			#[cfg(feature = "kv-surrealkv")]
 			Self::SurrealKV(_) => write!(f, "surrealkv"),
+			#[cfg(feature = "kv-redb")]
+			Self::Redb(_) => write!(f, "redb"),
 			#[allow(unreachable_patterns)]
 			_ => unreachable!(),
 		}
@@ -670,6 +773,8 @@ impl Datastore {
 			index_builder: IndexBuilder::new(tf.clone()),
 			#[cfg(feature = "jwks")]
 			jwks_cache: Arc::new(RwLock::new(JwksCache::new())),
+			#[cfg(feature = "jwks")]
+			oidc_cache: Arc::new(RwLock::new(OidcCache::new())),
 			#[cfg(storage)]
 			temporary_directory: None,
 			cache: Arc::new(DatastoreCache::new()),
@@ -711,6 +816,8 @@ impl Datastore {
 			index_builder: IndexBuilder::new(self.transaction_factory.clone()),
 			#[cfg(feature = "jwks")]
 			jwks_cache: Arc::new(Default::default()),
+			#[cfg(feature = "jwks")]
+			oidc_cache: Arc::new(Default::default()),
 			#[cfg(storage)]
 			temporary_directory: self.temporary_directory,
 			cache: Arc::new(DatastoreCache::new()),
@@ -723,6 +830,55 @@ impl Datastore {
 		}
 	}
 
+	/// Encrypt the values stored in this datastore at rest.
+	///
+	/// This loads the data encryption key from the underlying storage, or
+	/// creates it for a new datastore, so it must be called before the
+	/// datastore is used. See [`super::encryption`] for more details.
+	pub async fn with_encryption(self, config: &EncryptionConfig) -> Result<Self> {
+		let builder =
+			EncryptedBuilder::new(self.transaction_factory.builder.clone(), config).await?;
+		let tf = TransactionFactory::new(self.async_event_trigger.clone(), Box::new(builder));
+		info!(target: TARGET, "Encryption at rest is enabled");
+		Ok(Self {
+			index_builder: IndexBuilder::new(tf.clone()),
+			sequences: Sequences::new(tf.clone(), self.id),
+			transaction_factory: tf,
+			..self
+		})
+	}
+
+	/// Check that the values stored in this datastore are not encrypted.
+	///
+	/// The values of an encrypted datastore can only be read with its key, so
+	/// an encrypted datastore which is opened without a key is refused.
+	pub async fn without_encryption(self) -> Result<Self> {
+		if super::encryption::is_encrypted(&**self.transaction_factory.builder).await? {
+			bail!(Error::Kvs(crate::kvs::Error::Encryption(
+				"The datastore is encrypted, so an encryption key must be specified".to_owned()
+			)));
+		}
+		Ok(self)
+	}
+
+	/// Inject faults into the transactions of this datastore, for testing.
+	///
+	/// See [`super::simulation`] for more details.
+	#[cfg(feature = "kv-simulation")]
+	pub fn with_simulation(self, config: super::simulation::SimulationConfig) -> Self {
+		let builder = super::simulation::SimulatedBuilder::new(
+			self.transaction_factory.builder.clone(),
+			config,
+		);
+		let tf = TransactionFactory::new(self.async_event_trigger.clone(), Box::new(builder));
+		Self {
+			index_builder: IndexBuilder::new(tf.clone()),
+			sequences: Sequences::new(tf.clone(), self.id),
+			transaction_factory: tf,
+			..self
+		}
+	}
+
 	/// Set the node id for this datastore.
 	pub fn with_node_id(mut self, id: Uuid) -> Self {
 		self.id = id;
@@ -835,6 +991,11 @@ impl Datastore {
 		&self.jwks_cache
 	}
 
+	#[cfg(feature = "jwks")]
+	pub(crate) fn oidc_cache(&self) -> &Arc<RwLock<OidcCache>> {
+		&self.oidc_cache
+	}
+
 	pub(super) fn clock_now(&self) -> Timestamp {
 		SystemClock::new().now()
 	}
@@ -1444,6 +1605,255 @@ impl Datastore {
 		Ok(())
 	}
 
+	/// Processes the history retention garbage collection.
+	///
+	/// This method removes the history of records which is older than the
+	/// `HISTORY RETENTION` period defined on each table, or on its database.
+	/// Only storage engines which store history in a separate keyspace are
+	/// affected, as engines with native versioning manage their own retention.
+	/// It uses a distributed task lease so that only one node performs this
+	/// maintenance operation at a time.
+	///
+	/// # Arguments
+	/// * `interval` - The interval between garbage collection runs, to calculate the lease
+	///   duration
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn history_process(&self, interval: &Duration) -> Result<()> {
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting history garbage collection");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::HistoryCleanup,
+			*interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running history garbage collection");
+		// Fetch the ranges to prune in a short read transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		let ranges = catch!(txn, Self::history_ranges(&txn).await);
+		txn.cancel().await?;
+		// Prune each range in batches, with a transaction for each batch
+		for (mut rng, before) in ranges {
+			loop {
+				let txn = self.transaction(Write, Optimistic).await?;
+				let next =
+					catch!(txn, txn.prune_versions(rng.clone(), before, *NORMAL_FETCH_SIZE).await);
+				catch!(txn, txn.commit().await);
+				match next {
+					Some(key) => rng.start = key,
+					None => break,
+				}
+			}
+		}
+		// Everything ok
+		Ok(())
+	}
+
+	/// Fetches the ranges of keys which have a history retention period,
+	/// and the version before which the history of each range is removed.
+	async fn history_ranges(txn: &Transaction) -> Result<Vec<(Range<Vec<u8>>, u64)>> {
+		let mut ranges = Vec::new();
+		// Storage engines with native versioning retain their own history
+		if !txn.can_prune_versions() {
+			return Ok(ranges);
+		}
+		// Use the timestamp of the storage engine as the current version
+		let Some(now) = txn.timestamp().await?.as_version() else {
+			bail!(Error::Kvs(crate::kvs::Error::TimestampInvalid(
+				"The transaction timestamp is not a valid version".to_owned()
+			)));
+		};
+		let cutoff = |retention: Duration| now.saturating_sub(retention.as_nanos() as u64);
+		for ns in txn.all_ns().await?.iter() {
+			let ns = ns.namespace_id;
+			for db in txn.all_db(ns).await?.iter() {
+				// Prune the keys of the database which are not within a table
+				if let Some(retention) = db.history {
+					let root = crate::key::database::all::new(ns, db.database_id).encode_key()?;
+					let at = |b: u8| [root.as_slice(), &[b]].concat();
+					ranges.push((at(0x00)..at(b'*'), cutoff(retention)));
+					ranges.push((at(b'+')..at(0xff), cutoff(retention)));
+				}
+				// Prune each table, which can override the database retention
+				for tb in txn.all_tb(ns, db.database_id, None).await?.iter() {
+					let Some(retention) = tb.history.or(db.history) else {
+						continue;
+					};
+					let key = crate::key::table::all::new(ns, db.database_id, &tb.name);
+					let rng = super::util::to_prefix_range(key)?;
+					ranges.push((rng, cutoff(retention)));
+				}
+			}
+		}
+		Ok(ranges)
+	}
+
+	/// Streams the changefeed of a table to a change data capture consumer
+	/// group.
+	///
+	/// The stream starts after the last change set which the consumer group
+	/// has acknowledged with [`Datastore::ack_changes`], and yields each
+	/// change set in versionstamp order. Once the stream has caught up with
+	/// the changefeed, it checks the changefeed for new change sets at a
+	/// regular interval, so the stream only ends once the session expires.
+	/// Change sets which are delivered but not acknowledged are delivered
+	/// again when a consumer of the group streams the changefeed again, so
+	/// delivery is at-least-once. The consumer group is registered when it
+	/// first streams the changefeed, after which changefeed garbage
+	/// collection keeps the change sets which it has not acknowledged, until
+	/// it is removed with [`Datastore::remove_consumer_group`]. Registering a
+	/// consumer group stores it on the table, so this requires permission to
+	/// edit the table.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip(self, sess))]
+	pub async fn changes(&self, sess: &Session, tb: &str, group: &str) -> Result<ChangeStream> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check the permissions level
+		self.check(sess, Action::Edit, ResourceKind::Table.on_db(&ns, &db))?;
+		// Fetch the table and the offset of the consumer group
+		let tb = TableName::from(tb);
+		let txn = self.transaction(Write, Optimistic).await?;
+		let (db, offset) = catch!(txn, Self::change_offset(&txn, &ns, &db, &tb, group).await);
+		// Register the consumer group
+		let (ns, db) = (db.namespace_id, db.database_id);
+		catch!(txn, crate::cf::consumer::register(&txn, ns, db, &tb, group).await);
+		catch!(txn, txn.commit().await);
+		// Stream the changefeed from the offset
+		let factory = self.transaction_factory.clone();
+		let sequences = self.sequences.clone();
+		let interval = Duration::from_millis(*CHANGE_STREAM_POLL_INTERVAL_MS);
+		let pending = VecDeque::<ChangeSet>::new();
+		let state = (factory, sequences, sess.clone(), tb, offset, pending, false);
+		let stream = futures::stream::unfold(state, move |mut state| async move {
+			let (factory, sequences, sess, tb, offset, pending, ended) = &mut state;
+			// The stream ends once the session has expired
+			if *ended {
+				return None;
+			}
+			loop {
+				// Check if the session has expired
+				if sess.expired() {
+					*ended = true;
+					return Some((Err(Error::ExpiredSession.into()), state));
+				}
+				// Deliver the change sets which have already been read
+				if let Some(cs) = pending.pop_front() {
+					let Ok(versionstamp) = u64::try_from(cs.0) else {
+						let err = Error::unreachable(format!("Invalid versionstamp `{}`", cs.0));
+						return Some((Err(err.into()), state));
+					};
+					*offset = versionstamp;
+					let res = convert_value_to_public_value(cs.into_value());
+					return Some((res, state));
+				}
+				// Read the next batch of change sets
+				let res = async {
+					let txn = factory.transaction(Read, Optimistic, sequences.clone()).await?;
+					let batch = *CHANGE_STREAM_BATCH_SIZE;
+					let res = crate::cf::consumer::read(&txn, ns, db, tb, *offset, batch).await;
+					txn.cancel().await?;
+					res
+				};
+				match res.await {
+					// Wait for new changes once the stream has caught up
+					Ok(v) if v.is_empty() => sleep(interval).await,
+					Ok(v) => pending.extend(v),
+					Err(e) => return Some((Err(e), state)),
+				}
+			}
+		});
+		Ok(Box::pin(stream))
+	}
+
+	/// Acknowledges the change sets of a table for a change data capture
+	/// consumer group, up to and including a versionstamp.
+	///
+	/// Acknowledged change sets are not delivered to the consumer group
+	/// again when it next streams the changefeed of the table. This stores
+	/// the offset of the consumer group, so it requires permission to edit
+	/// the table.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip(self, sess))]
+	pub async fn ack_changes(
+		&self,
+		sess: &Session,
+		tb: &str,
+		group: &str,
+		versionstamp: u64,
+	) -> Result<()> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check the permissions level
+		self.check(sess, Action::Edit, ResourceKind::Table.on_db(&ns, &db))?;
+		// Store the offset of the consumer group
+		let tb = TableName::from(tb);
+		let txn = self.transaction(Write, Optimistic).await?;
+		let (db, _) = catch!(txn, Self::change_offset(&txn, &ns, &db, &tb, group).await);
+		let (ns, db) = (db.namespace_id, db.database_id);
+		catch!(txn, crate::cf::consumer::commit(&txn, ns, db, &tb, group, versionstamp).await);
+		catch!(txn, txn.commit().await);
+		// All ok
+		Ok(())
+	}
+
+	/// Removes a change data capture consumer group from a table.
+	///
+	/// Changefeed garbage collection no longer keeps the change sets which
+	/// the consumer group has not acknowledged. If a consumer of the group
+	/// streams the changefeed again, the consumer group is registered again
+	/// and starts from the first change set in the changefeed.
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip(self, sess))]
+	pub async fn remove_consumer_group(&self, sess: &Session, tb: &str, group: &str) -> Result<()> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Retrieve the provided NS and DB
+		let (ns, db) = crate::iam::check::check_ns_db(sess)?;
+		// Check the permissions level
+		self.check(sess, Action::Edit, ResourceKind::Table.on_db(&ns, &db))?;
+		// Remove the consumer group
+		let tb = TableName::from(tb);
+		let txn = self.transaction(Write, Optimistic).await?;
+		let db = catch!(txn, txn.expect_db_by_name(&ns, &db).await);
+		let (ns, db) = (db.namespace_id, db.database_id);
+		catch!(txn, crate::cf::consumer::remove(&txn, ns, db, &tb, group).await);
+		catch!(txn, txn.commit().await);
+		// All ok
+		Ok(())
+	}
+
+	/// Fetches the database of a table with a changefeed, and the offset of
+	/// a consumer group on the table.
+	async fn change_offset(
+		txn: &Transaction,
+		ns: &str,
+		db: &str,
+		tb: &TableName,
+		group: &str,
+	) -> Result<(Arc<DatabaseDefinition>, u64)> {
+		let db = txn.expect_db_by_name(ns, db).await?;
+		let table = txn.expect_tb_by_name(ns, &db.name, tb).await?;
+		// Change data capture reads the changefeed of the table
+		ensure!(
+			table.changefeed.is_some() || db.changefeed.is_some(),
+			Error::TbChangefeedMissing {
+				name: tb.clone(),
+			}
+		);
+		let offset =
+			crate::cf::consumer::offset(txn, db.namespace_id, db.database_id, tb, group).await?;
+		Ok((db, offset))
+	}
+
 	// --------------------------------------------------
 	// Indexing functions
 	// --------------------------------------------------
@@ -1691,6 +2101,79 @@ impl Datastore {
 		}
 	}
 
+	/// Removes files which have expired according to the lifecycle rules
+	/// defined on each bucket, using a distributed lease so that only one
+	/// node applies the rules at a time.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self))]
+	pub async fn bucket_lifecycle(&self, interval: Duration) -> Result<()> {
+		// Buckets can only be defined when files are enabled
+		if !self.capabilities.allows_experimental(&ExperimentalTarget::Files) {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Attempting bucket lifecycle process");
+		// Create a new lease handler
+		let lh = LeaseHandler::new(
+			self.sequences.clone(),
+			self.id,
+			self.transaction_factory.clone(),
+			TaskLeaseType::BucketLifecycle,
+			interval * 2,
+		)?;
+		// If we don't get the lease, another node is handling this task
+		if !lh.has_lease().await? {
+			return Ok(());
+		}
+		// Output function invocation details to logs
+		trace!(target: TARGET, "Running bucket lifecycle process");
+		// The bucket definitions are read within a short readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		let res: Result<Vec<_>> = async {
+			let mut expiring = Vec::new();
+			for ns in txn.all_ns().await?.iter() {
+				for db in txn.all_db(ns.namespace_id).await?.iter() {
+					let buckets = txn.all_db_buckets(ns.namespace_id, db.database_id).await?;
+					for bu in buckets.iter() {
+						// Readonly buckets are never modified
+						if bu.lifecycle.is_empty() || bu.readonly {
+							continue;
+						}
+						// A bucket which can not be opened does not stop the others
+						match self
+							.buckets
+							.get_bucket_store(&txn, ns.namespace_id, db.database_id, &bu.name)
+							.await
+						{
+							Ok(store) => expiring.push((bu.clone(), store)),
+							Err(e) => {
+								error!(target: TARGET, "Failed to open bucket {}: {e}", bu.name)
+							}
+						}
+					}
+				}
+			}
+			Ok(expiring)
+		}
+		.await;
+		txn.cancel().await?;
+		// The expired files are removed one bucket at a time, outside of any transaction
+		let now = Utc::now();
+		for (bu, store) in res? {
+			// A failing bucket does not stop the lifecycle rules of the others
+			match lifecycle::apply_rules(store.as_ref(), &bu.lifecycle, now).await {
+				Ok(0) => {}
+				Ok(removed) => {
+					trace!(target: TARGET, "Removed {removed} expired files from bucket {}", bu.name)
+				}
+				Err(e) => {
+					let e = Error::ObjectStoreFailure(bu.name.clone(), e);
+					error!(target: TARGET, "Failed to apply the lifecycle rules: {e}")
+				}
+			}
+		}
+		Ok(())
+	}
+
 	// --------------------------------------------------
 	// Other functions
 	// --------------------------------------------------
@@ -2162,6 +2645,21 @@ impl Datastore {
 		})
 	}
 
+	/// Creates an on-disk checkpoint of the live datastore
+	#[instrument(level = "debug", target = "surrealdb::core::kvs::ds", skip(self, sess))]
+	pub async fn checkpoint(&self, sess: &Session, path: &Path) -> Result<()> {
+		// Check if the session has expired
+		ensure!(!sess.expired(), Error::ExpiredSession);
+		// Only root users can create checkpoints
+		self.check(sess, Action::Edit, ResourceKind::Any.on_level(Level::Root))?;
+		// Create a new readonly transaction
+		let txn = self.transaction(Read, Optimistic).await?;
+		// Create the checkpoint at the path
+		let res = txn.checkpoint(path).await;
+		txn.cancel().await?;
+		res
+	}
+
 	/// Checks the required permissions level for this session
 	#[instrument(level = "trace", target = "surrealdb::core::kvs::ds", skip(self, sess))]
 	pub fn check(&self, sess: &Session, action: Action, resource: Resource) -> Result<()> {
@@ -2361,6 +2859,126 @@ impl Datastore {
 		res
 	}
 
+	/// Generates the OpenAPI document describing the APIs defined on a database.
+	pub async fn api_document(&self, ns: &str, db: &str, session: &Session) -> Result<PublicValue> {
+		// Check that the session can view the API definitions
+		self.check(session, Action::View, ResourceKind::Api.on_db(ns, db))?;
+		// Generate the document within a readonly transaction
+		let tx = self.transaction(Read, Optimistic).await?;
+		let doc = catch!(tx, openapi::generate(&tx, ns, db).await);
+		tx.cancel().await?;
+		convert_value_to_public_value(doc)
+	}
+
+	/// Streams a file from a bucket, optionally limited to a byte range.
+	///
+	/// Returns `None` if the file does not exist.
+	pub async fn get_file(
+		&self,
+		session: &Session,
+		bucket: &str,
+		key: &str,
+		range: Option<ByteRange>,
+	) -> Result<Option<FileStream>> {
+		// Check that files are enabled
+		ensure!(
+			self.capabilities.allows_experimental(&ExperimentalTarget::Files),
+			Error::InvalidFunction {
+				name: "file::*".to_string(),
+				message: "Experimental capability `files` is not enabled".to_string(),
+			}
+		);
+		// Check if the session has expired
+		ensure!(!session.expired(), Error::ExpiredSession);
+		// Setup the context within a readonly transaction
+		let tx = Arc::new(self.transaction(Read, Optimistic).await?);
+		let opt = self.setup_options(session);
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(Arc::clone(&tx));
+		ctx.attach_session(session)?;
+		let ctx = &ctx.freeze();
+		let key = ObjectKey::new(key);
+		// Fetch the file, checking the bucket permissions
+		let mut stack = TreeStack::new();
+		let res = stack
+			.enter(|stk| async move {
+				let mut controller = BucketController::new(stk, ctx, &opt, None, bucket).await?;
+				let Some(meta) = controller.head(&key).await? else {
+					return Ok(None);
+				};
+				let content_type = controller.metadata(&key).await?.and_then(|m| m.content_type);
+				let content = match range.map(|r| r.resolve(meta.size)) {
+					None => match controller.get_stream(&key, None).await? {
+						Some(stream) => FileContent::Full(stream),
+						None => return Ok(None),
+					},
+					Some(Some(range)) => {
+						match controller.get_stream(&key, Some(range.clone())).await? {
+							Some(stream) => FileContent::Partial(range, stream),
+							None => return Ok(None),
+						}
+					}
+					Some(None) => FileContent::Unsatisfiable,
+				};
+				Ok(Some(FileStream {
+					size: meta.size,
+					updated: meta.updated,
+					content_type,
+					content,
+				}))
+			})
+			.finish()
+			.await;
+		tx.cancel().await?;
+		res
+	}
+
+	/// Stores a stream of data as a file in a bucket, overwriting any existing data.
+	///
+	/// The content type, if specified, is stored in the metadata of the file.
+	pub async fn put_file(
+		&self,
+		session: &Session,
+		bucket: &str,
+		key: &str,
+		content_type: Option<String>,
+		stream: ByteStream,
+	) -> Result<()> {
+		// Check that files are enabled
+		ensure!(
+			self.capabilities.allows_experimental(&ExperimentalTarget::Files),
+			Error::InvalidFunction {
+				name: "file::*".to_string(),
+				message: "Experimental capability `files` is not enabled".to_string(),
+			}
+		);
+		// Check if the session has expired
+		ensure!(!session.expired(), Error::ExpiredSession);
+		// Setup the context within a readonly transaction
+		let tx = Arc::new(self.transaction(Read, Optimistic).await?);
+		let opt = self.setup_options(session);
+		let mut ctx = self.setup_ctx()?;
+		ctx.set_transaction(Arc::clone(&tx));
+		ctx.attach_session(session)?;
+		let ctx = &ctx.freeze();
+		let key = ObjectKey::new(key);
+		// Store the file, checking the bucket permissions
+		let mut stack = TreeStack::new();
+		let res = stack
+			.enter(|stk| async move {
+				let mut controller = BucketController::new(stk, ctx, &opt, None, bucket).await?;
+				let meta = FileMetadata {
+					content_type,
+					..Default::default()
+				};
+				controller.put_stream(&key, stream, meta).await
+			})
+			.finish()
+			.await;
+		tx.cancel().await?;
+		res
+	}
+
 	pub async fn put_ml_model(
 		&self,
 		session: &Session,
@@ -2449,6 +3067,67 @@ mod test {
 		}
 	}
 
+	#[tokio::test]
+	async fn test_encryption_key_rotation() {
+		use crate::kvs::encryption::{EncryptionKey, is_encrypted};
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let inner = ds.transaction_factory.builder.clone();
+		let old: EncryptionKey = "ab".repeat(32).parse().unwrap();
+		let new: EncryptionKey = "cd".repeat(32).parse().unwrap();
+		let config = |key: &EncryptionKey, previous: &[&EncryptionKey]| EncryptionConfig {
+			key: key.clone(),
+			previous: previous.iter().map(|&k| k.clone()).collect(),
+		};
+		assert!(!is_encrypted(&**inner).await.unwrap());
+		// A new datastore creates its data encryption key
+		let builder = EncryptedBuilder::new(inner.clone(), &config(&old, &[])).await.unwrap();
+		assert!(is_encrypted(&**inner).await.unwrap());
+		let (tx, _) = builder.new_transaction(true, false).await.unwrap();
+		tx.set(b"k".to_vec(), b"v".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// The new key can only be used when the old key is a previous key
+		assert!(EncryptedBuilder::new(inner.clone(), &config(&new, &[])).await.is_err());
+		assert!(EncryptedBuilder::new(inner.clone(), &config(&new, &[&old])).await.is_ok());
+		// The data encryption key is now only wrapped with the new key
+		assert!(EncryptedBuilder::new(inner.clone(), &config(&old, &[])).await.is_err());
+		let builder = EncryptedBuilder::new(inner, &config(&new, &[])).await.unwrap();
+		let (tx, _) = builder.new_transaction(false, false).await.unwrap();
+		assert_eq!(tx.get(b"k".to_vec(), None).await.unwrap(), Some(b"v".to_vec()));
+		tx.cancel().await.unwrap();
+		// The datastore can not be opened without its key
+		assert!(ds.without_encryption().await.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_consumer_group_registration() {
+		// The lowest offset of the consumer groups in the database
+		async fn offset(ds: &Datastore) -> Option<u64> {
+			let txn = ds.transaction(Read, Optimistic).await.unwrap();
+			let db = txn.expect_db_by_name("test", "test").await.unwrap();
+			let (ns, db) = (db.namespace_id, db.database_id);
+			let tbs = txn.all_tb(ns, db, None).await.unwrap();
+			let res = crate::cf::consumer::min_offset(&txn, ns, db, &tbs).await.unwrap();
+			txn.cancel().await.unwrap();
+			res
+		}
+
+		let ds = Datastore::new("memory").await.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE NAMESPACE test; DEFINE DATABASE test; DEFINE TABLE person CHANGEFEED 1h";
+		for res in ds.execute(sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		// A consumer group is registered when it first streams the changefeed
+		assert_eq!(offset(&ds).await, None);
+		drop(ds.changes(&sess, "person", "group").await.unwrap());
+		assert_eq!(offset(&ds).await, Some(0));
+		// A removed consumer group no longer keeps the change sets
+		ds.remove_consumer_group(&sess, "person", "group").await.unwrap();
+		assert_eq!(offset(&ds).await, None);
+		assert!(ds.remove_consumer_group(&sess, "person", "group").await.is_err());
+	}
+
 	#[tokio::test]
 	pub async fn very_deep_query() -> Result<()> {
 		use reblessive::{Stack, Stk};
@@ -0,0 +1,591 @@
+//! Transparent encryption at rest for the key-value store.
+//!
+//! When enabled, every value is encrypted with AES-256-GCM before it is passed
+//! to the storage engine, and decrypted when it is read back. This works the
+//! same way for all storage engines, including the snapshots and append-only
+//! logs of the in-memory engine, as the engines only ever see ciphertext.
+//!
+//! Values are encrypted with a random data encryption key (DEK), which is
+//! stored in the datastore itself, wrapped with the key encryption key (KEK)
+//! passed to the server on startup. Rotating the KEK only re-wraps the DEK, so
+//! no data needs to be rewritten: start the server with the new key, and with
+//! the old key as a previous key, and the DEK is re-wrapped on startup.
+//!
+//! Each value is bound to its key, so that encrypted values can not be moved
+//! between keys. Keys themselves are stored in plaintext, as the storage
+//! engines rely on their ordering for range scans.
+//!
+//! A datastore lives far longer than the roughly 2^32 values which can safely
+//! be encrypted under a single AES-GCM key with random nonces. Each value is
+//! therefore encrypted with its own subkey, derived from the DEK and a random
+//! salt which is stored alongside the value, so the DEK never needs rotating
+//! and no nonce state needs to be shared between nodes.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, Payload};
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+use super::api::{ScanLimit, SizeEstimate, Transactable};
+use super::batch::Batch;
+use super::ds::{Metrics, TransactionBuilder};
+use super::err::{Error, Result};
+use super::{BoxTimeStamp, BoxTimeStampImpl, Key, TransactionBuilderRequirements, Val};
+
+/// The key where the wrapped data encryption key is stored
+const DEK_KEY: &[u8] = b"!e";
+
+/// The version of the encrypted value format
+const FORMAT_VERSION: u8 = 2;
+
+/// The context which separates derived value subkeys from other uses of the DEK
+const SUBKEY_CONTEXT: &[u8] = b"surrealdb-kvs-value";
+
+/// The length of an encryption key in bytes
+const KEY_LEN: usize = 32;
+
+/// The length of the random salt used to derive the subkey of a value
+const SALT_LEN: usize = 16;
+
+/// The length of an AES-GCM nonce in bytes
+const NONCE_LEN: usize = 12;
+
+/// The length of an AES-GCM authentication tag in bytes
+const TAG_LEN: usize = 16;
+
+/// A 256-bit key encryption key
+#[derive(Clone)]
+pub struct EncryptionKey([u8; KEY_LEN]);
+
+impl fmt::Debug for EncryptionKey {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str("EncryptionKey(********)")
+	}
+}
+
+impl Drop for EncryptionKey {
+	fn drop(&mut self) {
+		self.0.zeroize();
+	}
+}
+
+impl FromStr for EncryptionKey {
+	type Err = Error;
+
+	/// Parse a key from 64 hexadecimal characters
+	fn from_str(s: &str) -> Result<Self> {
+		let mut key = [0u8; KEY_LEN];
+		hex::decode_to_slice(s.trim(), &mut key).map_err(|_| {
+			Error::Encryption("The encryption key must be 64 hexadecimal characters".to_owned())
+		})?;
+		Ok(Self(key))
+	}
+}
+
+impl EncryptionKey {
+	fn cipher(&self) -> Cipher {
+		Cipher::new(&self.0)
+	}
+}
+
+/// The encryption configuration of a datastore
+#[derive(Clone, Debug)]
+pub struct EncryptionConfig {
+	/// The current key encryption key
+	pub key: EncryptionKey,
+	/// Previous key encryption keys, used when rotating the current key
+	pub previous: Vec<EncryptionKey>,
+}
+
+/// An AES-256-GCM cipher, which encrypts each value with a derived subkey
+struct Cipher([u8; KEY_LEN]);
+
+impl Drop for Cipher {
+	fn drop(&mut self) {
+		self.0.zeroize();
+	}
+}
+
+impl Cipher {
+	fn new(key: &[u8; KEY_LEN]) -> Self {
+		Self(*key)
+	}
+
+	/// Derive the AES-GCM cipher for a value from its salt
+	fn subkey(&self, salt: &[u8]) -> Aes256Gcm {
+		let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.0)
+			.expect("HMAC can take a key of any size");
+		mac.update(SUBKEY_CONTEXT);
+		mac.update(salt);
+		let mut subkey: [u8; KEY_LEN] = mac.finalize().into_bytes().into();
+		let cipher = Aes256Gcm::new((&subkey).into());
+		subkey.zeroize();
+		cipher
+	}
+
+	/// Encrypt a value, authenticating it along with the key it is stored at
+	fn encrypt(&self, key: &[u8], val: &[u8]) -> Result<Val> {
+		let salt: [u8; SALT_LEN] = rand::random();
+		let nonce: [u8; NONCE_LEN] = rand::random();
+		let payload = Payload {
+			msg: val,
+			aad: key,
+		};
+		let enc = self
+			.subkey(&salt)
+			.encrypt(Nonce::from_slice(&nonce), payload)
+			.map_err(|_| Error::Encryption("Unable to encrypt a value".to_owned()))?;
+		let mut res = Vec::with_capacity(1 + SALT_LEN + NONCE_LEN + enc.len());
+		res.push(FORMAT_VERSION);
+		res.extend_from_slice(&salt);
+		res.extend_from_slice(&nonce);
+		res.extend_from_slice(&enc);
+		Ok(res)
+	}
+
+	/// Decrypt a value stored at the specified key
+	fn decrypt(&self, key: &[u8], val: &[u8]) -> Result<Val> {
+		if val.len() < 1 + SALT_LEN + NONCE_LEN + TAG_LEN || val[0] != FORMAT_VERSION {
+			return Err(Error::Encryption("The stored value is not encrypted".to_owned()));
+		}
+		let (salt, rest) = val[1..].split_at(SALT_LEN);
+		let (nonce, enc) = rest.split_at(NONCE_LEN);
+		let payload = Payload {
+			msg: enc,
+			aad: key,
+		};
+		self.subkey(salt)
+			.decrypt(Nonce::from_slice(nonce), payload)
+			.map_err(|_| Error::Encryption("Unable to decrypt a stored value".to_owned()))
+	}
+}
+
+/// A transaction builder which encrypts the values of the wrapped builder
+pub(super) struct EncryptedBuilder {
+	/// The wrapped transaction builder
+	inner: Arc<Box<dyn TransactionBuilder>>,
+	/// The data encryption key cipher
+	cipher: Arc<Cipher>,
+}
+
+impl fmt::Display for EncryptedBuilder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.inner.fmt(f)
+	}
+}
+
+impl EncryptedBuilder {
+	/// Wrap a transaction builder, loading or creating the data encryption key
+	pub(super) async fn new(
+		inner: Arc<Box<dyn TransactionBuilder>>,
+		config: &EncryptionConfig,
+	) -> anyhow::Result<Self> {
+		let (tx, _) = inner.new_transaction(true, false).await?;
+		let mut dek = match Self::load_key(&*tx, config).await {
+			Ok(dek) => {
+				tx.commit().await?;
+				dek
+			}
+			Err(e) => {
+				tx.cancel().await?;
+				return Err(e.into());
+			}
+		};
+		let cipher = Arc::new(Cipher::new(&dek));
+		dek.zeroize();
+		Ok(Self {
+			inner,
+			cipher,
+		})
+	}
+
+	/// Load the data encryption key, creating it for a new datastore
+	async fn load_key(tx: &dyn Transactable, config: &EncryptionConfig) -> Result<[u8; KEY_LEN]> {
+		let kek = config.key.cipher();
+		match tx.get(DEK_KEY.to_vec(), None).await? {
+			Some(wrapped) => {
+				// Unwrap the data encryption key with the current key
+				if let Ok(dek) = kek.decrypt(DEK_KEY, &wrapped) {
+					return Self::to_key(dek);
+				}
+				// Otherwise the key is being rotated
+				for previous in config.previous.iter() {
+					if let Ok(dek) = previous.cipher().decrypt(DEK_KEY, &wrapped) {
+						info!("Rotating the datastore key encryption key");
+						let dek = Self::to_key(dek)?;
+						tx.set(DEK_KEY.to_vec(), kek.encrypt(DEK_KEY, &dek)?, None).await?;
+						return Ok(dek);
+					}
+				}
+				Err(Error::Encryption(
+					"The datastore encryption key can not be decrypted with the specified keys"
+						.to_owned(),
+				))
+			}
+			None => {
+				// Existing data can not be encrypted in place
+				let rng = vec![0x00]..vec![0xff];
+				if !tx.keys(rng, ScanLimit::Count(1), 0, None).await?.is_empty() {
+					return Err(Error::Encryption(
+						"Encryption can not be enabled on an existing unencrypted datastore"
+							.to_owned(),
+					));
+				}
+				let dek: [u8; KEY_LEN] = rand::random();
+				tx.put(DEK_KEY.to_vec(), kek.encrypt(DEK_KEY, &dek)?, None).await?;
+				Ok(dek)
+			}
+		}
+	}
+
+	fn to_key(mut dek: Vec<u8>) -> Result<[u8; KEY_LEN]> {
+		let key = dek
+			.as_slice()
+			.try_into()
+			.map_err(|_| Error::Encryption("The datastore encryption key is not valid".to_owned()));
+		dek.zeroize();
+		key
+	}
+}
+
+/// Check whether the datastore of a transaction builder is encrypted at rest
+pub(super) async fn is_encrypted(inner: &dyn TransactionBuilder) -> anyhow::Result<bool> {
+	let (tx, _) = inner.new_transaction(false, false).await?;
+	let res = tx.exists(DEK_KEY.to_vec(), None).await;
+	tx.cancel().await?;
+	Ok(res?)
+}
+
+impl TransactionBuilderRequirements for EncryptedBuilder {}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl TransactionBuilder for EncryptedBuilder {
+	async fn new_transaction(
+		&self,
+		write: bool,
+		lock: bool,
+	) -> anyhow::Result<(Box<dyn Transactable>, bool)> {
+		let (inner, local) = self.inner.new_transaction(write, lock).await?;
+		let tx = EncryptedTransaction {
+			inner,
+			cipher: self.cipher.clone(),
+		};
+		Ok((Box::new(tx), local))
+	}
+
+	async fn shutdown(&self) -> anyhow::Result<()> {
+		self.inner.shutdown().await
+	}
+
+	fn register_metrics(&self) -> Option<Metrics> {
+		self.inner.register_metrics()
+	}
+
+	fn collect_u64_metric(&self, metric: &str) -> Option<u64> {
+		self.inner.collect_u64_metric(metric)
+	}
+}
+
+/// A transaction which encrypts the values of the wrapped transaction
+struct EncryptedTransaction {
+	/// The wrapped transaction
+	inner: Box<dyn Transactable>,
+	/// The data encryption key cipher
+	cipher: Arc<Cipher>,
+}
+
+impl EncryptedTransaction {
+	fn encrypt(&self, key: &[u8], val: Val) -> Result<Val> {
+		self.cipher.encrypt(key, &val)
+	}
+
+	fn decrypt(&self, key: &[u8], val: Val) -> Result<Val> {
+		// The wrapped data encryption key is stored as is
+		if key == DEK_KEY {
+			return Ok(val);
+		}
+		self.cipher.decrypt(key, &val)
+	}
+
+	fn decrypt_pairs(&self, pairs: Vec<(Key, Val)>) -> Result<Vec<(Key, Val)>> {
+		pairs
+			.into_iter()
+			.map(|(k, v)| {
+				let v = self.decrypt(&k, v)?;
+				Ok((k, v))
+			})
+			.collect()
+	}
+
+	/// Check that the current value of a key matches a condition
+	async fn check(&self, key: &Key, chk: Option<Val>) -> Result<()> {
+		// Encrypted values can not be compared by the storage engine
+		if self.get(key.clone(), None).await? != chk {
+			return Err(Error::TransactionConditionNotMet);
+		}
+		Ok(())
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for EncryptedTransaction {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		self.inner.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		self.inner.commit().await
+	}
+
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		self.inner.exists(key, version).await
+	}
+
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		match self.inner.get(key.clone(), version).await? {
+			Some(val) => Ok(Some(self.decrypt(&key, val)?)),
+			None => Ok(None),
+		}
+	}
+
+	async fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> Result<Vec<Option<Val>>> {
+		let vals = self.inner.getm(keys.clone(), version).await?;
+		keys.iter().zip(vals).map(|(k, v)| v.map(|v| self.decrypt(k, v)).transpose()).collect()
+	}
+
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		let val = self.encrypt(&key, val)?;
+		self.inner.set(key, val, version).await
+	}
+
+	async fn replace(&self, key: Key, val: Val) -> Result<()> {
+		let val = self.encrypt(&key, val)?;
+		self.inner.replace(key, val).await
+	}
+
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		let val = self.encrypt(&key, val)?;
+		self.inner.put(key, val, version).await
+	}
+
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		self.check(&key, chk).await?;
+		let val = self.encrypt(&key, val)?;
+		self.inner.set(key, val, None).await
+	}
+
+	async fn del(&self, key: Key) -> Result<()> {
+		self.inner.del(key).await
+	}
+
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.check(&key, chk).await?;
+		self.inner.del(key).await
+	}
+
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.inner.clr(key).await
+	}
+
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.check(&key, chk).await?;
+		self.inner.clr(key).await
+	}
+
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keys(rng, limit, skip, version).await
+	}
+
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.inner.keysr(rng, limit, skip, version).await
+	}
+
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scan(rng, limit, skip, version).await?;
+		self.decrypt_pairs(res)
+	}
+
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.scanr(rng, limit, skip, version).await?;
+		self.decrypt_pairs(res)
+	}
+
+	async fn getr(&self, rng: Range<Key>, version: Option<u64>) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.getr(rng, version).await?;
+		self.decrypt_pairs(res)
+	}
+
+	async fn getp(&self, key: Key) -> Result<Vec<(Key, Val)>> {
+		let res = self.inner.getp(key).await?;
+		self.decrypt_pairs(res)
+	}
+
+	async fn delr(&self, rng: Range<Key>) -> Result<()> {
+		self.inner.delr(rng).await
+	}
+
+	async fn delp(&self, key: Key) -> Result<()> {
+		self.inner.delp(key).await
+	}
+
+	async fn clrr(&self, rng: Range<Key>) -> Result<()> {
+		self.inner.clrr(rng).await
+	}
+
+	async fn clrp(&self, key: Key) -> Result<()> {
+		self.inner.clrp(key).await
+	}
+
+	async fn count(&self, rng: Range<Key>, version: Option<u64>) -> Result<usize> {
+		self.inner.count(rng, version).await
+	}
+
+	async fn batch_keys(
+		&self,
+		rng: Range<Key>,
+		batch: u32,
+		version: Option<u64>,
+	) -> Result<Batch<Key>> {
+		self.inner.batch_keys(rng, batch, version).await
+	}
+
+	async fn batch_keys_vals(
+		&self,
+		rng: Range<Key>,
+		batch: u32,
+		version: Option<u64>,
+	) -> Result<Batch<(Key, Val)>> {
+		let res = self.inner.batch_keys_vals(rng, batch, version).await?;
+		Ok(Batch::new(res.next, self.decrypt_pairs(res.result)?))
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		self.inner.new_save_point().await
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.release_last_save_point().await
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		self.inner.rollback_to_save_point().await
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.inner.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		self.inner.compact(range).await
+	}
+
+	fn has_tier(&self, name: &str) -> bool {
+		self.inner.has_tier(name)
+	}
+
+	async fn set_tier(&self, prefix: Key, tier: Option<String>) -> Result<()> {
+		self.inner.set_tier(prefix, tier).await
+	}
+
+	async fn checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+		self.inner.checkpoint(path).await
+	}
+
+	async fn size(&self, rng: Range<Key>) -> Result<Option<SizeEstimate>> {
+		self.inner.size(rng).await
+	}
+
+	fn can_prune_versions(&self) -> bool {
+		self.inner.can_prune_versions()
+	}
+
+	async fn prune_versions(
+		&self,
+		rng: Range<Key>,
+		before: u64,
+		limit: u32,
+	) -> Result<Option<Key>> {
+		self.inner.prune_versions(rng, before, limit).await
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encrypt_decrypt() {
+		let cipher = Cipher::new(&[7; KEY_LEN]);
+		let enc = cipher.encrypt(b"key", b"value").unwrap();
+		assert_ne!(&enc[1 + SALT_LEN + NONCE_LEN..], b"value");
+		assert_eq!(cipher.decrypt(b"key", &enc).unwrap(), b"value");
+		// Each value is encrypted with its own salt and nonce
+		let again = cipher.encrypt(b"key", b"value").unwrap();
+		assert_ne!(enc[1..1 + SALT_LEN + NONCE_LEN], again[1..1 + SALT_LEN + NONCE_LEN]);
+		assert_eq!(cipher.decrypt(b"key", &again).unwrap(), b"value");
+		// Tampering with the salt breaks the derived subkey
+		let mut tampered = enc.clone();
+		tampered[1] ^= 1;
+		assert!(cipher.decrypt(b"key", &tampered).is_err());
+		// Values are bound to their key
+		assert!(cipher.decrypt(b"other", &enc).is_err());
+		// Values can not be decrypted with another key
+		assert!(Cipher::new(&[8; KEY_LEN]).decrypt(b"key", &enc).is_err());
+	}
+
+	#[test]
+	fn parse_key() {
+		assert!(EncryptionKey::from_str(&"ab".repeat(32)).is_ok());
+		assert!(EncryptionKey::from_str(&"ab".repeat(16)).is_err());
+		assert!(EncryptionKey::from_str(&"zz".repeat(32)).is_err());
+	}
+}
@@ -0,0 +1,161 @@
+//! Registration of external storage engines.
+//!
+//! The storage engines built into SurrealDB are selected by the URL scheme
+//! of the datastore path, such as `rocksdb://` or `surrealkv://`. External
+//! crates can add their own storage engines under a new URL scheme, without
+//! needing to implement a custom [`TransactionBuilderFactory`].
+//!
+//! A storage engine implements [`StorageEngine`], which opens the engine and
+//! returns a [`TransactionBuilder`]. The transaction builder then creates a
+//! [`Transactable`] for each transaction. Once registered with [`register`],
+//! the engine can be used with [`Datastore::new`] and the SurrealDB server.
+//!
+//! ```rust,no_run
+//! # use std::collections::HashMap;
+//! # use surrealdb_core::kvs::engine::{self, StorageEngine};
+//! # use surrealdb_core::kvs::{Datastore, TransactionBuilder};
+//! # use tokio_util::sync::CancellationToken;
+//! struct MyEngine;
+//!
+//! #[async_trait::async_trait]
+//! impl StorageEngine for MyEngine {
+//!     async fn open(
+//!         &self,
+//!         path: &str,
+//!         params: &HashMap<String, String>,
+//!         canceller: CancellationToken,
+//!     ) -> anyhow::Result<Box<dyn TransactionBuilder>> {
+//!         // Open the storage engine at the given path
+//!         # unimplemented!()
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> anyhow::Result<()> {
+//! engine::register("myengine", MyEngine)?;
+//! let ds = Datastore::new("myengine://path/to/data").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TransactionBuilderFactory`]: super::TransactionBuilderFactory
+//! [`Transactable`]: super::Transactable
+//! [`Datastore::new`]: super::Datastore::new
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+use parking_lot::RwLock;
+use tokio_util::sync::CancellationToken;
+
+use super::ds::TransactionBuilder;
+use super::err::{Error, Result};
+
+/// The URL schemes used by the built-in storage engines, which can not be registered.
+const RESERVED: [&str; 8] =
+	["memory", "mem", "file", "rocksdb", "surrealkv", "redb", "indxdb", "tikv"];
+
+/// The storage engines which have been registered, by URL scheme
+static ENGINES: LazyLock<RwLock<HashMap<String, Arc<dyn StorageEngine>>>> =
+	LazyLock::new(Default::default);
+
+/// A storage engine which can be registered under a URL scheme.
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+pub trait StorageEngine: Send + Sync + 'static {
+	/// Open the storage engine.
+	///
+	/// # Parameters
+	/// - `path`: The datastore path, without the URL scheme or query string
+	/// - `params`: The query parameters specified on the datastore path
+	/// - `canceller`: Token for graceful shutdown and cancellation of long-running operations
+	async fn open(
+		&self,
+		path: &str,
+		params: &HashMap<String, String>,
+		canceller: CancellationToken,
+	) -> anyhow::Result<Box<dyn TransactionBuilder>>;
+}
+
+/// Register a storage engine under a URL scheme.
+///
+/// This must be called before a datastore is created with this scheme. The
+/// schemes of the built-in storage engines can not be registered, and a scheme
+/// can only be registered once.
+pub fn register(scheme: &str, engine: impl StorageEngine) -> Result<()> {
+	// Check that the scheme is valid
+	if scheme.is_empty() || !scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+		return Err(Error::Datastore(format!("The storage engine scheme `{scheme}` is invalid")));
+	}
+	// Check that the scheme is not used by a built-in engine
+	if RESERVED.contains(&scheme) {
+		return Err(Error::Datastore(format!(
+			"The storage engine scheme `{scheme}` is used by a built-in storage engine"
+		)));
+	}
+	// Check that the scheme is not already registered
+	let mut engines = ENGINES.write();
+	if engines.contains_key(scheme) {
+		return Err(Error::Datastore(format!(
+			"A storage engine is already registered for the scheme `{scheme}`"
+		)));
+	}
+	engines.insert(scheme.to_owned(), Arc::new(engine));
+	Ok(())
+}
+
+/// Check whether a storage engine is registered under a URL scheme.
+pub fn is_registered(scheme: &str) -> bool {
+	ENGINES.read().contains_key(scheme)
+}
+
+/// Fetch the storage engine registered under a URL scheme.
+pub(crate) fn get(scheme: &str) -> Option<Arc<dyn StorageEngine>> {
+	ENGINES.read().get(scheme).cloned()
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use super::*;
+	use crate::kvs::config::MemoryConfig;
+	use crate::kvs::{Datastore, DatastoreFlavor, LockType, TransactionType};
+
+	struct TestEngine;
+
+	#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+	#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+	impl StorageEngine for TestEngine {
+		async fn open(
+			&self,
+			_: &str,
+			_: &HashMap<String, String>,
+			_: CancellationToken,
+		) -> anyhow::Result<Box<dyn TransactionBuilder>> {
+			crate::kvs::threadpool::initialise();
+			let ds = crate::kvs::mem::Datastore::new(MemoryConfig::default()).await?;
+			Ok(Box::new(DatastoreFlavor::Mem(ds)))
+		}
+	}
+
+	#[test]
+	fn register_reserved() {
+		assert!(register("rocksdb", TestEngine).is_err());
+		assert!(register("not/valid", TestEngine).is_err());
+		assert!(!is_registered("rocksdb"));
+	}
+
+	#[tokio::test]
+	async fn register_engine() {
+		register("test-engine", TestEngine).unwrap();
+		assert!(register("test-engine", TestEngine).is_err());
+		assert!(is_registered("test-engine"));
+		// Open a datastore using the registered scheme
+		let ds = Datastore::new("test-engine://data").await.unwrap();
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.set(&"test", &"ok".as_bytes().to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		assert_eq!(tx.get(&"test", None).await.unwrap().as_deref(), Some(b"ok".as_slice()));
+		tx.cancel().await.unwrap();
+	}
+}
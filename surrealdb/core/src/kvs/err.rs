@@ -60,6 +60,10 @@ pub enum Error {
 	#[error("The underlying datastore does not support versioned queries")]
 	UnsupportedVersionedQueries,
 
+	/// The underlying datastore can not remove the history of a range of keys
+	#[error("The underlying datastore does not support a history retention period")]
+	UnsupportedHistoryRetention,
+
 	/// The specified timestamp is not valid for the underlying datastore
 	#[error("The specified timestamp is not valid for the underlying datastore: {0}")]
 	TimestampInvalid(String),
@@ -70,6 +74,13 @@ pub enum Error {
 
 	#[error("The storage layer does not support compaction requests.")]
 	CompactionNotSupported,
+
+	#[error("The storage layer does not support checkpoints.")]
+	CheckpointNotSupported,
+
+	/// There was a problem encrypting or decrypting stored data
+	#[error("There was a problem with datastore encryption: {0}")]
+	Encryption(String),
 }
 
 impl Error {
@@ -112,6 +123,41 @@ impl From<surrealkv::Error> for Error {
 	}
 }
 
+#[cfg(feature = "kv-redb")]
+impl From<redb::DatabaseError> for Error {
+	fn from(e: redb::DatabaseError) -> Error {
+		Error::Datastore(e.to_string())
+	}
+}
+
+#[cfg(feature = "kv-redb")]
+impl From<redb::TransactionError> for Error {
+	fn from(e: redb::TransactionError) -> Error {
+		Error::Transaction(e.to_string())
+	}
+}
+
+#[cfg(feature = "kv-redb")]
+impl From<redb::TableError> for Error {
+	fn from(e: redb::TableError) -> Error {
+		Error::Transaction(e.to_string())
+	}
+}
+
+#[cfg(feature = "kv-redb")]
+impl From<redb::StorageError> for Error {
+	fn from(e: redb::StorageError) -> Error {
+		Error::Transaction(e.to_string())
+	}
+}
+
+#[cfg(feature = "kv-redb")]
+impl From<redb::CommitError> for Error {
+	fn from(e: redb::CommitError) -> Error {
+		Error::Transaction(e.to_string())
+	}
+}
+
 #[cfg(feature = "kv-rocksdb")]
 impl From<rocksdb::Error> for Error {
 	fn from(e: rocksdb::Error) -> Error {
@@ -0,0 +1,617 @@
+#![cfg(any(
+	feature = "kv-rocksdb",
+	feature = "kv-tikv",
+	feature = "kv-redb",
+	feature = "kv-indxdb"
+))]
+
+//! An MVCC history keyspace for storage engines without native versioning.
+//!
+//! The in-memory and SurrealKV storage engines support versioned reads
+//! natively. For the other storage engines, this wrapper stores the history
+//! of each key in a separate keyspace, so that versioned queries behave the
+//! same on every storage engine.
+//!
+//! When a transaction commits, the final value of each key written by the
+//! transaction is appended to the history keyspace, at the commit timestamp.
+//! Deleted keys are recorded with a tombstone. A versioned read finds the
+//! newest history entry at or before the requested version. History is only
+//! stored for keys within a namespace, as the root and node keys are written
+//! frequently, and are never read at a version.
+//!
+//! History entries are stored at `PREFIX ‖ escape(key) ‖ 0x00 0x00 ‖ ts`,
+//! where `0x00` bytes in the key are escaped as `0x00 0xff`. This keeps the
+//! entries for each key together, and ordered by key and then by timestamp.
+//! Old entries are removed by [`Transactable::prune_versions`], which is
+//! called by the history cleanup task for tables and databases which have a
+//! history retention period.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+
+use parking_lot::Mutex;
+
+use super::api::{ScanLimit, SizeEstimate, Transactable};
+use super::ds::{Metrics, TransactionBuilder};
+use super::err::{Error, Result};
+use super::util::Collector;
+use super::{BoxTimeStamp, BoxTimeStampImpl, Key, TransactionBuilderRequirements, Val};
+use crate::cnf::NORMAL_FETCH_SIZE;
+
+/// The prefix of the history keyspace
+const PREFIX: &[u8] = &[0xff, b'h'];
+
+/// The prefix of the keys for which history is stored
+const TRACKED: &[u8] = b"/*";
+
+/// The marker of a history entry for a deleted key
+const TOMBSTONE: u8 = 0;
+
+/// The marker of a history entry for a value
+const VALUE: u8 = 1;
+
+/// Encode a key into the history keyspace, without a timestamp
+fn bound(key: &[u8]) -> Key {
+	let mut k = Vec::with_capacity(PREFIX.len() + key.len() + 2);
+	k.extend_from_slice(PREFIX);
+	for &b in key {
+		k.push(b);
+		if b == 0x00 {
+			k.push(0xff);
+		}
+	}
+	k
+}
+
+/// Encode the history entry of a key at a timestamp
+fn entry(key: &[u8], ts: u64) -> Key {
+	let mut k = bound(key);
+	k.extend_from_slice(&[0x00, 0x00]);
+	k.extend_from_slice(&ts.to_be_bytes());
+	k
+}
+
+/// The range of all history entries of a key
+fn entries(key: &[u8]) -> Range<Key> {
+	let mut beg = bound(key);
+	let mut end = beg.clone();
+	beg.extend_from_slice(&[0x00, 0x00]);
+	end.extend_from_slice(&[0x00, 0x01]);
+	beg..end
+}
+
+/// Decode a history entry into its key and timestamp
+fn decode(k: &[u8]) -> Option<(Key, u64)> {
+	let k = k.strip_prefix(PREFIX)?;
+	let mut key = Vec::with_capacity(k.len());
+	let mut i = 0;
+	while i + 1 < k.len() {
+		match (k[i], k[i + 1]) {
+			(0x00, 0x00) => {
+				let ts = k.get(i + 2..)?.try_into().ok()?;
+				return Some((key, u64::from_be_bytes(ts)));
+			}
+			(0x00, 0xff) => {
+				key.push(0x00);
+				i += 2;
+			}
+			(0x00, _) => return None,
+			(b, _) => {
+				key.push(b);
+				i += 1;
+			}
+		}
+	}
+	None
+}
+
+/// Encode a value, or a deletion, as a history value
+fn encode(val: Option<&[u8]>) -> Val {
+	match val {
+		Some(v) => {
+			let mut res = Vec::with_capacity(v.len() + 1);
+			res.push(VALUE);
+			res.extend_from_slice(v);
+			res
+		}
+		None => vec![TOMBSTONE],
+	}
+}
+
+/// Decode a history value, returning `None` for a deletion
+fn value(mut val: Val) -> Result<Option<Val>> {
+	match val.first() {
+		Some(&TOMBSTONE) => Ok(None),
+		Some(&VALUE) => {
+			val.remove(0);
+			Ok(Some(val))
+		}
+		_ => Err(Error::Datastore("The stored history entry is not valid".to_owned())),
+	}
+}
+
+/// A transaction builder which stores the history of the wrapped builder
+pub(super) struct HistoryBuilder {
+	/// The wrapped transaction builder
+	inner: Box<dyn TransactionBuilder>,
+}
+
+impl fmt::Display for HistoryBuilder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.inner.fmt(f)
+	}
+}
+
+impl HistoryBuilder {
+	/// Wrap a transaction builder
+	pub(super) fn new(inner: Box<dyn TransactionBuilder>) -> Self {
+		Self {
+			inner,
+		}
+	}
+}
+
+impl TransactionBuilderRequirements for HistoryBuilder {}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl TransactionBuilder for HistoryBuilder {
+	async fn new_transaction(
+		&self,
+		write: bool,
+		lock: bool,
+	) -> anyhow::Result<(Box<dyn Transactable>, bool)> {
+		let (inner, local) = self.inner.new_transaction(write, lock).await?;
+		let tx = HistoryTransaction {
+			inner,
+			writes: Default::default(),
+			savepoints: Default::default(),
+		};
+		Ok((Box::new(tx), local))
+	}
+
+	async fn shutdown(&self) -> anyhow::Result<()> {
+		self.inner.shutdown().await
+	}
+
+	fn register_metrics(&self) -> Option<Metrics> {
+		self.inner.register_metrics()
+	}
+
+	fn collect_u64_metric(&self, metric: &str) -> Option<u64> {
+		self.inner.collect_u64_metric(metric)
+	}
+}
+
+/// The final value of each key written in a transaction
+type Writes = BTreeMap<Key, Option<Val>>;
+
+/// A transaction which stores the history of the wrapped transaction
+struct HistoryTransaction {
+	/// The wrapped transaction
+	inner: Box<dyn Transactable>,
+	/// The keys written in this transaction
+	writes: Mutex<Writes>,
+	/// The keys written before each save point
+	savepoints: Mutex<Vec<Writes>>,
+}
+
+impl HistoryTransaction {
+	/// Record a write to a key, to be stored in the history on commit
+	fn record(&self, key: &Key, val: Option<&Val>) {
+		if key.starts_with(TRACKED) {
+			self.writes.lock().insert(key.clone(), val.cloned());
+		}
+	}
+
+	/// The version at which the writes of this transaction are stored
+	///
+	/// This uses the timestamp of the storage engine, so that the history
+	/// entries are ordered consistently across the nodes of a cluster.
+	async fn version(&self) -> Result<u64> {
+		let ts = self.inner.timestamp().await?;
+		ts.as_version().ok_or_else(|| {
+			Error::TimestampInvalid("The transaction timestamp is not a valid version".to_owned())
+		})
+	}
+
+	/// Store a history entry for a key at a specific version
+	async fn record_at(&self, key: &Key, val: Option<&Val>, ts: u64) -> Result<()> {
+		self.inner.set(entry(key, ts), encode(val.map(Vec::as_slice)), None).await
+	}
+
+	/// Remove the history of a key
+	async fn forget(&self, key: &Key) -> Result<()> {
+		self.writes.lock().remove(key);
+		self.inner.delr(entries(key)).await
+	}
+
+	/// Fetch the value of a key at a specific version
+	async fn get_at(&self, key: &Key, version: u64) -> Result<Option<Val>> {
+		let mut rng = entries(key);
+		rng.end = entry(key, version);
+		rng.end.push(0x00);
+		match self.inner.scanr(rng, ScanLimit::Count(1), 0, None).await?.pop() {
+			Some((_, v)) => value(v),
+			None => Ok(None),
+		}
+	}
+
+	/// Scan a range of keys at a specific version
+	async fn scan_at(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: u64,
+		values: bool,
+		reverse: bool,
+	) -> Result<Vec<(Key, Val)>> {
+		let batch = *NORMAL_FETCH_SIZE;
+		let mut col = Collector::new(limit, skip, values);
+		if col.full() {
+			return Ok(col.out);
+		}
+		let mut rng = bound(&rng.start)..bound(&rng.end);
+		// The current key, and its newest entry at or before the version
+		let mut group: Option<(Key, Option<(u64, Option<Val>)>)> = None;
+		loop {
+			let res = if reverse {
+				self.inner.scanr(rng.clone(), ScanLimit::Count(batch), 0, None).await?
+			} else {
+				self.inner.scan(rng.clone(), ScanLimit::Count(batch), 0, None).await?
+			};
+			let done = res.len() < batch as usize;
+			// Move the range past this batch
+			if let Some((k, _)) = res.last() {
+				if reverse {
+					rng.end = k.clone();
+				} else {
+					rng.start = k.clone();
+					rng.start.push(0x00);
+				}
+			}
+			for (k, v) in res {
+				let Some((key, ts)) = decode(&k) else {
+					continue;
+				};
+				// Output the previous key once all its entries are seen
+				if group.as_ref().is_some_and(|(k, _)| *k != key)
+					&& let Some((k, Some((_, Some(v))))) = group.take()
+					&& col.push(k, v)
+				{
+					return Ok(col.out);
+				}
+				let (_, newest) = group.get_or_insert_with(|| (key, None));
+				if ts <= version && newest.as_ref().is_none_or(|(t, _)| ts > *t) {
+					*newest = Some((ts, value(v)?));
+				}
+			}
+			if done {
+				break;
+			}
+		}
+		if let Some((k, Some((_, Some(v))))) = group.take() {
+			col.push(k, v);
+		}
+		Ok(col.out)
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for HistoryTransaction {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		self.inner.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		// Store the history of the keys written in this transaction
+		let writes = std::mem::take(&mut *self.writes.lock());
+		if !writes.is_empty() && self.writeable() && !self.closed() {
+			let ts = self.version().await?;
+			for (key, val) in writes {
+				self.record_at(&key, val.as_ref(), ts).await?;
+			}
+		}
+		self.inner.commit().await
+	}
+
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		match version {
+			Some(v) => Ok(self.get_at(&key, v).await?.is_some()),
+			None => self.inner.exists(key, None).await,
+		}
+	}
+
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		match version {
+			Some(v) => self.get_at(&key, v).await,
+			None => self.inner.get(key, None).await,
+		}
+	}
+
+	async fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> Result<Vec<Option<Val>>> {
+		match version {
+			Some(v) => {
+				let mut out = Vec::with_capacity(keys.len());
+				for key in keys {
+					out.push(self.get_at(&key, v).await?);
+				}
+				Ok(out)
+			}
+			None => self.inner.getm(keys, None).await,
+		}
+	}
+
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		if let Some(ts) = version {
+			self.record_at(&key, Some(&val), ts).await?;
+		} else {
+			self.record(&key, Some(&val));
+		}
+		self.inner.set(key, val, None).await
+	}
+
+	async fn replace(&self, key: Key, val: Val) -> Result<()> {
+		self.record(&key, Some(&val));
+		self.inner.replace(key, val).await
+	}
+
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		self.inner.put(key.clone(), val.clone(), None).await?;
+		match version {
+			Some(ts) => self.record_at(&key, Some(&val), ts).await,
+			None => {
+				self.record(&key, Some(&val));
+				Ok(())
+			}
+		}
+	}
+
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		self.inner.putc(key.clone(), val.clone(), chk).await?;
+		self.record(&key, Some(&val));
+		Ok(())
+	}
+
+	async fn del(&self, key: Key) -> Result<()> {
+		self.inner.del(key.clone()).await?;
+		self.record(&key, None);
+		Ok(())
+	}
+
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.inner.delc(key.clone(), chk).await?;
+		self.record(&key, None);
+		Ok(())
+	}
+
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.inner.clr(key.clone()).await?;
+		self.forget(&key).await
+	}
+
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.inner.clrc(key.clone(), chk).await?;
+		self.forget(&key).await
+	}
+
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		match version {
+			Some(v) => {
+				let res = self.scan_at(rng, limit, skip, v, false, false).await?;
+				Ok(res.into_iter().map(|(k, _)| k).collect())
+			}
+			None => self.inner.keys(rng, limit, skip, None).await,
+		}
+	}
+
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		match version {
+			Some(v) => {
+				let res = self.scan_at(rng, limit, skip, v, false, true).await?;
+				Ok(res.into_iter().map(|(k, _)| k).collect())
+			}
+			None => self.inner.keysr(rng, limit, skip, None).await,
+		}
+	}
+
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		match version {
+			Some(v) => self.scan_at(rng, limit, skip, v, true, false).await,
+			None => self.inner.scan(rng, limit, skip, None).await,
+		}
+	}
+
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		match version {
+			Some(v) => self.scan_at(rng, limit, skip, v, true, true).await,
+			None => self.inner.scanr(rng, limit, skip, None).await,
+		}
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		self.inner.new_save_point().await?;
+		let writes = self.writes.lock().clone();
+		self.savepoints.lock().push(writes);
+		Ok(())
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.release_last_save_point().await?;
+		self.savepoints.lock().pop();
+		Ok(())
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		self.inner.rollback_to_save_point().await?;
+		if let Some(writes) = self.savepoints.lock().pop() {
+			*self.writes.lock() = writes;
+		}
+		Ok(())
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.inner.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		self.inner.compact(range).await
+	}
+
+	fn has_tier(&self, name: &str) -> bool {
+		self.inner.has_tier(name)
+	}
+
+	async fn set_tier(&self, prefix: Key, tier: Option<String>) -> Result<()> {
+		self.inner.set_tier(prefix, tier).await
+	}
+
+	async fn checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+		self.inner.checkpoint(path).await
+	}
+
+	async fn size(&self, rng: Range<Key>) -> Result<Option<SizeEstimate>> {
+		self.inner.size(rng).await
+	}
+
+	fn can_prune_versions(&self) -> bool {
+		true
+	}
+
+	async fn prune_versions(
+		&self,
+		rng: Range<Key>,
+		before: u64,
+		limit: u32,
+	) -> Result<Option<Key>> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Continue with function logic
+		let batch = *NORMAL_FETCH_SIZE;
+		let mut rng = bound(&rng.start)..bound(&rng.end);
+		// The number of history entries which have been checked
+		let mut count = 0u32;
+		// The key of the previous history entry
+		let mut last: Option<Key> = None;
+		// The newest entry before the cutoff, for the current key
+		let mut newest: Option<(Key, Key)> = None;
+		loop {
+			let res = self.inner.scan(rng.clone(), ScanLimit::Count(batch), 0, None).await?;
+			let done = res.len() < batch as usize;
+			if let Some((k, _)) = res.last() {
+				rng.start = k.clone();
+				rng.start.push(0x00);
+			}
+			for (k, v) in res {
+				let Some((key, ts)) = decode(&k) else {
+					continue;
+				};
+				// Stop at the start of the next key once the limit is reached
+				if last.as_ref() != Some(&key) {
+					if last.is_some() && count >= limit {
+						return Ok(Some(key));
+					}
+					last = Some(key.clone());
+				}
+				count = count.saturating_add(1);
+				// The newest entry of the previous key is kept
+				if newest.as_ref().is_some_and(|(n, _)| *n != key) {
+					newest = None;
+				}
+				if ts >= before {
+					continue;
+				}
+				// Any older entry is superseded by this one
+				if let Some((_, old)) = newest.take() {
+					self.inner.del(old).await?;
+				}
+				// A deletion needs no entry, as there is no value to read
+				if value(v)?.is_some() {
+					newest = Some((key, k));
+				} else {
+					self.inner.del(k).await?;
+				}
+			}
+			if done {
+				break;
+			}
+		}
+		Ok(None)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn encode_decode() {
+		let key = b"/*\x00\x00\x00\x01*tb\0*id".to_vec();
+		let enc = entry(&key, 42);
+		assert_eq!(decode(&enc), Some((key.clone(), 42)));
+		assert!(entries(&key).contains(&enc));
+		// Entries are ordered by key and then by timestamp
+		assert!(entry(b"a", u64::MAX) < entry(b"a\0", 0));
+		assert!(entry(b"a\0", u64::MAX) < entry(b"a\x01", 0));
+		assert!(entry(b"a", 1) < entry(b"a", 2));
+		assert!(entry(b"a", 1) < bound(b"b"));
+		assert!(bound(b"a") <= entry(b"a", 0));
+	}
+
+	#[test]
+	fn encode_values() {
+		assert_eq!(value(encode(Some(b"val"))).unwrap(), Some(b"val".to_vec()));
+		assert_eq!(value(encode(None)).unwrap(), None);
+		assert!(value(vec![]).is_err());
+	}
+}
@@ -13,26 +13,34 @@
 //! - `tikv`: [TiKV](https://github.com/tikv/tikv) a distributed, and transactional key-value
 //!   database
 //! - `mem`: in-memory database
+//! - `redb`: [redb](https://github.com/cberner/redb) a pure-Rust, single-file embedded key-value
+//!   store
 
 pub mod config;
+pub mod encryption;
+pub mod engine;
 pub mod export;
+pub mod simulation;
 
 mod api;
 mod batch;
 mod clock;
 mod ds;
 mod err;
+mod history;
 mod into;
 mod key;
 mod scanner;
 mod threadpool;
+mod tiered;
 mod timestamp;
 mod tr;
 mod tx;
-mod util;
+pub(crate) mod util;
 
 mod indxdb;
 mod mem;
+mod redb;
 mod rocksdb;
 mod surrealkv;
 mod tikv;
@@ -47,11 +55,12 @@ pub(crate) mod slowlog;
 pub(crate) mod tasklease;
 pub(crate) mod version;
 
-pub use api::{ScanLimit, Transactable};
+pub use api::{ScanLimit, SizeEstimate, Transactable};
 pub(crate) use ds::TransactionFactory;
 pub use ds::requirements::{TransactionBuilderFactoryRequirements, TransactionBuilderRequirements};
 pub use ds::{
-	Datastore, DatastoreFlavor, Metric, Metrics, TransactionBuilder, TransactionBuilderFactory,
+	ChangeStream, Datastore, DatastoreFlavor, Metric, Metrics, TransactionBuilder,
+	TransactionBuilderFactory,
 };
 pub use err::{Error, Result};
 pub use into::IntoBytes;
@@ -0,0 +1,687 @@
+#![cfg(feature = "kv-redb")]
+
+//! A single-file storage engine, built on the pure-Rust [redb] B-tree store.
+//!
+//! redb only allows a single write transaction at a time, so write
+//! transactions here are optimistic. Each transaction reads from a redb
+//! snapshot, and buffers its writes in memory until it is committed. On
+//! commit, the writes are checked for conflicts with any transactions which
+//! committed since this transaction started, and are then applied in a single
+//! redb write transaction.
+//!
+//! Commits are serialised by a dedicated commit lock, and run on the blocking
+//! threadpool. The shared conflict state is only locked briefly before and
+//! after the writes are applied, so starting a new transaction never waits
+//! for a commit to be written to disk. A snapshot taken while a commit is
+//! being applied may already contain its writes, which can only cause a
+//! spurious conflict, and never a missed one.
+//!
+//! [redb]: https://github.com/cberner/redb
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::iter::Peekable;
+use std::mem;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+
+use parking_lot::Mutex;
+use redb::{Database, ReadOnlyTable, TableDefinition};
+use tokio::sync::RwLock;
+
+use super::api::ScanLimit;
+use super::err::{Error, Result};
+use crate::key::debug::Sprintable;
+use crate::kvs::api::Transactable;
+use crate::kvs::{Key, Val};
+
+const TARGET: &str = "surrealdb::core::kvs::redb";
+
+/// The table in which all keys are stored
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("surrealdb");
+
+/// The pending writes of a transaction, where `None` marks a deleted key
+type Writes = BTreeMap<Key, Option<Val>>;
+
+pub struct Datastore {
+	/// The underlying redb database
+	db: Arc<Database>,
+	/// The shared state used to detect write conflicts
+	state: Arc<Mutex<State>>,
+	/// The lock which ensures that only one transaction commits at a time
+	commit: Arc<Mutex<()>>,
+}
+
+pub struct Transaction {
+	/// Is the transaction complete?
+	done: AtomicBool,
+	/// Is the transaction writeable?
+	write: bool,
+	/// The commit version at which this transaction started
+	version: u64,
+	/// The snapshot of the datastore which this transaction reads from
+	snapshot: ReadOnlyTable<&'static [u8], &'static [u8]>,
+	/// The pending writes, and the writes at each save point
+	inner: RwLock<Pending>,
+	/// The underlying redb database
+	db: Arc<Database>,
+	/// The shared state used to detect write conflicts
+	state: Arc<Mutex<State>>,
+	/// The lock which ensures that only one transaction commits at a time
+	commit: Arc<Mutex<()>>,
+}
+
+#[derive(Default)]
+struct Pending {
+	/// The writes which have not yet been committed
+	writes: Writes,
+	/// The writes at each save point
+	savepoints: Vec<Writes>,
+}
+
+#[derive(Default)]
+struct State {
+	/// The version of the latest committed transaction
+	version: u64,
+	/// The number of open write transactions, by starting version
+	active: BTreeMap<u64, usize>,
+	/// The version at which each recently written key was committed
+	writes: HashMap<Key, u64>,
+}
+
+impl State {
+	/// Register a new write transaction, returning its starting version
+	fn begin(&mut self) -> u64 {
+		*self.active.entry(self.version).or_default() += 1;
+		self.version
+	}
+
+	/// Check that no key was changed since a transaction started
+	fn check(&self, version: u64, writes: &Writes) -> Result<()> {
+		match writes.keys().find(|k| self.writes.get(*k).is_some_and(|v| *v > version)) {
+			Some(key) => Err(Error::TransactionConflict(key.sprint())),
+			None => Ok(()),
+		}
+	}
+
+	/// Record the version at which the keys of a committed transaction changed
+	fn record(&mut self, writes: Writes) {
+		self.version += 1;
+		for key in writes.into_keys() {
+			self.writes.insert(key, self.version);
+		}
+	}
+
+	/// Remove a finished write transaction, pruning writes which can no longer conflict
+	fn finish(&mut self, version: u64) {
+		if let Some(count) = self.active.get_mut(&version) {
+			*count -= 1;
+			if *count == 0 {
+				self.active.remove(&version);
+			}
+		}
+		match self.active.keys().next() {
+			Some(&oldest) => self.writes.retain(|_, v| *v > oldest),
+			None => self.writes.clear(),
+		}
+	}
+}
+
+/// Apply the writes of a transaction in a single redb write transaction
+fn apply(db: &Database, writes: &Writes) -> Result<()> {
+	let tx = db.begin_write()?;
+	{
+		let mut table = tx.open_table(TABLE)?;
+		for (key, val) in writes.iter() {
+			match val {
+				Some(val) => {
+					table.insert(key.as_slice(), val.as_slice())?;
+				}
+				None => {
+					table.remove(key.as_slice())?;
+				}
+			}
+		}
+	}
+	tx.commit()?;
+	Ok(())
+}
+
+impl Datastore {
+	/// Open a new database
+	pub(crate) async fn new(path: &str) -> Result<Datastore> {
+		// Ensure that the parent directory exists
+		if let Some(parent) = Path::new(path).parent().filter(|p| !p.as_os_str().is_empty()) {
+			std::fs::create_dir_all(parent).map_err(|e| Error::Datastore(e.to_string()))?;
+		}
+		// Open or create the database file
+		info!(target: TARGET, "Opening database file at {path}");
+		let db = Database::create(path)?;
+		// Ensure that the table exists
+		let tx = db.begin_write()?;
+		tx.open_table(TABLE)?;
+		tx.commit()?;
+		// Return the new datastore
+		Ok(Datastore {
+			db: Arc::new(db),
+			state: Default::default(),
+			commit: Default::default(),
+		})
+	}
+
+	/// Shutdown the database
+	pub(crate) async fn shutdown(&self) -> Result<()> {
+		// Nothing to do here
+		Ok(())
+	}
+
+	/// Start a new transaction
+	pub(crate) async fn transaction(&self, write: bool, _: bool) -> Result<Box<dyn Transactable>> {
+		// Take the snapshot while no other transaction can commit
+		let mut state = self.state.lock();
+		let snapshot = self.db.begin_read()?.open_table(TABLE)?;
+		// Only write transactions can conflict
+		let version = match write {
+			true => state.begin(),
+			false => state.version,
+		};
+		drop(state);
+		// Return the new transaction
+		Ok(Box::new(Transaction {
+			done: AtomicBool::new(false),
+			write,
+			version,
+			snapshot,
+			inner: RwLock::new(Pending::default()),
+			db: self.db.clone(),
+			state: self.state.clone(),
+			commit: self.commit.clone(),
+		}))
+	}
+}
+
+impl Drop for Transaction {
+	fn drop(&mut self) {
+		// Release write transactions which were not committed or cancelled
+		if self.write && !self.done.swap(true, AtomicOrdering::AcqRel) {
+			self.state.lock().finish(self.version);
+		}
+	}
+}
+
+impl Transaction {
+	/// Fetch a key, taking into account any pending writes
+	fn fetch(&self, writes: &Writes, key: &Key) -> Result<Option<Val>> {
+		match writes.get(key) {
+			Some(val) => Ok(val.clone()),
+			None => Ok(self.snapshot.get(key.as_slice())?.map(|v| v.value().to_vec())),
+		}
+	}
+
+	/// Retrieve a range of entries, taking into account any pending writes
+	fn range(
+		&self,
+		writes: &Writes,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		values: bool,
+		reverse: bool,
+	) -> Result<Vec<(Key, Val)>> {
+		// An empty or inverted range contains no keys
+		if rng.start >= rng.end {
+			return Ok(Vec::new());
+		}
+		// Iterate over the committed entries
+		let committed = self.snapshot.range(rng.start.as_slice()..rng.end.as_slice())?.map(
+			move |res| -> Result<(Key, Val)> {
+				let (k, v) = res?;
+				let v = match values {
+					true => v.value().to_vec(),
+					false => Vec::new(),
+				};
+				Ok((k.value().to_vec(), v))
+			},
+		);
+		// Iterate over the pending writes
+		let pending = writes.range(rng);
+		// Merge the entries in the desired direction
+		let iter = match reverse {
+			true => Merge::new(committed.rev(), pending.rev(), values, true),
+			false => Merge::new(committed, pending, values, false),
+		};
+		// Consume the iterator
+		consume(iter, limit, skip)
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for Transaction {
+	fn kind(&self) -> &'static str {
+		"redb"
+	}
+
+	/// Check if closed
+	fn closed(&self) -> bool {
+		self.done.load(AtomicOrdering::Relaxed)
+	}
+
+	/// Check if writeable
+	fn writeable(&self) -> bool {
+		self.write
+	}
+
+	/// Cancels the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn cancel(&self) -> Result<()> {
+		// Atomically mark transaction as done and check if it was already closed
+		if self.done.swap(true, AtomicOrdering::AcqRel) {
+			return Err(Error::TransactionFinished);
+		}
+		// Release this transaction
+		if self.write {
+			self.state.lock().finish(self.version);
+		}
+		// Continue
+		Ok(())
+	}
+
+	/// Commits the transaction.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self))]
+	async fn commit(&self) -> Result<()> {
+		// Atomically mark transaction as done and check if it was already closed
+		if self.done.swap(true, AtomicOrdering::AcqRel) {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Take the pending writes
+		let writes = mem::take(&mut self.inner.write().await.writes);
+		// Execute on the blocking threadpool
+		let db = self.db.clone();
+		let state = self.state.clone();
+		let commit = self.commit.clone();
+		let version = self.version;
+		affinitypool::spawn_local(move || -> Result<()> {
+			// Apply the writes, if there are any
+			let res = match writes.is_empty() {
+				true => Ok(()),
+				false => {
+					// Only one transaction commits at a time, so the check remains valid
+					let _commit = commit.lock();
+					let checked = state.lock().check(version, &writes);
+					// The writes are applied without holding the shared state
+					let res = checked.and_then(|_| apply(&db, &writes));
+					if res.is_ok() {
+						state.lock().record(writes);
+					}
+					res
+				}
+			};
+			// Release this transaction
+			state.lock().finish(version);
+			res
+		})
+		.await
+	}
+
+	/// Checks if a key exists in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		Ok(self.get(key, version).await?.is_some())
+	}
+
+	/// Fetch a key from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Load the pending writes
+		let inner = self.inner.read().await;
+		// Get the key
+		self.fetch(&inner.writes, &key)
+	}
+
+	/// Insert or update a key in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Set the key
+		self.inner.write().await.writes.insert(key, Some(val));
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if it doesn't exist in the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Load the pending writes
+		let mut inner = self.inner.write().await;
+		// Set the key if empty
+		if self.fetch(&inner.writes, &key)?.is_some() {
+			return Err(Error::TransactionKeyAlreadyExists);
+		}
+		inner.writes.insert(key, Some(val));
+		// Return result
+		Ok(())
+	}
+
+	/// Insert a key if the current value matches a condition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Load the pending writes
+		let mut inner = self.inner.write().await;
+		// Set the key if valid
+		match (self.fetch(&inner.writes, &key)?, chk) {
+			(Some(v), Some(w)) if v == w => inner.writes.insert(key, Some(val)),
+			(None, None) => inner.writes.insert(key, Some(val)),
+			_ => return Err(Error::TransactionConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key from the database.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn del(&self, key: Key) -> Result<()> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Remove the key
+		self.inner.write().await.writes.insert(key, None);
+		// Return result
+		Ok(())
+	}
+
+	/// Delete a key if the current value matches a condition.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(key = key.sprint()))]
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Load the pending writes
+		let mut inner = self.inner.write().await;
+		// Delete the key if valid
+		match (self.fetch(&inner.writes, &key)?, chk) {
+			(Some(v), Some(w)) if v == w => inner.writes.insert(key, None),
+			(None, None) => inner.writes.insert(key, None),
+			_ => return Err(Error::TransactionConditionNotMet),
+		};
+		// Return result
+		Ok(())
+	}
+
+	/// Retrieve a range of keys.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Load the pending writes
+		let inner = self.inner.read().await;
+		// Scan the keys
+		let res = self.range(&inner.writes, rng, limit, skip, false, false)?;
+		// Return result
+		Ok(res.into_iter().map(|(k, _)| k).collect())
+	}
+
+	/// Retrieve a range of keys, in reverse.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Load the pending writes
+		let inner = self.inner.read().await;
+		// Scan the keys
+		let res = self.range(&inner.writes, rng, limit, skip, false, true)?;
+		// Return result
+		Ok(res.into_iter().map(|(k, _)| k).collect())
+	}
+
+	/// Retrieve a range of key-value pairs.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Load the pending writes
+		let inner = self.inner.read().await;
+		// Scan the key-value pairs
+		self.range(&inner.writes, rng, limit, skip, true, false)
+	}
+
+	/// Retrieve a range of key-value pairs, in reverse.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		// redb does not support versioned queries.
+		if version.is_some() {
+			return Err(Error::UnsupportedVersionedQueries);
+		}
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Load the pending writes
+		let inner = self.inner.read().await;
+		// Scan the key-value pairs
+		self.range(&inner.writes, rng, limit, skip, true, true)
+	}
+
+	/// Set a new save point on the transaction.
+	async fn new_save_point(&self) -> Result<()> {
+		let mut inner = self.inner.write().await;
+		let writes = inner.writes.clone();
+		inner.savepoints.push(writes);
+		Ok(())
+	}
+
+	/// Rollback to the last save point.
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		let mut inner = self.inner.write().await;
+		match inner.savepoints.pop() {
+			Some(writes) => inner.writes = writes,
+			None => return Err(Error::Transaction("There is no save point to rollback to".into())),
+		}
+		Ok(())
+	}
+
+	/// Release the last save point.
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.write().await.savepoints.pop();
+		Ok(())
+	}
+}
+
+/// Merges committed entries with the pending writes of a transaction
+struct Merge<'a> {
+	/// The committed entries from the snapshot
+	committed: Peekable<Box<dyn Iterator<Item = Result<(Key, Val)>> + 'a>>,
+	/// The pending writes, which take precedence over committed entries
+	pending: Peekable<Box<dyn Iterator<Item = (&'a Key, &'a Option<Val>)> + 'a>>,
+	/// Whether to return values along with keys
+	values: bool,
+	/// Whether the entries are in reverse order
+	reverse: bool,
+}
+
+impl<'a> Merge<'a> {
+	fn new(
+		committed: impl Iterator<Item = Result<(Key, Val)>> + 'a,
+		pending: impl Iterator<Item = (&'a Key, &'a Option<Val>)> + 'a,
+		values: bool,
+		reverse: bool,
+	) -> Self {
+		let committed: Box<dyn Iterator<Item = _> + 'a> = Box::new(committed);
+		let pending: Box<dyn Iterator<Item = _> + 'a> = Box::new(pending);
+		Self {
+			committed: committed.peekable(),
+			pending: pending.peekable(),
+			values,
+			reverse,
+		}
+	}
+}
+
+impl Iterator for Merge<'_> {
+	type Item = Result<(Key, Val)>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			// Determine which iterator contains the next key
+			let order = match (self.committed.peek(), self.pending.peek()) {
+				(None, None) => return None,
+				(Some(_), None) => Ordering::Less,
+				(None, Some(_)) => Ordering::Greater,
+				(Some(Err(_)), Some(_)) => Ordering::Less,
+				(Some(Ok((a, _))), Some((b, _))) => match self.reverse {
+					true => (*b).cmp(a),
+					false => a.cmp(*b),
+				},
+			};
+			match order {
+				Ordering::Less => return self.committed.next(),
+				// A pending write replaces the committed entry
+				Ordering::Equal => {
+					self.committed.next();
+				}
+				Ordering::Greater => {}
+			}
+			// Skip any keys which have been deleted
+			if let (k, Some(v)) = self.pending.next()? {
+				let v = match self.values {
+					true => v.clone(),
+					false => Vec::new(),
+				};
+				return Some(Ok((k.clone(), v)));
+			}
+		}
+	}
+}
+
+// Consume an iterator of entries, skipping and limiting the results
+fn consume(
+	mut iter: impl Iterator<Item = Result<(Key, Val)>>,
+	limit: ScanLimit,
+	skip: u32,
+) -> Result<Vec<(Key, Val)>> {
+	// Skip entries without allocation
+	for _ in 0..skip {
+		if iter.next().transpose()?.is_none() {
+			return Ok(Vec::new());
+		}
+	}
+	// Determine the count and byte limits
+	let (count, bytes) = match limit {
+		ScanLimit::Count(c) => (c as usize, usize::MAX),
+		ScanLimit::Bytes(b) => (usize::MAX, b as usize),
+		ScanLimit::BytesOrCount(b, c) => (c as usize, b as usize),
+	};
+	// Create the result set
+	let mut res = Vec::with_capacity(count.min(4096));
+	// Count the bytes fetched
+	let mut bytes_fetched = 0usize;
+	// Check that we don't exceed the count limit AND the byte limit
+	while res.len() < count && bytes_fetched < bytes {
+		match iter.next().transpose()? {
+			Some((k, v)) => {
+				bytes_fetched += k.len() + v.len();
+				res.push((k, v));
+			}
+			None => break,
+		}
+	}
+	// Return the result
+	Ok(res)
+}
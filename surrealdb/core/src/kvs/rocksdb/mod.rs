@@ -7,6 +7,7 @@ mod disk_space_manager;
 mod memory_manager;
 
 use std::ops::Range;
+use std::path::Path;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
@@ -22,7 +23,7 @@ use rocksdb::{
 use tokio::sync::Mutex;
 
 use super::Direction;
-use super::api::ScanLimit;
+use super::api::{ScanLimit, SizeEstimate};
 use super::config::{RocksDbConfig, SyncMode};
 use super::err::{Error, Result};
 use crate::key::debug::Sprintable;
@@ -960,6 +961,36 @@ impl Transactable for Transaction {
 		Ok(())
 	}
 
+	/// Estimate the storage used by a range of keys.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::api", skip(self), fields(rng = rng.sprint()))]
+	async fn size(&self, rng: Range<Key>) -> Result<Option<SizeEstimate>> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Execute on the blocking threadpool
+		let res = affinitypool::spawn_local(move || -> Result<_> {
+			// Estimate the size of the key range on disk
+			let range = rocksdb::Range::new(&rng.start, &rng.end);
+			let bytes = self.db.get_approximate_sizes(&[range]).first().copied().unwrap_or(0);
+			// Estimate the number of keys from the average entry size
+			let size = self.db.property_int_value("rocksdb.estimate-live-data-size")?.unwrap_or(0);
+			let count = self.db.property_int_value("rocksdb.estimate-num-keys")?.unwrap_or(0);
+			let keys = match size {
+				0 => 0,
+				size => (bytes as u128 * count as u128 / size as u128) as u64,
+			};
+			// Return result
+			Ok(SizeEstimate {
+				bytes,
+				keys,
+			})
+		})
+		.await?;
+		// Return result
+		Ok(Some(res))
+	}
+
 	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
 		let (start, end) = match range {
 			Some(r) => (Some(r.start), Some(r.end)),
@@ -968,6 +999,21 @@ impl Transactable for Transaction {
 		self.db.compact_range(start, end);
 		Ok(())
 	}
+
+	async fn checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+		// Execute on the blocking threadpool
+		affinitypool::spawn_local(move || -> Result<()> {
+			// Create a checkpoint of the live database
+			let checkpoint = rocksdb::checkpoint::Checkpoint::new(&*self.db)?;
+			// Hard-link or copy the database files to the path
+			checkpoint.create_checkpoint(path)?;
+			// Checkpoint created successfully
+			Ok(())
+		})
+		.await?;
+		// Return result
+		Ok(())
+	}
 }
 
 // Consume and iterate over only keys
@@ -0,0 +1,541 @@
+#![cfg(feature = "kv-simulation")]
+
+//! A fault-injecting wrapper around a storage engine, for deterministic testing.
+//!
+//! The simulation wraps the transactions of any storage engine, and injects
+//! faults into operations which touch configured key ranges. Faults can add
+//! latency to reads and writes, or cause a commit to fail with a conflict, an
+//! error, or a simulated crash. After a crash, every operation on the
+//! datastore fails, as if the process had died before the commit completed.
+//!
+//! Whether a fault is injected is decided by a random number generator with a
+//! fixed seed, so a test which performs the same operations in the same order
+//! will always see the same faults.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+
+use super::api::{ScanLimit, SizeEstimate, Transactable};
+use super::ds::{Metrics, TransactionBuilder};
+use super::err::{Error, Result};
+use super::{BoxTimeStamp, BoxTimeStampImpl, Key, TransactionBuilderRequirements, Val, util};
+
+/// The configuration of a fault-injecting simulation.
+#[derive(Clone, Debug, Default)]
+pub struct SimulationConfig {
+	/// The seed for the random number generator
+	pub seed: u64,
+	/// The faults which can be injected
+	pub faults: Vec<Fault>,
+}
+
+/// A fault which can be injected into operations on a range of keys.
+#[derive(Clone, Debug)]
+pub struct Fault {
+	/// The kind of fault to inject
+	pub kind: FaultKind,
+	/// The range of keys which the fault applies to
+	pub range: Range<Key>,
+	/// The probability of the fault being injected, between 0 and 1
+	pub probability: f64,
+}
+
+/// The kind of fault which is injected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FaultKind {
+	/// Delay every operation which touches the key range
+	Latency(Duration),
+	/// Fail the commit of a transaction which wrote to the key range with a
+	/// retryable transaction conflict
+	Conflict,
+	/// Fail the commit of a transaction which wrote to the key range
+	CommitError,
+	/// Crash the datastore when committing a transaction which wrote to the
+	/// key range, without applying the transaction
+	Crash,
+}
+
+impl Fault {
+	/// Create a fault which is always injected into operations on a key range
+	pub fn new(kind: FaultKind, range: Range<Key>) -> Self {
+		Self {
+			kind,
+			range,
+			probability: 1.0,
+		}
+	}
+
+	/// Set the probability of the fault being injected, between 0 and 1
+	pub fn with_probability(mut self, probability: f64) -> Self {
+		self.probability = probability.clamp(0.0, 1.0);
+		self
+	}
+
+	/// Check whether this fault applies to a key
+	fn contains(&self, key: &Key) -> bool {
+		self.range.contains(key)
+	}
+
+	/// Check whether this fault applies to any key in a range
+	fn overlaps(&self, rng: &Range<Key>) -> bool {
+		rng.start < self.range.end && self.range.start < rng.end
+	}
+}
+
+/// The state of a simulation, shared between its transactions
+struct Simulation {
+	/// The faults which can be injected
+	faults: Vec<Fault>,
+	/// The seeded random number generator
+	rng: Mutex<SmallRng>,
+	/// Whether the datastore has crashed
+	crashed: AtomicBool,
+}
+
+impl Simulation {
+	/// Decide whether a fault is injected
+	fn inject(&self, fault: &Fault) -> bool {
+		self.rng.lock().gen_bool(fault.probability.clamp(0.0, 1.0))
+	}
+
+	/// Check that the datastore has not crashed
+	fn check(&self) -> Result<()> {
+		match self.crashed.load(Ordering::Acquire) {
+			true => Err(Error::Datastore("The simulated datastore has crashed".to_owned())),
+			false => Ok(()),
+		}
+	}
+}
+
+/// A transaction builder which injects faults into the wrapped builder
+pub(super) struct SimulatedBuilder {
+	/// The wrapped transaction builder
+	inner: Arc<Box<dyn TransactionBuilder>>,
+	/// The shared simulation state
+	simulation: Arc<Simulation>,
+}
+
+impl fmt::Display for SimulatedBuilder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.inner.fmt(f)
+	}
+}
+
+impl SimulatedBuilder {
+	/// Wrap a transaction builder with a fault-injecting simulation
+	pub(super) fn new(inner: Arc<Box<dyn TransactionBuilder>>, config: SimulationConfig) -> Self {
+		Self {
+			inner,
+			simulation: Arc::new(Simulation {
+				faults: config.faults,
+				rng: Mutex::new(SmallRng::seed_from_u64(config.seed)),
+				crashed: AtomicBool::new(false),
+			}),
+		}
+	}
+}
+
+impl TransactionBuilderRequirements for SimulatedBuilder {}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl TransactionBuilder for SimulatedBuilder {
+	async fn new_transaction(
+		&self,
+		write: bool,
+		lock: bool,
+	) -> anyhow::Result<(Box<dyn Transactable>, bool)> {
+		self.simulation.check()?;
+		let (inner, local) = self.inner.new_transaction(write, lock).await?;
+		let tx = SimulatedTransaction {
+			inner,
+			touched: self.simulation.faults.iter().map(|_| AtomicBool::new(false)).collect(),
+			simulation: self.simulation.clone(),
+		};
+		Ok((Box::new(tx), local))
+	}
+
+	async fn shutdown(&self) -> anyhow::Result<()> {
+		self.inner.shutdown().await
+	}
+
+	fn register_metrics(&self) -> Option<Metrics> {
+		self.inner.register_metrics()
+	}
+
+	fn collect_u64_metric(&self, metric: &str) -> Option<u64> {
+		self.inner.collect_u64_metric(metric)
+	}
+}
+
+/// A transaction which injects faults into the wrapped transaction
+struct SimulatedTransaction {
+	/// The wrapped transaction
+	inner: Box<dyn Transactable>,
+	/// Whether this transaction has written to the key range of each fault
+	touched: Vec<AtomicBool>,
+	/// The shared simulation state
+	simulation: Arc<Simulation>,
+}
+
+impl SimulatedTransaction {
+	/// Inject any latency for an operation on a key
+	async fn read(&self, key: &Key) -> Result<()> {
+		self.simulation.check()?;
+		for fault in self.simulation.faults.iter().filter(|f| f.contains(key)) {
+			self.delay(fault).await;
+		}
+		Ok(())
+	}
+
+	/// Inject any latency for an operation on a key range
+	async fn read_range(&self, rng: &Range<Key>) -> Result<()> {
+		self.simulation.check()?;
+		for fault in self.simulation.faults.iter().filter(|f| f.overlaps(rng)) {
+			self.delay(fault).await;
+		}
+		Ok(())
+	}
+
+	/// Inject any latency for a write to a key, and record the write
+	async fn write(&self, key: &Key) -> Result<()> {
+		self.read(key).await?;
+		for (fault, touched) in self.simulation.faults.iter().zip(&self.touched) {
+			if fault.contains(key) {
+				touched.store(true, Ordering::Release);
+			}
+		}
+		Ok(())
+	}
+
+	/// Inject any latency for a write to a key range, and record the write
+	async fn write_range(&self, rng: &Range<Key>) -> Result<()> {
+		self.read_range(rng).await?;
+		for (fault, touched) in self.simulation.faults.iter().zip(&self.touched) {
+			if fault.overlaps(rng) {
+				touched.store(true, Ordering::Release);
+			}
+		}
+		Ok(())
+	}
+
+	/// Delay the operation if this is a latency fault which is injected
+	async fn delay(&self, fault: &Fault) {
+		if let FaultKind::Latency(duration) = fault.kind
+			&& self.simulation.inject(fault)
+		{
+			tokio::time::sleep(duration).await;
+		}
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for SimulatedTransaction {
+	fn kind(&self) -> &'static str {
+		self.inner.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.inner.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.inner.writeable()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		self.simulation.check()?;
+		self.inner.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		self.simulation.check()?;
+		// Inject any faults for the key ranges written by this transaction
+		for (fault, touched) in self.simulation.faults.iter().zip(&self.touched) {
+			if !touched.load(Ordering::Acquire) || !self.simulation.inject(fault) {
+				continue;
+			}
+			match fault.kind {
+				FaultKind::Latency(duration) => tokio::time::sleep(duration).await,
+				FaultKind::Conflict => {
+					self.inner.cancel().await?;
+					return Err(Error::TransactionConflict(
+						"Simulated transaction conflict".to_owned(),
+					));
+				}
+				FaultKind::CommitError => {
+					self.inner.cancel().await?;
+					return Err(Error::Transaction("Simulated commit failure".to_owned()));
+				}
+				FaultKind::Crash => {
+					self.simulation.crashed.store(true, Ordering::Release);
+					self.inner.cancel().await?;
+					return self.simulation.check();
+				}
+			}
+		}
+		self.inner.commit().await
+	}
+
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		self.read(&key).await?;
+		self.inner.exists(key, version).await
+	}
+
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		self.read(&key).await?;
+		self.inner.get(key, version).await
+	}
+
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.set(key, val, version).await
+	}
+
+	async fn replace(&self, key: Key, val: Val) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.replace(key, val).await
+	}
+
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.put(key, val, version).await
+	}
+
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.putc(key, val, chk).await
+	}
+
+	async fn del(&self, key: Key) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.del(key).await
+	}
+
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.delc(key, chk).await
+	}
+
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.clr(key).await
+	}
+
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.write(&key).await?;
+		self.inner.clrc(key, chk).await
+	}
+
+	async fn delr(&self, rng: Range<Key>) -> Result<()> {
+		self.write_range(&rng).await?;
+		self.inner.delr(rng).await
+	}
+
+	async fn delp(&self, key: Key) -> Result<()> {
+		self.write_range(&util::to_prefix_range(key.clone())?).await?;
+		self.inner.delp(key).await
+	}
+
+	async fn clrr(&self, rng: Range<Key>) -> Result<()> {
+		self.write_range(&rng).await?;
+		self.inner.clrr(rng).await
+	}
+
+	async fn clrp(&self, key: Key) -> Result<()> {
+		self.write_range(&util::to_prefix_range(key.clone())?).await?;
+		self.inner.clrp(key).await
+	}
+
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.read_range(&rng).await?;
+		self.inner.keys(rng, limit, skip, version).await
+	}
+
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		self.read_range(&rng).await?;
+		self.inner.keysr(rng, limit, skip, version).await
+	}
+
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		self.read_range(&rng).await?;
+		self.inner.scan(rng, limit, skip, version).await
+	}
+
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		self.read_range(&rng).await?;
+		self.inner.scanr(rng, limit, skip, version).await
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		self.inner.new_save_point().await
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		self.inner.release_last_save_point().await
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		self.inner.rollback_to_save_point().await
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.inner.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.inner.timestamp_impl()
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		self.inner.compact(range).await
+	}
+
+	fn has_tier(&self, name: &str) -> bool {
+		self.inner.has_tier(name)
+	}
+
+	async fn set_tier(&self, prefix: Key, tier: Option<String>) -> Result<()> {
+		self.inner.set_tier(prefix, tier).await
+	}
+
+	async fn checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+		self.inner.checkpoint(path).await
+	}
+
+	async fn size(&self, rng: Range<Key>) -> Result<Option<SizeEstimate>> {
+		self.inner.size(rng).await
+	}
+
+	fn can_prune_versions(&self) -> bool {
+		self.inner.can_prune_versions()
+	}
+
+	async fn prune_versions(
+		&self,
+		rng: Range<Key>,
+		before: u64,
+		limit: u32,
+	) -> Result<Option<Key>> {
+		self.inner.prune_versions(rng, before, limit).await
+	}
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use super::*;
+	use crate::kvs::{Datastore, LockType, TransactionType};
+
+	async fn new_ds(faults: Vec<Fault>) -> Datastore {
+		let config = SimulationConfig {
+			seed: 1,
+			faults,
+		};
+		Datastore::new("memory").await.unwrap().with_simulation(config)
+	}
+
+	fn range(beg: &str, end: &str) -> Range<Key> {
+		beg.as_bytes().to_vec()..end.as_bytes().to_vec()
+	}
+
+	#[tokio::test]
+	async fn inject_conflict() {
+		let ds = new_ds(vec![Fault::new(FaultKind::Conflict, range("a", "b"))]).await;
+		// Writes outside of the key range are unaffected
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.set(&"c", &b"ok".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// Writes within the key range fail with a retryable conflict
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.set(&"a1", &b"ok".to_vec(), None).await.unwrap();
+		let err = tx.commit().await.unwrap_err();
+		assert!(
+			matches!(err.downcast_ref::<Error>(), Some(Error::TransactionConflict(_))),
+			"{err}"
+		);
+		// The failed transaction was not applied
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		assert!(tx.get(&"a1", None).await.unwrap().is_none());
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn inject_range_writes() {
+		let ds = new_ds(vec![Fault::new(FaultKind::Conflict, range("a", "b"))]).await;
+		// Range deletes which overlap the key range are faulted like single writes
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.clrr(range("0", "z")).await.unwrap();
+		let err = tx.commit().await.unwrap_err();
+		assert!(
+			matches!(err.downcast_ref::<Error>(), Some(Error::TransactionConflict(_))),
+			"{err}"
+		);
+		// Range deletes outside of the key range are unaffected
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.delr(range("c", "d")).await.unwrap();
+		tx.commit().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn inject_crash() {
+		let ds = new_ds(vec![Fault::new(FaultKind::Crash, range("a", "b"))]).await;
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.set(&"a1", &b"ok".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap_err();
+		// The datastore is unusable after a crash
+		assert!(ds.transaction(TransactionType::Read, LockType::Optimistic).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn inject_deterministically() {
+		// Count the number of failed commits with a fault probability
+		async fn failures() -> Vec<bool> {
+			let fault = Fault::new(FaultKind::CommitError, range("a", "b")).with_probability(0.5);
+			let ds = new_ds(vec![fault]).await;
+			let mut res = Vec::new();
+			for _ in 0..32 {
+				let tx =
+					ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+				tx.set(&"a1", &b"ok".to_vec(), None).await.unwrap();
+				res.push(tx.commit().await.is_err());
+			}
+			res
+		}
+		let res = failures().await;
+		assert!(res.contains(&true) && res.contains(&false));
+		assert_eq!(res, failures().await);
+	}
+}
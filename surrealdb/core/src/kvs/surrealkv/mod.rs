@@ -4,6 +4,7 @@ mod cnf;
 mod sync;
 
 use std::ops::Range;
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -43,6 +44,8 @@ pub struct Transaction {
 	inner: RwLock<Tx>,
 	/// Commit coordinator for grouped fsync (when sync=every)
 	commit_coordinator: Option<Arc<CommitCoordinator>>,
+	/// The underlying datastore, used for checkpoints
+	db: Tree,
 }
 
 impl Datastore {
@@ -156,6 +159,7 @@ impl Datastore {
 			enable_versions: self.enable_versions,
 			inner: RwLock::new(txn),
 			commit_coordinator: self.commit_coordinator.clone(),
+			db: self.db.clone(),
 		}))
 	}
 }
@@ -727,6 +731,26 @@ impl Transactable for Transaction {
 	async fn release_last_save_point(&self) -> Result<()> {
 		Ok(())
 	}
+
+	/// Create a checkpoint of the datastore at the specified path.
+	async fn checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+		// The storage engine schedules background tasks on the runtime
+		let handle = tokio::runtime::Handle::current();
+		// Execute on the blocking threadpool
+		affinitypool::spawn_local(move || -> Result<()> {
+			// Enter the runtime on the blocking thread
+			let _rt = handle.enter();
+			// Flush the WAL so the checkpoint includes all committed writes
+			self.db.flush_wal(true)?;
+			// Copy the database files to the path
+			self.db.create_checkpoint(path)?;
+			// Checkpoint created successfully
+			Ok(())
+		})
+		.await?;
+		// Return result
+		Ok(())
+	}
 }
 
 // A cursor advances through entries and returns the next key or key-value pair.
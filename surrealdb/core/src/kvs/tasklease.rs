@@ -27,6 +27,10 @@ pub(crate) enum TaskLeaseType {
 	IndexCompaction,
 	/// Event processing
 	EventProcessing,
+	/// Removing expired files from buckets
+	BucketLifecycle,
+	/// Task for cleaning up old history data
+	HistoryCleanup,
 }
 
 /// Represents a distributed task lease stored in the datastore.
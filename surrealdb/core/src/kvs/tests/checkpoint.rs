@@ -0,0 +1,64 @@
+//! Tests for online checkpoints of a live datastore
+
+use temp_dir::TempDir;
+use tokio_util::sync::CancellationToken;
+
+use super::{CreateDs, Kvs};
+use crate::CommunityComposer;
+use crate::dbs::Session;
+use crate::kvs::Datastore;
+use crate::kvs::LockType::*;
+use crate::kvs::TransactionType::*;
+use crate::val::Uuid;
+
+pub async fn checkpoint(new_ds: impl CreateDs) {
+	// Create a new datastore
+	let (ds, kvs) = new_ds.create_ds(Uuid::new_v7().into()).await;
+	// Insert an initial key
+	let tx = ds.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"test", &"some text".as_bytes().to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	// Create a transaction which is open during the checkpoint
+	let txw = ds.transaction(Write, Optimistic).await.unwrap();
+	txw.set(&"open", &"other text".as_bytes().to_vec(), None).await.unwrap();
+	// Create a checkpoint of the live datastore
+	let dir = TempDir::new().unwrap();
+	let path = dir.path().join("checkpoint");
+	ds.checkpoint(&Session::owner(), &path).await.unwrap();
+	// Commit the open transaction after the checkpoint
+	txw.commit().await.unwrap();
+	// Open the checkpoint as a new datastore
+	let path = match kvs {
+		Kvs::Rocksdb => format!("rocksdb:{}", path.display()),
+		Kvs::SurrealKV => format!("surrealkv:{}", path.display()),
+		kvs => panic!("{kvs:?} does not support checkpoints"),
+	};
+	let copy = Datastore::new_with_factory(CommunityComposer(), &path, CancellationToken::new())
+		.await
+		.unwrap();
+	// The checkpoint contains the writes committed before it was created
+	let tx = copy.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&"test", None).await.unwrap().unwrap();
+	assert_eq!(val, b"some text");
+	assert!(tx.get(&"open", None).await.unwrap().is_none());
+	tx.cancel().await.unwrap();
+	// The checkpoint can be written to independently
+	let tx = copy.transaction(Write, Optimistic).await.unwrap();
+	tx.set(&"test", &"copied text".as_bytes().to_vec(), None).await.unwrap();
+	tx.commit().await.unwrap();
+	let tx = ds.transaction(Read, Optimistic).await.unwrap();
+	let val = tx.get(&"test", None).await.unwrap().unwrap();
+	assert_eq!(val, b"some text");
+	tx.cancel().await.unwrap();
+}
+
+macro_rules! define_tests {
+	($new_ds:ident) => {
+		#[tokio::test]
+		#[serial_test::serial]
+		async fn checkpoint() {
+			super::checkpoint::checkpoint($new_ds).await;
+		}
+	};
+}
+pub(crate) use define_tests;
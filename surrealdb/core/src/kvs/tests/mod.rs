@@ -4,6 +4,7 @@
 	feature = "kv-indxdb",
 	feature = "kv-tikv",
 	feature = "kv-surrealkv",
+	feature = "kv-redb",
 ))]
 
 use std::future::Future;
@@ -20,6 +21,8 @@ macro_rules! include_tests {
 	};
 }
 
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+mod checkpoint;
 #[cfg(feature = "kv-rocksdb")]
 mod metrics;
 
@@ -44,6 +47,8 @@ pub(crate) enum Kvs {
 	Tikv,
 	#[cfg_attr(not(feature = "kv-surrealkv"), expect(dead_code))]
 	SurrealKV,
+	#[cfg_attr(not(feature = "kv-redb"), expect(dead_code))]
+	Redb,
 }
 
 trait CreateDs {
@@ -90,6 +95,150 @@ mod mem {
 	);
 }
 
+#[cfg(feature = "kv-mem")]
+mod encrypted {
+	use tokio_util::sync::CancellationToken;
+	use uuid::Uuid;
+
+	use super::Kvs;
+	use crate::CommunityComposer;
+	use crate::kvs::Datastore;
+	use crate::kvs::encryption::EncryptionConfig;
+
+	async fn new_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Use a memory datastore instance
+		let path = "memory";
+		// Setup the encryption configuration
+		let config = EncryptionConfig {
+			key: "ab".repeat(32).parse().unwrap(),
+			previous: vec![],
+		};
+		// Setup the encrypted in-memory datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id)
+			.with_encryption(&config)
+			.await
+			.unwrap();
+		// Return the datastore
+		(ds, Kvs::Mem)
+	}
+
+	include_tests!(new_ds =>
+		raw,
+		snapshot,
+		multireader,
+		multiwriter_different_keys,
+		multiwriter_same_keys_conflict,
+	);
+}
+
+#[cfg(all(feature = "kv-simulation", feature = "kv-mem"))]
+mod simulation {
+	use std::time::Duration;
+
+	use tokio_util::sync::CancellationToken;
+	use uuid::Uuid;
+
+	use super::Kvs;
+	use crate::CommunityComposer;
+	use crate::dbs::{QueryResult, Session};
+	use crate::kvs::Datastore;
+	use crate::kvs::simulation::{Fault, FaultKind, SimulationConfig};
+	use crate::types::PublicValue;
+
+	async fn new_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Use a memory datastore instance
+		let path = "memory";
+		// Add latency to half of all operations
+		let config = SimulationConfig {
+			seed: 42,
+			faults: vec![
+				Fault::new(FaultKind::Latency(Duration::from_millis(1)), vec![0x00]..vec![0xff])
+					.with_probability(0.5),
+			],
+		};
+		// Setup the simulated in-memory datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id)
+			.with_simulation(config);
+		// Return the datastore
+		(ds, Kvs::Mem)
+	}
+
+	include_tests!(new_ds =>
+		multireader,
+		multiwriter_different_keys,
+		multiwriter_same_keys_conflict,
+	);
+
+	/// Create a datastore with an initial record, which injects faults into
+	/// every later transaction which writes to the datastore
+	async fn new_faulty_ds(faults: Vec<Fault>) -> (Datastore, Session) {
+		let ds = Datastore::new_with_factory(CommunityComposer(), "memory", CancellationToken::new())
+			.await
+			.unwrap();
+		let sess = Session::owner().with_ns("test").with_db("test");
+		let sql = "DEFINE NAMESPACE test; DEFINE DATABASE test; UPSERT total:1 SET n = 0";
+		for res in ds.execute(sql, &sess, None).await.unwrap() {
+			res.result.unwrap();
+		}
+		let config = SimulationConfig {
+			seed: 7,
+			faults,
+		};
+		(ds.with_simulation(config), sess)
+	}
+
+	/// Check whether any statement of a query failed
+	fn failed<E>(res: Result<Vec<QueryResult>, E>) -> bool {
+		match res {
+			Ok(res) => res.iter().any(|r| r.result.is_err()),
+			Err(_) => true,
+		}
+	}
+
+	#[tokio::test]
+	async fn transactions_are_atomic_under_faults() {
+		let all = vec![0x00]..vec![0xff];
+		let (ds, sess) = new_faulty_ds(vec![
+			Fault::new(FaultKind::Conflict, all.clone()).with_probability(0.25),
+			Fault::new(FaultKind::CommitError, all).with_probability(0.25),
+		])
+		.await;
+		// Run transactions which write two records, some of which fail to commit
+		let mut applied = 0;
+		for i in 0..32 {
+			let sql = format!("BEGIN; CREATE item:{i}; UPDATE total:1 SET n += 1; COMMIT;");
+			if !failed(ds.execute(&sql, &sess, None).await) {
+				applied += 1;
+			}
+		}
+		assert!(applied > 0 && applied < 32, "{applied} transactions were applied");
+		// The failed transactions were not partially applied
+		let sql = format!(
+			"RETURN [count(SELECT * FROM item), (SELECT VALUE n FROM ONLY total:1)] == [{applied}, {applied}]"
+		);
+		let mut res = ds.execute(&sql, &sess, None).await.unwrap();
+		assert_eq!(res.remove(0).result.unwrap(), PublicValue::Bool(true));
+	}
+
+	#[tokio::test]
+	async fn crashes_stop_the_datastore() {
+		let all = vec![0x00]..vec![0xff];
+		let (ds, sess) = new_faulty_ds(vec![Fault::new(FaultKind::Crash, all)]).await;
+		// Reads are unaffected until the datastore crashes
+		assert!(!failed(ds.execute("SELECT * FROM total", &sess, None).await));
+		// The datastore crashes when a write is committed
+		assert!(failed(ds.execute("CREATE item:1", &sess, None).await));
+		// Every later query fails
+		assert!(failed(ds.execute("SELECT * FROM total", &sess, None).await));
+	}
+}
+
 #[cfg(feature = "kv-rocksdb")]
 mod rocksdb {
 	use temp_dir::TempDir;
@@ -115,6 +264,7 @@ mod rocksdb {
 
 	include_tests!(new_ds =>
 		raw,
+		checkpoint,
 		snapshot,
 		multireader,
 		multiwriter_different_keys,
@@ -147,6 +297,39 @@ mod surrealkv {
 		(ds, Kvs::SurrealKV)
 	}
 
+	include_tests!(new_ds =>
+		raw,
+		checkpoint,
+		snapshot,
+		multireader,
+		multiwriter_different_keys,
+		multiwriter_same_keys_conflict,
+	);
+}
+
+#[cfg(feature = "kv-redb")]
+mod redb {
+	use temp_dir::TempDir;
+	use tokio_util::sync::CancellationToken;
+	use uuid::Uuid;
+
+	use super::Kvs;
+	use crate::CommunityComposer;
+	use crate::kvs::Datastore;
+
+	async fn new_ds(id: Uuid) -> (Datastore, Kvs) {
+		// Setup the temporary data storage path
+		let path = TempDir::new().unwrap().path().to_string_lossy().to_string();
+		let path = format!("redb:{path}/data.redb");
+		// Setup the redb datastore
+		let ds = Datastore::new_with_factory(CommunityComposer(), &path, CancellationToken::new())
+			.await
+			.unwrap()
+			.with_node_id(id);
+		// Return the datastore
+		(ds, Kvs::Redb)
+	}
+
 	include_tests!(new_ds =>
 		raw,
 		snapshot,
@@ -32,6 +32,7 @@ async fn setup_tx_with_ns_db() -> (Datastore, crate::kvs::Transaction, Namespace
 		strict: false,
 		comment: None,
 		changefeed: None,
+		history: None,
 	};
 	tx.put_db("test", db_def).await.unwrap();
 
@@ -275,6 +276,7 @@ async fn test_single_tx_cache_invalidation_on_db_put_and_del() {
 		strict: false,
 		comment: None,
 		changefeed: None,
+		history: None,
 	};
 	tx.put_db("test", db_def).await.unwrap();
 
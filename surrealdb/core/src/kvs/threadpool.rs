@@ -1,4 +1,9 @@
-#![cfg(any(feature = "kv-mem", feature = "kv-rocksdb", feature = "kv-surrealkv"))]
+#![cfg(any(
+	feature = "kv-mem",
+	feature = "kv-rocksdb",
+	feature = "kv-surrealkv",
+	feature = "kv-redb"
+))]
 
 /// Create a new blocking threadpool
 pub(super) fn initialise() {
@@ -0,0 +1,1076 @@
+//! Tiered storage, which stores tables in secondary storage engines.
+//!
+//! Storage tiers are configured with `tier.<name>=<path>` query parameters on
+//! the datastore path, for example
+//! `rocksdb:///nvme/data.db?tier.cold=rocksdb:///hdd/cold.db`. A table is
+//! assigned to a tier when it is defined, with `DEFINE TABLE ... TIER cold`,
+//! after which all keys under the table, including its records and indexes,
+//! are stored in the storage engine of that tier. All other keys, including
+//! the catalog, sequences, and change feeds, are stored in the primary
+//! datastore.
+//!
+//! The tier of each table is stored in the primary datastore, alongside the
+//! table definition, and is loaded when the datastore is opened. Each
+//! transaction opens a transaction on every storage engine, and dispatches
+//! each key, or each part of a key range, to the engine which stores it.
+//!
+//! A transaction commits the storage tiers before the primary datastore, so
+//! the primary datastore decides whether the transaction commits. Before a
+//! transaction first writes a key in a storage tier, it stores the previous
+//! value of the key in its intent record in the same storage tier, and before
+//! it commits, the primary datastore stores a commit record. If a storage tier
+//! or the primary datastore fails to commit, for example because of a
+//! conflict, the writes to the storage tiers which have already committed are
+//! rolled back using their intent records. Intent records are removed once the
+//! primary datastore has committed. When the datastore is opened, any
+//! remaining intent records without a commit record, for example after a
+//! crash, are rolled back. If a storage tier can not be rolled back while the
+//! datastore is running, no further transactions are started until the
+//! datastore is opened again.
+//!
+//! Transactions which write to the storage tiers commit one at a time, and no
+//! transaction is started from when the storage tiers commit until the primary
+//! datastore has committed or the storage tiers have been rolled back. Other
+//! transactions therefore never read writes to the storage tiers which are not
+//! committed in the primary datastore, and no other transaction writes the
+//! keys which are rolled back. As this is coordinated within a single process,
+//! storage tiers can not be used with distributed storage engines. The tier of
+//! a table can not be changed once it is defined.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::ops::{Bound, Range};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parking_lot::{Mutex, RwLock};
+use uuid::Uuid;
+
+use super::api::{ScanLimit, SizeEstimate, Transactable};
+use super::ds::{Metrics, TransactionBuilder};
+use super::err::{Error, Result};
+use super::util::{self, Collector};
+use super::{BoxTimeStamp, BoxTimeStampImpl, Key, TransactionBuilderRequirements, Val};
+use crate::cnf::NORMAL_FETCH_SIZE;
+
+const TARGET: &str = "surrealdb::core::kvs::tiered";
+
+/// The prefix of the keys which store the tier of each table
+const ROUTES: &[u8] = &[0xff, b't'];
+
+/// The prefix of the keys which store the intent records of a transaction
+const INTENTS: &[u8] = &[0xff, b'i'];
+
+/// The prefix of the keys which store the commit record of a transaction
+const COMMITS: &[u8] = &[0xff, b'c'];
+
+/// The storage tier of each key prefix
+type Routes = BTreeMap<Key, String>;
+
+/// The changes to the storage tiers of key prefixes in a transaction
+type Changes = BTreeMap<Key, Option<String>>;
+
+/// The key which stores the storage tier of a key prefix
+fn route_key(prefix: &[u8]) -> Key {
+	[ROUTES, prefix].concat()
+}
+
+/// The end of the range of keys which start with a prefix
+fn prefix_end(prefix: &[u8]) -> Key {
+	let mut end = prefix.to_vec();
+	util::advance_key(&mut end);
+	end
+}
+
+/// The key which stores a record of a transaction
+fn record_key(prefix: &[u8], id: Uuid) -> Key {
+	[prefix, id.as_bytes().as_slice()].concat()
+}
+
+/// The key which stores the intent record of a transaction for a key
+fn intent_key(id: Uuid, key: &[u8]) -> Key {
+	[INTENTS, id.as_bytes().as_slice(), key].concat()
+}
+
+/// Decode the id of a transaction from the key of a record
+fn record_id(prefix: &[u8], key: &[u8]) -> Result<Uuid> {
+	key.get(prefix.len()..prefix.len() + 16)
+		.and_then(|id| Uuid::from_slice(id).ok())
+		.ok_or_else(|| Error::Datastore("The stored transaction record is not valid".to_owned()))
+}
+
+/// Encode the value of a key before a transaction
+fn encode_intent(val: &Option<Val>) -> Result<Val> {
+	revision::to_vec(val).map_err(|e| Error::Datastore(e.to_string()))
+}
+
+/// Decode the value of a key before a transaction
+fn decode_intent(val: &[u8]) -> Result<Option<Val>> {
+	revision::from_slice(val).map_err(|e| Error::Datastore(e.to_string()))
+}
+
+/// Cancel a transaction which failed, logging any error
+async fn cancel(tx: &dyn Transactable) {
+	if !tx.closed()
+		&& let Err(e) = tx.cancel().await
+	{
+		warn!(target: TARGET, "Failed to cancel a transaction: {e}");
+	}
+}
+
+/// Fetch all keys with a prefix
+async fn scan_prefix(tx: &dyn Transactable, prefix: &[u8]) -> Result<Vec<(Key, Val)>> {
+	let batch = *NORMAL_FETCH_SIZE;
+	let mut rng = prefix.to_vec()..prefix_end(prefix);
+	let mut out = Vec::new();
+	loop {
+		let res = tx.scan(rng.clone(), ScanLimit::Count(batch), 0, None).await?;
+		let done = res.len() < batch as usize;
+		if let Some((k, _)) = res.last() {
+			rng.start.clone_from(k);
+			rng.start.push(0x00);
+		}
+		out.extend(res);
+		if done {
+			break;
+		}
+	}
+	Ok(out)
+}
+
+/// Roll back the writes of a transaction to a storage tier
+///
+/// The previous value of each key is restored in batches, and the intent
+/// record of each key is removed as it is restored, so a roll back which
+/// fails can be resumed.
+async fn undo(builder: &dyn TransactionBuilder, id: Uuid) -> anyhow::Result<()> {
+	let batch = *NORMAL_FETCH_SIZE;
+	let prefix = record_key(INTENTS, id);
+	let rng = prefix.clone()..prefix_end(&prefix);
+	loop {
+		let (tx, _) = builder.new_transaction(true, false).await?;
+		let res = async {
+			let res = tx.scan(rng.clone(), ScanLimit::Count(batch), 0, None).await?;
+			let done = res.len() < batch as usize;
+			for (k, v) in res {
+				let key = k[prefix.len()..].to_vec();
+				match decode_intent(&v)? {
+					Some(val) => tx.set(key, val, None).await?,
+					None => tx.del(key).await?,
+				}
+				tx.del(k).await?;
+			}
+			tx.commit().await?;
+			Ok(done)
+		}
+		.await;
+		match res {
+			Ok(true) => return Ok(()),
+			Ok(false) => continue,
+			Err(e) => {
+				cancel(tx.as_ref()).await;
+				return Err(e);
+			}
+		}
+	}
+}
+
+/// Remove the records of a transaction with a prefix
+async fn forget(builder: &dyn TransactionBuilder, prefix: Key) -> anyhow::Result<()> {
+	let (tx, _) = builder.new_transaction(true, false).await?;
+	let end = prefix_end(&prefix);
+	if let Err(e) = tx.delr(prefix..end).await {
+		cancel(tx.as_ref()).await;
+		return Err(e.into());
+	}
+	Ok(tx.commit().await?)
+}
+
+/// Roll back the transactions which did not commit in the primary datastore
+async fn recover(
+	primary: &dyn TransactionBuilder,
+	tiers: &BTreeMap<String, Box<dyn TransactionBuilder>>,
+) -> anyhow::Result<()> {
+	// Fetch the transactions which committed in the primary datastore
+	let (tx, _) = primary.new_transaction(false, false).await?;
+	let res = scan_prefix(tx.as_ref(), COMMITS).await;
+	tx.cancel().await?;
+	let mut commits = BTreeSet::new();
+	for (k, _) in res? {
+		commits.insert(record_id(COMMITS, &k)?);
+	}
+	// Resolve the intent records in each storage tier, one transaction at a time
+	let rng = INTENTS.to_vec()..prefix_end(INTENTS);
+	for (name, builder) in tiers.iter() {
+		loop {
+			let (tx, _) = builder.new_transaction(false, false).await?;
+			let res = tx.keys(rng.clone(), ScanLimit::Count(1), 0, None).await;
+			tx.cancel().await?;
+			let Some(k) = res?.pop() else {
+				break;
+			};
+			let id = record_id(INTENTS, &k)?;
+			if commits.contains(&id) {
+				forget(builder.as_ref(), record_key(INTENTS, id)).await?;
+			} else {
+				warn!(target: TARGET, "Rolling back transaction {id} in the storage tier '{name}'");
+				undo(builder.as_ref(), id).await?;
+			}
+		}
+	}
+	// Every intent record is resolved, so the commit records are not needed
+	for id in commits {
+		forget(primary, record_key(COMMITS, id)).await?;
+	}
+	Ok(())
+}
+
+/// Find the entry for the prefix of a key
+fn lookup<'a, V>(map: &'a BTreeMap<Key, V>, key: &[u8]) -> Option<&'a V> {
+	map.range::<[u8], _>((Bound::Unbounded, Bound::Included(key)))
+		.next_back()
+		.filter(|(p, _)| key.starts_with(p))
+		.map(|(_, v)| v)
+}
+
+/// The transaction builders of the primary datastore and each storage tier
+struct Builders {
+	/// The primary transaction builder
+	primary: Box<dyn TransactionBuilder>,
+	/// The transaction builders of each storage tier
+	tiers: BTreeMap<String, Box<dyn TransactionBuilder>>,
+	/// Held exclusively while a transaction commits to the storage tiers
+	gate: tokio::sync::RwLock<()>,
+	/// Whether a storage tier could not be rolled back
+	poisoned: AtomicBool,
+}
+
+/// A transaction builder which stores tables in secondary storage engines
+pub(super) struct TieredBuilder {
+	/// The transaction builders of each storage engine
+	builders: Arc<Builders>,
+	/// The storage tier of each table prefix
+	routes: Arc<RwLock<Routes>>,
+}
+
+impl fmt::Display for TieredBuilder {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		self.builders.primary.fmt(f)
+	}
+}
+
+impl TieredBuilder {
+	/// Wrap a primary transaction builder with storage tiers
+	pub(super) async fn new(
+		primary: Box<dyn TransactionBuilder>,
+		tiers: BTreeMap<String, Box<dyn TransactionBuilder>>,
+	) -> anyhow::Result<Self> {
+		// Load the storage tier of each table
+		let mut routes = Routes::new();
+		let (tx, _) = primary.new_transaction(false, false).await?;
+		let res = scan_prefix(tx.as_ref(), ROUTES).await;
+		tx.cancel().await?;
+		for (k, v) in res? {
+			let tier = String::from_utf8(v)
+				.map_err(|_| Error::Datastore("The stored storage tier is not valid".to_owned()))?;
+			// Data in a tier which is not configured can not be read
+			if !tiers.contains_key(&tier) {
+				return Err(Error::Datastore(format!(
+					"Tables are stored in the storage tier '{tier}', which is not configured"
+				))
+				.into());
+			}
+			routes.insert(k[ROUTES.len()..].to_vec(), tier);
+		}
+		// Roll back any transactions which did not complete
+		recover(primary.as_ref(), &tiers).await?;
+		// Return the builder
+		Ok(Self {
+			builders: Arc::new(Builders {
+				primary,
+				tiers,
+				gate: tokio::sync::RwLock::new(()),
+				poisoned: AtomicBool::new(false),
+			}),
+			routes: Arc::new(RwLock::new(routes)),
+		})
+	}
+}
+
+impl TransactionBuilderRequirements for TieredBuilder {}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl TransactionBuilder for TieredBuilder {
+	async fn new_transaction(
+		&self,
+		write: bool,
+		lock: bool,
+	) -> anyhow::Result<(Box<dyn Transactable>, bool)> {
+		// The storage tiers must be recovered before they can be used again
+		if self.builders.poisoned.load(Ordering::Acquire) {
+			return Err(Error::Datastore(
+				"A storage tier could not be rolled back, so the datastore must be restarted"
+					.to_owned(),
+			)
+			.into());
+		}
+		// Wait for any transaction which is committing to the storage tiers
+		let _gate = self.builders.gate.read().await;
+		let (primary, mut local) = self.builders.primary.new_transaction(write, lock).await?;
+		let mut tiers = BTreeMap::new();
+		for (name, builder) in self.builders.tiers.iter() {
+			let (tx, l) = builder.new_transaction(write, lock).await?;
+			tiers.insert(name.clone(), tx);
+			local &= l;
+		}
+		let tx = TieredTransaction {
+			id: Uuid::new_v4(),
+			primary,
+			tiers,
+			builders: self.builders.clone(),
+			routes: self.routes.clone(),
+			changes: Default::default(),
+			savepoints: Default::default(),
+			written: Default::default(),
+		};
+		Ok((Box::new(tx), local))
+	}
+
+	async fn shutdown(&self) -> anyhow::Result<()> {
+		for builder in self.builders.tiers.values() {
+			builder.shutdown().await?;
+		}
+		self.builders.primary.shutdown().await
+	}
+
+	fn register_metrics(&self) -> Option<Metrics> {
+		self.builders.primary.register_metrics()
+	}
+
+	fn collect_u64_metric(&self, metric: &str) -> Option<u64> {
+		self.builders.primary.collect_u64_metric(metric)
+	}
+}
+
+/// A transaction which dispatches keys to the storage engine which stores them
+struct TieredTransaction {
+	/// The id of the records of this transaction
+	id: Uuid,
+	/// The transaction on the primary datastore
+	primary: Box<dyn Transactable>,
+	/// The transactions on each storage tier
+	tiers: BTreeMap<String, Box<dyn Transactable>>,
+	/// The transaction builders, to resolve the transaction records
+	builders: Arc<Builders>,
+	/// The storage tier of each table prefix
+	routes: Arc<RwLock<Routes>>,
+	/// The storage tiers set in this transaction
+	changes: Mutex<Changes>,
+	/// The storage tiers set before each save point
+	savepoints: Mutex<Vec<Changes>>,
+	/// The storage tiers written in this transaction
+	written: Mutex<BTreeSet<String>>,
+}
+
+impl TieredTransaction {
+	/// The storage tier which stores a key
+	fn tier(&self, key: &[u8]) -> Option<String> {
+		if let Some(tier) = lookup(&self.changes.lock(), key) {
+			return tier.clone();
+		}
+		lookup(&self.routes.read(), key).cloned()
+	}
+
+	/// The transaction on a storage tier, or on the primary datastore
+	fn inner(&self, tier: Option<&str>) -> Result<&dyn Transactable> {
+		match tier {
+			None => Ok(self.primary.as_ref()),
+			Some(name) => match self.tiers.get(name) {
+				Some(tx) => Ok(tx.as_ref()),
+				None => {
+					Err(Error::Datastore(format!("The storage tier '{name}' is not configured")))
+				}
+			},
+		}
+	}
+
+	/// The transaction which stores a key
+	fn route(&self, key: &[u8]) -> Result<&dyn Transactable> {
+		self.inner(self.tier(key).as_deref())
+	}
+
+	/// The transaction which stores a key which is about to be written
+	///
+	/// The value of a key in a storage tier is saved in the intent record of
+	/// this transaction before this transaction first writes it, so that the
+	/// write can be rolled back.
+	async fn write(&self, key: &Key) -> Result<&dyn Transactable> {
+		let Some(tier) = self.tier(key) else {
+			return Ok(self.primary.as_ref());
+		};
+		let tx = self.inner(Some(tier.as_str()))?;
+		let intent = intent_key(self.id, key);
+		if !tx.exists(intent.clone(), None).await? {
+			let val = tx.get(key.clone(), None).await?;
+			tx.set(intent, encode_intent(&val)?, None).await?;
+		}
+		self.written.lock().insert(tier);
+		Ok(tx)
+	}
+
+	/// Remove a range of keys in a storage tier
+	///
+	/// The keys are removed in batches, after saving the value of each key in
+	/// the intent record of this transaction, so that the removal can be
+	/// rolled back.
+	async fn write_range(
+		&self,
+		tier: &str,
+		tx: &dyn Transactable,
+		mut rng: Range<Key>,
+		clear: bool,
+	) -> Result<()> {
+		self.written.lock().insert(tier.to_owned());
+		let batch = *NORMAL_FETCH_SIZE;
+		loop {
+			let res = tx.scan(rng.clone(), ScanLimit::Count(batch), 0, None).await?;
+			let done = res.len() < batch as usize;
+			let Some((k, _)) = res.last() else {
+				break;
+			};
+			let mut end = k.clone();
+			end.push(0x00);
+			// Save the keys which this transaction has not written yet
+			let intents = res.iter().map(|(k, _)| intent_key(self.id, k)).collect();
+			let saved = tx.getm(intents, None).await?;
+			for ((k, v), saved) in res.into_iter().zip(saved) {
+				if saved.is_none() {
+					tx.set(intent_key(self.id, &k), encode_intent(&Some(v))?, None).await?;
+				}
+				// Remove the key, now that it can be restored
+				match clear {
+					true => tx.clr(k).await?,
+					false => tx.del(k).await?,
+				}
+			}
+			rng.start = end;
+			if done {
+				break;
+			}
+		}
+		Ok(())
+	}
+
+	/// Store the storage tiers set in this transaction, and the commit record
+	/// if this transaction wrote to any storage tier
+	async fn prepare(&self, changes: &Changes) -> Result<BTreeSet<String>> {
+		// Store the storage tiers set in this transaction
+		for (prefix, tier) in changes {
+			match tier {
+				Some(tier) => self.primary.set(route_key(prefix), tier.clone().into_bytes(), None),
+				None => self.primary.del(route_key(prefix)),
+			}
+			.await?;
+		}
+		// Store the commit record, which commits with the primary datastore
+		let written = std::mem::take(&mut *self.written.lock());
+		if !written.is_empty() {
+			self.primary.set(record_key(COMMITS, self.id), Val::new(), None).await?;
+		}
+		Ok(written)
+	}
+
+	/// Cancel the transactions which are still open
+	async fn abort(&self) {
+		for tx in self.tiers.values() {
+			cancel(tx.as_ref()).await;
+		}
+		cancel(self.primary.as_ref()).await;
+	}
+
+	/// Roll back the writes to the storage tiers which have committed
+	///
+	/// If a storage tier can not be rolled back, no further transactions are
+	/// started, and the roll back is retried when the datastore is next opened.
+	async fn rollback(&self, committed: &[&str]) {
+		for name in committed {
+			let Some(builder) = self.builders.tiers.get(*name) else {
+				continue;
+			};
+			if let Err(e) = undo(builder.as_ref(), self.id).await {
+				error!(target: TARGET, "Failed to roll back transaction {} in the storage tier '{name}': {e}", self.id);
+				self.builders.poisoned.store(true, Ordering::Release);
+			}
+		}
+	}
+
+	/// Remove the records of a committed transaction
+	///
+	/// Any records which are not removed are removed when the datastore is
+	/// next opened.
+	async fn resolve(&self, written: &BTreeSet<String>) {
+		if written.is_empty() {
+			return;
+		}
+		// The intent records are removed before the commit record
+		for name in written {
+			let Some(builder) = self.builders.tiers.get(name) else {
+				continue;
+			};
+			if let Err(e) = forget(builder.as_ref(), record_key(INTENTS, self.id)).await {
+				warn!(target: TARGET, "Failed to remove the intent record of transaction {}: {e}", self.id);
+				return;
+			}
+		}
+		if let Err(e) = forget(self.builders.primary.as_ref(), record_key(COMMITS, self.id)).await {
+			warn!(target: TARGET, "Failed to remove the commit record of transaction {}: {e}", self.id);
+		}
+	}
+
+	/// Split a range of keys into the ranges stored by each storage engine
+	fn segments(&self, rng: Range<Key>) -> Vec<(Range<Key>, Option<String>)> {
+		// Apply the storage tiers set in this transaction
+		let mut routes = self.routes.read().clone();
+		for (prefix, tier) in self.changes.lock().iter() {
+			match tier {
+				Some(tier) => routes.insert(prefix.clone(), tier.clone()),
+				None => routes.remove(prefix),
+			};
+		}
+		// Split the range at the bounds of each prefix
+		let mut out = Vec::new();
+		let mut beg = rng.start;
+		for (prefix, tier) in
+			routes.range::<[u8], _>((Bound::Unbounded, Bound::Excluded(rng.end.as_slice())))
+		{
+			let end = prefix_end(prefix);
+			if end <= beg {
+				continue;
+			}
+			if *prefix > beg {
+				let start = std::mem::replace(&mut beg, prefix.clone());
+				out.push((start..prefix.clone(), None));
+			}
+			let end = end.min(rng.end.clone());
+			out.push((beg..end.clone(), Some(tier.clone())));
+			beg = end;
+		}
+		if beg < rng.end {
+			out.push((beg..rng.end, None));
+		}
+		out
+	}
+
+	/// Scan the ranges of keys stored by several storage engines
+	async fn range(
+		&self,
+		mut segments: Vec<(Range<Key>, Option<String>)>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+		values: bool,
+		reverse: bool,
+	) -> Result<Vec<(Key, Val)>> {
+		let batch = *NORMAL_FETCH_SIZE;
+		if reverse {
+			segments.reverse();
+		}
+		let mut col = Collector::new(limit, skip, values);
+		for (mut rng, tier) in segments {
+			let tx = self.inner(tier.as_deref())?;
+			loop {
+				if col.full() {
+					return Ok(col.out);
+				}
+				let res = match (values, reverse) {
+					(true, false) => {
+						tx.scan(rng.clone(), ScanLimit::Count(batch), 0, version).await?
+					}
+					(true, true) => {
+						tx.scanr(rng.clone(), ScanLimit::Count(batch), 0, version).await?
+					}
+					(false, false) => {
+						let res = tx.keys(rng.clone(), ScanLimit::Count(batch), 0, version).await?;
+						res.into_iter().map(|k| (k, Val::new())).collect()
+					}
+					(false, true) => {
+						let res =
+							tx.keysr(rng.clone(), ScanLimit::Count(batch), 0, version).await?;
+						res.into_iter().map(|k| (k, Val::new())).collect()
+					}
+				};
+				let done = res.len() < batch as usize;
+				// Move the range past this batch
+				if let Some((k, _)) = res.last() {
+					if reverse {
+						rng.end.clone_from(k);
+					} else {
+						rng.start.clone_from(k);
+						rng.start.push(0x00);
+					}
+				}
+				for (k, v) in res {
+					if col.push(k, v) {
+						return Ok(col.out);
+					}
+				}
+				if done {
+					break;
+				}
+			}
+		}
+		Ok(col.out)
+	}
+}
+
+#[cfg_attr(target_family = "wasm", async_trait::async_trait(?Send))]
+#[cfg_attr(not(target_family = "wasm"), async_trait::async_trait)]
+impl Transactable for TieredTransaction {
+	fn kind(&self) -> &'static str {
+		self.primary.kind()
+	}
+
+	fn closed(&self) -> bool {
+		self.primary.closed()
+	}
+
+	fn writeable(&self) -> bool {
+		self.primary.writeable()
+	}
+
+	async fn cancel(&self) -> Result<()> {
+		for tx in self.tiers.values() {
+			if !tx.closed() {
+				tx.cancel().await?;
+			}
+		}
+		self.primary.cancel().await
+	}
+
+	async fn commit(&self) -> Result<()> {
+		// Store the storage tiers, and the commit record of the transaction
+		let changes = std::mem::take(&mut *self.changes.lock());
+		let written = match self.prepare(&changes).await {
+			Ok(written) => written,
+			Err(e) => {
+				self.abort().await;
+				return Err(e);
+			}
+		};
+		// No other transaction is started or committed to the storage tiers
+		// until the primary datastore decides whether this transaction commits
+		let gate = match written.is_empty() && changes.is_empty() {
+			true => None,
+			false => Some(self.builders.gate.write().await),
+		};
+		if !written.is_empty() && self.builders.poisoned.load(Ordering::Acquire) {
+			self.abort().await;
+			return Err(Error::Datastore(
+				"A storage tier could not be rolled back, so the datastore must be restarted"
+					.to_owned(),
+			));
+		}
+		// Commit the storage tiers, which checks their writes for conflicts
+		let mut committed = Vec::with_capacity(written.len());
+		for (name, tx) in self.tiers.iter() {
+			if let Err(e) = tx.commit().await {
+				self.abort().await;
+				self.rollback(&committed).await;
+				return Err(e);
+			}
+			if written.contains(name) {
+				committed.push(name.as_str());
+			}
+		}
+		// Commit the primary datastore, which commits the transaction
+		if let Err(e) = self.primary.commit().await {
+			self.rollback(&committed).await;
+			return Err(e);
+		}
+		// New transactions use the committed storage tiers
+		{
+			let mut routes = self.routes.write();
+			for (prefix, tier) in changes {
+				match tier {
+					Some(tier) => routes.insert(prefix, tier),
+					None => routes.remove(&prefix),
+				};
+			}
+		}
+		drop(gate);
+		// The records of the transaction are no longer needed
+		self.resolve(&written).await;
+		Ok(())
+	}
+
+	async fn exists(&self, key: Key, version: Option<u64>) -> Result<bool> {
+		self.route(&key)?.exists(key, version).await
+	}
+
+	async fn get(&self, key: Key, version: Option<u64>) -> Result<Option<Val>> {
+		self.route(&key)?.get(key, version).await
+	}
+
+	async fn getm(&self, keys: Vec<Key>, version: Option<u64>) -> Result<Vec<Option<Val>>> {
+		// Group the keys by the storage engine which stores them
+		let mut groups: BTreeMap<Option<String>, (Vec<usize>, Vec<Key>)> = BTreeMap::new();
+		let len = keys.len();
+		for (i, key) in keys.into_iter().enumerate() {
+			let (idx, keys) = groups.entry(self.tier(&key)).or_default();
+			idx.push(i);
+			keys.push(key);
+		}
+		// Fetch the keys from each storage engine
+		let mut out = vec![None; len];
+		for (tier, (idx, keys)) in groups {
+			let res = self.inner(tier.as_deref())?.getm(keys, version).await?;
+			for (i, val) in idx.into_iter().zip(res) {
+				out[i] = val;
+			}
+		}
+		Ok(out)
+	}
+
+	async fn set(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		self.write(&key).await?.set(key, val, version).await
+	}
+
+	async fn replace(&self, key: Key, val: Val) -> Result<()> {
+		self.write(&key).await?.replace(key, val).await
+	}
+
+	async fn put(&self, key: Key, val: Val, version: Option<u64>) -> Result<()> {
+		self.write(&key).await?.put(key, val, version).await
+	}
+
+	async fn putc(&self, key: Key, val: Val, chk: Option<Val>) -> Result<()> {
+		self.write(&key).await?.putc(key, val, chk).await
+	}
+
+	async fn del(&self, key: Key) -> Result<()> {
+		self.write(&key).await?.del(key).await
+	}
+
+	async fn delc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.write(&key).await?.delc(key, chk).await
+	}
+
+	async fn clr(&self, key: Key) -> Result<()> {
+		self.write(&key).await?.clr(key).await
+	}
+
+	async fn clrc(&self, key: Key, chk: Option<Val>) -> Result<()> {
+		self.write(&key).await?.clrc(key, chk).await
+	}
+
+	async fn delr(&self, rng: Range<Key>) -> Result<()> {
+		for (rng, tier) in self.segments(rng) {
+			let tx = self.inner(tier.as_deref())?;
+			match tier {
+				Some(tier) => self.write_range(&tier, tx, rng, false).await?,
+				None => tx.delr(rng).await?,
+			}
+		}
+		Ok(())
+	}
+
+	async fn clrr(&self, rng: Range<Key>) -> Result<()> {
+		for (rng, tier) in self.segments(rng) {
+			let tx = self.inner(tier.as_deref())?;
+			match tier {
+				Some(tier) => self.write_range(&tier, tx, rng, true).await?,
+				None => tx.clrr(rng).await?,
+			}
+		}
+		Ok(())
+	}
+
+	async fn count(&self, rng: Range<Key>, version: Option<u64>) -> Result<usize> {
+		let mut count = 0;
+		for (rng, tier) in self.segments(rng) {
+			count += self.inner(tier.as_deref())?.count(rng, version).await?;
+		}
+		Ok(count)
+	}
+
+	async fn keys(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		let mut segments = self.segments(rng);
+		if segments.len() == 1 {
+			let (rng, tier) = segments.pop().expect("a single segment");
+			return self.inner(tier.as_deref())?.keys(rng, limit, skip, version).await;
+		}
+		let res = self.range(segments, limit, skip, version, false, false).await?;
+		Ok(res.into_iter().map(|(k, _)| k).collect())
+	}
+
+	async fn keysr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<Key>> {
+		let mut segments = self.segments(rng);
+		if segments.len() == 1 {
+			let (rng, tier) = segments.pop().expect("a single segment");
+			return self.inner(tier.as_deref())?.keysr(rng, limit, skip, version).await;
+		}
+		let res = self.range(segments, limit, skip, version, false, true).await?;
+		Ok(res.into_iter().map(|(k, _)| k).collect())
+	}
+
+	async fn scan(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let mut segments = self.segments(rng);
+		if segments.len() == 1 {
+			let (rng, tier) = segments.pop().expect("a single segment");
+			return self.inner(tier.as_deref())?.scan(rng, limit, skip, version).await;
+		}
+		self.range(segments, limit, skip, version, true, false).await
+	}
+
+	async fn scanr(
+		&self,
+		rng: Range<Key>,
+		limit: ScanLimit,
+		skip: u32,
+		version: Option<u64>,
+	) -> Result<Vec<(Key, Val)>> {
+		let mut segments = self.segments(rng);
+		if segments.len() == 1 {
+			let (rng, tier) = segments.pop().expect("a single segment");
+			return self.inner(tier.as_deref())?.scanr(rng, limit, skip, version).await;
+		}
+		self.range(segments, limit, skip, version, true, true).await
+	}
+
+	async fn new_save_point(&self) -> Result<()> {
+		for tx in self.tiers.values() {
+			tx.new_save_point().await?;
+		}
+		self.primary.new_save_point().await?;
+		let changes = self.changes.lock().clone();
+		self.savepoints.lock().push(changes);
+		Ok(())
+	}
+
+	async fn release_last_save_point(&self) -> Result<()> {
+		for tx in self.tiers.values() {
+			tx.release_last_save_point().await?;
+		}
+		self.primary.release_last_save_point().await?;
+		self.savepoints.lock().pop();
+		Ok(())
+	}
+
+	async fn rollback_to_save_point(&self) -> Result<()> {
+		for tx in self.tiers.values() {
+			tx.rollback_to_save_point().await?;
+		}
+		self.primary.rollback_to_save_point().await?;
+		if let Some(changes) = self.savepoints.lock().pop() {
+			*self.changes.lock() = changes;
+		}
+		Ok(())
+	}
+
+	async fn timestamp(&self) -> Result<BoxTimeStamp> {
+		self.primary.timestamp().await
+	}
+
+	fn timestamp_impl(&self) -> BoxTimeStampImpl {
+		self.primary.timestamp_impl()
+	}
+
+	async fn compact(&self, range: Option<Range<Key>>) -> anyhow::Result<()> {
+		match range {
+			Some(rng) => {
+				for (rng, tier) in self.segments(rng) {
+					self.inner(tier.as_deref())?.compact(Some(rng)).await?;
+				}
+			}
+			None => {
+				for tx in self.tiers.values() {
+					tx.compact(None).await?;
+				}
+				self.primary.compact(None).await?;
+			}
+		}
+		Ok(())
+	}
+
+	async fn checkpoint(&self, path: &Path) -> anyhow::Result<()> {
+		// Block tiered commits so that every copy is taken at the same point
+		let _gate = self.builders.gate.write().await;
+		// The primary datastore is copied first, as it holds the commit
+		// records which decide the outcome of any intents in the tiers
+		self.primary.checkpoint(path).await?;
+		// Each storage tier is copied next to the primary datastore
+		for (name, tx) in self.tiers.iter() {
+			let mut tier = path.as_os_str().to_owned();
+			tier.push(format!(".{name}"));
+			tx.checkpoint(&PathBuf::from(tier)).await?;
+		}
+		Ok(())
+	}
+
+	fn has_tier(&self, name: &str) -> bool {
+		self.tiers.contains_key(name)
+	}
+
+	async fn set_tier(&self, prefix: Key, tier: Option<String>) -> Result<()> {
+		// Check to see if transaction is closed
+		if self.closed() {
+			return Err(Error::TransactionFinished);
+		}
+		// Check to see if transaction is writable
+		if !self.writeable() {
+			return Err(Error::TransactionReadonly);
+		}
+		// Check that the storage tier is configured
+		self.inner(tier.as_deref())?;
+		// Store the keys in the storage tier from now on
+		self.changes.lock().insert(prefix, tier);
+		Ok(())
+	}
+
+	async fn size(&self, rng: Range<Key>) -> Result<Option<SizeEstimate>> {
+		let mut size: Option<SizeEstimate> = None;
+		for (rng, tier) in self.segments(rng) {
+			if let Some(s) = self.inner(tier.as_deref())?.size(rng).await? {
+				let total = size.get_or_insert(SizeEstimate {
+					bytes: 0,
+					keys: 0,
+				});
+				total.bytes += s.bytes;
+				total.keys += s.keys;
+			}
+		}
+		Ok(size)
+	}
+
+	fn can_prune_versions(&self) -> bool {
+		self.primary.can_prune_versions() && self.tiers.values().all(|tx| tx.can_prune_versions())
+	}
+
+	async fn prune_versions(
+		&self,
+		rng: Range<Key>,
+		before: u64,
+		limit: u32,
+	) -> Result<Option<Key>> {
+		for (rng, tier) in self.segments(rng) {
+			let next = self.inner(tier.as_deref())?.prune_versions(rng, before, limit).await?;
+			if next.is_some() {
+				return Ok(next);
+			}
+		}
+		Ok(None)
+	}
+}
+
+#[cfg(all(test, feature = "kv-mem"))]
+mod tests {
+	use super::*;
+	use crate::kvs::{Datastore, LockType, TransactionType};
+
+	#[test]
+	fn encode_intents() {
+		for intent in [None, Some(b"1".to_vec())] {
+			let val = encode_intent(&intent).unwrap();
+			assert_eq!(decode_intent(&val).unwrap(), intent);
+		}
+		assert!(decode_intent(b"\xff").is_err());
+		// The id of a transaction is decoded from the intent record of a key
+		let id = Uuid::new_v4();
+		assert_eq!(record_id(INTENTS, &intent_key(id, b"key")).unwrap(), id);
+		assert!(record_id(INTENTS, INTENTS).is_err());
+	}
+
+	#[tokio::test]
+	async fn rollback_on_primary_conflict() {
+		let ds = Datastore::new("memory?tier.cold=memory").await.unwrap();
+		// Store the keys with the prefix in the storage tier
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.set_tier(&"t", Some("cold")).await.unwrap();
+		tx.set(&"t1", &b"old".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// Two transactions conflict in the primary datastore
+		let tx1 = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		let tx2 = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx1.set(&"p", &b"1".to_vec(), None).await.unwrap();
+		tx1.set(&"t1", &b"new".to_vec(), None).await.unwrap();
+		tx1.set(&"t2", &b"new".to_vec(), None).await.unwrap();
+		tx2.set(&"p", &b"2".to_vec(), None).await.unwrap();
+		tx2.commit().await.unwrap();
+		assert!(tx1.commit().await.is_err());
+		// The writes to the storage tier are rolled back
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		assert_eq!(tx.get(&"t1", None).await.unwrap(), Some(b"old".to_vec()));
+		assert!(tx.get(&"t2", None).await.unwrap().is_none());
+		assert_eq!(tx.get(&"p", None).await.unwrap(), Some(b"2".to_vec()));
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn rollback_range_delete() {
+		let ds = Datastore::new("memory?tier.cold=memory").await.unwrap();
+		// Store more keys in the storage tier than are removed in one batch
+		let count = *NORMAL_FETCH_SIZE as usize * 2 + 1;
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.set_tier(&"t", Some("cold")).await.unwrap();
+		for i in 0..count {
+			tx.set(&format!("t{i:05}"), &b"old".to_vec(), None).await.unwrap();
+		}
+		tx.commit().await.unwrap();
+		// A transaction which removes the keys conflicts in the primary datastore
+		let tx1 = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		let tx2 = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx1.set(&"p", &b"1".to_vec(), None).await.unwrap();
+		tx1.set(&"t00000", &b"new".to_vec(), None).await.unwrap();
+		tx1.delr(b"t".to_vec()..b"u".to_vec()).await.unwrap();
+		tx2.set(&"p", &b"2".to_vec(), None).await.unwrap();
+		tx2.commit().await.unwrap();
+		assert!(tx1.commit().await.is_err());
+		// The removed keys are restored to their values before the transaction
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		let res = tx.scan(b"t".to_vec()..b"u".to_vec(), u32::MAX, 0, None).await.unwrap();
+		assert_eq!(res.len(), count);
+		assert!(res.iter().all(|(_, v)| v == b"old"));
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn commit_removes_records() {
+		let ds = Datastore::new("memory?tier.cold=memory").await.unwrap();
+		let tx = ds.transaction(TransactionType::Write, LockType::Optimistic).await.unwrap();
+		tx.set_tier(&"t", Some("cold")).await.unwrap();
+		tx.set(&"t1", &b"val".to_vec(), None).await.unwrap();
+		tx.commit().await.unwrap();
+		// The commit record is removed from the primary datastore
+		let tx = ds.transaction(TransactionType::Read, LockType::Optimistic).await.unwrap();
+		let rng = COMMITS.to_vec()..prefix_end(COMMITS);
+		assert!(tx.keys(rng, u32::MAX, 0, None).await.unwrap().is_empty());
+		assert_eq!(tx.get(&"t1", None).await.unwrap(), Some(b"val".to_vec()));
+		tx.cancel().await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn reject_distributed_engines() {
+		assert!(Datastore::new("memory?tier.cold=tikv://127.0.0.1:2379").await.is_err());
+		assert!(Datastore::new("tikv://127.0.0.1:2379?tier.cold=memory").await.is_err());
+	}
+}
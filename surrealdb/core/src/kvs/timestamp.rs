@@ -77,6 +77,14 @@ impl BoxTimeStamp {
 		self.0.as_datetime()
 	}
 
+	/// Returns the version of the timestamp, as used by versioned reads.
+	///
+	/// Versions are the nanoseconds since the unix epoch. Can return none if the timestamp is
+	/// outside of the range of a version.
+	pub fn as_version(&self) -> Option<u64> {
+		self.0.as_datetime()?.timestamp_nanos_opt()?.try_into().ok()
+	}
+
 	/// Subtract a duration from the timestamp returning a duration that much in the past from this
 	/// timestamp. Can return none if the new time is outside of the range of the timestamp.
 	pub fn sub_checked(&self, duration: Duration) -> Option<BoxTimeStamp> {
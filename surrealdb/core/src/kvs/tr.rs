@@ -4,7 +4,7 @@ use std::ops::Range;
 
 use futures::stream::Stream;
 
-use super::api::{ScanLimit, Transactable};
+use super::api::{ScanLimit, SizeEstimate, Transactable};
 use super::batch::Batch;
 use super::scanner::{Direction, Scanner};
 use super::{IntoBytes, Key, Result, Val};
@@ -420,6 +420,35 @@ impl Transactor {
 		self.inner.count(beg..end, version).await
 	}
 
+	/// Estimate the storage used by a range of keys in the datastore.
+	///
+	/// Returns `None` if the storage engine can not estimate the size.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tr", skip_all)]
+	pub async fn size<K>(&self, rng: Range<K>) -> Result<Option<SizeEstimate>>
+	where
+		K: IntoBytes + Debug,
+	{
+		let beg = rng.start.into_vec();
+		let end = rng.end.into_vec();
+		self.inner.size(beg..end).await
+	}
+
+	/// Remove the history of a range of keys which is older than a version.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tr", skip_all)]
+	pub async fn prune_versions<K>(
+		&self,
+		rng: Range<K>,
+		before: u64,
+		limit: u32,
+	) -> Result<Option<Key>>
+	where
+		K: IntoBytes + Debug,
+	{
+		let beg = rng.start.into_vec();
+		let end = rng.end.into_vec();
+		self.inner.prune_versions(beg..end, before, limit).await
+	}
+
 	// --------------------------------------------------
 	// Batch functions
 	// --------------------------------------------------
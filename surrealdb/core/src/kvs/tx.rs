@@ -2,6 +2,7 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::ops::{Deref, Range};
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 
@@ -32,7 +33,7 @@ use crate::kvs::cache::tx::TransactionCache;
 use crate::kvs::index::{BatchId, BatchIdsCleanQueue, SharedIndexKey};
 use crate::kvs::scanner::Direction;
 use crate::kvs::sequences::Sequences;
-use crate::kvs::{BoxTimeStamp, BoxTimeStampImpl, KVKey, KVValue, Transactor, cache};
+use crate::kvs::{BoxTimeStamp, BoxTimeStampImpl, KVKey, KVValue, SizeEstimate, Transactor, cache};
 use crate::val::{RecordId, RecordIdKey, TableName};
 
 /// Controls whether `getm_records` populates the transaction cache on miss.
@@ -613,6 +614,40 @@ impl Transaction {
 		Ok(self.tr.count(beg..end, version).await.map_err(Error::from)?)
 	}
 
+	/// Estimate the storage used by a range of keys in the datastore.
+	///
+	/// Returns `None` if the storage engine can not estimate the size.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub async fn size<K>(&self, rng: Range<K>) -> Result<Option<SizeEstimate>>
+	where
+		K: KVKey + Debug,
+	{
+		let beg = rng.start.encode_key()?;
+		let end = rng.end.encode_key()?;
+		Ok(self.tr.size(beg..end).await.map_err(Error::from)?)
+	}
+
+	/// Checks whether the history of a range of keys can be removed.
+	pub fn can_prune_versions(&self) -> bool {
+		self.tr.inner.can_prune_versions()
+	}
+
+	/// Remove the history of a range of keys which is older than a version.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub async fn prune_versions<K>(
+		&self,
+		rng: Range<K>,
+		before: u64,
+		limit: u32,
+	) -> Result<Option<Key>>
+	where
+		K: KVKey + Debug,
+	{
+		let beg = rng.start.encode_key()?;
+		let end = rng.end.encode_key()?;
+		Ok(self.tr.prune_versions(beg..end, before, limit).await.map_err(Error::from)?)
+	}
+
 	// --------------------------------------------------
 	// Batch functions
 	// --------------------------------------------------
@@ -871,6 +906,27 @@ impl Transaction {
 		self.tr.inner.compact(rng).await
 	}
 
+	/// Checks whether a storage tier is available to store tables.
+	pub fn has_tier(&self, name: &str) -> bool {
+		self.tr.inner.has_tier(name)
+	}
+
+	/// Stores the keys with a prefix in a storage tier.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip_all)]
+	pub async fn set_tier<K>(&self, prefix: &K, tier: Option<&str>) -> Result<()>
+	where
+		K: KVKey + Debug,
+	{
+		let prefix = prefix.encode_key()?;
+		Ok(self.tr.inner.set_tier(prefix, tier.map(str::to_owned)).await.map_err(Error::from)?)
+	}
+
+	/// Creates a consistent on-disk copy of the datastore at the given path.
+	#[instrument(level = "trace", target = "surrealdb::core::kvs::tx", skip(self))]
+	pub async fn checkpoint(&self, path: &Path) -> Result<()> {
+		self.tr.inner.checkpoint(path).await
+	}
+
 	/// Mark this transaction to wake the async event processor after commit.
 	pub(crate) fn trigger_async_event(&self) {
 		self.trigger_async_event.store(true, Ordering::Relaxed);
@@ -1124,6 +1180,7 @@ impl DatabaseProvider for Transaction {
 					comment: None,
 					changefeed: None,
 					strict: false,
+					history: None,
 				};
 
 				return self.put_db(&ns_def.name, db_def).await;
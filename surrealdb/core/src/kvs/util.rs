@@ -3,7 +3,8 @@ use std::sync::Arc;
 
 use anyhow::Result;
 
-use crate::kvs::{KVKey, KVValue};
+use super::api::ScanLimit;
+use crate::kvs::{KVKey, KVValue, Key, Val};
 
 /// Advances a key to the next value,
 /// can be used to skip over a certain key.
@@ -26,6 +27,58 @@ pub fn to_prefix_range<K: KVKey>(key: K) -> Result<Range<Vec<u8>>> {
 	})
 }
 
+/// Collects the results of a range scan which is made of several scans
+pub(crate) struct Collector {
+	/// The collected key-value pairs
+	pub(crate) out: Vec<(Key, Val)>,
+	/// The scan limit
+	limit: ScanLimit,
+	/// The number of entries still to skip
+	skip: u32,
+	/// Whether values count towards the byte limit
+	values: bool,
+	/// The number of bytes collected
+	bytes: usize,
+}
+
+impl Collector {
+	/// Create a collector for a scan limit, skipping the first entries
+	pub(crate) fn new(limit: ScanLimit, skip: u32, values: bool) -> Self {
+		Self {
+			out: Vec::new(),
+			limit,
+			skip,
+			values,
+			bytes: 0,
+		}
+	}
+
+	/// Check whether the scan limit has been reached
+	pub(crate) fn full(&self) -> bool {
+		match self.limit {
+			ScanLimit::Count(c) => self.out.len() >= c as usize,
+			ScanLimit::Bytes(b) => self.bytes >= b as usize,
+			ScanLimit::BytesOrCount(b, c) => {
+				self.out.len() >= c as usize || self.bytes >= b as usize
+			}
+		}
+	}
+
+	/// Add an entry, returning whether the scan limit has been reached
+	pub(crate) fn push(&mut self, key: Key, val: Val) -> bool {
+		if self.skip > 0 {
+			self.skip -= 1;
+			return false;
+		}
+		self.bytes += key.len();
+		if self.values {
+			self.bytes += val.len();
+		}
+		self.out.push((key, val));
+		self.full()
+	}
+}
+
 /// Takes an iterator of byte slices and deserializes the byte slices to the
 /// expected type, returning an error if any of the values fail to serialize.
 pub fn deserialize_cache<'a, I, T>(iter: I) -> Result<Arc<[T]>>
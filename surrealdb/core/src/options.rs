@@ -27,6 +27,16 @@ pub struct EngineOptions {
 	///
 	/// Default: 5 seconds
 	pub event_processing_interval: Duration,
+	/// Interval for removing files which have expired according to the
+	/// lifecycle rules of a bucket.
+	///
+	/// Default: 60 seconds
+	pub bucket_lifecycle_interval: Duration,
+	/// Interval for removing record history which is older than the
+	/// history retention period of a table or database.
+	///
+	/// Default: 60 seconds
+	pub history_gc_interval: Duration,
 }
 
 impl Default for EngineOptions {
@@ -38,6 +48,8 @@ impl Default for EngineOptions {
 			changefeed_gc_interval: Duration::from_secs(30),
 			index_compaction_interval: Duration::from_secs(5),
 			event_processing_interval: Duration::from_secs(5),
+			bucket_lifecycle_interval: Duration::from_secs(60),
+			history_gc_interval: Duration::from_secs(60),
 		}
 	}
 }
@@ -69,4 +81,14 @@ impl EngineOptions {
 		self.event_processing_interval = interval;
 		self
 	}
+
+	pub fn with_bucket_lifecycle_interval(mut self, interval: Duration) -> Self {
+		self.bucket_lifecycle_interval = interval;
+		self
+	}
+
+	pub fn with_history_gc_interval(mut self, interval: Duration) -> Self {
+		self.history_gc_interval = interval;
+		self
+	}
 }
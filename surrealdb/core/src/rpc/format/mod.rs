@@ -1,11 +1,15 @@
 pub mod cbor;
 pub mod flatbuffers;
 pub mod json;
+pub mod msgpack;
+pub mod protobuf;
 
-pub const PROTOCOLS: [&str; 3] = [
+pub const PROTOCOLS: [&str; 5] = [
 	"json",        // For basic JSON serialisation
 	"cbor",        // For basic CBOR serialisation
 	"flatbuffers", // For flatbuffers serialisation
+	"msgpack",     // For MessagePack serialisation
+	"protobuf",    // For Protobuf serialisation
 ];
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -13,6 +17,8 @@ pub enum Format {
 	Json,        // For basic JSON serialisation
 	Cbor,        // For basic CBOR serialisation
 	Flatbuffers, // For flatbuffers serialisation
+	MessagePack, // For MessagePack serialisation
+	Protobuf,    // For Protobuf serialisation
 	Unsupported, // Unsupported format
 }
 
@@ -22,6 +28,8 @@ impl From<&str> for Format {
 			"json" => Format::Json,
 			"cbor" => Format::Cbor,
 			"flatbuffers" => Format::Flatbuffers,
+			"msgpack" => Format::MessagePack,
+			"protobuf" => Format::Protobuf,
 			_ => Format::Unsupported,
 		}
 	}
@@ -0,0 +1,523 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter::once;
+use std::ops::Bound;
+
+use anyhow::{Result, anyhow};
+use geo::{LineString, Polygon};
+use geo_types::{MultiLineString, MultiPoint, MultiPolygon};
+use rmpv::Value as MsgpackValue;
+use rust_decimal::Decimal;
+
+use crate::types::{
+	PublicArray, PublicDatetime, PublicDuration, PublicFile, PublicGeometry, PublicNumber,
+	PublicObject, PublicRange, PublicRecordId, PublicRecordIdKey, PublicRecordIdKeyRange,
+	PublicSet, PublicTable, PublicUuid, PublicValue,
+};
+use crate::val::DecimalExt;
+
+// Extension types from the spec - https://github.com/msgpack/msgpack/blob/master/spec.md
+const EXT_SPEC_TIMESTAMP: i8 = -1;
+
+// Custom extension types, using the same numbers as the CBOR tags. Unless
+// noted otherwise, the extension data is a MessagePack encoded value.
+const EXT_NONE: i8 = 6; // Empty extension data
+const EXT_TABLE: i8 = 7;
+const EXT_RECORDID: i8 = 8;
+const EXT_DECIMAL: i8 = 10;
+const EXT_DURATION: i8 = 14;
+const EXT_UUID: i8 = 37; // 16 raw bytes as extension data
+
+// Ranges
+const EXT_RANGE: i8 = 49;
+const EXT_BOUND_INCLUDED: i8 = 50;
+const EXT_BOUND_EXCLUDED: i8 = 51;
+
+// Custom types
+const EXT_FILE: i8 = 55;
+const EXT_SET: i8 = 56;
+
+// Custom Geometries
+const EXT_GEOMETRY_POINT: i8 = 88;
+const EXT_GEOMETRY_LINE: i8 = 89;
+const EXT_GEOMETRY_POLYGON: i8 = 90;
+const EXT_GEOMETRY_MULTIPOINT: i8 = 91;
+const EXT_GEOMETRY_MULTILINE: i8 = 92;
+const EXT_GEOMETRY_MULTIPOLYGON: i8 = 93;
+const EXT_GEOMETRY_COLLECTION: i8 = 94;
+
+pub fn to_value(val: MsgpackValue) -> Result<PublicValue> {
+	match val {
+		MsgpackValue::Nil => Ok(PublicValue::Null),
+		MsgpackValue::Boolean(v) => Ok(PublicValue::Bool(v)),
+		MsgpackValue::Integer(v) => match v.as_i64() {
+			Some(v) => Ok(PublicValue::Number(PublicNumber::Int(v))),
+			None => Err(anyhow!("Expected a MessagePack integer within the i64 range")),
+		},
+		MsgpackValue::F32(v) => Ok(PublicValue::Number(PublicNumber::Float(v as f64))),
+		MsgpackValue::F64(v) => Ok(PublicValue::Number(PublicNumber::Float(v))),
+		MsgpackValue::Binary(v) => Ok(PublicValue::Bytes(v.into())),
+		MsgpackValue::String(v) => match v.into_str() {
+			Some(v) => Ok(PublicValue::String(v)),
+			None => Err(anyhow!("Expected a valid UTF-8 MessagePack string")),
+		},
+		MsgpackValue::Array(v) => Ok(PublicValue::Array(to_array(v)?)),
+		MsgpackValue::Map(v) => Ok(PublicValue::Object(to_object(v)?)),
+		MsgpackValue::Ext(t, data) => match t {
+			// A [nanos: u32, seconds: i64] timestamp
+			EXT_SPEC_TIMESTAMP => to_datetime(&data).map(PublicValue::Datetime),
+			// A literal NONE
+			EXT_NONE => Ok(PublicValue::None),
+			// A byte string uuid
+			EXT_UUID => to_uuid(&data).map(PublicValue::Uuid),
+			// A literal decimal
+			EXT_DECIMAL => match Decimal::from_str_normalized(&to_string(unpack(&data)?)?) {
+				Ok(v) => Ok(PublicValue::Number(PublicNumber::Decimal(v))),
+				_ => Err(anyhow!("Expected a valid Decimal value")),
+			},
+			// A custom [seconds: Option<u64>, nanos: Option<u32>] duration
+			EXT_DURATION => match unpack(&data)? {
+				MsgpackValue::Array(v) if v.len() <= 2 => {
+					let mut iter = v.into_iter();
+
+					let seconds = match iter.next() {
+						Some(MsgpackValue::Integer(v)) => match v.as_u64() {
+							Some(v) => v,
+							_ => return Err(anyhow!("Expected a MessagePack integer data type")),
+						},
+						_ => 0,
+					};
+
+					let nanos = match iter.next() {
+						Some(MsgpackValue::Integer(v)) => match v.as_u64() {
+							Some(v) if v <= u32::MAX as u64 => v as u32,
+							_ => return Err(anyhow!("Expected a MessagePack integer data type")),
+						},
+						_ => 0,
+					};
+
+					Ok(PublicValue::Duration(PublicDuration::new(seconds, nanos)))
+				}
+				_ => Err(anyhow!("Expected a MessagePack array with at most 2 elements")),
+			},
+			// A [table, key] recordid
+			EXT_RECORDID => match unpack(&data)? {
+				MsgpackValue::Array(v) if v.len() == 2 => {
+					let mut v = v.into_iter();
+					match (v.next(), v.next()) {
+						(Some(table), Some(key)) => Ok(PublicValue::RecordId(PublicRecordId {
+							table: to_string(table)?.into(),
+							key: to_record_id_key(key)?,
+						})),
+						_ => Err(anyhow!("Expected a MessagePack array with 2 elements")),
+					}
+				}
+				_ => Err(anyhow!("Expected a MessagePack array with 2 elements")),
+			},
+			// A literal table
+			EXT_TABLE => Ok(PublicValue::Table(PublicTable::new(to_string(unpack(&data)?)?))),
+			// A range
+			EXT_RANGE => Ok(PublicValue::Range(Box::new(to_range(unpack(&data)?)?))),
+			EXT_GEOMETRY_POINT => match unpack(&data)? {
+				MsgpackValue::Array(v) if v.len() == 2 => {
+					let mut iter = v.into_iter().map(to_value);
+					match (iter.next().transpose()?, iter.next().transpose()?) {
+						(Some(PublicValue::Number(x)), Some(PublicValue::Number(y))) => {
+							match (x.to_f64(), y.to_f64()) {
+								(Some(x), Some(y)) if x.is_finite() && y.is_finite() => {
+									Ok(PublicValue::Geometry(PublicGeometry::Point((x, y).into())))
+								}
+								_ => Err(anyhow!("Expected a point with finite coordinates")),
+							}
+						}
+						_ => Err(anyhow!("Expected a MessagePack array with 2 decimal values")),
+					}
+				}
+				_ => Err(anyhow!("Expected a MessagePack array with 2 decimal values")),
+			},
+			EXT_GEOMETRY_LINE => {
+				let points = to_geometries(&data, |v| match v {
+					PublicGeometry::Point(v) => Ok(v),
+					_ => Err(anyhow!("Expected a MessagePack array with Geometry Point values")),
+				})?;
+
+				Ok(PublicValue::Geometry(PublicGeometry::Line(LineString::from(points))))
+			}
+			EXT_GEOMETRY_POLYGON => {
+				let mut lines = to_geometries(&data, |v| match v {
+					PublicGeometry::Line(v) => Ok(v),
+					_ => Err(anyhow!("Expected a MessagePack array with Geometry Line values")),
+				})?;
+
+				if lines.is_empty() {
+					return Err(anyhow!(
+						"Expected a MessagePack array with at least one Geometry Line values"
+					));
+				}
+				let exterior = lines.remove(0);
+
+				Ok(PublicValue::Geometry(PublicGeometry::Polygon(Polygon::new(exterior, lines))))
+			}
+			EXT_GEOMETRY_MULTIPOINT => {
+				let points = to_geometries(&data, |v| match v {
+					PublicGeometry::Point(v) => Ok(v),
+					_ => Err(anyhow!("Expected a MessagePack array with Geometry Point values")),
+				})?;
+
+				Ok(PublicValue::Geometry(PublicGeometry::MultiPoint(MultiPoint::from(points))))
+			}
+			EXT_GEOMETRY_MULTILINE => {
+				let lines = to_geometries(&data, |v| match v {
+					PublicGeometry::Line(v) => Ok(v),
+					_ => Err(anyhow!("Expected a MessagePack array with Geometry Line values")),
+				})?;
+
+				Ok(PublicValue::Geometry(PublicGeometry::MultiLine(MultiLineString::new(lines))))
+			}
+			EXT_GEOMETRY_MULTIPOLYGON => {
+				let polygons = to_geometries(&data, |v| match v {
+					PublicGeometry::Polygon(v) => Ok(v),
+					_ => Err(anyhow!("Expected a MessagePack array with Geometry Polygon values")),
+				})?;
+
+				Ok(PublicValue::Geometry(PublicGeometry::MultiPolygon(MultiPolygon::from(
+					polygons,
+				))))
+			}
+			EXT_GEOMETRY_COLLECTION => {
+				let geometries = to_geometries(&data, Ok)?;
+
+				Ok(PublicValue::Geometry(PublicGeometry::Collection(geometries)))
+			}
+			EXT_FILE => match unpack(&data)? {
+				MsgpackValue::Array(v) if v.len() == 2 => {
+					let mut v = v.into_iter();
+					match (v.next(), v.next()) {
+						(Some(bucket), Some(key)) => Ok(PublicValue::File(PublicFile::new(
+							to_string(bucket)?,
+							to_string(key)?,
+						))),
+						_ => Err(anyhow!("Expected a MessagePack array with 2 elements")),
+					}
+				}
+				_ => Err(anyhow!(
+					"Expected a MessagePack array with two String bucket and key values"
+				)),
+			},
+			EXT_SET => match unpack(&data)? {
+				MsgpackValue::Array(v) => Ok(PublicValue::Set(PublicSet::from(
+					v.into_iter().map(to_value).collect::<Result<BTreeSet<PublicValue>>>()?,
+				))),
+				_ => Err(anyhow!("Expected a MessagePack array with Set values")),
+			},
+			// An unknown extension type
+			_ => Err(anyhow!("Encountered an unknown MessagePack extension type")),
+		},
+	}
+}
+
+pub fn from_value(val: PublicValue) -> Result<MsgpackValue> {
+	match val {
+		PublicValue::None => Ok(MsgpackValue::Ext(EXT_NONE, Vec::new())),
+		PublicValue::Null => Ok(MsgpackValue::Nil),
+		PublicValue::Bool(v) => Ok(MsgpackValue::Boolean(v)),
+		PublicValue::Number(v) => match v {
+			PublicNumber::Int(v) => Ok(MsgpackValue::from(v)),
+			PublicNumber::Float(v) => Ok(MsgpackValue::F64(v)),
+			PublicNumber::Decimal(v) => Ok(ext(EXT_DECIMAL, MsgpackValue::from(v.to_string()))),
+		},
+		PublicValue::String(v) => Ok(MsgpackValue::from(v)),
+		PublicValue::Duration(v) => {
+			let seconds = v.secs();
+			let nanos = v.subsec_nanos();
+
+			let data = match (seconds, nanos) {
+				(0, 0) => vec![],
+				(_, 0) => vec![MsgpackValue::from(seconds)],
+				_ => vec![MsgpackValue::from(seconds), MsgpackValue::from(nanos)],
+			};
+
+			Ok(ext(EXT_DURATION, MsgpackValue::Array(data)))
+		}
+		PublicValue::Datetime(v) => {
+			// The 96-bit timestamp format, which supports the full datetime range
+			let mut data = Vec::with_capacity(12);
+			data.extend_from_slice(&v.timestamp_subsec_nanos().to_be_bytes());
+			data.extend_from_slice(&v.timestamp().to_be_bytes());
+			Ok(MsgpackValue::Ext(EXT_SPEC_TIMESTAMP, data))
+		}
+		PublicValue::Uuid(v) => Ok(from_uuid(v)),
+		PublicValue::Array(v) => from_array(v),
+		PublicValue::Object(v) => from_object(v),
+		PublicValue::Bytes(v) => Ok(MsgpackValue::Binary(v.into_inner().to_vec())),
+		PublicValue::Table(v) => Ok(ext(EXT_TABLE, MsgpackValue::from(v.into_string()))),
+		PublicValue::RecordId(PublicRecordId {
+			table,
+			key,
+		}) => Ok(ext(
+			EXT_RECORDID,
+			MsgpackValue::Array(vec![
+				MsgpackValue::from(table.into_string()),
+				from_record_id_key(key)?,
+			]),
+		)),
+		PublicValue::Geometry(v) => Ok(from_geometry(v)),
+		PublicValue::Range(v) => Ok(ext(EXT_RANGE, from_range(*v)?)),
+		PublicValue::File(file) => Ok(ext(
+			EXT_FILE,
+			MsgpackValue::Array(vec![
+				MsgpackValue::from(file.bucket),
+				MsgpackValue::from(file.key),
+			]),
+		)),
+		PublicValue::Set(v) => Ok(ext(
+			EXT_SET,
+			MsgpackValue::Array(
+				v.into_iter().map(from_value).collect::<Result<Vec<MsgpackValue>>>()?,
+			),
+		)),
+		PublicValue::Regex(_) => {
+			// Unsupported value type, as with CBOR
+			Err(anyhow!("Unsupported value type: Regex"))
+		}
+	}
+}
+
+/// Wrap a value as the data of a custom extension type
+fn ext(ty: i8, val: MsgpackValue) -> MsgpackValue {
+	let mut data = Vec::new();
+	rmpv::encode::write_value(&mut data, &val).expect("writing to vec should not fail");
+	MsgpackValue::Ext(ty, data)
+}
+
+/// Read the value stored in the data of a custom extension type
+fn unpack(mut data: &[u8]) -> Result<MsgpackValue> {
+	let val = rmpv::decode::read_value(&mut data).map_err(|e| anyhow!(e.to_string()))?;
+	if !data.is_empty() {
+		return Err(anyhow!("Unexpected trailing bytes in MessagePack extension data"));
+	}
+	Ok(val)
+}
+
+fn to_string(val: MsgpackValue) -> Result<String> {
+	match val {
+		MsgpackValue::String(v) => {
+			v.into_str().ok_or_else(|| anyhow!("Expected a valid UTF-8 MessagePack string"))
+		}
+		_ => Err(anyhow!("Expected a MessagePack string data type")),
+	}
+}
+
+fn to_datetime(data: &[u8]) -> Result<PublicDatetime> {
+	let (seconds, nanos) = match data.len() {
+		// timestamp 32: [seconds: u32]
+		4 => (u32::from_be_bytes(data.try_into()?) as i64, 0),
+		// timestamp 64: [nanos: u30, seconds: u34]
+		8 => {
+			let v = u64::from_be_bytes(data.try_into()?);
+			((v & 0x0000_0003_ffff_ffff) as i64, (v >> 34) as u32)
+		}
+		// timestamp 96: [nanos: u32, seconds: i64]
+		12 => {
+			(i64::from_be_bytes(data[4..].try_into()?), u32::from_be_bytes(data[..4].try_into()?))
+		}
+		_ => return Err(anyhow!("Expected a MessagePack timestamp with 4, 8 or 12 bytes")),
+	};
+	PublicDatetime::try_from((seconds, nanos))
+		.map_err(|_| anyhow!("Expected a valid Datetime value"))
+}
+
+fn from_geometry(v: PublicGeometry) -> MsgpackValue {
+	match v {
+		PublicGeometry::Point(v) => ext(
+			EXT_GEOMETRY_POINT,
+			MsgpackValue::Array(vec![MsgpackValue::F64(v.x()), MsgpackValue::F64(v.y())]),
+		),
+		PublicGeometry::Line(v) => {
+			let data = v.points().map(|v| from_geometry(PublicGeometry::Point(v))).collect();
+			ext(EXT_GEOMETRY_LINE, MsgpackValue::Array(data))
+		}
+		PublicGeometry::Polygon(v) => {
+			let data = once(v.exterior())
+				.chain(v.interiors())
+				.map(|v| from_geometry(PublicGeometry::Line(v.clone())))
+				.collect();
+			ext(EXT_GEOMETRY_POLYGON, MsgpackValue::Array(data))
+		}
+		PublicGeometry::MultiPoint(v) => {
+			let data = v.into_iter().map(|v| from_geometry(PublicGeometry::Point(v))).collect();
+			ext(EXT_GEOMETRY_MULTIPOINT, MsgpackValue::Array(data))
+		}
+		PublicGeometry::MultiLine(v) => {
+			let data = v.into_iter().map(|v| from_geometry(PublicGeometry::Line(v))).collect();
+			ext(EXT_GEOMETRY_MULTILINE, MsgpackValue::Array(data))
+		}
+		PublicGeometry::MultiPolygon(v) => {
+			let data = v.into_iter().map(|v| from_geometry(PublicGeometry::Polygon(v))).collect();
+			ext(EXT_GEOMETRY_MULTIPOLYGON, MsgpackValue::Array(data))
+		}
+		PublicGeometry::Collection(v) => {
+			let data = v.into_iter().map(from_geometry).collect();
+			ext(EXT_GEOMETRY_COLLECTION, MsgpackValue::Array(data))
+		}
+	}
+}
+
+/// Decode an array of geometries, checking the kind of each geometry
+fn to_geometries<T>(data: &[u8], check: impl Fn(PublicGeometry) -> Result<T>) -> Result<Vec<T>> {
+	match unpack(data)? {
+		MsgpackValue::Array(v) => v
+			.into_iter()
+			.map(|v| match to_value(v)? {
+				PublicValue::Geometry(v) => check(v),
+				_ => Err(anyhow!("Expected a MessagePack array with Geometry values")),
+			})
+			.collect(),
+		_ => Err(anyhow!("Expected a MessagePack array with Geometry values")),
+	}
+}
+
+fn to_range(val: MsgpackValue) -> Result<PublicRange> {
+	fn decode_bound(v: MsgpackValue) -> Result<Bound<PublicValue>> {
+		match v {
+			MsgpackValue::Ext(EXT_BOUND_INCLUDED, v) => Ok(Bound::Included(to_value(unpack(&v)?)?)),
+			MsgpackValue::Ext(EXT_BOUND_EXCLUDED, v) => Ok(Bound::Excluded(to_value(unpack(&v)?)?)),
+			MsgpackValue::Nil => Ok(Bound::Unbounded),
+			_ => Err(anyhow!("Expected a bound extension type")),
+		}
+	}
+
+	match val {
+		MsgpackValue::Array(v) if v.len() == 2 => {
+			let mut v = v;
+			let beg = decode_bound(v.remove(0))?;
+			let end = decode_bound(v.remove(0))?;
+			Ok(PublicRange::new(beg, end))
+		}
+		_ => Err(anyhow!("Expected a MessagePack array with 2 bounds")),
+	}
+}
+
+fn from_range(r: PublicRange) -> Result<MsgpackValue> {
+	fn encode(b: Bound<PublicValue>) -> Result<MsgpackValue> {
+		match b {
+			Bound::Included(v) => Ok(ext(EXT_BOUND_INCLUDED, from_value(v)?)),
+			Bound::Excluded(v) => Ok(ext(EXT_BOUND_EXCLUDED, from_value(v)?)),
+			Bound::Unbounded => Ok(MsgpackValue::Nil),
+		}
+	}
+	let (start, end) = r.into_inner();
+	Ok(MsgpackValue::Array(vec![encode(start)?, encode(end)?]))
+}
+
+fn from_record_id_key_range(r: PublicRecordIdKeyRange) -> Result<MsgpackValue> {
+	fn encode(b: Bound<PublicRecordIdKey>) -> Result<MsgpackValue> {
+		match b {
+			Bound::Included(v) => Ok(ext(EXT_BOUND_INCLUDED, from_record_id_key(v)?)),
+			Bound::Excluded(v) => Ok(ext(EXT_BOUND_EXCLUDED, from_record_id_key(v)?)),
+			Bound::Unbounded => Ok(MsgpackValue::Nil),
+		}
+	}
+
+	let (start, end) = r.into_inner();
+	Ok(MsgpackValue::Array(vec![encode(start)?, encode(end)?]))
+}
+
+fn to_record_id_key_range(val: MsgpackValue) -> Result<PublicRecordIdKeyRange> {
+	fn decode_bound(v: MsgpackValue) -> Result<Bound<PublicRecordIdKey>> {
+		match v {
+			MsgpackValue::Ext(EXT_BOUND_INCLUDED, v) => {
+				Ok(Bound::Included(to_record_id_key(unpack(&v)?)?))
+			}
+			MsgpackValue::Ext(EXT_BOUND_EXCLUDED, v) => {
+				Ok(Bound::Excluded(to_record_id_key(unpack(&v)?)?))
+			}
+			MsgpackValue::Nil => Ok(Bound::Unbounded),
+			_ => Err(anyhow!("Expected a bound extension type")),
+		}
+	}
+
+	match val {
+		MsgpackValue::Array(v) if v.len() == 2 => {
+			let mut v = v;
+			let start = decode_bound(v.remove(0))?;
+			let end = decode_bound(v.remove(0))?;
+
+			Ok(PublicRecordIdKeyRange {
+				start,
+				end,
+			})
+		}
+		_ => Err(anyhow!("Expected a MessagePack array with 2 bounds")),
+	}
+}
+
+fn from_record_id_key(v: PublicRecordIdKey) -> Result<MsgpackValue> {
+	match v {
+		PublicRecordIdKey::Number(v) => Ok(MsgpackValue::from(v)),
+		PublicRecordIdKey::String(v) => Ok(MsgpackValue::from(v)),
+		PublicRecordIdKey::Array(v) => from_array(v),
+		PublicRecordIdKey::Object(v) => from_object(v),
+		PublicRecordIdKey::Range(v) => Ok(ext(EXT_RANGE, from_record_id_key_range(*v)?)),
+		PublicRecordIdKey::Uuid(v) => Ok(from_uuid(v)),
+	}
+}
+
+fn to_record_id_key(val: MsgpackValue) -> Result<PublicRecordIdKey> {
+	match val {
+		MsgpackValue::Integer(v) => match v.as_i64() {
+			Some(v) => Ok(PublicRecordIdKey::Number(v)),
+			None => Err(anyhow!("Expected a MessagePack integer within the i64 range")),
+		},
+		MsgpackValue::String(_) => Ok(PublicRecordIdKey::String(to_string(val)?)),
+		MsgpackValue::Array(v) => Ok(PublicRecordIdKey::Array(to_array(v)?)),
+		MsgpackValue::Map(v) => Ok(PublicRecordIdKey::Object(to_object(v)?)),
+		MsgpackValue::Ext(EXT_RANGE, v) => {
+			Ok(PublicRecordIdKey::Range(Box::new(to_record_id_key_range(unpack(&v)?)?)))
+		}
+		MsgpackValue::Ext(EXT_UUID, v) => to_uuid(&v).map(PublicRecordIdKey::Uuid),
+		_ => Err(anyhow!("Expected a MessagePack integer, string, array or map")),
+	}
+}
+
+fn from_uuid(val: PublicUuid) -> MsgpackValue {
+	MsgpackValue::Ext(EXT_UUID, val.into_bytes().to_vec())
+}
+
+fn to_uuid(data: &[u8]) -> Result<PublicUuid> {
+	match data.try_into() {
+		Ok(v) => Ok(PublicUuid::from(uuid::Uuid::from_bytes(v))),
+		Err(_) => Err(anyhow!("Expected a MessagePack extension with 16 bytes")),
+	}
+}
+
+fn from_array(array: PublicArray) -> Result<MsgpackValue> {
+	array
+		.into_iter()
+		.map(from_value)
+		.collect::<Result<Vec<MsgpackValue>>>()
+		.map(MsgpackValue::Array)
+}
+
+fn to_array(array: Vec<MsgpackValue>) -> Result<PublicArray> {
+	Ok(array.into_iter().map(to_value).collect::<Result<Vec<PublicValue>, _>>()?.into())
+}
+
+fn from_object(obj: PublicObject) -> Result<MsgpackValue> {
+	obj.into_iter()
+		.map(|(k, v)| Ok((MsgpackValue::from(k), from_value(v)?)))
+		.collect::<Result<Vec<(MsgpackValue, MsgpackValue)>>>()
+		.map(MsgpackValue::Map)
+}
+
+fn to_object(obj: Vec<(MsgpackValue, MsgpackValue)>) -> Result<PublicObject> {
+	let res = obj
+		.into_iter()
+		.map(|(k, v)| {
+			let MsgpackValue::String(_) = k else {
+				return Err(anyhow!("Expected object key to be a string"));
+			};
+			Ok((to_string(k)?, to_value(v)?))
+		})
+		.collect::<Result<BTreeMap<_, _>>>()?;
+	Ok(PublicObject::from(res))
+}
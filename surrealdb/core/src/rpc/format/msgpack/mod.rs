@@ -0,0 +1,70 @@
+mod convert;
+
+use surrealdb_types::Value;
+
+pub fn encode(v: Value) -> anyhow::Result<Vec<u8>> {
+	// Convert public value to internal value for encoding
+	let encoding = convert::from_value(v)?;
+	let mut res = Vec::new();
+	rmpv::encode::write_value(&mut res, &encoding).expect("writing to vec should not fail");
+	Ok(res)
+}
+
+pub fn decode(mut bytes: &[u8]) -> anyhow::Result<Value> {
+	let encoding =
+		rmpv::decode::read_value(&mut bytes).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+	convert::to_value(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_types::{Bytes, File, Set, Table};
+
+	use super::*;
+	use crate::syn;
+
+	#[test]
+	fn roundtrip() {
+		let Value::Object(mut obj) = syn::value(
+			r#"{
+				none: NONE,
+				null: NULL,
+				int: -42,
+				float: 1.5f,
+				decimal: 12.3456789012345678901dec,
+				string: 'text',
+				duration: 1y2w3d4h5m6s7ms8us9ns,
+				datetime: d"2024-02-29T12:34:56.123456789Z",
+				uuid: u"018f4b52-8d7a-7f3e-9a6b-0c2d3e4f5a6b",
+				ids: [person:tobie, person:1, person:[1, 'a'], person:{ a: 1 }, person:1..=5],
+				range: 1..10,
+				point: (-0.118092, 51.509865),
+				polygon: {
+					type: "Polygon",
+					coordinates: [[[-0.38, 51.41], [0.15, 51.41], [0.15, 51.7], [-0.38, 51.41]]]
+				},
+			}"#,
+		)
+		.unwrap() else {
+			panic!("Expected an object value");
+		};
+		obj.insert("bytes", Value::Bytes(Bytes::from(vec![0, 1, 255])));
+		obj.insert("table", Value::Table(Table::from("person")));
+		obj.insert("set", Value::Set(Set::from(vec![Value::from_t(1), Value::from_t(2)])));
+		obj.insert("file", Value::File(File::new("bucket", "/some/key")));
+		let val = Value::Object(obj);
+		let res = decode(&encode(val.clone()).unwrap()).unwrap();
+		assert_eq!(res, val);
+	}
+
+	#[test]
+	fn non_finite_point() {
+		let mut data = Vec::new();
+		let point = rmpv::Value::Array(vec![f64::INFINITY.into(), 0.0f64.into()]);
+		rmpv::encode::write_value(&mut data, &point).unwrap();
+		let mut bytes = Vec::new();
+		// The extension type of a geometry point
+		rmpv::encode::write_value(&mut bytes, &rmpv::Value::Ext(88, data)).unwrap();
+		decode(&bytes).unwrap_err();
+	}
+}
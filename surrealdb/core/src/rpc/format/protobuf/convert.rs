@@ -0,0 +1,326 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Bound;
+
+use anyhow::{Result, anyhow};
+use geo::{LineString, Point, Polygon};
+use geo_types::{MultiLineString, MultiPoint, MultiPolygon};
+use rust_decimal::Decimal;
+
+use super::proto::{self, bound, geometry, record_id_key, record_id_key_bound, value};
+use crate::types::{
+	PublicArray, PublicDatetime, PublicDuration, PublicFile, PublicGeometry, PublicNumber,
+	PublicObject, PublicRange, PublicRecordId, PublicRecordIdKey, PublicRecordIdKeyRange,
+	PublicRegex, PublicSet, PublicTable, PublicUuid, PublicValue,
+};
+use crate::val::DecimalExt;
+
+/// Convert a Protobuf value into a value
+pub fn to_value(val: proto::Value) -> Result<PublicValue> {
+	let Some(kind) = val.kind else {
+		return Err(anyhow!("Expected a Protobuf value with a kind"));
+	};
+	match kind {
+		value::Kind::None(_) => Ok(PublicValue::None),
+		value::Kind::Null(_) => Ok(PublicValue::Null),
+		value::Kind::Bool(v) => Ok(PublicValue::Bool(v)),
+		value::Kind::Int(v) => Ok(PublicValue::Number(PublicNumber::Int(v))),
+		value::Kind::Float(v) => Ok(PublicValue::Number(PublicNumber::Float(v))),
+		value::Kind::Decimal(v) => match Decimal::from_str_normalized(&v) {
+			Ok(v) => Ok(PublicValue::Number(PublicNumber::Decimal(v))),
+			_ => Err(anyhow!("Expected a valid Decimal value")),
+		},
+		value::Kind::String(v) => Ok(PublicValue::String(v)),
+		value::Kind::Bytes(v) => Ok(PublicValue::Bytes(v.into())),
+		value::Kind::Duration(v) => {
+			Ok(PublicValue::Duration(PublicDuration::new(v.seconds, v.nanos)))
+		}
+		value::Kind::Datetime(v) => match PublicDatetime::try_from((v.seconds, v.nanos)) {
+			Ok(v) => Ok(PublicValue::Datetime(v)),
+			_ => Err(anyhow!("Expected a valid Datetime value")),
+		},
+		value::Kind::Uuid(v) => to_uuid(&v).map(PublicValue::Uuid),
+		value::Kind::Array(v) => Ok(PublicValue::Array(to_array(v)?)),
+		value::Kind::Object(v) => Ok(PublicValue::Object(to_object(v)?)),
+		value::Kind::Table(v) => Ok(PublicValue::Table(PublicTable::new(v))),
+		value::Kind::RecordId(v) => {
+			let Some(key) = v.key else {
+				return Err(anyhow!("Expected a Protobuf record id with a key"));
+			};
+			Ok(PublicValue::RecordId(PublicRecordId {
+				table: v.table.into(),
+				key: to_record_id_key(key)?,
+			}))
+		}
+		value::Kind::Geometry(v) => Ok(PublicValue::Geometry(to_geometry(v)?)),
+		value::Kind::Range(v) => Ok(PublicValue::Range(Box::new(to_range(v)?))),
+		value::Kind::File(v) => Ok(PublicValue::File(PublicFile::new(v.bucket, v.key))),
+		value::Kind::Set(v) => Ok(PublicValue::Set(PublicSet::from(
+			v.values.into_iter().map(to_value).collect::<Result<BTreeSet<PublicValue>>>()?,
+		))),
+		value::Kind::Regex(v) => match v.parse::<PublicRegex>() {
+			Ok(v) => Ok(PublicValue::Regex(v)),
+			_ => Err(anyhow!("Expected a valid Regex value")),
+		},
+	}
+}
+
+/// Convert a value into a Protobuf value
+pub fn from_value(val: PublicValue) -> Result<proto::Value> {
+	let kind = match val {
+		PublicValue::None => value::Kind::None(true),
+		PublicValue::Null => value::Kind::Null(true),
+		PublicValue::Bool(v) => value::Kind::Bool(v),
+		PublicValue::Number(v) => match v {
+			PublicNumber::Int(v) => value::Kind::Int(v),
+			PublicNumber::Float(v) => value::Kind::Float(v),
+			PublicNumber::Decimal(v) => value::Kind::Decimal(v.to_string()),
+		},
+		PublicValue::String(v) => value::Kind::String(v),
+		PublicValue::Duration(v) => value::Kind::Duration(proto::Duration {
+			seconds: v.secs(),
+			nanos: v.subsec_nanos(),
+		}),
+		PublicValue::Datetime(v) => value::Kind::Datetime(proto::Datetime {
+			seconds: v.timestamp(),
+			nanos: v.timestamp_subsec_nanos(),
+		}),
+		PublicValue::Uuid(v) => value::Kind::Uuid(v.into_bytes().to_vec()),
+		PublicValue::Array(v) => value::Kind::Array(from_array(v)?),
+		PublicValue::Object(v) => value::Kind::Object(from_object(v)?),
+		PublicValue::Bytes(v) => value::Kind::Bytes(v.into_inner().to_vec()),
+		PublicValue::Table(v) => value::Kind::Table(v.into_string()),
+		PublicValue::RecordId(PublicRecordId {
+			table,
+			key,
+		}) => value::Kind::RecordId(proto::RecordId {
+			table: table.into_string(),
+			key: Some(from_record_id_key(key)?),
+		}),
+		PublicValue::Geometry(v) => value::Kind::Geometry(from_geometry(v)),
+		PublicValue::Range(v) => value::Kind::Range(from_range(*v)?),
+		PublicValue::File(file) => value::Kind::File(proto::File {
+			bucket: file.bucket,
+			key: file.key,
+		}),
+		PublicValue::Set(v) => value::Kind::Set(proto::Array {
+			values: v.into_iter().map(from_value).collect::<Result<Vec<_>>>()?,
+		}),
+		PublicValue::Regex(v) => value::Kind::Regex(v.regex().as_str().to_string()),
+	};
+	Ok(proto::Value {
+		kind: Some(kind),
+	})
+}
+
+fn to_geometry(val: proto::Geometry) -> Result<PublicGeometry> {
+	fn point(v: proto::Point) -> Point {
+		(v.x, v.y).into()
+	}
+	fn line(v: proto::LineString) -> LineString {
+		LineString::from(v.points.into_iter().map(point).collect::<Vec<_>>())
+	}
+	fn polygon(v: proto::Polygon) -> Result<Polygon> {
+		let Some(exterior) = v.exterior else {
+			return Err(anyhow!("Expected a Protobuf polygon with an exterior line"));
+		};
+		Ok(Polygon::new(line(exterior), v.interiors.into_iter().map(line).collect()))
+	}
+
+	let Some(kind) = val.kind else {
+		return Err(anyhow!("Expected a Protobuf geometry with a kind"));
+	};
+	Ok(match kind {
+		geometry::Kind::Point(v) => PublicGeometry::Point(point(v)),
+		geometry::Kind::Line(v) => PublicGeometry::Line(line(v)),
+		geometry::Kind::Polygon(v) => PublicGeometry::Polygon(polygon(v)?),
+		geometry::Kind::MultiPoint(v) => PublicGeometry::MultiPoint(MultiPoint::from(
+			v.points.into_iter().map(point).collect::<Vec<_>>(),
+		)),
+		geometry::Kind::MultiLine(v) => {
+			PublicGeometry::MultiLine(MultiLineString::new(v.lines.into_iter().map(line).collect()))
+		}
+		geometry::Kind::MultiPolygon(v) => PublicGeometry::MultiPolygon(MultiPolygon::from(
+			v.polygons.into_iter().map(polygon).collect::<Result<Vec<_>>>()?,
+		)),
+		geometry::Kind::Collection(v) => PublicGeometry::Collection(
+			v.geometries.into_iter().map(to_geometry).collect::<Result<Vec<_>>>()?,
+		),
+	})
+}
+
+fn from_geometry(val: PublicGeometry) -> proto::Geometry {
+	fn point(v: Point) -> proto::Point {
+		proto::Point {
+			x: v.x(),
+			y: v.y(),
+		}
+	}
+	fn line(v: LineString) -> proto::LineString {
+		proto::LineString {
+			points: v.into_points().into_iter().map(point).collect(),
+		}
+	}
+	fn polygon(v: Polygon) -> proto::Polygon {
+		let (exterior, interiors) = v.into_inner();
+		proto::Polygon {
+			exterior: Some(line(exterior)),
+			interiors: interiors.into_iter().map(line).collect(),
+		}
+	}
+
+	let kind = match val {
+		PublicGeometry::Point(v) => geometry::Kind::Point(point(v)),
+		PublicGeometry::Line(v) => geometry::Kind::Line(line(v)),
+		PublicGeometry::Polygon(v) => geometry::Kind::Polygon(polygon(v)),
+		PublicGeometry::MultiPoint(v) => geometry::Kind::MultiPoint(proto::MultiPoint {
+			points: v.into_iter().map(point).collect(),
+		}),
+		PublicGeometry::MultiLine(v) => geometry::Kind::MultiLine(proto::MultiLineString {
+			lines: v.into_iter().map(line).collect(),
+		}),
+		PublicGeometry::MultiPolygon(v) => geometry::Kind::MultiPolygon(proto::MultiPolygon {
+			polygons: v.into_iter().map(polygon).collect(),
+		}),
+		PublicGeometry::Collection(v) => geometry::Kind::Collection(proto::GeometryCollection {
+			geometries: v.into_iter().map(from_geometry).collect(),
+		}),
+	};
+	proto::Geometry {
+		kind: Some(kind),
+	}
+}
+
+fn to_range(val: proto::Range) -> Result<PublicRange> {
+	fn decode_bound(v: Option<proto::Bound>) -> Result<Bound<PublicValue>> {
+		match v.and_then(|v| v.kind) {
+			Some(bound::Kind::Included(v)) => Ok(Bound::Included(to_value(*v)?)),
+			Some(bound::Kind::Excluded(v)) => Ok(Bound::Excluded(to_value(*v)?)),
+			None => Ok(Bound::Unbounded),
+		}
+	}
+
+	Ok(PublicRange::new(decode_bound(val.start)?, decode_bound(val.end)?))
+}
+
+fn from_range(r: PublicRange) -> Result<proto::Range> {
+	fn encode(b: Bound<PublicValue>) -> Result<Option<proto::Bound>> {
+		let kind = match b {
+			Bound::Included(v) => bound::Kind::Included(Box::new(from_value(v)?)),
+			Bound::Excluded(v) => bound::Kind::Excluded(Box::new(from_value(v)?)),
+			Bound::Unbounded => return Ok(None),
+		};
+		Ok(Some(proto::Bound {
+			kind: Some(kind),
+		}))
+	}
+
+	let (start, end) = r.into_inner();
+	Ok(proto::Range {
+		start: encode(start)?,
+		end: encode(end)?,
+	})
+}
+
+fn to_record_id_key_range(val: proto::RecordIdKeyRange) -> Result<PublicRecordIdKeyRange> {
+	fn decode_bound(v: Option<proto::RecordIdKeyBound>) -> Result<Bound<PublicRecordIdKey>> {
+		match v.and_then(|v| v.kind) {
+			Some(record_id_key_bound::Kind::Included(v)) => {
+				Ok(Bound::Included(to_record_id_key(*v)?))
+			}
+			Some(record_id_key_bound::Kind::Excluded(v)) => {
+				Ok(Bound::Excluded(to_record_id_key(*v)?))
+			}
+			None => Ok(Bound::Unbounded),
+		}
+	}
+
+	Ok(PublicRecordIdKeyRange {
+		start: decode_bound(val.start)?,
+		end: decode_bound(val.end)?,
+	})
+}
+
+fn from_record_id_key_range(r: PublicRecordIdKeyRange) -> Result<proto::RecordIdKeyRange> {
+	fn encode(b: Bound<PublicRecordIdKey>) -> Result<Option<proto::RecordIdKeyBound>> {
+		let kind = match b {
+			Bound::Included(v) => {
+				record_id_key_bound::Kind::Included(Box::new(from_record_id_key(v)?))
+			}
+			Bound::Excluded(v) => {
+				record_id_key_bound::Kind::Excluded(Box::new(from_record_id_key(v)?))
+			}
+			Bound::Unbounded => return Ok(None),
+		};
+		Ok(Some(proto::RecordIdKeyBound {
+			kind: Some(kind),
+		}))
+	}
+
+	let (start, end) = r.into_inner();
+	Ok(proto::RecordIdKeyRange {
+		start: encode(start)?,
+		end: encode(end)?,
+	})
+}
+
+fn to_record_id_key(val: proto::RecordIdKey) -> Result<PublicRecordIdKey> {
+	let Some(kind) = val.kind else {
+		return Err(anyhow!("Expected a Protobuf record id key with a kind"));
+	};
+	match kind {
+		record_id_key::Kind::Number(v) => Ok(PublicRecordIdKey::Number(v)),
+		record_id_key::Kind::String(v) => Ok(PublicRecordIdKey::String(v)),
+		record_id_key::Kind::Uuid(v) => to_uuid(&v).map(PublicRecordIdKey::Uuid),
+		record_id_key::Kind::Array(v) => Ok(PublicRecordIdKey::Array(to_array(v)?)),
+		record_id_key::Kind::Object(v) => Ok(PublicRecordIdKey::Object(to_object(v)?)),
+		record_id_key::Kind::Range(v) => {
+			Ok(PublicRecordIdKey::Range(Box::new(to_record_id_key_range(v)?)))
+		}
+	}
+}
+
+fn from_record_id_key(v: PublicRecordIdKey) -> Result<proto::RecordIdKey> {
+	let kind = match v {
+		PublicRecordIdKey::Number(v) => record_id_key::Kind::Number(v),
+		PublicRecordIdKey::String(v) => record_id_key::Kind::String(v),
+		PublicRecordIdKey::Uuid(v) => record_id_key::Kind::Uuid(v.into_bytes().to_vec()),
+		PublicRecordIdKey::Array(v) => record_id_key::Kind::Array(from_array(v)?),
+		PublicRecordIdKey::Object(v) => record_id_key::Kind::Object(from_object(v)?),
+		PublicRecordIdKey::Range(v) => record_id_key::Kind::Range(from_record_id_key_range(*v)?),
+	};
+	Ok(proto::RecordIdKey {
+		kind: Some(kind),
+	})
+}
+
+fn to_uuid(data: &[u8]) -> Result<PublicUuid> {
+	match data.try_into() {
+		Ok(v) => Ok(PublicUuid::from(uuid::Uuid::from_bytes(v))),
+		Err(_) => Err(anyhow!("Expected a Protobuf UUID with 16 bytes")),
+	}
+}
+
+fn from_array(array: PublicArray) -> Result<proto::Array> {
+	Ok(proto::Array {
+		values: array.into_iter().map(from_value).collect::<Result<Vec<_>>>()?,
+	})
+}
+
+fn to_array(array: proto::Array) -> Result<PublicArray> {
+	Ok(array.values.into_iter().map(to_value).collect::<Result<Vec<PublicValue>>>()?.into())
+}
+
+fn from_object(obj: PublicObject) -> Result<proto::Object> {
+	Ok(proto::Object {
+		fields: obj.into_iter().map(|(k, v)| Ok((k, from_value(v)?))).collect::<Result<_>>()?,
+	})
+}
+
+fn to_object(obj: proto::Object) -> Result<PublicObject> {
+	let res = obj
+		.fields
+		.into_iter()
+		.map(|(k, v)| Ok((k, to_value(v)?)))
+		.collect::<Result<BTreeMap<_, _>>>()?;
+	Ok(PublicObject::from(res))
+}
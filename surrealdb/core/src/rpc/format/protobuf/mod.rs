@@ -0,0 +1,63 @@
+//! A Protobuf encoding of SurrealDB values.
+//!
+//! The messages are described in `proto/surrealdb/rpc/v1/value.proto` in the
+//! server crate, so that clients can generate their own value types.
+
+mod convert;
+pub mod proto;
+
+pub use convert::{from_value, to_value};
+
+use prost::Message;
+use surrealdb_types::Value;
+
+pub fn encode(v: Value) -> anyhow::Result<Vec<u8>> {
+	Ok(convert::from_value(v)?.encode_to_vec())
+}
+
+pub fn decode(bytes: &[u8]) -> anyhow::Result<Value> {
+	let encoding = proto::Value::decode(bytes).map_err(|e| anyhow::anyhow!(e.to_string()))?;
+	convert::to_value(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_types::{Bytes, File, Set, Table};
+
+	use super::*;
+	use crate::syn;
+
+	#[test]
+	fn roundtrip() {
+		let Value::Object(mut obj) = syn::value(
+			r#"{
+				none: NONE,
+				null: NULL,
+				int: -42,
+				float: 1.5f,
+				decimal: 12.3456789012345678901dec,
+				string: 'text',
+				duration: 1y2w3d4h5m6s7ms8us9ns,
+				datetime: d"2024-02-29T12:34:56.123456789Z",
+				uuid: u"018f4b52-8d7a-7f3e-9a6b-0c2d3e4f5a6b",
+				ids: [person:tobie, person:1, person:[1, 'a'], person:{ a: 1 }, person:1..=5],
+				range: 1..10,
+				point: (-0.118092, 51.509865),
+				polygon: {
+					type: "Polygon",
+					coordinates: [[[-0.38, 51.41], [0.15, 51.41], [0.15, 51.7], [-0.38, 51.41]]]
+				},
+			}"#,
+		)
+		.unwrap() else {
+			panic!("Expected an object value");
+		};
+		obj.insert("bytes", Value::Bytes(Bytes::from(vec![0, 1, 255])));
+		obj.insert("table", Value::Table(Table::from("person")));
+		obj.insert("set", Value::Set(Set::from(vec![Value::from_t(1), Value::from_t(2)])));
+		obj.insert("file", Value::File(File::new("bucket", "/some/key")));
+		let val = Value::Object(obj);
+		let res = decode(&encode(val.clone()).unwrap()).unwrap();
+		assert_eq!(res, val);
+	}
+}
@@ -0,0 +1,510 @@
+//! The Protobuf messages of the `surrealdb.rpc.v1` value encoding.
+//!
+//! These messages mirror `value.proto` in the server crate, and a test checks
+//! that their field numbers and wire types match the schema.
+
+use std::collections::BTreeMap;
+
+/// Any SurrealDB value
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Value {
+	#[prost(
+		oneof = "value::Kind",
+		tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20"
+	)]
+	pub kind: Option<value::Kind>,
+}
+
+pub mod value {
+	/// The kind of a value
+	#[derive(Clone, PartialEq, prost::Oneof)]
+	pub enum Kind {
+		/// The NONE value, always set to true
+		#[prost(bool, tag = "1")]
+		None(bool),
+		/// The NULL value, always set to true
+		#[prost(bool, tag = "2")]
+		Null(bool),
+		#[prost(bool, tag = "3")]
+		Bool(bool),
+		#[prost(int64, tag = "4")]
+		Int(i64),
+		#[prost(double, tag = "5")]
+		Float(f64),
+		/// A decimal number, in its string representation
+		#[prost(string, tag = "6")]
+		Decimal(String),
+		#[prost(string, tag = "7")]
+		String(String),
+		#[prost(bytes = "vec", tag = "8")]
+		Bytes(Vec<u8>),
+		#[prost(message, tag = "9")]
+		Duration(super::Duration),
+		#[prost(message, tag = "10")]
+		Datetime(super::Datetime),
+		/// A UUID, as 16 bytes
+		#[prost(bytes = "vec", tag = "11")]
+		Uuid(Vec<u8>),
+		#[prost(message, tag = "12")]
+		Array(super::Array),
+		#[prost(message, tag = "13")]
+		Object(super::Object),
+		#[prost(string, tag = "14")]
+		Table(String),
+		#[prost(message, tag = "15")]
+		RecordId(super::RecordId),
+		#[prost(message, tag = "16")]
+		Geometry(super::Geometry),
+		#[prost(message, tag = "17")]
+		Range(super::Range),
+		#[prost(message, tag = "18")]
+		File(super::File),
+		#[prost(message, tag = "19")]
+		Set(super::Array),
+		/// A regular expression, without the `/` delimiters
+		#[prost(string, tag = "20")]
+		Regex(String),
+	}
+}
+
+/// A duration, as seconds and nanoseconds
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Duration {
+	#[prost(uint64, tag = "1")]
+	pub seconds: u64,
+	#[prost(uint32, tag = "2")]
+	pub nanos: u32,
+}
+
+/// A datetime, as seconds and nanoseconds since the UNIX epoch
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Datetime {
+	#[prost(int64, tag = "1")]
+	pub seconds: i64,
+	#[prost(uint32, tag = "2")]
+	pub nanos: u32,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Array {
+	#[prost(message, repeated, tag = "1")]
+	pub values: Vec<Value>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Object {
+	#[prost(btree_map = "string, message", tag = "1")]
+	pub fields: BTreeMap<String, Value>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RecordId {
+	#[prost(string, tag = "1")]
+	pub table: String,
+	#[prost(message, optional, tag = "2")]
+	pub key: Option<RecordIdKey>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RecordIdKey {
+	#[prost(oneof = "record_id_key::Kind", tags = "1, 2, 3, 4, 5, 6")]
+	pub kind: Option<record_id_key::Kind>,
+}
+
+pub mod record_id_key {
+	/// The kind of a record id key
+	#[derive(Clone, PartialEq, prost::Oneof)]
+	pub enum Kind {
+		#[prost(int64, tag = "1")]
+		Number(i64),
+		#[prost(string, tag = "2")]
+		String(String),
+		/// A UUID, as 16 bytes
+		#[prost(bytes = "vec", tag = "3")]
+		Uuid(Vec<u8>),
+		#[prost(message, tag = "4")]
+		Array(super::Array),
+		#[prost(message, tag = "5")]
+		Object(super::Object),
+		#[prost(message, tag = "6")]
+		Range(super::RecordIdKeyRange),
+	}
+}
+
+/// A range of record id keys, where a missing bound is unbounded
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RecordIdKeyRange {
+	#[prost(message, optional, tag = "1")]
+	pub start: Option<RecordIdKeyBound>,
+	#[prost(message, optional, tag = "2")]
+	pub end: Option<RecordIdKeyBound>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RecordIdKeyBound {
+	#[prost(oneof = "record_id_key_bound::Kind", tags = "1, 2")]
+	pub kind: Option<record_id_key_bound::Kind>,
+}
+
+pub mod record_id_key_bound {
+	/// The kind of a record id key bound
+	#[derive(Clone, PartialEq, prost::Oneof)]
+	pub enum Kind {
+		#[prost(message, boxed, tag = "1")]
+		Included(Box<super::RecordIdKey>),
+		#[prost(message, boxed, tag = "2")]
+		Excluded(Box<super::RecordIdKey>),
+	}
+}
+
+/// A range of values, where a missing bound is unbounded
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Range {
+	#[prost(message, optional, tag = "1")]
+	pub start: Option<Bound>,
+	#[prost(message, optional, tag = "2")]
+	pub end: Option<Bound>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Bound {
+	#[prost(oneof = "bound::Kind", tags = "1, 2")]
+	pub kind: Option<bound::Kind>,
+}
+
+pub mod bound {
+	/// The kind of a range bound
+	#[derive(Clone, PartialEq, prost::Oneof)]
+	pub enum Kind {
+		#[prost(message, boxed, tag = "1")]
+		Included(Box<super::Value>),
+		#[prost(message, boxed, tag = "2")]
+		Excluded(Box<super::Value>),
+	}
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct File {
+	#[prost(string, tag = "1")]
+	pub bucket: String,
+	#[prost(string, tag = "2")]
+	pub key: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Geometry {
+	#[prost(oneof = "geometry::Kind", tags = "1, 2, 3, 4, 5, 6, 7")]
+	pub kind: Option<geometry::Kind>,
+}
+
+pub mod geometry {
+	/// The kind of a geometry
+	#[derive(Clone, PartialEq, prost::Oneof)]
+	pub enum Kind {
+		#[prost(message, tag = "1")]
+		Point(super::Point),
+		#[prost(message, tag = "2")]
+		Line(super::LineString),
+		#[prost(message, tag = "3")]
+		Polygon(super::Polygon),
+		#[prost(message, tag = "4")]
+		MultiPoint(super::MultiPoint),
+		#[prost(message, tag = "5")]
+		MultiLine(super::MultiLineString),
+		#[prost(message, tag = "6")]
+		MultiPolygon(super::MultiPolygon),
+		#[prost(message, tag = "7")]
+		Collection(super::GeometryCollection),
+	}
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Point {
+	#[prost(double, tag = "1")]
+	pub x: f64,
+	#[prost(double, tag = "2")]
+	pub y: f64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LineString {
+	#[prost(message, repeated, tag = "1")]
+	pub points: Vec<Point>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Polygon {
+	#[prost(message, optional, tag = "1")]
+	pub exterior: Option<LineString>,
+	#[prost(message, repeated, tag = "2")]
+	pub interiors: Vec<LineString>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MultiPoint {
+	#[prost(message, repeated, tag = "1")]
+	pub points: Vec<Point>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MultiLineString {
+	#[prost(message, repeated, tag = "1")]
+	pub lines: Vec<LineString>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MultiPolygon {
+	#[prost(message, repeated, tag = "1")]
+	pub polygons: Vec<Polygon>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GeometryCollection {
+	#[prost(message, repeated, tag = "1")]
+	pub geometries: Vec<Geometry>,
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeSet;
+
+	use prost::Message;
+	use prost::bytes::Buf;
+	use prost::encoding::{DecodeContext, WireType, decode_key, skip_field};
+
+	use super::*;
+
+	/// The schema which the messages must match.
+	const SCHEMA: &str = include_str!("../../../../../server/proto/surrealdb/rpc/v1/value.proto");
+
+	type Fields = BTreeMap<String, BTreeSet<(u32, u8)>>;
+
+	/// Parse the field numbers and wire types of every message in the schema.
+	fn schema() -> Fields {
+		let mut res = Fields::new();
+		let mut message = None;
+		for line in SCHEMA.lines().map(str::trim) {
+			if let Some(name) = line.strip_prefix("message ") {
+				let name = name.trim_end_matches('{').trim().to_string();
+				res.insert(name.clone(), BTreeSet::new());
+				message = Some(name);
+				continue;
+			}
+			let Some(message) = &message else {
+				continue;
+			};
+			let Some((def, num)) = line.strip_suffix(';').and_then(|v| v.split_once(" = ")) else {
+				continue;
+			};
+			let ty = def.trim_start_matches("repeated ").split(' ').next().unwrap();
+			let wire = match ty {
+				"double" => WireType::SixtyFourBit,
+				"bool" | "int64" | "uint64" | "uint32" => WireType::Varint,
+				_ => WireType::LengthDelimited,
+			};
+			let entry = res.get_mut(message).unwrap();
+			entry.insert((num.parse().unwrap(), wire as u8));
+		}
+		res
+	}
+
+	/// Encode the messages, collecting the field numbers and wire types used.
+	fn fields<M: Message>(msgs: &[M]) -> BTreeSet<(u32, u8)> {
+		let mut res = BTreeSet::new();
+		for msg in msgs {
+			let buf = msg.encode_to_vec();
+			let mut buf = buf.as_slice();
+			while buf.has_remaining() {
+				let (tag, wire) = decode_key(&mut buf).unwrap();
+				skip_field(wire, tag, &mut buf, DecodeContext::default()).unwrap();
+				res.insert((tag, wire as u8));
+			}
+		}
+		res
+	}
+
+	#[test]
+	fn matches_schema() {
+		use self::value::Kind as V;
+		let mut encoded = Fields::new();
+		let mut add = |name: &str, fields| {
+			encoded.insert(name.to_string(), fields);
+		};
+		let value = |kind| Value {
+			kind: Some(kind),
+		};
+		add(
+			"Value",
+			fields(&[
+				value(V::None(true)),
+				value(V::Null(true)),
+				value(V::Bool(true)),
+				value(V::Int(1)),
+				value(V::Float(1.0)),
+				value(V::Decimal("1".into())),
+				value(V::String("a".into())),
+				value(V::Bytes(vec![1])),
+				value(V::Duration(Duration::default())),
+				value(V::Datetime(Datetime::default())),
+				value(V::Uuid(vec![1])),
+				value(V::Array(Array::default())),
+				value(V::Object(Object::default())),
+				value(V::Table("a".into())),
+				value(V::RecordId(RecordId::default())),
+				value(V::Geometry(Geometry::default())),
+				value(V::Range(Range::default())),
+				value(V::File(File::default())),
+				value(V::Set(Array::default())),
+				value(V::Regex("a".into())),
+			]),
+		);
+		add(
+			"Duration",
+			fields(&[Duration {
+				seconds: 1,
+				nanos: 1,
+			}]),
+		);
+		add(
+			"Datetime",
+			fields(&[Datetime {
+				seconds: 1,
+				nanos: 1,
+			}]),
+		);
+		add(
+			"Array",
+			fields(&[Array {
+				values: vec![Value::default()],
+			}]),
+		);
+		add(
+			"Object",
+			fields(&[Object {
+				fields: BTreeMap::from([("a".to_string(), Value::default())]),
+			}]),
+		);
+		add(
+			"RecordId",
+			fields(&[RecordId {
+				table: "a".into(),
+				key: Some(RecordIdKey::default()),
+			}]),
+		);
+		let key = |kind| RecordIdKey {
+			kind: Some(kind),
+		};
+		add(
+			"RecordIdKey",
+			fields(&[
+				key(record_id_key::Kind::Number(1)),
+				key(record_id_key::Kind::String("a".into())),
+				key(record_id_key::Kind::Uuid(vec![1])),
+				key(record_id_key::Kind::Array(Array::default())),
+				key(record_id_key::Kind::Object(Object::default())),
+				key(record_id_key::Kind::Range(RecordIdKeyRange::default())),
+			]),
+		);
+		add(
+			"RecordIdKeyRange",
+			fields(&[RecordIdKeyRange {
+				start: Some(RecordIdKeyBound::default()),
+				end: Some(RecordIdKeyBound::default()),
+			}]),
+		);
+		let key_bound = |kind| RecordIdKeyBound {
+			kind: Some(kind),
+		};
+		add(
+			"RecordIdKeyBound",
+			fields(&[
+				key_bound(record_id_key_bound::Kind::Included(Box::default())),
+				key_bound(record_id_key_bound::Kind::Excluded(Box::default())),
+			]),
+		);
+		add(
+			"Range",
+			fields(&[Range {
+				start: Some(Bound::default()),
+				end: Some(Bound::default()),
+			}]),
+		);
+		let bound = |kind| Bound {
+			kind: Some(kind),
+		};
+		add(
+			"Bound",
+			fields(&[
+				bound(bound::Kind::Included(Box::default())),
+				bound(bound::Kind::Excluded(Box::default())),
+			]),
+		);
+		add(
+			"File",
+			fields(&[File {
+				bucket: "a".into(),
+				key: "a".into(),
+			}]),
+		);
+		let geometry = |kind| Geometry {
+			kind: Some(kind),
+		};
+		add(
+			"Geometry",
+			fields(&[
+				geometry(geometry::Kind::Point(Point::default())),
+				geometry(geometry::Kind::Line(LineString::default())),
+				geometry(geometry::Kind::Polygon(Polygon::default())),
+				geometry(geometry::Kind::MultiPoint(MultiPoint::default())),
+				geometry(geometry::Kind::MultiLine(MultiLineString::default())),
+				geometry(geometry::Kind::MultiPolygon(MultiPolygon::default())),
+				geometry(geometry::Kind::Collection(GeometryCollection::default())),
+			]),
+		);
+		add(
+			"Point",
+			fields(&[Point {
+				x: 1.0,
+				y: 1.0,
+			}]),
+		);
+		add(
+			"LineString",
+			fields(&[LineString {
+				points: vec![Point::default()],
+			}]),
+		);
+		add(
+			"Polygon",
+			fields(&[Polygon {
+				exterior: Some(LineString::default()),
+				interiors: vec![LineString::default()],
+			}]),
+		);
+		add(
+			"MultiPoint",
+			fields(&[MultiPoint {
+				points: vec![Point::default()],
+			}]),
+		);
+		add(
+			"MultiLineString",
+			fields(&[MultiLineString {
+				lines: vec![LineString::default()],
+			}]),
+		);
+		add(
+			"MultiPolygon",
+			fields(&[MultiPolygon {
+				polygons: vec![Polygon::default()],
+			}]),
+		);
+		add(
+			"GeometryCollection",
+			fields(&[GeometryCollection {
+				geometries: vec![Geometry::default()],
+			}]),
+		);
+		assert_eq!(encoded, schema());
+	}
+}
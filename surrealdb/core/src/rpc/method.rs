@@ -14,6 +14,9 @@ pub enum Method {
 	Reset,
 	Kill,
 	Live,
+	Changes,
+	Ack,
+	RemoveGroup,
 	Set,
 	Unset,
 	Select,
@@ -72,6 +75,9 @@ impl Method {
 			"reset" => Self::Reset,
 			"kill" => Self::Kill,
 			"live" => Self::Live,
+			"changes" => Self::Changes,
+			"ack" => Self::Ack,
+			"remove_group" => Self::RemoveGroup,
 			"set" | "let" => Self::Set,
 			"unset" => Self::Unset,
 			"select" => Self::Select,
@@ -114,6 +120,9 @@ impl Method {
 			Self::Reset => "reset",
 			Self::Kill => "kill",
 			Self::Live => "live",
+			Self::Changes => "changes",
+			Self::Ack => "ack",
+			Self::RemoveGroup => "remove_group",
 			Self::Set => "set",
 			Self::Unset => "unset",
 			Self::Select => "select",
@@ -9,7 +9,7 @@ use crate::catalog::providers::{CatalogProvider, NamespaceProvider, RootProvider
 use crate::dbs::capabilities::{ExperimentalTarget, MethodTarget};
 use crate::dbs::{QueryResult, QueryType, Session};
 use crate::iam::token::Token;
-use crate::kvs::{Datastore, LockType, TransactionType};
+use crate::kvs::{ChangeStream, Datastore, LockType, TransactionType};
 use crate::rpc::args::extract_args;
 use crate::rpc::{
 	DbResult, Method, bad_lq_config, invalid_params, method_not_allowed, method_not_found,
@@ -162,6 +162,25 @@ pub trait RpcProtocol {
 		async { unimplemented!("handle_kill function must be implemented if LQ_SUPPORT = true") }
 	}
 
+	/// Change data capture streams are disabled by default
+	const CDC_SUPPORT: bool = false;
+
+	/// Handles the delivery of a change data capture stream, failing if the
+	/// id of the stream is already in use
+	fn handle_changes(
+		&self,
+		_id: &Uuid,
+		_session_id: Option<Uuid>,
+		_stream: ChangeStream,
+	) -> impl std::future::Future<Output = Result<(), surrealdb_types::Error>> + Send {
+		async { unimplemented!("handle_changes function must be implemented if CDC_SUPPORT = true") }
+	}
+	/// Handles the cancellation of a change data capture stream, returning
+	/// whether the stream was delivered on this connection
+	fn handle_kill_changes(&self, _id: &Uuid) -> impl std::future::Future<Output = bool> + Send {
+		async { false }
+	}
+
 	/// Handles the cleanup of live queries
 	fn cleanup_lqs(
 		&self,
@@ -204,6 +223,9 @@ pub trait RpcProtocol {
 			Method::Reset => self.reset(session).await,
 			Method::Kill => self.kill(txn, session, params).await,
 			Method::Live => self.live(txn, session, params).await,
+			Method::Changes => self.changes(session, params).await,
+			Method::Ack => self.ack(session, params).await,
+			Method::RemoveGroup => self.remove_group(session, params).await,
 			Method::Set => self.set(session, params).await,
 			Method::Unset => self.unset(session, params).await,
 			Method::Query => self.query(txn, session, params).await,
@@ -689,6 +711,12 @@ pub trait RpcProtocol {
 		// Process the method arguments
 		let (id,) = extract_args::<(PublicValue,)>(params.into_vec())
 			.ok_or(invalid_params("Expected (id)".to_string()))?;
+		// Stop the change data capture stream with this id
+		if let PublicValue::Uuid(stream) = &id
+			&& self.handle_kill_changes(stream).await
+		{
+			return Ok(DbResult::Other(PublicValue::None));
+		}
 
 		// Specify the SQL query string
 		let ast = Ast {
@@ -756,6 +784,122 @@ pub trait RpcProtocol {
 		Ok(DbResult::Other(first))
 	}
 
+	// ------------------------------
+	// Methods for change data capture
+	// ------------------------------
+
+	async fn changes(
+		&self,
+		session_id: Option<Uuid>,
+		params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		// Change sets are pushed over realtime connections
+		if !Self::CDC_SUPPORT {
+			return Err(method_not_allowed(Method::Changes.to_string()));
+		}
+		let session_lock = self.get_session(&session_id)?;
+		let session = session_lock.read().await;
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(session.au.as_ref()) {
+			return Err(method_not_allowed(Method::Changes.to_string()));
+		}
+		// Process the method arguments
+		let Some((table, PublicValue::String(group), id)) =
+			extract_args::<(PublicValue, PublicValue, Option<PublicValue>)>(params.into_vec())
+		else {
+			return Err(invalid_params("Expected (table, group:string, id:uuid)".to_string()));
+		};
+		let table = match table {
+			PublicValue::String(x) => x,
+			PublicValue::Table(x) => x.into_string(),
+			_ => return Err(invalid_params("Expected (table, group:string, id:uuid)".to_string())),
+		};
+		// The client can choose the id, to receive change sets before this method returns
+		let id = match id {
+			None | Some(PublicValue::None) => Uuid::new_v4(),
+			Some(PublicValue::Uuid(id)) => id.into_inner(),
+			_ => return Err(invalid_params("Expected (table, group:string, id:uuid)".to_string())),
+		};
+		// Stream the changefeed of the table to the consumer group
+		let stream =
+			self.kvs().changes(&session, &table, &group).await.map_err(types_error_from_anyhow)?;
+		// Deliver the change sets on this connection
+		self.handle_changes(&id, session_id, stream).await?;
+		// Return the id of the change stream
+		Ok(DbResult::Other(PublicValue::Uuid(PublicUuid::from(id))))
+	}
+
+	async fn ack(
+		&self,
+		session_id: Option<Uuid>,
+		params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		let session_lock = self.get_session(&session_id)?;
+		let session = session_lock.read().await;
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(session.au.as_ref()) {
+			return Err(method_not_allowed(Method::Ack.to_string()));
+		}
+		// Process the method arguments
+		let Some((table, PublicValue::String(group), PublicValue::Number(versionstamp))) =
+			extract_args::<(PublicValue, PublicValue, PublicValue)>(params.into_vec())
+		else {
+			return Err(invalid_params(
+				"Expected (table, group:string, versionstamp:number)".to_string(),
+			));
+		};
+		let table = match table {
+			PublicValue::String(x) => x,
+			PublicValue::Table(x) => x.into_string(),
+			_ => {
+				return Err(invalid_params(
+					"Expected (table, group:string, versionstamp:number)".to_string(),
+				));
+			}
+		};
+		let Some(versionstamp) = versionstamp.to_int().and_then(|x| u64::try_from(x).ok()) else {
+			return Err(invalid_params("Expected a positive integer versionstamp".to_string()));
+		};
+		// Store the offset of the consumer group
+		self.kvs()
+			.ack_changes(&session, &table, &group, versionstamp)
+			.await
+			.map_err(types_error_from_anyhow)?;
+		// Return nothing
+		Ok(DbResult::Other(PublicValue::None))
+	}
+
+	async fn remove_group(
+		&self,
+		session_id: Option<Uuid>,
+		params: PublicArray,
+	) -> Result<DbResult, surrealdb_types::Error> {
+		let session_lock = self.get_session(&session_id)?;
+		let session = session_lock.read().await;
+		// Check if the user is allowed to query
+		if !self.kvs().allows_query_by_subject(session.au.as_ref()) {
+			return Err(method_not_allowed(Method::RemoveGroup.to_string()));
+		}
+		// Process the method arguments
+		let Some((table, PublicValue::String(group))) =
+			extract_args::<(PublicValue, PublicValue)>(params.into_vec())
+		else {
+			return Err(invalid_params("Expected (table, group:string)".to_string()));
+		};
+		let table = match table {
+			PublicValue::String(x) => x,
+			PublicValue::Table(x) => x.into_string(),
+			_ => return Err(invalid_params("Expected (table, group:string)".to_string())),
+		};
+		// Remove the consumer group
+		self.kvs()
+			.remove_consumer_group(&session, &table, &group)
+			.await
+			.map_err(types_error_from_anyhow)?;
+		// Return nothing
+		Ok(DbResult::Other(PublicValue::None))
+	}
+
 	// ------------------------------
 	// Methods for selecting
 	// ------------------------------
@@ -122,6 +122,8 @@ impl SurrealValue for DbResult {
 						"CREATE" => crate::types::PublicAction::Create,
 						"UPDATE" => crate::types::PublicAction::Update,
 						"DELETE" => crate::types::PublicAction::Delete,
+						"KILLED" => crate::types::PublicAction::Killed,
+						"CHANGES" => crate::types::PublicAction::Changes,
 						_ => {
 							return Err(TypesError::internal(format!(
 								"Invalid action: {}",
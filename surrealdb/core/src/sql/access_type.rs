@@ -146,6 +146,24 @@ impl ToSql for JwtAccess {
 			JwtAccessVerify::Jwks(v) => {
 				write_sql!(f, sql_fmt, "URL {}", CoverStmts(&v.url));
 			}
+			JwtAccessVerify::Oidc(v) => {
+				write_sql!(f, sql_fmt, "OPENID {}", CoverStmts(&v.issuer));
+				if let Some(ref aud) = v.audience {
+					write_sql!(f, sql_fmt, " AUDIENCE {}", CoverStmts(aud));
+				}
+				if let Some(ref roles) = v.roles {
+					write_sql!(f, sql_fmt, " ROLES {}", CoverStmts(roles));
+				}
+				if let Some(ref int) = v.introspect {
+					write_sql!(
+						f,
+						sql_fmt,
+						" INTROSPECT CLIENT {} SECRET {}",
+						CoverStmts(&int.client),
+						CoverStmts(&int.secret)
+					);
+				}
+			}
 		}
 		if let Some(iss) = &self.issue {
 			write_sql!(f, sql_fmt, " WITH ISSUER KEY {}", CoverStmts(&iss.key));
@@ -213,6 +231,7 @@ impl From<crate::expr::access_type::JwtAccessIssue> for JwtAccessIssue {
 pub(crate) enum JwtAccessVerify {
 	Key(JwtAccessVerifyKey),
 	Jwks(JwtAccessVerifyJwks),
+	Oidc(JwtAccessVerifyOidc),
 }
 
 impl From<JwtAccessVerify> for crate::expr::access_type::JwtAccessVerify {
@@ -220,6 +239,7 @@ impl From<JwtAccessVerify> for crate::expr::access_type::JwtAccessVerify {
 		match v {
 			JwtAccessVerify::Key(v) => Self::Key(v.into()),
 			JwtAccessVerify::Jwks(v) => Self::Jwks(v.into()),
+			JwtAccessVerify::Oidc(v) => Self::Oidc(v.into()),
 		}
 	}
 }
@@ -229,6 +249,7 @@ impl From<crate::expr::access_type::JwtAccessVerify> for JwtAccessVerify {
 		match v {
 			crate::expr::access_type::JwtAccessVerify::Key(v) => Self::Key(v.into()),
 			crate::expr::access_type::JwtAccessVerify::Jwks(v) => Self::Jwks(v.into()),
+			crate::expr::access_type::JwtAccessVerify::Oidc(v) => Self::Oidc(v.into()),
 		}
 	}
 }
@@ -280,6 +301,62 @@ impl From<crate::expr::access_type::JwtAccessVerifyJwks> for JwtAccessVerifyJwks
 	}
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct JwtAccessVerifyOidc {
+	pub issuer: Expr,
+	pub audience: Option<Expr>,
+	pub roles: Option<Expr>,
+	pub introspect: Option<JwtAccessIntrospect>,
+}
+
+impl From<JwtAccessVerifyOidc> for crate::expr::access_type::JwtAccessVerifyOidc {
+	fn from(v: JwtAccessVerifyOidc) -> Self {
+		Self {
+			issuer: v.issuer.into(),
+			audience: v.audience.map(Into::into),
+			roles: v.roles.map(Into::into),
+			introspect: v.introspect.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::expr::access_type::JwtAccessVerifyOidc> for JwtAccessVerifyOidc {
+	fn from(v: crate::expr::access_type::JwtAccessVerifyOidc) -> Self {
+		Self {
+			issuer: v.issuer.into(),
+			audience: v.audience.map(Into::into),
+			roles: v.roles.map(Into::into),
+			introspect: v.introspect.map(Into::into),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct JwtAccessIntrospect {
+	pub client: Expr,
+	pub secret: Expr,
+}
+
+impl From<JwtAccessIntrospect> for crate::expr::access_type::JwtAccessIntrospect {
+	fn from(v: JwtAccessIntrospect) -> Self {
+		Self {
+			client: v.client.into(),
+			secret: v.secret.into(),
+		}
+	}
+}
+
+impl From<crate::expr::access_type::JwtAccessIntrospect> for JwtAccessIntrospect {
+	fn from(v: crate::expr::access_type::JwtAccessIntrospect) -> Self {
+		Self {
+			client: v.client.into(),
+			secret: v.secret.into(),
+		}
+	}
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub(crate) struct RecordAccess {
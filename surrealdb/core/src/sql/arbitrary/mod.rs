@@ -11,6 +11,7 @@ use rust_decimal::Decimal;
 use surrealdb_types::Duration;
 pub(crate) use utils::*;
 
+use crate::sql::RateLimit;
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::statements::SleepStatement;
 use crate::val::Bytes;
@@ -24,6 +25,21 @@ impl<'a> Arbitrary<'a> for ChangeFeed {
 	}
 }
 
+impl<'a> Arbitrary<'a> for RateLimit {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let mut res = Self {
+			rate: u.arbitrary()?,
+			concurrency: u.arbitrary()?,
+			timeout: u.arbitrary()?,
+		};
+		// A limit clause must contain at least one limit
+		if res.rate.is_none() && res.concurrency.is_none() && res.timeout.is_none() {
+			res.rate = Some(u.arbitrary()?);
+		}
+		Ok(res)
+	}
+}
+
 impl<'a> Arbitrary<'a> for SleepStatement {
 	fn arbitrary(_u: &mut Unstructured<'a>) -> Result<Self> {
 		Ok(Self {
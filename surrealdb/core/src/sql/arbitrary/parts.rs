@@ -8,6 +8,7 @@ use crate::sql::access_type::{JwtAccess, JwtAccessIssue, JwtAccessVerify};
 use crate::sql::arbitrary::idiom::plain_idiom;
 use crate::sql::arbitrary::{arb_vec1, arb_vec2, atleast_one, basic_idiom};
 use crate::sql::field::Selector;
+use crate::sql::kind::KindLiteral;
 use crate::sql::order::{OrderList, Ordering};
 use crate::sql::statements::access::Subject;
 use crate::sql::statements::define::config::api::Middleware;
@@ -356,6 +357,15 @@ pub fn either_kind<'a>(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result
 	Ok(k)
 }
 
+/// Generates an optional object literal kind, as used by the `PARAMS` and
+/// `QUERY` clauses of an API action.
+pub fn object_kind<'a>(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Option<Kind>> {
+	if !u.arbitrary()? {
+		return Ok(None);
+	}
+	Ok(Some(Kind::Literal(KindLiteral::Object(u.arbitrary()?))))
+}
+
 pub fn arb_splits<'a>(
 	u: &mut arbitrary::Unstructured<'a>,
 	expr: &mut Fields,
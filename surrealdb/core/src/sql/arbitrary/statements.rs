@@ -66,6 +66,7 @@ impl<'a> Arbitrary<'a> for DefineAccessStatement {
 			access_type,
 			authenticate,
 			duration,
+			limit: u.arbitrary()?,
 			comment,
 		})
 	}
@@ -103,6 +104,8 @@ impl<'a> arbitrary::Arbitrary<'a> for DefineUserStatement {
 			token_duration: u.arbitrary()?,
 			session_duration: u.arbitrary()?,
 			roles,
+			totp: u.arbitrary()?,
+			limit: u.arbitrary()?,
 			comment,
 		})
 	}
@@ -318,6 +321,7 @@ impl<'a> arbitrary::Arbitrary<'a> for AlterSystemStatement {
 		Ok(AlterSystemStatement {
 			query_timeout,
 			compact: u.arbitrary()?,
+			checkpoint: u.arbitrary()?,
 		})
 	}
 }
@@ -35,6 +35,7 @@ pub(crate) mod output;
 pub(crate) mod param;
 pub(crate) mod part;
 pub(crate) mod permission;
+pub(crate) mod rate_limit;
 pub(crate) mod record_id;
 pub(crate) mod reference;
 pub(crate) mod scoring;
@@ -92,6 +93,7 @@ pub(crate) use self::output::Output;
 pub(crate) use self::param::Param;
 pub(crate) use self::part::Part;
 pub(crate) use self::permission::{Permission, Permissions};
+pub(crate) use self::rate_limit::RateLimit;
 pub(crate) use self::record_id::{
 	RecordIdKeyGen, RecordIdKeyLit, RecordIdKeyRangeLit, RecordIdLit,
 };
@@ -0,0 +1,47 @@
+use surrealdb_types::{SqlFormat, ToSql};
+
+use crate::types::PublicDuration;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct RateLimit {
+	pub rate: Option<u32>,
+	pub concurrency: Option<u32>,
+	pub timeout: Option<PublicDuration>,
+}
+
+impl ToSql for RateLimit {
+	fn fmt_sql(&self, f: &mut String, _fmt: SqlFormat) {
+		let mut clauses = Vec::new();
+		if let Some(v) = self.rate {
+			clauses.push(format!("RATE {v}"));
+		}
+		if let Some(v) = self.concurrency {
+			clauses.push(format!("CONCURRENCY {v}"));
+		}
+		if let Some(ref v) = self.timeout {
+			clauses.push(format!("TIMEOUT {}", v.to_sql()));
+		}
+		f.push_str("LIMIT ");
+		f.push_str(&clauses.join(", "));
+	}
+}
+
+impl From<RateLimit> for crate::catalog::RateLimit {
+	fn from(v: RateLimit) -> Self {
+		crate::catalog::RateLimit {
+			rate: v.rate,
+			concurrency: v.concurrency,
+			timeout: v.timeout.map(Into::into),
+		}
+	}
+}
+
+impl From<crate::catalog::RateLimit> for RateLimit {
+	fn from(v: crate::catalog::RateLimit) -> Self {
+		RateLimit {
+			rate: v.rate,
+			concurrency: v.concurrency,
+			timeout: v.timeout.map(PublicDuration::from),
+		}
+	}
+}
@@ -10,11 +10,14 @@ use crate::sql::statements::alter::AlterKind;
 /// - `QUERY_TIMEOUT <duration>`: sets the global query timeout
 /// - `DROP QUERY_TIMEOUT`: clears the global query timeout
 /// - `COMPACT`: requests datastore‑wide compaction
+/// - `CHECKPOINT <path>`: writes an on-disk checkpoint of the datastore
 pub struct AlterSystemStatement {
 	/// Tri‑state alteration for the `QUERY_TIMEOUT` parameter.
 	pub query_timeout: AlterKind<Expr>,
 	/// When true, emits `COMPACT`.
 	pub compact: bool,
+	/// Optional directory for a datastore checkpoint.
+	pub checkpoint: Option<Expr>,
 }
 
 impl ToSql for AlterSystemStatement {
@@ -32,6 +35,9 @@ impl ToSql for AlterSystemStatement {
 		if self.compact {
 			write_sql!(f, fmt, " COMPACT");
 		}
+		if let Some(path) = &self.checkpoint {
+			write_sql!(f, fmt, " CHECKPOINT {}", path);
+		}
 	}
 }
 
@@ -40,6 +46,7 @@ impl From<AlterSystemStatement> for crate::expr::statements::alter::AlterSystemS
 		crate::expr::statements::alter::AlterSystemStatement {
 			query_timeout: v.query_timeout.into(),
 			compact: v.compact,
+			checkpoint: v.checkpoint.map(Into::into),
 		}
 	}
 }
@@ -48,6 +55,7 @@ impl From<crate::expr::statements::alter::AlterSystemStatement> for AlterSystemS
 		AlterSystemStatement {
 			query_timeout: v.query_timeout.into(),
 			compact: v.compact,
+			checkpoint: v.checkpoint.map(Into::into),
 		}
 	}
 }
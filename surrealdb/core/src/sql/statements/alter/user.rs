@@ -20,6 +20,7 @@ pub struct AlterUserStatement {
 	pub roles: AlterKind<Vec<String>>,
 	pub token_duration: AlterKind<PublicDuration>,
 	pub session_duration: AlterKind<PublicDuration>,
+	pub totp: AlterKind<String>,
 	pub comment: AlterKind<String>,
 }
 
@@ -61,6 +62,12 @@ impl ToSql for AlterUserStatement {
 			AlterKind::None => {}
 		}
 
+		match self.totp {
+			AlterKind::Set(ref v) => write_sql!(f, fmt, " TOTP {}", QuoteStr(v)),
+			AlterKind::Drop => f.push_str(" DROP TOTP"),
+			AlterKind::None => {}
+		}
+
 		match self.comment {
 			AlterKind::Set(ref v) => write_sql!(f, fmt, " COMMENT {}", QuoteStr(v)),
 			AlterKind::Drop => f.push_str(" DROP COMMENT"),
@@ -102,6 +109,7 @@ impl From<AlterUserStatement> for crate::expr::statements::alter::AlterUserState
 				AlterKind::Drop => crate::expr::statements::alter::AlterKind::Set(None),
 				AlterKind::None => crate::expr::statements::alter::AlterKind::None,
 			},
+			totp: v.totp.into(),
 			comment: v.comment.into(),
 		}
 	}
@@ -135,6 +143,7 @@ impl From<crate::expr::statements::alter::AlterUserStatement> for AlterUserState
 				crate::expr::statements::alter::AlterKind::Drop => AlterKind::Drop,
 				crate::expr::statements::alter::AlterKind::None => AlterKind::None,
 			},
+			totp: v.totp.into(),
 			comment: v.comment.into(),
 		}
 	}
@@ -3,7 +3,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 use super::DefineKind;
 use crate::fmt::CoverStmts;
 use crate::sql::access::AccessDuration;
-use crate::sql::{AccessType, Base, Expr, Literal};
+use crate::sql::{AccessType, Base, Expr, Literal, RateLimit};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub(crate) struct DefineAccessStatement {
@@ -13,6 +13,7 @@ pub(crate) struct DefineAccessStatement {
 	pub access_type: AccessType,
 	pub authenticate: Option<Expr>,
 	pub duration: AccessDuration,
+	pub limit: Option<RateLimit>,
 	pub comment: Expr,
 }
 
@@ -53,6 +54,10 @@ impl ToSql for DefineAccessStatement {
 		}
 
 		write_sql!(f, fmt, " FOR SESSION {}", CoverStmts(&self.duration.session));
+		// The request limits for this access method
+		if let Some(ref v) = self.limit {
+			write_sql!(f, fmt, " {}", v);
+		}
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
@@ -68,6 +73,7 @@ impl From<DefineAccessStatement> for crate::expr::statements::DefineAccessStatem
 			access_type: v.access_type.into(),
 			authenticate: v.authenticate.map(Into::into),
 			duration: v.duration.into(),
+			limit: v.limit.map(Into::into),
 			comment: v.comment.into(),
 		}
 	}
@@ -82,6 +88,7 @@ impl From<crate::expr::statements::DefineAccessStatement> for DefineAccessStatem
 			access_type: v.access_type.into(),
 			authenticate: v.authenticate.map(Into::into),
 			duration: v.duration.into(),
+			limit: v.limit.map(Into::into),
 			comment: v.comment.into(),
 		}
 	}
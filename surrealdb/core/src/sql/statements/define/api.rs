@@ -4,7 +4,7 @@ use super::DefineKind;
 use super::config::api::ApiConfig;
 use crate::catalog::ApiMethod;
 use crate::fmt::{CoverStmts, Fmt};
-use crate::sql::{Expr, Literal};
+use crate::sql::{Expr, Kind, Literal};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -95,6 +95,11 @@ pub(crate) struct ApiAction {
 	pub methods: Vec<ApiMethod>,
 	pub action: Expr,
 	pub config: ApiConfig,
+	#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::sql::arbitrary::object_kind))]
+	pub params: Option<Kind>,
+	#[cfg_attr(feature = "arbitrary", arbitrary(with = crate::sql::arbitrary::object_kind))]
+	pub query: Option<Kind>,
+	pub body: Option<Kind>,
 }
 
 impl ToSql for ApiAction {
@@ -106,6 +111,15 @@ impl ToSql for ApiAction {
 			let inner_fmt = sql_fmt.increment();
 			inner_fmt.write_indent(f);
 		}
+		if let Some(v) = &self.params {
+			write_sql!(f, sql_fmt, " PARAMS {}", v);
+		}
+		if let Some(v) = &self.query {
+			write_sql!(f, sql_fmt, " QUERY {}", v);
+		}
+		if let Some(v) = &self.body {
+			write_sql!(f, sql_fmt, " BODY {}", v);
+		}
 		write_sql!(f, sql_fmt, "{} THEN {}", self.config, self.action);
 	}
 }
@@ -116,6 +130,9 @@ impl From<ApiAction> for crate::expr::statements::define::ApiAction {
 			methods: v.methods,
 			action: v.action.into(),
 			config: v.config.into(),
+			params: v.params.map(Into::into),
+			query: v.query.map(Into::into),
+			body: v.body.map(Into::into),
 		}
 	}
 }
@@ -126,6 +143,9 @@ impl From<crate::expr::statements::define::ApiAction> for ApiAction {
 			methods: v.methods,
 			action: v.action.into(),
 			config: v.config.into(),
+			params: v.params.map(Into::into),
+			query: v.query.map(Into::into),
+			body: v.body.map(Into::into),
 		}
 	}
 }
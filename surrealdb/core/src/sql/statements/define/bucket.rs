@@ -1,8 +1,43 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
-use crate::fmt::CoverStmts;
+use crate::fmt::{CoverStmts, QuoteStr};
 use crate::sql::{Expr, Literal, Permission};
+use crate::types::PublicDuration;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub(crate) struct BucketLifecycleRule {
+	pub expire: PublicDuration,
+	pub prefix: Option<String>,
+}
+
+impl ToSql for BucketLifecycleRule {
+	fn fmt_sql(&self, f: &mut String, sql_fmt: SqlFormat) {
+		write_sql!(f, sql_fmt, "EXPIRE {}", self.expire);
+		if let Some(ref prefix) = self.prefix {
+			write_sql!(f, sql_fmt, " PREFIX {}", QuoteStr(prefix));
+		}
+	}
+}
+
+impl From<BucketLifecycleRule> for crate::catalog::BucketLifecycleRule {
+	fn from(v: BucketLifecycleRule) -> Self {
+		crate::catalog::BucketLifecycleRule {
+			expire: v.expire.into(),
+			prefix: v.prefix,
+		}
+	}
+}
+
+impl From<crate::catalog::BucketLifecycleRule> for BucketLifecycleRule {
+	fn from(v: crate::catalog::BucketLifecycleRule) -> Self {
+		BucketLifecycleRule {
+			expire: PublicDuration::from(v.expire),
+			prefix: v.prefix,
+		}
+	}
+}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -12,6 +47,7 @@ pub(crate) struct DefineBucketStatement {
 	pub backend: Option<Expr>,
 	pub permissions: Permission,
 	pub readonly: bool,
+	pub lifecycle: Vec<BucketLifecycleRule>,
 	pub comment: Expr,
 }
 
@@ -23,6 +59,7 @@ impl Default for DefineBucketStatement {
 			backend: None,
 			permissions: Permission::default(),
 			readonly: false,
+			lifecycle: Vec::new(),
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -46,6 +83,10 @@ impl ToSql for DefineBucketStatement {
 			write_sql!(f, sql_fmt, " BACKEND {}", CoverStmts(backend));
 		}
 
+		for rule in &self.lifecycle {
+			write_sql!(f, sql_fmt, " {}", rule);
+		}
+
 		write_sql!(f, sql_fmt, " PERMISSIONS {}", self.permissions);
 
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
@@ -62,6 +103,7 @@ impl From<DefineBucketStatement> for crate::expr::statements::define::DefineBuck
 			backend: v.backend.map(Into::into),
 			permissions: v.permissions.into(),
 			readonly: v.readonly,
+			lifecycle: v.lifecycle.into_iter().map(Into::into).collect(),
 			comment: v.comment.into(),
 		}
 	}
@@ -75,6 +117,7 @@ impl From<crate::expr::statements::define::DefineBucketStatement> for DefineBuck
 			backend: v.backend.map(Into::into),
 			permissions: v.permissions.into(),
 			readonly: v.readonly,
+			lifecycle: v.lifecycle.into_iter().map(Into::into).collect(),
 			comment: v.comment.into(),
 		}
 	}
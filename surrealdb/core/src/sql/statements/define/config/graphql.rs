@@ -1,6 +1,6 @@
-use surrealdb_types::{SqlFormat, ToSql};
+use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
-use crate::fmt::EscapeKwFreeIdent;
+use crate::fmt::{EscapeKwFreeIdent, QuoteStr};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -10,6 +10,8 @@ pub struct GraphQLConfig {
 	pub depth_limit: Option<u32>,
 	pub complexity_limit: Option<u32>,
 	pub introspection: IntrospectionConfig,
+	pub federation: FederationConfig,
+	pub persisted_queries: Vec<String>,
 }
 
 impl From<GraphQLConfig> for crate::catalog::GraphQLConfig {
@@ -20,6 +22,12 @@ impl From<GraphQLConfig> for crate::catalog::GraphQLConfig {
 			depth_limit: v.depth_limit,
 			complexity_limit: v.complexity_limit,
 			introspection: v.introspection.into(),
+			federation: v.federation.into(),
+			persisted_queries: v
+				.persisted_queries
+				.into_iter()
+				.map(|q| (crate::catalog::persisted_query_hash(&q), q))
+				.collect(),
 		}
 	}
 }
@@ -32,6 +40,8 @@ impl From<crate::catalog::GraphQLConfig> for GraphQLConfig {
 			depth_limit: v.depth_limit,
 			complexity_limit: v.complexity_limit,
 			introspection: v.introspection.into(),
+			federation: v.federation.into(),
+			persisted_queries: v.persisted_queries.into_values().collect(),
 		}
 	}
 }
@@ -151,6 +161,48 @@ impl ToSql for IntrospectionConfig {
 	}
 }
 
+/// Controls whether the GraphQL schema is exposed as an Apollo Federation v2 subgraph.
+///
+/// When set to `Auto`, each table type is marked with `@key(fields: "id")` and the
+/// `_service` and `_entities` fields are added to the Query type, so that the
+/// database can be composed into a federated supergraph.
+///
+/// Defaults to `None` (federation disabled).
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FederationConfig {
+	#[default]
+	None,
+	Auto,
+}
+
+impl From<FederationConfig> for crate::catalog::GraphQLFederationConfig {
+	fn from(v: FederationConfig) -> Self {
+		match v {
+			FederationConfig::None => Self::None,
+			FederationConfig::Auto => Self::Auto,
+		}
+	}
+}
+
+impl From<crate::catalog::GraphQLFederationConfig> for FederationConfig {
+	fn from(v: crate::catalog::GraphQLFederationConfig) -> Self {
+		match v {
+			crate::catalog::GraphQLFederationConfig::None => Self::None,
+			crate::catalog::GraphQLFederationConfig::Auto => Self::Auto,
+		}
+	}
+}
+
+impl ToSql for FederationConfig {
+	fn fmt_sql(&self, f: &mut String, _fmt: SqlFormat) {
+		match self {
+			FederationConfig::None => f.push_str("NONE"),
+			FederationConfig::Auto => f.push_str("AUTO"),
+		}
+	}
+}
+
 impl ToSql for GraphQLConfig {
 	fn fmt_sql(&self, f: &mut String, fmt: SqlFormat) {
 		f.push_str("GRAPHQL");
@@ -169,6 +221,21 @@ impl ToSql for GraphQLConfig {
 			f.push_str(" INTROSPECTION ");
 			self.introspection.fmt_sql(f, fmt);
 		}
+		// Only emit FEDERATION clause when it differs from the default (NONE)
+		if matches!(self.federation, FederationConfig::Auto) {
+			f.push_str(" FEDERATION ");
+			self.federation.fmt_sql(f, fmt);
+		}
+		if !self.persisted_queries.is_empty() {
+			f.push_str(" PERSISTED [");
+			for (i, query) in self.persisted_queries.iter().enumerate() {
+				if i > 0 {
+					f.push_str(", ");
+				}
+				write_sql!(f, fmt, "{}", QuoteStr(query));
+			}
+			f.push(']');
+		}
 	}
 }
 
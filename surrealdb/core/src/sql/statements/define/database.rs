@@ -4,6 +4,7 @@ use super::DefineKind;
 use crate::fmt::CoverStmts;
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::{Expr, Literal};
+use crate::types::PublicDuration;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -14,6 +15,7 @@ pub(crate) struct DefineDatabaseStatement {
 	pub strict: bool,
 	pub comment: Expr,
 	pub changefeed: Option<ChangeFeed>,
+	pub history: Option<PublicDuration>,
 }
 
 impl Default for DefineDatabaseStatement {
@@ -24,6 +26,7 @@ impl Default for DefineDatabaseStatement {
 			name: Expr::Literal(Literal::None),
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
+			history: None,
 			strict: false,
 		}
 	}
@@ -47,6 +50,9 @@ impl ToSql for DefineDatabaseStatement {
 		if let Some(ref v) = self.changefeed {
 			write_sql!(f, sql_fmt, " {v}");
 		}
+		if let Some(ref v) = self.history {
+			write_sql!(f, sql_fmt, " HISTORY RETENTION {v}");
+		}
 	}
 }
 
@@ -58,6 +64,7 @@ impl From<DefineDatabaseStatement> for crate::expr::statements::DefineDatabaseSt
 			name: v.name.into(),
 			comment: v.comment.into(),
 			changefeed: v.changefeed.map(Into::into),
+			history: v.history.map(Into::into),
 			strict: v.strict,
 		}
 	}
@@ -73,6 +80,7 @@ impl From<crate::expr::statements::DefineDatabaseStatement> for DefineDatabaseSt
 			strict: v.strict,
 			comment: v.comment.into(),
 			changefeed: v.changefeed.map(Into::into),
+			history: v.history.map(Into::into),
 		}
 	}
 }
@@ -19,7 +19,7 @@ pub mod user;
 pub(crate) use access::DefineAccessStatement;
 pub(crate) use analyzer::DefineAnalyzerStatement;
 pub(crate) use api::{ApiAction, DefineApiStatement};
-pub(crate) use bucket::DefineBucketStatement;
+pub(crate) use bucket::{BucketLifecycleRule, DefineBucketStatement};
 pub(crate) use config::DefineConfigStatement;
 pub(crate) use database::DefineDatabaseStatement;
 pub(crate) use event::DefineEventStatement;
@@ -1,9 +1,10 @@
 use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
-use crate::fmt::{CoverStmts, EscapeKwFreeIdent};
+use crate::fmt::{CoverStmts, EscapeIdent, EscapeKwFreeIdent};
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::{Expr, Literal, Permissions, TableType, View};
+use crate::types::PublicDuration;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -16,6 +17,8 @@ pub(crate) struct DefineTableStatement {
 	pub view: Option<View>,
 	pub permissions: Permissions,
 	pub changefeed: Option<ChangeFeed>,
+	pub history: Option<PublicDuration>,
+	pub tier: Option<String>,
 	pub comment: Expr,
 	pub table_type: TableType,
 }
@@ -31,6 +34,8 @@ impl Default for DefineTableStatement {
 			view: None,
 			permissions: Permissions::none(),
 			changefeed: None,
+			history: None,
+			tier: None,
 			comment: Expr::Literal(Literal::None),
 			table_type: TableType::default(),
 		}
@@ -92,6 +97,12 @@ impl ToSql for DefineTableStatement {
 		if let Some(ref v) = self.changefeed {
 			write_sql!(f, sql_fmt, " {}", v);
 		}
+		if let Some(ref v) = self.history {
+			write_sql!(f, sql_fmt, " HISTORY RETENTION {}", v);
+		}
+		if let Some(ref v) = self.tier {
+			write_sql!(f, sql_fmt, " TIER {}", EscapeIdent(v));
+		}
 		if sql_fmt.is_pretty() {
 			f.push('\n');
 			let inner_fmt = sql_fmt.increment();
@@ -114,6 +125,8 @@ impl From<DefineTableStatement> for crate::expr::statements::DefineTableStatemen
 			view: v.view.map(Into::into),
 			permissions: v.permissions.into(),
 			changefeed: v.changefeed.map(Into::into),
+			history: v.history.map(Into::into),
+			tier: v.tier,
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
 		}
@@ -132,6 +145,8 @@ impl From<crate::expr::statements::DefineTableStatement> for DefineTableStatemen
 			view: v.view.map(Into::into),
 			permissions: v.permissions.into(),
 			changefeed: v.changefeed.map(Into::into),
+			history: v.history.map(Into::into),
+			tier: v.tier,
 			comment: v.comment.into(),
 			table_type: v.table_type.into(),
 		}
@@ -7,7 +7,7 @@ use surrealdb_types::{SqlFormat, ToSql, write_sql};
 
 use super::DefineKind;
 use crate::fmt::{CoverStmts, EscapeKwFreeIdent, QuoteStr};
-use crate::sql::{Base, Expr, Literal};
+use crate::sql::{Base, Expr, Literal, RateLimit};
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -27,7 +27,8 @@ pub(crate) struct DefineUserStatement {
 	pub roles: Vec<String>,
 	pub token_duration: Expr,
 	pub session_duration: Expr,
-
+	pub totp: Option<String>,
+	pub limit: Option<RateLimit>,
 	pub comment: Expr,
 }
 
@@ -41,6 +42,8 @@ impl Default for DefineUserStatement {
 			roles: vec![],
 			token_duration: Expr::Literal(Literal::None),
 			session_duration: Expr::Literal(Literal::None),
+			totp: None,
+			limit: None,
 			comment: Expr::Literal(Literal::None),
 		}
 	}
@@ -63,6 +66,10 @@ impl ToSql for DefineUserStatement {
 			PassType::Password(ref x) => write_sql!(f, fmt, " PASSWORD {}", QuoteStr(x)),
 		}
 
+		if let Some(ref x) = self.totp {
+			write_sql!(f, fmt, " TOTP {}", QuoteStr(x));
+		}
+
 		write_sql!(f, fmt, " ROLES ");
 		for (idx, r) in self.roles.iter().enumerate() {
 			if idx != 0 {
@@ -80,6 +87,9 @@ impl ToSql for DefineUserStatement {
 		CoverStmts(&self.token_duration).fmt_sql(f, fmt);
 		f.push_str(", FOR SESSION ");
 		CoverStmts(&self.session_duration).fmt_sql(f, fmt);
+		if let Some(ref v) = self.limit {
+			write_sql!(f, fmt, " {}", v);
+		}
 		if !matches!(self.comment, Expr::Literal(Literal::None)) {
 			write_sql!(f, fmt, " COMMENT {}", CoverStmts(&self.comment));
 		}
@@ -116,6 +126,8 @@ impl From<DefineUserStatement> for crate::expr::statements::DefineUserStatement
 				token: v.token_duration.into(),
 				session: v.session_duration.into(),
 			},
+			totp: v.totp,
+			limit: v.limit.map(Into::into),
 			comment: v.comment.into(),
 		}
 	}
@@ -131,6 +143,8 @@ impl From<crate::expr::statements::DefineUserStatement> for DefineUserStatement
 			roles: v.roles,
 			token_duration: v.duration.token.into(),
 			session_duration: v.duration.session.into(),
+			totp: v.totp,
+			limit: v.limit.map(Into::into),
 			comment: v.comment.into(),
 		}
 	}
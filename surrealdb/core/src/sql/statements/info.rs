@@ -10,8 +10,8 @@ pub enum InfoStatement {
 	// removing not at the end of the enum definition.
 	Root(bool),
 	Ns(bool),
-	Db(bool, Option<Expr>),
-	Tb(Expr, bool, Option<Expr>),
+	Db(bool, Option<Expr>, bool),
+	Tb(Expr, bool, Option<Expr>, bool),
 	User(Expr, Option<Base>, bool),
 	Index(Expr, Expr, bool),
 }
@@ -23,17 +23,17 @@ impl ToSql for InfoStatement {
 			Self::Root(true) => f.push_str("INFO FOR ROOT STRUCTURE"),
 			Self::Ns(false) => f.push_str("INFO FOR NAMESPACE"),
 			Self::Ns(true) => f.push_str("INFO FOR NAMESPACE STRUCTURE"),
-			Self::Db(false, v) => match v {
+			Self::Db(false, v, _) => match v {
 				Some(v) => write_sql!(f, sql_fmt, "INFO FOR DATABASE VERSION {}", CoverStmts(v)),
 				None => f.push_str("INFO FOR DATABASE"),
 			},
-			Self::Db(true, v) => match v {
+			Self::Db(true, v, _) => match v {
 				Some(v) => {
 					write_sql!(f, sql_fmt, "INFO FOR DATABASE VERSION {} STRUCTURE", CoverStmts(v))
 				}
 				None => f.push_str("INFO FOR DATABASE STRUCTURE"),
 			},
-			Self::Tb(t, false, v) => match v {
+			Self::Tb(t, false, v, _) => match v {
 				Some(v) => {
 					write_sql!(
 						f,
@@ -45,7 +45,7 @@ impl ToSql for InfoStatement {
 				}
 				None => write_sql!(f, sql_fmt, "INFO FOR TABLE {}", CoverStmts(t)),
 			},
-			Self::Tb(t, true, v) => match v {
+			Self::Tb(t, true, v, _) => match v {
 				Some(v) => write_sql!(
 					f,
 					sql_fmt,
@@ -78,6 +78,9 @@ impl ToSql for InfoStatement {
 				)
 			}
 		}
+		if let Self::Db(.., true) | Self::Tb(.., true) = self {
+			f.push_str(" STATS");
+		}
 	}
 }
 
@@ -86,8 +89,8 @@ impl From<InfoStatement> for crate::expr::statements::InfoStatement {
 		match v {
 			InfoStatement::Root(v) => Self::Root(v),
 			InfoStatement::Ns(v) => Self::Ns(v),
-			InfoStatement::Db(v, ver) => Self::Db(v, ver.map(From::from)),
-			InfoStatement::Tb(t, v, ver) => Self::Tb(t.into(), v, ver.map(From::from)),
+			InfoStatement::Db(v, ver, s) => Self::Db(v, ver.map(From::from), s),
+			InfoStatement::Tb(t, v, ver, s) => Self::Tb(t.into(), v, ver.map(From::from), s),
 			InfoStatement::User(u, b, v) => Self::User(u.into(), b.map(Into::into), v),
 			InfoStatement::Index(i, t, v) => Self::Index(i.into(), t.into(), v),
 		}
@@ -99,9 +102,11 @@ impl From<crate::expr::statements::InfoStatement> for InfoStatement {
 		match v {
 			crate::expr::statements::InfoStatement::Root(v) => Self::Root(v),
 			crate::expr::statements::InfoStatement::Ns(v) => Self::Ns(v),
-			crate::expr::statements::InfoStatement::Db(v, ver) => Self::Db(v, ver.map(From::from)),
-			crate::expr::statements::InfoStatement::Tb(t, v, ver) => {
-				Self::Tb(t.into(), v, ver.map(From::from))
+			crate::expr::statements::InfoStatement::Db(v, ver, s) => {
+				Self::Db(v, ver.map(From::from), s)
+			}
+			crate::expr::statements::InfoStatement::Tb(t, v, ver, s) => {
+				Self::Tb(t.into(), v, ver.map(From::from), s)
 			}
 			crate::expr::statements::InfoStatement::User(u, b, v) => {
 				Self::User(u.into(), b.map(Into::into), v)
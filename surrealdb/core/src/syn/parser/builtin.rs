@@ -112,6 +112,10 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("crypto::pbkdf2::generate") => (PathKind::Function, None),
 		UniCase::ascii("crypto::scrypt::compare") => (PathKind::Function, None),
 		UniCase::ascii("crypto::scrypt::generate") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::generate") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::secret") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::uri") => (PathKind::Function, None),
+		UniCase::ascii("crypto::totp::verify") => (PathKind::Function, None),
 		//
 		UniCase::ascii("duration::days") => (PathKind::Function, None),
 		UniCase::ascii("duration::hours") => (PathKind::Function, None),
@@ -150,6 +154,8 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("file::put") => (PathKind::Function, None),
 		UniCase::ascii("file::put_if_not_exists") => (PathKind::Function, None),
 		UniCase::ascii("file::get") => (PathKind::Function, None),
+		UniCase::ascii("file::get_range") => (PathKind::Function, None),
+		UniCase::ascii("file::presign") => (PathKind::Function, None),
 		UniCase::ascii("file::head") => (PathKind::Function, None),
 		UniCase::ascii("file::delete") => (PathKind::Function, None),
 		UniCase::ascii("file::copy") => (PathKind::Function, None),
@@ -158,6 +164,10 @@ pub(crate) static PATHS: phf::Map<
 		UniCase::ascii("file::rename_if_not_exists") => (PathKind::Function, None),
 		UniCase::ascii("file::exists") => (PathKind::Function, None),
 		UniCase::ascii("file::list") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::abort") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::complete") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::create") => (PathKind::Function, None),
+		UniCase::ascii("file::multipart::put") => (PathKind::Function, None),
 		//
 		UniCase::ascii("geo::area") => (PathKind::Function, None),
 		UniCase::ascii("geo::bearing") => (PathKind::Function, None),
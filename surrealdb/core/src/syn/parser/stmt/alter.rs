@@ -11,7 +11,6 @@ use crate::sql::statements::alter::{
 	AlterModuleStatement, AlterNamespaceStatement, AlterParamStatement, AlterSequenceStatement,
 	AlterSystemStatement, AlterUserStatement,
 };
-use crate::sql::statements::define::ApiAction;
 use crate::sql::statements::{AlterStatement, AlterTableStatement};
 use crate::sql::tokenizer::Tokenizer;
 use crate::syn::error::bail;
@@ -82,15 +81,20 @@ impl Parser<'_> {
 							let duration = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
 							res.query_timeout = AlterKind::Set(duration);
 						}
-						_ => unexpected!(self, peek, "`QUERY_TIMEOUT`"),
+						"CHECKPOINT" => {
+							let path = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+							res.checkpoint = Some(path);
+						}
+						_ => unexpected!(self, peek, "`CHECKPOINT` or `QUERY_TIMEOUT`"),
 					}
 				}
 				_ => break,
 			}
 		}
 
-		if !res.compact && matches!(res.query_timeout, AlterKind::None) {
-			unexpected!(self, self.peek(), "`COMPACT`, `DROP` or `QUERY_TIMEOUT`")
+		if !res.compact && res.checkpoint.is_none() && matches!(res.query_timeout, AlterKind::None)
+		{
+			unexpected!(self, self.peek(), "`CHECKPOINT`, `COMPACT`, `DROP` or `QUERY_TIMEOUT`")
 		}
 		Ok(res)
 	}
@@ -825,7 +829,13 @@ impl Parser<'_> {
 							self.pop_peek();
 							res.comment = AlterKind::Drop;
 						}
-						_ => unexpected!(self, peek, "`COMMENT`"),
+						TokenKind::Identifier
+							if self.span_str(peek.span).eq_ignore_ascii_case("TOTP") =>
+						{
+							self.pop_peek();
+							res.totp = AlterKind::Drop;
+						}
+						_ => unexpected!(self, peek, "`COMMENT` or `TOTP`"),
 					}
 				}
 				t!("PASSWORD") => {
@@ -881,6 +891,14 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.comment = AlterKind::Set(self.parse_string_lit()?);
 				}
+				TokenKind::Identifier => {
+					let token = self.peek();
+					if !self.span_str(token.span).eq_ignore_ascii_case("TOTP") {
+						break;
+					}
+					self.pop_peek();
+					res.totp = AlterKind::Set(self.parse_string_lit()?);
+				}
 				_ => break,
 			}
 		}
@@ -1103,14 +1121,8 @@ impl Parser<'_> {
 									methods,
 								});
 							} else {
-								let config = self.parse_api_config(stk).await?;
-								expected!(self, t!("THEN"));
-								let action = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
-								res.clauses.push(AlterApiClause::SetAction(ApiAction {
-									methods,
-									action,
-									config,
-								}));
+								let action = self.parse_api_action(stk, methods).await?;
+								res.clauses.push(AlterApiClause::SetAction(action));
 							}
 						}
 						_ => {
@@ -13,11 +13,11 @@ use crate::sql::statements::define::config::graphql::{GraphQLConfig, TableConfig
 use crate::sql::statements::define::config::{ConfigInner, graphql};
 use crate::sql::statements::define::user::PassType;
 use crate::sql::statements::define::{
-	ApiAction, DefineAccessStatement, DefineAnalyzerStatement, DefineApiStatement,
-	DefineBucketStatement, DefineConfigStatement, DefineDatabaseStatement, DefineDefault,
-	DefineEventStatement, DefineFieldStatement, DefineFunctionStatement, DefineIndexStatement,
-	DefineKind, DefineNamespaceStatement, DefineParamStatement, DefineSequenceStatement,
-	DefineStatement, DefineTableStatement, DefineUserStatement,
+	ApiAction, BucketLifecycleRule, DefineAccessStatement, DefineAnalyzerStatement,
+	DefineApiStatement, DefineBucketStatement, DefineConfigStatement, DefineDatabaseStatement,
+	DefineDefault, DefineEventStatement, DefineFieldStatement, DefineFunctionStatement,
+	DefineIndexStatement, DefineKind, DefineNamespaceStatement, DefineParamStatement,
+	DefineSequenceStatement, DefineStatement, DefineTableStatement, DefineUserStatement,
 };
 use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
@@ -127,6 +127,14 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				TokenKind::Identifier => {
+					let token = self.peek();
+					if !self.span_str(token.span).eq_ignore_ascii_case("HISTORY") {
+						break;
+					}
+					self.pop_peek();
+					res.history = Some(self.parse_history_retention()?);
+				}
 				t!("STRICT") => {
 					self.pop_peek();
 					res.strict = true;
@@ -405,6 +413,18 @@ impl Parser<'_> {
 						}
 					}
 				}
+				t!("LIMIT") => {
+					self.pop_peek();
+					res.limit = Some(self.parse_rate_limit()?);
+				}
+				TokenKind::Identifier => {
+					let token = self.peek();
+					if !self.span_str(token.span).eq_ignore_ascii_case("TOTP") {
+						break;
+					}
+					self.pop_peek();
+					res.totp = Some(self.parse_string_lit()?);
+				}
 				_ => break,
 			}
 		}
@@ -436,6 +456,7 @@ impl Parser<'_> {
 			authenticate: None,
 			access_type: AccessType::default(),
 			duration: AccessDuration::default(),
+			limit: None,
 			comment: Expr::Literal(Literal::None),
 		};
 
@@ -571,6 +592,10 @@ impl Parser<'_> {
 						self.eat(t!(","));
 					}
 				}
+				t!("LIMIT") => {
+					self.pop_peek();
+					res.limit = Some(self.parse_rate_limit()?);
+				}
 				_ => break,
 			}
 		}
@@ -693,6 +718,19 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.changefeed = Some(self.parse_changefeed()?);
 				}
+				TokenKind::Identifier => {
+					let token = self.peek();
+					let word = self.span_str(token.span);
+					if word.eq_ignore_ascii_case("HISTORY") {
+						self.pop_peek();
+						res.history = Some(self.parse_history_retention()?);
+					} else if word.eq_ignore_ascii_case("TIER") {
+						self.pop_peek();
+						res.tier = Some(self.parse_ident()?);
+					} else {
+						break;
+					}
+				}
 				t!("AS") => {
 					self.pop_peek();
 					let peek = self.peek();
@@ -785,15 +823,7 @@ impl Parser<'_> {
 						}
 					}
 
-					let config = self.parse_api_config(stk).await?;
-
-					expected!(self, t!("THEN"));
-					let action = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
-					res.actions.push(ApiAction {
-						methods,
-						action,
-						config,
-					});
+					res.actions.push(self.parse_api_action(stk, methods).await?);
 				}
 				_ => {
 					unexpected!(
@@ -1366,7 +1396,8 @@ impl Parser<'_> {
 		};
 
 		loop {
-			match self.peek_kind() {
+			let token = self.peek();
+			match token.kind {
 				t!("BACKEND") => {
 					self.pop_peek();
 					res.backend = Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
@@ -1383,6 +1414,25 @@ impl Parser<'_> {
 					self.pop_peek();
 					res.comment = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
 				}
+				TokenKind::Identifier
+					if self.span_str(token.span).eq_ignore_ascii_case("EXPIRE") =>
+				{
+					self.pop_peek();
+					let expire = self.next_token_value::<PublicDuration>()?;
+					let token = self.peek();
+					let prefix = if token.kind == TokenKind::Identifier
+						&& self.span_str(token.span).eq_ignore_ascii_case("PREFIX")
+					{
+						self.pop_peek();
+						Some(self.parse_string_lit()?)
+					} else {
+						None
+					};
+					res.lifecycle.push(BucketLifecycleRule {
+						expire,
+						prefix,
+					});
+				}
 				_ => {
 					break;
 				}
@@ -1520,13 +1570,72 @@ impl Parser<'_> {
 		Ok(config)
 	}
 
+	/// Parses the clauses of an API action following its methods, up to and
+	/// including the action expression.
+	pub(crate) async fn parse_api_action(
+		&mut self,
+		stk: &mut Stk,
+		methods: Vec<ApiMethod>,
+	) -> ParseResult<ApiAction> {
+		let mut res = ApiAction {
+			methods,
+			action: Expr::Literal(Literal::None),
+			config: ApiConfig::default(),
+			params: None,
+			query: None,
+			body: None,
+		};
+		loop {
+			let token = self.peek();
+			match token.kind {
+				TokenKind::Identifier
+					if self.span_str(token.span).eq_ignore_ascii_case("PARAMS") =>
+				{
+					self.pop_peek();
+					res.params = Some(self.parse_api_object_kind(stk).await?);
+				}
+				TokenKind::Identifier
+					if self.span_str(token.span).eq_ignore_ascii_case("QUERY") =>
+				{
+					self.pop_peek();
+					res.query = Some(self.parse_api_object_kind(stk).await?);
+				}
+				TokenKind::Identifier if self.span_str(token.span).eq_ignore_ascii_case("BODY") => {
+					self.pop_peek();
+					res.body = Some(self.parse_inner_kind(stk).await?);
+				}
+				_ => break,
+			}
+		}
+		res.config = self.parse_api_config(stk).await?;
+		expected!(self, t!("THEN"));
+		res.action = stk.run(|ctx| self.parse_expr_field(ctx)).await?;
+		Ok(res)
+	}
+
+	/// Parses the object type describing the path or query parameters of an
+	/// API action.
+	async fn parse_api_object_kind(&mut self, stk: &mut Stk) -> ParseResult<Kind> {
+		let token = self.peek();
+		if token.kind != t!("{") {
+			unexpected!(self, token, "an object type");
+		}
+		let kind = self.parse_inner_kind(stk).await?;
+		if !matches!(kind, Kind::Literal(KindLiteral::Object(_))) {
+			bail!("Unexpected type, expected an object type", @token.span => "Parameters must be described by a single object type");
+		}
+		Ok(kind)
+	}
+
 	pub(crate) fn parse_graphql_config(&mut self) -> ParseResult<GraphQLConfig> {
-		use graphql::{FunctionsConfig, IntrospectionConfig, TablesConfig};
+		use graphql::{FederationConfig, FunctionsConfig, IntrospectionConfig, TablesConfig};
 		let mut tmp_tables = Option::<TablesConfig>::None;
 		let mut tmp_fncs = Option::<FunctionsConfig>::None;
 		let mut tmp_depth = Option::<u32>::None;
 		let mut tmp_complexity = Option::<u32>::None;
 		let mut tmp_introspection = Option::<IntrospectionConfig>::None;
+		let mut tmp_federation = Option::<FederationConfig>::None;
+		let mut tmp_persisted = Option::<Vec<String>>::None;
 		loop {
 			match self.peek_kind() {
 				t!("NONE") => {
@@ -1597,6 +1706,41 @@ impl Parser<'_> {
 							}
 							_ => unexpected!(self, next, "`AUTO` or `NONE`"),
 						}
+					} else if ident.eq_ignore_ascii_case("FEDERATION") {
+						self.pop_peek();
+						let next = self.next();
+						match next.kind {
+							t!("AUTO") => {
+								tmp_federation = Some(FederationConfig::Auto);
+							}
+							t!("NONE") => {
+								tmp_federation = Some(FederationConfig::None);
+							}
+							_ => unexpected!(self, next, "`AUTO` or `NONE`"),
+						}
+					} else if ident.eq_ignore_ascii_case("PERSISTED") {
+						self.pop_peek();
+						let next = self.next();
+						match next.kind {
+							t!("NONE") => {
+								tmp_persisted = Some(Vec::new());
+							}
+							t!("[") => {
+								let mut queries = Vec::new();
+								loop {
+									if self.eat(t!("]")) {
+										break;
+									}
+									queries.push(self.parse_string_lit()?);
+									if !self.eat(t!(",")) {
+										self.expect_closing_delimiter(t!("]"), next.span)?;
+										break;
+									}
+								}
+								tmp_persisted = Some(queries);
+							}
+							_ => unexpected!(self, next, "`NONE` or an array of queries"),
+						}
 					} else {
 						break;
 					}
@@ -1611,6 +1755,8 @@ impl Parser<'_> {
 			depth_limit: tmp_depth,
 			complexity_limit: tmp_complexity,
 			introspection: tmp_introspection.unwrap_or_default(),
+			federation: tmp_federation.unwrap_or_default(),
+			persisted_queries: tmp_persisted.unwrap_or_default(),
 		})
 	}
 
@@ -1720,7 +1866,57 @@ impl Parser<'_> {
 					url,
 				});
 			}
-			_ => unexpected!(self, peek, "`ALGORITHM`, or `URL`"),
+			TokenKind::Identifier if self.span_str(peek.span).eq_ignore_ascii_case("OPENID") => {
+				self.pop_peek();
+				let mut oidc = access_type::JwtAccessVerifyOidc {
+					issuer: stk.run(|stk| self.parse_expr_field(stk)).await?,
+					audience: None,
+					roles: None,
+					introspect: None,
+				};
+				loop {
+					let peek = self.peek();
+					match peek.kind {
+						t!("ROLES") => {
+							self.pop_peek();
+							oidc.roles = Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
+						}
+						TokenKind::Identifier => {
+							let ident = self.span_str(peek.span);
+							if ident.eq_ignore_ascii_case("AUDIENCE") {
+								self.pop_peek();
+								oidc.audience =
+									Some(stk.run(|stk| self.parse_expr_field(stk)).await?);
+							} else if ident.eq_ignore_ascii_case("INTROSPECT") {
+								self.pop_peek();
+								let next = self.next();
+								if !(next.kind == TokenKind::Identifier
+									&& self.span_str(next.span).eq_ignore_ascii_case("CLIENT"))
+								{
+									unexpected!(self, next, "`CLIENT`");
+								}
+								let client = stk.run(|stk| self.parse_expr_field(stk)).await?;
+								let next = self.next();
+								if !(next.kind == TokenKind::Identifier
+									&& self.span_str(next.span).eq_ignore_ascii_case("SECRET"))
+								{
+									unexpected!(self, next, "`SECRET`");
+								}
+								let secret = stk.run(|stk| self.parse_expr_field(stk)).await?;
+								oidc.introspect = Some(access_type::JwtAccessIntrospect {
+									client,
+									secret,
+								});
+							} else {
+								break;
+							}
+						}
+						_ => break,
+					}
+				}
+				res.verify = access_type::JwtAccessVerify::Oidc(oidc);
+			}
+			_ => unexpected!(self, peek, "`ALGORITHM`, `URL`, or `OPENID`"),
 		}
 
 		if self.eat(t!("WITH")) {
@@ -433,7 +433,8 @@ impl Parser<'_> {
 					None
 				};
 				let structure = self.eat(t!("STRUCTURE"));
-				InfoStatement::Db(structure, version)
+				let stats = self.eat_info_stats();
+				InfoStatement::Db(structure, version, stats)
 			}
 			t!("TABLE") => {
 				let ident = stk.run(|stk| self.parse_expr_table(stk)).await?;
@@ -443,7 +444,8 @@ impl Parser<'_> {
 					None
 				};
 				let structure = self.eat(t!("STRUCTURE"));
-				InfoStatement::Tb(ident, structure, version)
+				let stats = self.eat_info_stats();
+				InfoStatement::Tb(ident, structure, version, stats)
 			}
 			t!("USER") => {
 				let ident = stk.run(|stk| self.parse_expr_inherit(stk)).await?;
@@ -465,6 +467,21 @@ impl Parser<'_> {
 		Ok(stmt)
 	}
 
+	/// Eats the `STATS` clause of an INFO statement, returning whether it was present.
+	///
+	/// `STATS` is not a keyword, so is matched as an identifier.
+	fn eat_info_stats(&mut self) -> bool {
+		let peek = self.peek();
+		if peek.kind == TokenKind::Identifier
+			&& self.span_str(peek.span).eq_ignore_ascii_case("STATS")
+		{
+			self.pop_peek();
+			true
+		} else {
+			false
+		}
+	}
+
 	/// Parsers a KILL statement.
 	///
 	/// # Parser State
@@ -8,7 +8,7 @@ use crate::sql::index::{Distance, VectorType};
 use crate::sql::reference::{Reference, ReferenceDeleteStrategy};
 use crate::sql::{
 	Base, Cond, Data, Explain, Expr, Fetch, Fetchs, Field, Fields, Group, Groups, Idiom, Literal,
-	Output, Permission, Permissions, View, With,
+	Output, Permission, Permissions, RateLimit, View, With,
 };
 use crate::syn::error::bail;
 use crate::syn::parser::mac::{expected, unexpected};
@@ -468,6 +468,56 @@ impl Parser<'_> {
 		})
 	}
 
+	/// Parses a history retention production
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `HISTORY` identifier
+	pub fn parse_history_retention(&mut self) -> ParseResult<PublicDuration> {
+		let token = self.next();
+		if token.kind != TokenKind::Identifier
+			|| !self.span_str(token.span).eq_ignore_ascii_case("RETENTION")
+		{
+			unexpected!(self, token, "`RETENTION`");
+		}
+		self.next_token_value::<PublicDuration>()
+	}
+
+	/// Parses a request limit production
+	///
+	/// # Parser State
+	/// Expects the parser to have already eaten the `LIMIT` keyword
+	pub(crate) fn parse_rate_limit(&mut self) -> ParseResult<RateLimit> {
+		let mut res = RateLimit::default();
+		loop {
+			let token = self.peek();
+			match token.kind {
+				t!("TIMEOUT") => {
+					self.pop_peek();
+					res.timeout = Some(self.next_token_value::<PublicDuration>()?);
+				}
+				TokenKind::Identifier if self.span_str(token.span).eq_ignore_ascii_case("RATE") => {
+					self.pop_peek();
+					let rate = self.next_token_value::<u32>()?;
+					if rate == 0 {
+						bail!("Invalid request rate limit", @self.last_span() => "The request rate must be greater than 0");
+					}
+					res.rate = Some(rate);
+				}
+				TokenKind::Identifier
+					if self.span_str(token.span).eq_ignore_ascii_case("CONCURRENCY") =>
+				{
+					self.pop_peek();
+					res.concurrency = Some(self.next_token_value::<u32>()?);
+				}
+				_ => unexpected!(self, token, "`RATE`, `CONCURRENCY`, or `TIMEOUT`"),
+			}
+			if !self.eat(t!(",")) {
+				break;
+			}
+		}
+		Ok(res)
+	}
+
 	/// Parses a reference
 	///
 	/// # Parser State
@@ -4,8 +4,9 @@ use chrono::{NaiveDate, Offset, Utc};
 use crate::catalog::EventKind;
 use crate::sql::access::AccessDuration;
 use crate::sql::access_type::{
-	AccessType, BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccess, JwtAccessIssue,
-	JwtAccessVerify, JwtAccessVerifyJwks, JwtAccessVerifyKey, RecordAccess,
+	AccessType, BearerAccess, BearerAccessSubject, BearerAccessType, JwtAccess,
+	JwtAccessIntrospect, JwtAccessIssue, JwtAccessVerify, JwtAccessVerifyJwks, JwtAccessVerifyKey,
+	JwtAccessVerifyOidc, RecordAccess,
 };
 use crate::sql::changefeed::ChangeFeed;
 use crate::sql::data::Assignment;
@@ -44,8 +45,8 @@ use crate::sql::tokenizer::Tokenizer;
 use crate::sql::{
 	Algorithm, AssignOperator, Base, BinaryOperator, Block, Cond, Data, Dir, Explain, Expr, Fetch,
 	Fetchs, Field, Fields, Group, Groups, Idiom, Index, Kind, Literal, Lookup, Mock, Output, Param,
-	Part, Permission, Permissions, RecordIdKeyLit, RecordIdLit, Scoring, TableType, TopLevelExpr,
-	With,
+	Part, Permission, Permissions, RateLimit, RecordIdKeyLit, RecordIdLit, Scoring, TableType,
+	TopLevelExpr, With,
 };
 use crate::syn;
 use crate::syn::parser::ParserSettings;
@@ -206,6 +207,7 @@ fn parse_define_database() {
 				expiry: PublicDuration::from_secs(60 * 10),
 				store_diff: true,
 			}),
+			history: None,
 		})))
 	);
 
@@ -222,10 +224,40 @@ fn parse_define_database() {
 			strict: false,
 			comment: Expr::Literal(Literal::None),
 			changefeed: None,
+			history: None,
 		})))
 	)
 }
 
+#[test]
+fn parse_define_database_history_retention() {
+	let res = syn::parse_with(
+		"DEFINE DATABASE a CHANGEFEED 10m HISTORY RETENTION 7d".as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Database(DefineDatabaseStatement {
+			kind: DefineKind::Default,
+			id: None,
+			name: Expr::Idiom(Idiom::field("a".to_string())),
+			strict: false,
+			comment: Expr::Literal(Literal::None),
+			changefeed: Some(ChangeFeed {
+				expiry: PublicDuration::from_secs(60 * 10),
+				store_diff: false,
+			}),
+			history: Some(PublicDuration::from_secs(60 * 60 * 24 * 7)),
+		})))
+	);
+
+	let res = syn::parse_with("DEFINE DATABASE a HISTORY 7d".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	});
+	assert!(res.is_err());
+}
+
 #[test]
 fn parse_define_function() {
 	let res = syn::parse_with(
@@ -425,6 +457,47 @@ fn parse_define_user() {
 		)
 		.unwrap_err();
 	}
+	// With request limits.
+	{
+		let res = syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' LIMIT RATE 10, CONCURRENCY 2, TIMEOUT 5s"#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+
+		let Expr::Define(res) = res else {
+			panic!()
+		};
+		let DefineStatement::User(stmt) = *res else {
+			panic!()
+		};
+
+		assert_eq!(
+			stmt.limit,
+			Some(RateLimit {
+				rate: Some(10),
+				concurrency: Some(2),
+				timeout: Some(PublicDuration::from_secs(5)),
+			})
+		);
+	}
+	// With an unknown limit.
+	{
+		syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' LIMIT BURST 10"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap_err();
+	}
+	// With a request rate of zero.
+	{
+		syn::parse_with(
+			r#"DEFINE USER user ON ROOT PASSHASH 'hunter2' LIMIT RATE 0"#.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap_err();
+	}
 }
 
 #[test]
@@ -457,6 +530,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::String("bar".to_string())),
 			}))),
 		)
@@ -487,6 +561,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -517,6 +592,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None)
 			}))),
 		)
@@ -551,6 +627,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -580,6 +657,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_secs(10))),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -610,6 +688,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -660,6 +739,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::String("bar".to_string())),
 			}))),
 		)
@@ -692,6 +772,7 @@ fn parse_define_access_jwt_key() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::String("bar".to_string())),
 			}))),
 		)
@@ -724,6 +805,7 @@ fn parse_define_access_jwt_jwks() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::String("bar".to_string())),
 			}))),
 		)
@@ -755,6 +837,7 @@ fn parse_define_access_jwt_jwks() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -785,6 +868,7 @@ fn parse_define_access_jwt_jwks() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_secs(10))),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -816,6 +900,7 @@ fn parse_define_access_jwt_jwks() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -848,12 +933,96 @@ fn parse_define_access_jwt_jwks() {
 						PublicDuration::from_days(2).unwrap()
 					)),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
 	}
 }
 
+#[test]
+fn parse_define_access_jwt_openid() {
+	// Verify only.
+	{
+		let res = syn::parse_with(
+			r#"DEFINE ACCESS a ON DATABASE TYPE JWT OPENID "https://idp.example.com/realms/test""#
+				.as_bytes(),
+			async |parser, stk| parser.parse_expr_inherit(stk).await,
+		)
+		.unwrap();
+		assert_eq!(
+			res,
+			Expr::Define(Box::new(DefineStatement::Access(DefineAccessStatement {
+				kind: DefineKind::Default,
+				name: Expr::Idiom(Idiom::field("a".to_string())),
+				base: Base::Db,
+				access_type: AccessType::Jwt(JwtAccess {
+					verify: JwtAccessVerify::Oidc(JwtAccessVerifyOidc {
+						issuer: Expr::Literal(Literal::String(
+							"https://idp.example.com/realms/test".to_string()
+						)),
+						audience: None,
+						roles: None,
+						introspect: None,
+					}),
+					issue: None,
+				}),
+				authenticate: None,
+				// Default durations.
+				duration: AccessDuration {
+					grant: Expr::Literal(Literal::Duration(PublicDuration::from_days(30).unwrap())),
+					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
+					session: Expr::Literal(Literal::None),
+				},
+				limit: None,
+				comment: Expr::Literal(Literal::None),
+			}))),
+		)
+	}
+	// With audience, roles claim and introspection, in any order.
+	{
+		let res = syn::parse_with(r#"DEFINE ACCESS a ON DATABASE TYPE JWT OPENID "https://idp.example.com" INTROSPECT CLIENT "surrealdb" SECRET "secret" ROLES "realm_access.roles" AUDIENCE "api" COMMENT "bar""#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await).unwrap();
+		assert_eq!(
+			res,
+			Expr::Define(Box::new(DefineStatement::Access(DefineAccessStatement {
+				kind: DefineKind::Default,
+				name: Expr::Idiom(Idiom::field("a".to_string())),
+				base: Base::Db,
+				access_type: AccessType::Jwt(JwtAccess {
+					verify: JwtAccessVerify::Oidc(JwtAccessVerifyOidc {
+						issuer: Expr::Literal(Literal::String(
+							"https://idp.example.com".to_string()
+						)),
+						audience: Some(Expr::Literal(Literal::String("api".to_string()))),
+						roles: Some(Expr::Literal(Literal::String(
+							"realm_access.roles".to_string()
+						))),
+						introspect: Some(JwtAccessIntrospect {
+							client: Expr::Literal(Literal::String("surrealdb".to_string())),
+							secret: Expr::Literal(Literal::String("secret".to_string())),
+						}),
+					}),
+					issue: None,
+				}),
+				authenticate: None,
+				// Default durations.
+				duration: AccessDuration {
+					grant: Expr::Literal(Literal::Duration(PublicDuration::from_days(30).unwrap())),
+					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
+					session: Expr::Literal(Literal::None),
+				},
+				limit: None,
+				comment: Expr::Literal(Literal::String("bar".to_string())),
+			}))),
+		)
+	}
+	// Introspection requires client credentials.
+	{
+		let res = syn::parse_with(r#"DEFINE ACCESS a ON DATABASE TYPE JWT OPENID "https://idp.example.com" INTROSPECT SECRET "secret""#.as_bytes(),async |parser,stk| parser. parse_expr_inherit(stk).await);
+		assert!(res.is_err());
+	}
+}
+
 #[test]
 fn parse_define_access_record() {
 	// With comment. Nothing is explicitly defined.
@@ -1062,6 +1231,7 @@ fn parse_define_access_record() {
 						PublicDuration::from_mins(15).unwrap()
 					)),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		);
@@ -1099,6 +1269,7 @@ fn parse_define_access_record() {
 						PublicDuration::from_mins(15).unwrap()
 					)),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		);
@@ -1154,6 +1325,7 @@ fn parse_define_access_record() {
 						PublicDuration::from_mins(15).unwrap()
 					)),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		);
@@ -1208,6 +1380,7 @@ fn parse_define_access_record() {
 						PublicDuration::from_mins(15).unwrap()
 					)),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		);
@@ -1245,6 +1418,7 @@ fn parse_define_access_record() {
 						PublicDuration::from_mins(15).unwrap()
 					)),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		);
@@ -1489,6 +1663,7 @@ fn parse_define_access_bearer() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_secs(10))),
 					session: Expr::Literal(Literal::Duration(PublicDuration::from_secs(900))),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -1528,6 +1703,7 @@ fn parse_define_access_bearer() {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_secs(10))),
 					session: Expr::Literal(Literal::Duration(PublicDuration::from_secs(900))),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::None),
 			}))),
 		)
@@ -1599,6 +1775,8 @@ fn parse_define_table() {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: true,
 			}),
+			history: None,
+			tier: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -1606,6 +1784,27 @@ fn parse_define_table() {
 	);
 }
 
+#[test]
+fn parse_define_table_storage() {
+	let res = syn::parse_with(
+		r#"DEFINE TABLE name HISTORY RETENTION 1h TIER cold"#.as_bytes(),
+		async |parser, stk| parser.parse_expr_inherit(stk).await,
+	)
+	.unwrap();
+
+	assert_eq!(
+		res,
+		Expr::Define(Box::new(DefineStatement::Table(DefineTableStatement {
+			kind: DefineKind::Default,
+			id: None,
+			name: Expr::Table("name".to_string()),
+			history: Some(PublicDuration::from_secs(60 * 60)),
+			tier: Some("cold".to_string()),
+			..Default::default()
+		})))
+	);
+}
+
 #[test]
 fn parse_define_event() {
 	let res = syn::parse_with(
@@ -1941,9 +2140,30 @@ fn parse_info() {
 	.unwrap();
 	assert_eq!(
 		res,
-		Expr::Info(Box::new(InfoStatement::Tb(Expr::Table("table".to_string()), false, None)))
+		Expr::Info(Box::new(InfoStatement::Tb(
+			Expr::Table("table".to_string()),
+			false,
+			None,
+			false
+		)))
+	);
+
+	let res =
+		syn::parse_with("INFO FOR TABLE table STRUCTURE STATS".as_bytes(), async |parser, stk| {
+			parser.parse_expr_inherit(stk).await
+		})
+		.unwrap();
+	assert_eq!(
+		res,
+		Expr::Info(Box::new(InfoStatement::Tb(Expr::Table("table".to_string()), true, None, true)))
 	);
 
+	let res = syn::parse_with("INFO FOR DB STATS".as_bytes(), async |parser, stk| {
+		parser.parse_expr_inherit(stk).await
+	})
+	.unwrap();
+	assert_eq!(res, Expr::Info(Box::new(InfoStatement::Db(false, None, true))));
+
 	let res = syn::parse_with("INFO FOR USER user".as_bytes(), async |parser, stk| {
 		parser.parse_expr_inherit(stk).await
 	})
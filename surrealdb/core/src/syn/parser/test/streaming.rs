@@ -182,6 +182,7 @@ fn statements() -> Vec<TopLevelExpr> {
 					expiry: PublicDuration::from_secs(60 * 10),
 					store_diff: false,
 				}),
+				history: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Database(
@@ -192,6 +193,7 @@ fn statements() -> Vec<TopLevelExpr> {
 				strict: false,
 				comment: Expr::Literal(Literal::None),
 				changefeed: None,
+				history: None,
 			},
 		)))),
 		TopLevelExpr::Expr(Expr::Define(Box::new(DefineStatement::Function(
@@ -235,6 +237,7 @@ fn statements() -> Vec<TopLevelExpr> {
 					token: Expr::Literal(Literal::Duration(PublicDuration::from_hours(1).unwrap())),
 					session: Expr::Literal(Literal::None),
 				},
+				limit: None,
 				comment: Expr::Literal(Literal::String("bar".to_string())),
 			},
 		)))),
@@ -283,6 +286,8 @@ fn statements() -> Vec<TopLevelExpr> {
 				expiry: PublicDuration::from_secs(1),
 				store_diff: false,
 			}),
+			history: None,
+			tier: None,
 			comment: Expr::Literal(Literal::None),
 
 			table_type: TableType::Normal,
@@ -8,6 +8,7 @@ fn main() {
 		feature = "storage-tikv",
 		feature = "storage-rocksdb",
 		feature = "storage-surrealkv",
+		feature = "storage-redb",
 	)) {
 		println!("cargo:rustc-cfg=storage");
 		println!("cargo::rustc-check-cfg=cfg(storage)");
@@ -0,0 +1,72 @@
+use anyhow::{Result, bail};
+use clap::Args;
+use surrealdb::engine::any::{self, connect};
+use surrealdb::opt::Config;
+use surrealdb::opt::capabilities::Capabilities;
+
+use crate::cli::abstraction::auth::{CredentialsBuilder, CredentialsLevel};
+use crate::cli::abstraction::{AuthArguments, DatabaseConnectionArguments};
+
+#[derive(Args, Debug)]
+pub struct CheckpointCommandArguments {
+	#[arg(help = "Path to the directory where the checkpoint will be created on the server")]
+	#[arg(index = 1)]
+	path: String,
+	#[command(flatten)]
+	conn: DatabaseConnectionArguments,
+	#[command(flatten)]
+	auth: AuthArguments,
+}
+
+pub async fn init(
+	CheckpointCommandArguments {
+		path,
+		conn: DatabaseConnectionArguments {
+			endpoint,
+		},
+		auth: AuthArguments {
+			username,
+			password,
+			token,
+			auth_level,
+		},
+	}: CheckpointCommandArguments,
+) -> Result<()> {
+	// Default datastore configuration for local engines
+	let config = Config::new().capabilities(Capabilities::all());
+	let is_local = any::__into_endpoint(&endpoint)?.parse_kind()?.is_local();
+	// Checkpoints can only be created by root users on remote servers, so
+	// authenticate at the root level. If we are connecting directly to a
+	// datastore (i.e. rocksdb://local.db) then auth is disabled.
+	let client = if username.is_some() && password.is_some() && !is_local {
+		debug!("Connecting to the database engine with authentication");
+		let creds = CredentialsBuilder::default()
+			.with_username(username.clone())
+			.with_password(password.clone());
+
+		let client = connect(endpoint).await?;
+
+		debug!("Signing in to the database engine at '{:?}' level", auth_level);
+		match auth_level {
+			CredentialsLevel::Root => client.signin(creds.root()?).await?,
+			_ => bail!("Checkpoints can only be created by root users"),
+		};
+
+		client
+	} else if token.is_some() && !is_local {
+		let client = connect(endpoint).await?;
+		if let Some(token) = token {
+			client.authenticate(token).await?;
+		}
+		client
+	} else {
+		debug!("Connecting to the database engine without authentication");
+		connect((endpoint, config)).await?
+	};
+
+	// Create the checkpoint on the server
+	client.query("ALTER SYSTEM CHECKPOINT $path").bind(("path", path)).await?.check()?;
+	info!("Checkpoint created with no errors");
+	// All ok
+	Ok(())
+}
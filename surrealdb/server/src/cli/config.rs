@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use anyhow::Result;
 use surrealdb_core::CommunityComposer;
+use surrealdb_core::catalog::RateLimit;
 use surrealdb_core::options::EngineOptions;
 
 use crate::ntw::client_ip::ClientIp;
@@ -54,4 +55,5 @@ pub struct Config {
 	pub engine: EngineOptions,
 	pub no_identification_headers: bool,
 	pub allow_origin: Vec<String>,
+	pub rate_limit: RateLimit,
 }
@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 
 pub(crate) mod abstraction;
+mod checkpoint;
 mod config;
 mod export;
 mod fix;
@@ -26,6 +27,7 @@ use std::process::ExitCode;
 use std::time::Duration;
 
 use anyhow::Result;
+use checkpoint::CheckpointCommandArguments;
 use clap::{Parser, Subcommand, ValueEnum};
 pub use config::{Config, ConfigCheck, ConfigCheckRequirements};
 use export::ExportCommandArguments;
@@ -169,6 +171,8 @@ enum Commands {
 	Import(ImportCommandArguments),
 	#[command(about = "Export an existing database as a SurrealQL script")]
 	Export(ExportCommandArguments),
+	#[command(about = "Create an on-disk checkpoint of a running RocksDB or SurrealKV datastore")]
+	Checkpoint(CheckpointCommandArguments),
 	#[command(about = "Output the command-line tool and remote server version information")]
 	Version(VersionCommandArguments),
 	#[command(about = "Upgrade to the latest stable version")]
@@ -291,6 +295,7 @@ pub async fn init<
 		Commands::Start(args) => start::init::<C>(composer, args).await,
 		Commands::Import(args) => import::init(args).await,
 		Commands::Export(args) => export::init(args).await,
+		Commands::Checkpoint(args) => checkpoint::init(args).await,
 		Commands::Version(args) => version::init(args).await,
 		Commands::Upgrade(args) => upgrade::init(args).await,
 		#[cfg(feature = "cli")]
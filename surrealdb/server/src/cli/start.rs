@@ -8,6 +8,7 @@ use clap::Args;
 use rustls::crypto::CryptoProvider;
 use surrealdb::engine::{any, tasks};
 use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::catalog::RateLimit;
 use surrealdb_core::kvs::TransactionBuilderFactory;
 use surrealdb_core::options::EngineOptions;
 use tokio_util::sync::CancellationToken;
@@ -72,6 +73,16 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ASYNC_EVENT_PROCESSING_INTERVAL", long = "async-event-interval", value_parser = super::validator::duration)]
 	#[arg(default_value = "5s")]
 	event_processing_interval: Duration,
+	#[arg(env = "SURREAL_BUCKET_LIFECYCLE_INTERVAL", long = "bucket-lifecycle-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	bucket_lifecycle_interval: Duration,
+	#[arg(
+		help = "The interval at which to remove history older than its retention period",
+		help_heading = "Database"
+	)]
+	#[arg(env = "SURREAL_HISTORY_GC_INTERVAL", long = "history-gc-interval", value_parser = super::validator::duration)]
+	#[arg(default_value = "60s")]
+	history_gc_interval: Duration,
 	//
 	// Authentication
 	#[arg(
@@ -124,6 +135,22 @@ pub struct StartCommandArguments {
 	#[arg(env = "SURREAL_ALLOW_ORIGIN", long = "allow-origin")]
 	#[arg(value_delimiter = ',', value_parser = super::validator::cors_origin)]
 	allow_origin: Vec<String>,
+	#[arg(
+		help = "The maximum number of requests per second for each user or client, unless limited on the user or access method"
+	)]
+	#[arg(env = "SURREAL_RATE_LIMIT", long = "rate-limit", value_parser = super::validator::rate_limit)]
+	rate_limit: Option<u32>,
+	#[arg(
+		help = "The maximum number of concurrent requests for each user or client, unless limited on the user or access method"
+	)]
+	#[arg(env = "SURREAL_CONCURRENCY_LIMIT", long = "concurrency-limit")]
+	concurrency_limit: Option<u32>,
+	#[arg(
+		help = "The maximum duration that the queries in a request can run for, unless limited on the user or access method"
+	)]
+	#[arg(env = "SURREAL_REQUEST_TIMEOUT", long = "request-timeout")]
+	#[arg(value_parser = super::validator::duration)]
+	request_timeout: Option<Duration>,
 	//
 	// Database options
 	#[command(flatten)]
@@ -186,9 +213,14 @@ pub async fn init<
 		changefeed_gc_interval,
 		index_compaction_interval,
 		event_processing_interval,
+		bucket_lifecycle_interval,
+		history_gc_interval,
 		no_banner,
 		no_identification_headers,
 		allow_origin,
+		rate_limit,
+		concurrency_limit,
+		request_timeout,
 		..
 	}: StartCommandArguments,
 ) -> Result<()> {
@@ -220,7 +252,9 @@ pub async fn init<
 		.with_node_membership_cleanup_interval(node_membership_cleanup_interval)
 		.with_changefeed_gc_interval(changefeed_gc_interval)
 		.with_index_compaction_interval(index_compaction_interval)
-		.with_event_processing_interval(event_processing_interval);
+		.with_event_processing_interval(event_processing_interval)
+		.with_bucket_lifecycle_interval(bucket_lifecycle_interval)
+		.with_history_gc_interval(history_gc_interval);
 	// Configure the config
 	let Some(bind) = listen_addresses.first().copied() else {
 		return Err(anyhow::anyhow!("No listen address provided"));
@@ -233,6 +267,11 @@ pub async fn init<
 		pass,
 		no_identification_headers,
 		allow_origin,
+		rate_limit: RateLimit {
+			rate: rate_limit,
+			concurrency: concurrency_limit,
+			timeout: request_timeout,
+		},
 		engine,
 		crt,
 		key,
@@ -35,6 +35,14 @@ pub(crate) fn dir_exists(path: &str) -> Result<PathBuf, String> {
 	Ok(path)
 }
 
+pub(crate) fn rate_limit(v: &str) -> Result<u32, String> {
+	match v.parse::<u32>() {
+		Ok(0) => Err(String::from("Ensure the rate limit is greater than 0")),
+		Ok(v) => Ok(v),
+		Err(e) => Err(e.to_string()),
+	}
+}
+
 pub(crate) fn endpoint_valid(v: &str) -> Result<String, String> {
 	// Split the endpoint into scheme and path
 	fn split_endpoint(v: &str) -> (&str, &str) {
@@ -51,7 +59,7 @@ pub(crate) fn endpoint_valid(v: &str) -> Result<String, String> {
 	}
 	// Validate the scheme
 	match split_endpoint(v).0 {
-		"http" | "https" | "ws" | "wss" | "mem" | "rocksdb" | "surrealkv" | "tikv" => {
+		"http" | "https" | "ws" | "wss" | "mem" | "rocksdb" | "surrealkv" | "redb" | "tikv" => {
 			Ok(v.to_string())
 		}
 		_ => Err(String::from("Provide a valid database connection string")),
@@ -37,10 +37,24 @@ pub const APP_ENDPOINT: &str = "https://surrealdb.com/surrealist";
 pub static NET_MAX_CONCURRENT_REQUESTS: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_NET_MAX_CONCURRENT_REQUESTS", usize, 1 << 20);
 
+/// How often the rate limits for an authenticated identity are refreshed
+/// from the datastore, in seconds (default: 5)
+pub static RATE_LIMIT_REFRESH_INTERVAL: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_RATE_LIMIT_REFRESH_INTERVAL", u64, 5);
+
+/// How many rate limited identities can be tracked before idle identities
+/// are removed (default: 10,000)
+pub static RATE_LIMIT_MAX_IDLE_ENTRIES: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_RATE_LIMIT_MAX_IDLE_ENTRIES", usize, 10_000);
+
 /// The maximum HTTP body size of the HTTP /ml endpoints (default: 4 GiB)
 pub static HTTP_MAX_ML_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_ML_BODY_SIZE", usize, 4 << 30);
 
+/// The maximum HTTP body size of the HTTP /files endpoints (default: 4 GiB)
+pub static HTTP_MAX_FILES_BODY_SIZE: LazyLock<usize> =
+	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_FILES_BODY_SIZE", usize, 4 << 30);
+
 /// The maximum HTTP body size of the HTTP /sql endpoint (default: 1 MiB)
 pub static HTTP_MAX_SQL_BODY_SIZE: LazyLock<usize> =
 	lazy_env_parse!(bytes, "SURREAL_HTTP_MAX_SQL_BODY_SIZE", usize, 1 << 20);
@@ -140,6 +154,34 @@ pub static WEBSOCKET_RESPONSE_FLUSH_PERIOD: LazyLock<u64> =
 pub static GQL_SUBSCRIPTION_CHANNEL_CAPACITY: LazyLock<usize> =
 	lazy_env_parse!("SURREAL_GQL_SUBSCRIPTION_CHANNEL_CAPACITY", usize, 1024);
 
+/// How many notifications can be buffered per gRPC LIVE stream before
+/// backpressure drops new notifications (default: 1024)
+#[cfg(feature = "grpc")]
+pub static GRPC_LIVE_CHANNEL_SIZE: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_GRPC_LIVE_CHANNEL_SIZE", usize, 1024);
+
+/// How many seconds a gRPC transaction can be idle before it is cancelled
+/// (default: 60 seconds)
+#[cfg(feature = "grpc")]
+pub static GRPC_TRANSACTION_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_GRPC_TRANSACTION_TIMEOUT", u64, 60);
+
+/// How many seconds an attached gRPC session can be idle before it is
+/// detached (default: 3600 seconds)
+#[cfg(feature = "grpc")]
+pub static GRPC_SESSION_TIMEOUT: LazyLock<u64> =
+	lazy_env_parse!("SURREAL_GRPC_SESSION_TIMEOUT", u64, 3600);
+
+/// The maximum number of open gRPC transactions (default: 1000)
+#[cfg(feature = "grpc")]
+pub static GRPC_MAX_TRANSACTIONS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_GRPC_MAX_TRANSACTIONS", usize, 1000);
+
+/// The maximum number of attached gRPC sessions (default: 10000)
+#[cfg(feature = "grpc")]
+pub static GRPC_MAX_SESSIONS: LazyLock<usize> =
+	lazy_env_parse!("SURREAL_GRPC_MAX_SESSIONS", usize, 10000);
+
 /// The number of runtime worker threads to start (default: the number of CPU
 /// cores, minimum 4)
 pub static RUNTIME_WORKER_THREADS: LazyLock<usize> =
@@ -7,6 +7,7 @@ use clap::Args;
 use rand::Rng;
 use surrealdb::opt::capabilities::Capabilities as SdkCapabilities;
 use surrealdb_core::buc::BucketStoreProvider;
+use surrealdb_core::kvs::encryption::{EncryptionConfig, EncryptionKey};
 use surrealdb_core::kvs::{Datastore, TransactionBuilderFactory};
 use tokio::time::{Instant, sleep, timeout};
 use tokio_util::sync::CancellationToken;
@@ -48,6 +49,15 @@ pub struct StartCommandDbsOptions {
 	#[arg(env = "SURREAL_IMPORT_FILE", long = "import-file")]
 	#[arg(value_parser = super::cli::validator::file_exists)]
 	import_file: Option<PathBuf>,
+	#[arg(help = "A 64 character hex encoded key used to encrypt stored data at rest")]
+	#[arg(env = "SURREAL_ENCRYPTION_KEY", long = "encryption-key")]
+	#[arg(hide_env_values = true)]
+	encryption_key: Option<EncryptionKey>,
+	#[arg(help = "A comma-separated list of previous encryption keys, used when rotating keys")]
+	#[arg(env = "SURREAL_ENCRYPTION_KEY_PREVIOUS", long = "encryption-key-previous")]
+	#[arg(value_delimiter = ',', num_args = 1.., requires = "encryption_key")]
+	#[arg(hide_env_values = true)]
+	encryption_key_previous: Vec<EncryptionKey>,
 	// Slow query logging configuration. When `slow_log_threshold` is set, any
 	// statement taking longer than the threshold will be logged along with a
 	// normalized, single-line SQL rendering. You can control which `$param`
@@ -729,6 +739,8 @@ pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
 		capabilities,
 		temporary_directory,
 		import_file,
+		encryption_key,
+		encryption_key_previous,
 		slow_log_threshold,
 		slow_log_param_allow,
 		slow_log_param_deny,
@@ -777,8 +789,20 @@ pub async fn init<C: TransactionBuilderFactory + BucketStoreProvider>(
 	// Log the specified server capabilities
 	debug!("Server capabilities: {capabilities}");
 	// Parse and setup the desired kv datastore
-	let dbs = Datastore::new_with_factory::<C>(composer, &opt.path, canceller)
-		.await?
+	let dbs = Datastore::new_with_factory::<C>(composer, &opt.path, canceller).await?;
+	// Enable encryption at rest if a key was specified, and otherwise check
+	// that the datastore was not encrypted when it was created
+	let dbs = match encryption_key {
+		Some(key) => {
+			let config = EncryptionConfig {
+				key,
+				previous: encryption_key_previous,
+			};
+			dbs.with_encryption(&config).await?
+		}
+		None => dbs.without_encryption().await?,
+	};
+	let dbs = dbs
 		.with_notifications()
 		.with_query_timeout(query_timeout)
 		.with_transaction_timeout(transaction_timeout)
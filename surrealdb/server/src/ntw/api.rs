@@ -2,7 +2,7 @@ use axum::body::Bytes;
 use axum::extract::{DefaultBodyLimit, Path, Query};
 use axum::http::{HeaderMap, Method};
 use axum::response::IntoResponse;
-use axum::routing::any;
+use axum::routing::{any, get};
 use axum::{Extension, Router};
 use surrealdb_core::api::err::ApiError;
 use surrealdb_core::api::request::ApiRequest;
@@ -15,6 +15,7 @@ use uuid::Uuid;
 
 use super::AppState;
 use super::error::{ApiHandlerError, ResponseError};
+use super::output::Output;
 use crate::cnf::HTTP_MAX_API_BODY_SIZE;
 use crate::ntw::error::Error as NetError;
 use crate::ntw::params::Params;
@@ -24,11 +25,30 @@ where
 	S: Clone + Send + Sync + 'static,
 {
 	Router::new()
+		.route("/api/{ns}/{db}/.well-known/openapi.json", get(openapi))
 		.route("/api/{ns}/{db}/{*path}", any(handler))
 		.route_layer(DefaultBodyLimit::disable())
 		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_API_BODY_SIZE))
 }
 
+/// Serves the OpenAPI document describing the APIs defined on a database
+async fn openapi(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Path((ns, db)): Path<(String, String)>,
+) -> Result<Output, ResponseError> {
+	// Get a database reference
+	let ds = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !ds.allows_http_route(&RouteTarget::Api) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Api);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Api.to_string()).into());
+	}
+	// Generate the document for the requested database
+	let doc = ds.api_document(&ns, &db, &session).await.map_err(ResponseError)?;
+	Ok(Output::json_value(&doc))
+}
+
 async fn handler(
 	Extension(state): Extension<AppState>,
 	Extension(session): Extension<Session>,
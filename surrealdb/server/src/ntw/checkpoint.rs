@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use anyhow::Context;
+use axum::routing::options;
+use axum::{Extension, Router};
+use bytes::Bytes;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::dbs::capabilities::RouteTarget;
+
+use super::AppState;
+use super::error::ResponseError;
+use super::output::Output;
+use crate::ntw::error::Error as NetError;
+use crate::ntw::input::bytes_to_utf8;
+
+pub fn router<S>() -> Router<S>
+where
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new().route("/checkpoint", options(|| async {}).post(handler))
+}
+
+async fn handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	body: Bytes,
+) -> Result<Output, ResponseError> {
+	// Get the datastore reference
+	let db = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !db.allows_http_route(&RouteTarget::Checkpoint) {
+		warn!(
+			"Capabilities denied HTTP route request attempt, target: '{}'",
+			&RouteTarget::Checkpoint
+		);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Checkpoint.to_string()).into());
+	}
+	// Convert the received checkpoint path
+	let path = bytes_to_utf8(&body).context("Non UTF-8 request body").map_err(ResponseError)?;
+	// Create the checkpoint in the requested directory
+	match db.checkpoint(&session, Path::new(path.trim())).await {
+		Ok(_) => Ok(Output::None),
+		// There was an error when creating the checkpoint
+		Err(err) => Err(ResponseError(err)),
+	}
+}
@@ -1,7 +1,9 @@
 use std::error::Error as StdError;
+use std::time::Duration;
 
 use axum::Json;
 use axum::response::{IntoResponse, Response};
+use http::header::RETRY_AFTER;
 use http::{HeaderName, HeaderValue, StatusCode};
 use serde::{Serialize, Serializer};
 use surrealdb_core::api::X_SURREAL_REQUEST_ID;
@@ -36,6 +38,9 @@ pub enum Error {
 
 	#[error("There was a problem parsing the header {0}: {1}")]
 	InvalidHeader(HeaderName, String),
+
+	#[error("Too many requests have been made, retry after {0:?}")]
+	TooManyRequests(Duration),
 }
 
 impl IntoResponse for Error {
@@ -73,6 +78,18 @@ impl IntoResponse for Error {
 					information: Some(self.to_string()),
 				}.into_response()
 			}
+			Error::TooManyRequests(retry) => {
+				// Round up to the next whole second
+				let secs = retry.as_secs() + u64::from(retry.subsec_nanos() > 0);
+				let mut res = ErrorMessage {
+					code: StatusCode::TOO_MANY_REQUESTS,
+					details: Some("Too many requests".to_string()),
+					description: Some("The rate limits for this client have been exceeded. Retry the request after the time specified in the Retry-After header.".to_string()),
+					information: Some(self.to_string()),
+				}.into_response();
+				res.headers_mut().insert(RETRY_AFTER, HeaderValue::from(secs));
+				res
+			}
 			Error::InvalidStorage =>
 				ErrorMessage {
 					code: StatusCode::INTERNAL_SERVER_ERROR,
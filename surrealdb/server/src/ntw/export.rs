@@ -55,6 +55,12 @@ async fn post_handler(
 		Format::Flatbuffers => surrealdb_core::rpc::format::flatbuffers::decode(&body)
 			.map_err(anyhow::Error::msg)
 			.map_err(ResponseError)?,
+		Format::MessagePack => surrealdb_core::rpc::format::msgpack::decode(&body)
+			.map_err(anyhow::Error::msg)
+			.map_err(ResponseError)?,
+		Format::Protobuf => surrealdb_core::rpc::format::protobuf::decode(&body)
+			.map_err(anyhow::Error::msg)
+			.map_err(ResponseError)?,
 		Format::Unsupported => {
 			return Err(ResponseError(anyhow::Error::msg("unsupported body format")));
 		}
@@ -0,0 +1,177 @@
+//! This file defines the endpoints for streaming files into and out of
+//! buckets, with support for HTTP range requests, and for serving files
+//! through presigned URLs.
+
+use axum::body::Body;
+use axum::extract::{DefaultBodyLimit, Path, Query};
+use axum::response::Response;
+use axum::routing::get;
+use axum::{Extension, Router};
+use futures_util::TryStreamExt;
+use http::header::{
+	ACCEPT_RANGES, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, LAST_MODIFIED, RANGE,
+};
+use http::{HeaderMap, StatusCode};
+use serde::Deserialize;
+use surrealdb_core::buc::presign::PresignedFile;
+use surrealdb_core::buc::store::ByteRange;
+use surrealdb_core::buc::{FileContent, FileStream};
+use surrealdb_core::dbs::Session;
+use surrealdb_core::dbs::capabilities::RouteTarget;
+use tower_http::limit::RequestBodyLimitLayer;
+
+use super::AppState;
+use super::error::{Error as NetError, ResponseError};
+use super::output::Output;
+use crate::cnf::HTTP_MAX_FILES_BODY_SIZE;
+
+pub fn router<S>() -> Router<S>
+where
+	S: Clone + Send + Sync + 'static,
+{
+	Router::new()
+		.route("/files/{ns}/{db}/{bucket}/{*key}", get(get_handler).put(put_handler))
+		.route("/presigned/{ns}/{db}/{bucket}/{*key}", get(presigned_handler))
+		.route_layer(DefaultBodyLimit::disable())
+		.layer(RequestBodyLimitLayer::new(*HTTP_MAX_FILES_BODY_SIZE))
+}
+
+#[derive(Deserialize, Debug, Clone)]
+struct PresignParams {
+	expires: i64,
+	signature: String,
+}
+
+/// Parses the byte range requested in the headers, ignoring ranges we do not support
+fn requested_range(headers: &HeaderMap) -> Result<Option<ByteRange>, NetError> {
+	match headers.get(RANGE) {
+		Some(v) => {
+			let v = v.to_str().map_err(|e| NetError::InvalidHeader(RANGE, e.to_string()))?;
+			Ok(ByteRange::parse_header(v))
+		}
+		None => Ok(None),
+	}
+}
+
+/// Builds the response for the requested content of a file
+fn file_response(file: FileStream) -> Result<Response, ResponseError> {
+	let mut res = Response::builder()
+		.header(ACCEPT_RANGES, "bytes")
+		.header(LAST_MODIFIED, file.updated.format("%a, %d %b %Y %H:%M:%S GMT").to_string());
+	if let Some(content_type) = file.content_type {
+		res = res.header(CONTENT_TYPE, content_type);
+	}
+	let res = match file.content {
+		FileContent::Full(stream) => res
+			.status(StatusCode::OK)
+			.header(CONTENT_LENGTH, file.size)
+			.body(Body::from_stream(stream.map_err(anyhow::Error::msg)))?,
+		FileContent::Partial(range, stream) => res
+			.status(StatusCode::PARTIAL_CONTENT)
+			.header(CONTENT_LENGTH, range.end - range.start)
+			.header(CONTENT_RANGE, format!("bytes {}-{}/{}", range.start, range.end - 1, file.size))
+			.body(Body::from_stream(stream.map_err(anyhow::Error::msg)))?,
+		FileContent::Unsatisfiable => res
+			.status(StatusCode::RANGE_NOT_SATISFIABLE)
+			.header(CONTENT_RANGE, format!("bytes */{}", file.size))
+			.body(Body::empty())?,
+	};
+	Ok(res)
+}
+
+/// Streams a file, or a byte range of a file, out of a bucket
+async fn get_handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Path((ns, db, bucket, key)): Path<(String, String, String, String)>,
+	headers: HeaderMap,
+) -> Result<Response, ResponseError> {
+	// Get the datastore reference
+	let ds = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !ds.allows_http_route(&RouteTarget::Files) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Files);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Files.to_string()).into());
+	}
+	// Update the session with the NS & DB
+	let session = session.with_ns(&ns).with_db(&db);
+	// Parse the requested byte range
+	let range = requested_range(&headers)?;
+	// Fetch the file from the bucket
+	let Some(file) = ds.get_file(&session, &bucket, &key, range).await.map_err(ResponseError)?
+	else {
+		return Err(NetError::NotFound(format!("/files/{ns}/{db}/{bucket}/{key}")).into());
+	};
+	file_response(file)
+}
+
+/// Streams a file, or a byte range of a file, out of a bucket using a
+/// presigned URL, without requiring authentication
+async fn presigned_handler(
+	Extension(state): Extension<AppState>,
+	Path((ns, db, bucket, key)): Path<(String, String, String, String)>,
+	Query(params): Query<PresignParams>,
+	headers: HeaderMap,
+) -> Result<Response, ResponseError> {
+	// Get the datastore reference
+	let ds = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !ds.allows_http_route(&RouteTarget::Files) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Files);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Files.to_string()).into());
+	}
+	// Check that the signature is valid and has not expired
+	let file = PresignedFile {
+		ns: &ns,
+		db: &db,
+		bucket: &bucket,
+		key: &key,
+	};
+	if !file.verify(params.expires, &params.signature) {
+		return Err(NetError::InvalidAuth.into());
+	}
+	// The signature grants access to the file, so it is read as the owner
+	let session = Session::owner().with_ns(&ns).with_db(&db);
+	// Parse the requested byte range
+	let range = requested_range(&headers)?;
+	// Fetch the file from the bucket
+	let Some(file) = ds.get_file(&session, &bucket, &key, range).await.map_err(ResponseError)?
+	else {
+		return Err(NetError::NotFound(format!("/presigned/{ns}/{db}/{bucket}/{key}")).into());
+	};
+	file_response(file)
+}
+
+/// Streams the request body into a file in a bucket
+async fn put_handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	Path((ns, db, bucket, key)): Path<(String, String, String, String)>,
+	headers: HeaderMap,
+	body: Body,
+) -> Result<Output, ResponseError> {
+	// Get the datastore reference
+	let ds = &state.datastore;
+	// Check if capabilities allow querying the requested HTTP route
+	if !ds.allows_http_route(&RouteTarget::Files) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Files);
+		return Err(NetError::ForbiddenRoute(RouteTarget::Files.to_string()).into());
+	}
+	// Update the session with the NS & DB
+	let session = session.with_ns(&ns).with_db(&db);
+	// Store the content type of the request body with the file
+	let content_type = match headers.get(CONTENT_TYPE) {
+		Some(v) => Some(
+			v.to_str()
+				.map_err(|e| NetError::InvalidHeader(CONTENT_TYPE, e.to_string()))?
+				.to_owned(),
+		),
+		None => None,
+	};
+	// Stream the request body into the bucket
+	let stream = body.into_data_stream().map_err(|e| e.to_string());
+	ds.put_file(&session, &bucket, &key, content_type, Box::pin(stream))
+		.await
+		.map_err(ResponseError)?;
+	Ok(Output::None)
+}
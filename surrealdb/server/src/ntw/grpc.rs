@@ -0,0 +1,138 @@
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{MethodRouter, post};
+use axum::{Extension, Router};
+use surrealdb_core::dbs::Session;
+use surrealdb_core::dbs::capabilities::RouteTarget;
+use surrealdb_core::mem::ALLOC;
+use surrealdb_core::rpc::{DbResult, Method};
+use surrealdb_types::Value;
+use tonic::Status;
+use tonic::server::Grpc;
+use tonic_prost::ProstCodec;
+
+use super::AppState;
+use crate::cnf::HTTP_MAX_RPC_BODY_SIZE;
+use crate::rpc::RpcState;
+use crate::rpc::grpc::proto::{
+	CredentialsRequest, EmptyRequest, KillRequest, LiveRequest, LiveResponse, PatchRequest,
+	QueryRequest, RelateRequest, ResourceRequest, RunRequest, SessionRequest, SetRequest,
+	TokenRequest, TransactionRequest, UnsetRequest, UseRequest,
+};
+use crate::rpc::grpc::{GrpcCall, GrpcRequest, proto, response, status};
+
+pub fn router() -> Router<Arc<RpcState>> {
+	Router::new()
+		.route("/surrealdb.rpc.v1.Surreal/Ping", unary::<EmptyRequest>(Method::Ping))
+		.route("/surrealdb.rpc.v1.Surreal/Version", unary::<EmptyRequest>(Method::Version))
+		.route("/surrealdb.rpc.v1.Surreal/Info", unary::<TransactionRequest>(Method::Info))
+		.route("/surrealdb.rpc.v1.Surreal/Use", unary::<UseRequest>(Method::Use))
+		.route("/surrealdb.rpc.v1.Surreal/Signup", unary::<CredentialsRequest>(Method::Signup))
+		.route("/surrealdb.rpc.v1.Surreal/Signin", unary::<CredentialsRequest>(Method::Signin))
+		.route(
+			"/surrealdb.rpc.v1.Surreal/Authenticate",
+			unary::<TokenRequest>(Method::Authenticate),
+		)
+		.route("/surrealdb.rpc.v1.Surreal/Refresh", unary::<TokenRequest>(Method::Refresh))
+		.route("/surrealdb.rpc.v1.Surreal/Revoke", unary::<TokenRequest>(Method::Revoke))
+		.route("/surrealdb.rpc.v1.Surreal/Invalidate", unary::<SessionRequest>(Method::Invalidate))
+		.route("/surrealdb.rpc.v1.Surreal/Reset", unary::<SessionRequest>(Method::Reset))
+		.route("/surrealdb.rpc.v1.Surreal/Set", unary::<SetRequest>(Method::Set))
+		.route("/surrealdb.rpc.v1.Surreal/Unset", unary::<UnsetRequest>(Method::Unset))
+		.route("/surrealdb.rpc.v1.Surreal/Query", unary::<QueryRequest>(Method::Query))
+		.route("/surrealdb.rpc.v1.Surreal/Select", unary::<ResourceRequest>(Method::Select))
+		.route("/surrealdb.rpc.v1.Surreal/Create", unary::<ResourceRequest>(Method::Create))
+		.route("/surrealdb.rpc.v1.Surreal/Insert", unary::<ResourceRequest>(Method::Insert))
+		.route(
+			"/surrealdb.rpc.v1.Surreal/InsertRelation",
+			unary::<ResourceRequest>(Method::InsertRelation),
+		)
+		.route("/surrealdb.rpc.v1.Surreal/Upsert", unary::<ResourceRequest>(Method::Upsert))
+		.route("/surrealdb.rpc.v1.Surreal/Update", unary::<ResourceRequest>(Method::Update))
+		.route("/surrealdb.rpc.v1.Surreal/Merge", unary::<ResourceRequest>(Method::Merge))
+		.route("/surrealdb.rpc.v1.Surreal/Patch", unary::<PatchRequest>(Method::Patch))
+		.route("/surrealdb.rpc.v1.Surreal/Delete", unary::<ResourceRequest>(Method::Delete))
+		.route("/surrealdb.rpc.v1.Surreal/Relate", unary::<RelateRequest>(Method::Relate))
+		.route("/surrealdb.rpc.v1.Surreal/Run", unary::<RunRequest>(Method::Run))
+		.route("/surrealdb.rpc.v1.Surreal/Live", post(live_handler))
+		.route("/surrealdb.rpc.v1.Surreal/Kill", unary::<KillRequest>(Method::Kill))
+		.route("/surrealdb.rpc.v1.Surreal/Attach", unary::<SessionRequest>(Method::Attach))
+		.route("/surrealdb.rpc.v1.Surreal/Detach", unary::<SessionRequest>(Method::Detach))
+		.route("/surrealdb.rpc.v1.Surreal/Sessions", unary::<EmptyRequest>(Method::Sessions))
+		.route("/surrealdb.rpc.v1.Surreal/Begin", unary::<SessionRequest>(Method::Begin))
+		.route("/surrealdb.rpc.v1.Surreal/Commit", unary::<TransactionRequest>(Method::Commit))
+		.route("/surrealdb.rpc.v1.Surreal/Cancel", unary::<TransactionRequest>(Method::Cancel))
+}
+
+/// Check that a gRPC call can be handled
+fn check(state: &AppState) -> Result<(), Status> {
+	// Check if capabilities allow querying the requested HTTP route
+	if !state.datastore.allows_http_route(&RouteTarget::Rpc) {
+		warn!("Capabilities denied HTTP route request attempt, target: '{}'", &RouteTarget::Rpc);
+		return Err(Status::permission_denied(format!("Forbidden route: {}", RouteTarget::Rpc)));
+	}
+	// Check to see available memory
+	if ALLOC.is_beyond_threshold() {
+		return Err(Status::resource_exhausted("The server is unable to handle the request"));
+	}
+	Ok(())
+}
+
+fn codec<T, U>() -> Grpc<ProstCodec<T, U>>
+where
+	T: prost::Message + 'static,
+	U: prost::Message + Default + 'static,
+{
+	Grpc::new(ProstCodec::default()).max_decoding_message_size(*HTTP_MAX_RPC_BODY_SIZE)
+}
+
+/// Route a gRPC call which executes an RPC method, and returns its result
+fn unary<R: GrpcRequest>(method: Method) -> MethodRouter<Arc<RpcState>> {
+	post(
+		move |Extension(state): Extension<AppState>,
+		      Extension(session): Extension<Session>,
+		      State(rpc_state): State<Arc<RpcState>>,
+		      req: Request| async move {
+			let service = tower::service_fn(move |req: tonic::Request<R>| {
+				let state = state.clone();
+				let grpc = rpc_state.grpc.clone();
+				let session = session.clone();
+				async move {
+					check(&state)?;
+					let call = req.into_inner().into_call(method)?;
+					// Execute the specified method
+					let res = GrpcCall::new(grpc, session).execute_call(call).await;
+					Ok::<_, Status>(tonic::Response::new(response(res)?))
+				}
+			});
+			codec::<proto::Response, R>().unary(service, req).await.into_response()
+		},
+	)
+}
+
+async fn live_handler(
+	Extension(state): Extension<AppState>,
+	Extension(session): Extension<Session>,
+	State(rpc_state): State<Arc<RpcState>>,
+	req: Request,
+) -> Response {
+	let service = tower::service_fn(move |req: tonic::Request<LiveRequest>| {
+		let state = state.clone();
+		let grpc = rpc_state.grpc.clone();
+		let session = session.clone();
+		async move {
+			check(&state)?;
+			let call = req.into_inner().into_call(Method::Live)?;
+			// Create the LIVE query
+			let stream = match GrpcCall::new(grpc.clone(), session).execute_call(call).await {
+				Ok(DbResult::Other(Value::Uuid(lqid))) => grpc.subscribe(lqid.into_inner()),
+				Ok(_) => return Err(Status::internal("Expected a LIVE query id")),
+				Err(err) => return Err(status(err)),
+			};
+			Ok::<_, Status>(tonic::Response::new(stream))
+		}
+	});
+	codec::<LiveResponse, LiveRequest>().server_streaming(service, req).await.into_response()
+}
@@ -3,9 +3,9 @@ use axum_extra::headers::Header;
 use http::{HeaderName, HeaderValue};
 
 use crate::ntw::headers::content_type::{
-	HEADER_VALUE_APPLICATION_CBOR, HEADER_VALUE_APPLICATION_JSON,
+	HEADER_VALUE_APPLICATION_CBOR, HEADER_VALUE_APPLICATION_JSON, HEADER_VALUE_APPLICATION_MSGPACK,
 	HEADER_VALUE_APPLICATION_OCTET_STREAM, HEADER_VALUE_APPLICATION_SURREAL_DB_FLATBUFFERS,
-	HEADER_VALUE_TEXT_PLAIN,
+	HEADER_VALUE_APPLICATION_SURREAL_DB_PROTOBUF, HEADER_VALUE_TEXT_PLAIN,
 };
 
 /// Typed header implementation for the `Accept` header.
@@ -16,6 +16,8 @@ pub enum Accept {
 	ApplicationCbor,
 	ApplicationOctetStream,
 	ApplicationFlatbuffers,
+	ApplicationMsgpack,
+	ApplicationProtobuf,
 }
 
 impl std::fmt::Display for Accept {
@@ -28,6 +30,8 @@ impl std::fmt::Display for Accept {
 				f.write_str(surrealdb_core::api::format::OCTET_STREAM)
 			}
 			Accept::ApplicationFlatbuffers => f.write_str(surrealdb_core::api::format::FLATBUFFERS),
+			Accept::ApplicationMsgpack => f.write_str(surrealdb_core::api::format::MSGPACK),
+			Accept::ApplicationProtobuf => f.write_str(surrealdb_core::api::format::PROTOBUF),
 		}
 	}
 }
@@ -52,6 +56,8 @@ impl Header for Accept {
 			surrealdb_core::api::format::CBOR => Ok(Accept::ApplicationCbor),
 			surrealdb_core::api::format::OCTET_STREAM => Ok(Accept::ApplicationOctetStream),
 			surrealdb_core::api::format::FLATBUFFERS => Ok(Accept::ApplicationFlatbuffers),
+			surrealdb_core::api::format::MSGPACK => Ok(Accept::ApplicationMsgpack),
+			surrealdb_core::api::format::PROTOBUF => Ok(Accept::ApplicationProtobuf),
 			_ => Err(headers::Error::invalid()),
 		}
 	}
@@ -80,6 +86,8 @@ impl From<&Accept> for HeaderValue {
 			Accept::ApplicationFlatbuffers => {
 				HEADER_VALUE_APPLICATION_SURREAL_DB_FLATBUFFERS.clone()
 			}
+			Accept::ApplicationMsgpack => HEADER_VALUE_APPLICATION_MSGPACK.clone(),
+			Accept::ApplicationProtobuf => HEADER_VALUE_APPLICATION_SURREAL_DB_PROTOBUF.clone(),
 		}
 	}
 }
@@ -19,6 +19,10 @@ pub enum ContentType {
 	ApplicationOctetStream,
 	/// SurrealDB Flatbuffers format (`application/surrealdb+flatbuffers`)
 	ApplicationSurrealDBFlatbuffers,
+	/// MessagePack format (`application/vnd.msgpack`)
+	ApplicationMsgpack,
+	/// SurrealDB Protobuf format (`application/vnd.surrealdb.protobuf`)
+	ApplicationSurrealDBProtobuf,
 }
 
 /// Pre-allocated static header value for `text/plain` content type
@@ -36,6 +40,12 @@ pub(super) static HEADER_VALUE_APPLICATION_OCTET_STREAM: HeaderValue =
 /// Pre-allocated static header value for `application/surrealdb+flatbuffers` content type
 pub(super) static HEADER_VALUE_APPLICATION_SURREAL_DB_FLATBUFFERS: HeaderValue =
 	HeaderValue::from_static(surrealdb_core::api::format::FLATBUFFERS);
+/// Pre-allocated static header value for `application/vnd.msgpack` content type
+pub(super) static HEADER_VALUE_APPLICATION_MSGPACK: HeaderValue =
+	HeaderValue::from_static(surrealdb_core::api::format::MSGPACK);
+/// Pre-allocated static header value for `application/vnd.surrealdb.protobuf` content type
+pub(super) static HEADER_VALUE_APPLICATION_SURREAL_DB_PROTOBUF: HeaderValue =
+	HeaderValue::from_static(surrealdb_core::api::format::PROTOBUF);
 
 impl std::fmt::Display for ContentType {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -49,6 +59,10 @@ impl std::fmt::Display for ContentType {
 			ContentType::ApplicationSurrealDBFlatbuffers => {
 				f.write_str(surrealdb_core::api::format::FLATBUFFERS)
 			}
+			ContentType::ApplicationMsgpack => f.write_str(surrealdb_core::api::format::MSGPACK),
+			ContentType::ApplicationSurrealDBProtobuf => {
+				f.write_str(surrealdb_core::api::format::PROTOBUF)
+			}
 		}
 	}
 }
@@ -74,6 +88,8 @@ impl Header for ContentType {
 			surrealdb_core::api::format::FLATBUFFERS => {
 				Ok(ContentType::ApplicationSurrealDBFlatbuffers)
 			}
+			surrealdb_core::api::format::MSGPACK => Ok(ContentType::ApplicationMsgpack),
+			surrealdb_core::api::format::PROTOBUF => Ok(ContentType::ApplicationSurrealDBProtobuf),
 			_ => Err(headers::Error::invalid()),
 		}
 	}
@@ -102,6 +118,10 @@ impl From<&ContentType> for HeaderValue {
 			ContentType::ApplicationSurrealDBFlatbuffers => {
 				HEADER_VALUE_APPLICATION_SURREAL_DB_FLATBUFFERS.clone()
 			}
+			ContentType::ApplicationMsgpack => HEADER_VALUE_APPLICATION_MSGPACK.clone(),
+			ContentType::ApplicationSurrealDBProtobuf => {
+				HEADER_VALUE_APPLICATION_SURREAL_DB_PROTOBUF.clone()
+			}
 		}
 	}
 }
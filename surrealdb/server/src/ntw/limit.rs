@@ -0,0 +1,372 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::Extension;
+use axum::body::Body;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use http::Request;
+use surrealdb_core::catalog::RateLimit;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::iam::Level;
+use surrealdb_core::iam::limit::rate_limit;
+use surrealdb_core::kvs::Datastore;
+use surrealdb_types::{Error as TypesError, NotAllowedError};
+
+use super::AppState;
+use crate::cnf::{RATE_LIMIT_MAX_IDLE_ENTRIES, RATE_LIMIT_REFRESH_INTERVAL};
+use crate::ntw::error::Error as NetError;
+
+/// The reason why a request was rejected by the [`Limiter`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rejection {
+	/// The request rate was exceeded, and a new request can be made after the duration
+	Rate(Duration),
+	/// The maximum number of concurrent requests was exceeded
+	Concurrency,
+}
+
+impl Rejection {
+	/// The duration after which the client should retry the request
+	pub fn retry_after(&self) -> Duration {
+		match self {
+			Rejection::Rate(v) => *v,
+			Rejection::Concurrency => Duration::from_secs(1),
+		}
+	}
+}
+
+impl From<Rejection> for TypesError {
+	fn from(v: Rejection) -> Self {
+		let message = match v {
+			Rejection::Rate(_) => "Too many requests have been made in a short period of time",
+			Rejection::Concurrency => "Too many requests are being processed at once",
+		};
+		TypesError::not_allowed(message.to_string(), NotAllowedError::RateLimited)
+	}
+}
+
+/// Enforces the request rate, concurrency, and query time limits for each
+/// authenticated identity.
+///
+/// The limits for an identity are taken from the `LIMIT` clause of the
+/// `DEFINE USER` or `DEFINE ACCESS` statement used to authenticate, with any
+/// limit which is not set there falling back to the server-wide defaults.
+/// Anonymous sessions are limited per client IP address using the defaults,
+/// and anonymous sessions without a client IP address share a single limit.
+#[derive(Debug, Default)]
+pub struct Limiter {
+	/// The server-wide limits
+	defaults: RateLimit,
+	/// The current state for each identity
+	entries: DashMap<String, Arc<Entry>>,
+}
+
+#[derive(Debug)]
+struct Entry {
+	/// The number of requests currently being processed
+	active: AtomicU32,
+	/// The limits and token bucket state for this identity
+	state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+	/// The limits which apply to this identity
+	limit: RateLimit,
+	/// When the limits were last fetched from the datastore
+	fetched: Option<Instant>,
+	/// The number of requests which can currently be made
+	tokens: f64,
+	/// When this identity last made a request
+	used: Instant,
+}
+
+/// A permit to process a request, which releases its concurrency slot when dropped
+#[derive(Debug)]
+pub struct Permit {
+	entry: Arc<Entry>,
+	timeout: Option<Duration>,
+}
+
+impl Permit {
+	/// The maximum duration that the queries in this request can run for
+	pub fn timeout(&self) -> Option<Duration> {
+		self.timeout
+	}
+}
+
+impl Drop for Permit {
+	fn drop(&mut self) {
+		self.entry.active.fetch_sub(1, Ordering::AcqRel);
+	}
+}
+
+impl Limiter {
+	/// Create a new limiter with the specified server-wide limits
+	pub fn new(defaults: RateLimit) -> Self {
+		Self {
+			defaults,
+			entries: DashMap::new(),
+		}
+	}
+
+	/// Attempt to start processing a request on behalf of the specified session
+	pub async fn acquire(&self, ds: &Datastore, session: &Session) -> Result<Permit, Rejection> {
+		// Remove any identities which are no longer being used
+		if self.entries.len() > *RATE_LIMIT_MAX_IDLE_ENTRIES {
+			self.prune();
+		}
+		// Fetch or create the entry for this identity
+		let entry =
+			self.entries.entry(identity(session)).or_insert_with(|| Arc::new(Entry::new())).clone();
+		// Refresh the limits for this identity if necessary
+		if entry.state().fetched.is_none_or(|v| v.elapsed() >= refresh_interval()) {
+			let limit = match rate_limit(ds, session).await {
+				Ok(Some(v)) => v.or(&self.defaults),
+				Ok(None) => self.defaults.clone(),
+				Err(err) => {
+					warn!("Unable to fetch the rate limits for a session: {err}");
+					self.defaults.clone()
+				}
+			};
+			let mut state = entry.state();
+			// Start with a full bucket the first time
+			if state.fetched.is_none()
+				&& let Some(rate) = limit.rate
+			{
+				state.tokens = f64::from(rate);
+			}
+			state.limit = limit;
+			state.fetched = Some(Instant::now());
+		}
+		// Check the request rate for this identity
+		let limit = {
+			let mut state = entry.state();
+			let now = Instant::now();
+			// A rate of 0 is rejected when the limits are defined
+			if let Some(rate) = state.limit.rate.filter(|v| *v > 0).map(f64::from) {
+				// Refill the bucket for the time which has passed
+				let elapsed = now.duration_since(state.used).as_secs_f64();
+				state.tokens = (state.tokens + elapsed * rate).min(rate);
+				state.used = now;
+				if state.tokens < 1.0 {
+					let wait = Duration::from_secs_f64((1.0 - state.tokens) / rate);
+					return Err(Rejection::Rate(wait));
+				}
+				state.tokens -= 1.0;
+			}
+			state.used = now;
+			state.limit.clone()
+		};
+		// Check the number of concurrent requests for this identity
+		let max = limit.concurrency.unwrap_or(u32::MAX);
+		if entry
+			.active
+			.fetch_update(Ordering::AcqRel, Ordering::Acquire, |v| (v < max).then_some(v + 1))
+			.is_err()
+		{
+			return Err(Rejection::Concurrency);
+		}
+		// The request can be processed
+		Ok(Permit {
+			entry,
+			timeout: limit.timeout,
+		})
+	}
+
+	/// Remove the identities which have no active requests and have been idle
+	/// for longer than the limit refresh interval
+	fn prune(&self) {
+		self.entries.retain(|_, v| {
+			v.active.load(Ordering::Acquire) > 0 || v.state().used.elapsed() < refresh_interval()
+		});
+	}
+}
+
+impl Entry {
+	fn new() -> Self {
+		Self {
+			active: AtomicU32::new(0),
+			state: Mutex::new(State {
+				limit: RateLimit::default(),
+				fetched: None,
+				tokens: 0.0,
+				used: Instant::now(),
+			}),
+		}
+	}
+
+	fn state(&self) -> std::sync::MutexGuard<'_, State> {
+		self.state.lock().unwrap_or_else(|err| err.into_inner())
+	}
+}
+
+/// How often the limits for an identity are fetched from the datastore
+fn refresh_interval() -> Duration {
+	Duration::from_secs(*RATE_LIMIT_REFRESH_INTERVAL)
+}
+
+/// The key used to track the requests made by a session
+fn identity(session: &Session) -> String {
+	match session.au.level() {
+		// Anonymous sessions are tracked by their IP address, or by their
+		// connection when the IP address of the client is not known
+		Level::No => match (&session.ip, &session.id) {
+			(Some(ip), _) => format!("ip:{ip}"),
+			(None, Some(id)) => format!("session:{id}"),
+			// Sessions which can not be identified share a single limit
+			(None, None) => "anonymous".to_string(),
+		},
+		// Authenticated sessions are tracked by their identity
+		level => match &session.ac {
+			Some(ac) => format!("{level}/ac:{ac}/id:{}", session.au.id()),
+			None => format!("{level}/id:{}", session.au.id()),
+		},
+	}
+}
+
+/// Middleware which enforces the rate limits for each HTTP request, applying
+/// the query time limit to the request session
+pub(super) async fn limit_middleware(
+	Extension(state): Extension<AppState>,
+	mut req: Request<Body>,
+	next: Next,
+) -> Response {
+	// Check the limits for the session of this request
+	let permit = match req.extensions().get::<Session>() {
+		Some(session) => match state.limiter.acquire(&state.datastore, session).await {
+			Ok(permit) => permit,
+			Err(err) => return NetError::TooManyRequests(err.retry_after()).into_response(),
+		},
+		// Requests without a session are not limited
+		None => return next.run(req).await,
+	};
+	// Apply the query time limit to this request
+	if let Some(session) = req.extensions_mut().get_mut::<Session>() {
+		session.timeout = permit.timeout();
+	}
+	// Hold the permit until the response is ready
+	let res = next.run(req).await;
+	drop(permit);
+	res
+}
+
+#[cfg(test)]
+mod tests {
+	use surrealdb_core::iam::{Actor, Auth};
+
+	use super::*;
+
+	fn session(ip: &str) -> Session {
+		Session {
+			ip: Some(ip.to_string()),
+			..Session::default()
+		}
+	}
+
+	#[tokio::test]
+	async fn test_rate_limit_defaults() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let limiter = Limiter::new(RateLimit {
+			rate: Some(2),
+			..RateLimit::default()
+		});
+		// The bucket starts full
+		let sess = session("127.0.0.1");
+		limiter.acquire(&ds, &sess).await.unwrap();
+		limiter.acquire(&ds, &sess).await.unwrap();
+		// The bucket is now empty
+		let err = limiter.acquire(&ds, &sess).await.unwrap_err();
+		assert!(matches!(err, Rejection::Rate(v) if v <= Duration::from_millis(500)));
+		// Other clients are tracked separately
+		limiter.acquire(&ds, &session("127.0.0.2")).await.unwrap();
+		// The bucket is refilled over time
+		tokio::time::sleep(Duration::from_millis(600)).await;
+		limiter.acquire(&ds, &sess).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_rate_limit_unidentified_sessions() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let limiter = Limiter::new(RateLimit {
+			rate: Some(1),
+			..RateLimit::default()
+		});
+		// Anonymous sessions without an IP address share a bucket
+		limiter.acquire(&ds, &Session::default()).await.unwrap();
+		let err = limiter.acquire(&ds, &Session::default()).await.unwrap_err();
+		assert!(matches!(err, Rejection::Rate(_)));
+		assert_eq!(limiter.entries.len(), 1);
+	}
+
+	#[test]
+	fn test_identity_keys() {
+		let sess = Session {
+			ip: None,
+			id: Some(uuid::Uuid::nil()),
+			..Session::default()
+		};
+		assert_eq!(identity(&sess), "session:00000000-0000-0000-0000-000000000000");
+		assert_eq!(identity(&session("127.0.0.1")), "ip:127.0.0.1");
+		assert_eq!(identity(&Session::default()), "anonymous");
+	}
+
+	#[tokio::test]
+	async fn test_concurrency_limit_defaults() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let limiter = Limiter::new(RateLimit {
+			concurrency: Some(1),
+			timeout: Some(Duration::from_secs(5)),
+			..RateLimit::default()
+		});
+		let sess = session("127.0.0.1");
+		let permit = limiter.acquire(&ds, &sess).await.unwrap();
+		assert_eq!(permit.timeout(), Some(Duration::from_secs(5)));
+		assert_eq!(limiter.acquire(&ds, &sess).await.unwrap_err(), Rejection::Concurrency);
+		// Dropping the permit releases the slot
+		drop(permit);
+		limiter.acquire(&ds, &sess).await.unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_rate_limit_user_definition() {
+		let ds = Datastore::new("memory").await.unwrap();
+		let root = Session::owner();
+		ds.execute(
+			"DEFINE USER alice ON ROOT PASSWORD 'secret' ROLES VIEWER LIMIT RATE 1, TIMEOUT 2s",
+			&root,
+			None,
+		)
+		.await
+		.unwrap();
+		// Server-wide limits apply where the definition does not specify a limit
+		let limiter = Limiter::new(RateLimit {
+			rate: Some(100),
+			concurrency: Some(10),
+			..RateLimit::default()
+		});
+		let sess = Session {
+			au: Arc::new(Auth::new(
+				Actor::from_role_names("alice".to_string(), &["viewer".to_string()], Level::Root)
+					.unwrap(),
+			)),
+			..Session::default()
+		};
+		let permit = limiter.acquire(&ds, &sess).await.unwrap();
+		assert_eq!(permit.timeout(), Some(Duration::from_secs(2)));
+		assert!(matches!(limiter.acquire(&ds, &sess).await, Err(Rejection::Rate(_))));
+		// Users without a limit only use the server-wide limits
+		let sess = Session {
+			au: Arc::new(Auth::new(
+				Actor::from_role_names("bob".to_string(), &["viewer".to_string()], Level::Root)
+					.unwrap(),
+			)),
+			..Session::default()
+		};
+		let permit = limiter.acquire(&ds, &sess).await.unwrap();
+		assert_eq!(permit.timeout(), None);
+	}
+}
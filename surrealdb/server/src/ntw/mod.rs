@@ -1,15 +1,20 @@
 pub mod api;
 mod auth;
+pub mod checkpoint;
 pub mod client_ip;
 pub mod error;
 pub mod export;
+pub mod files;
 #[cfg(feature = "graphql")]
 pub mod gql;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub(crate) mod headers;
 pub mod health;
 pub mod import;
 mod input;
 pub mod key;
+pub mod limit;
 pub mod ml;
 pub(crate) mod output;
 mod params;
@@ -36,6 +41,7 @@ use axum_server::tls_rustls::RustlsConfig;
 use http::header;
 use surrealdb::headers::{AUTH_DB, AUTH_NS, DB, ID, NS};
 use surrealdb_core::CommunityComposer;
+use surrealdb_core::catalog::RateLimit;
 use surrealdb_core::kvs::Datastore;
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
@@ -130,6 +136,7 @@ impl RouterFactory for CommunityComposer {
 			.merge(health::router())
 			.merge(export::router())
 			.merge(import::router())
+			.merge(checkpoint::router())
 			.merge(rpc::router())
 			.merge(version::router())
 			.merge(sync::router())
@@ -138,11 +145,15 @@ impl RouterFactory for CommunityComposer {
 			.merge(signup::router())
 			.merge(key::router())
 			.merge(ml::router())
-			.merge(api::router());
+			.merge(api::router())
+			.merge(files::router());
 
 		#[cfg(feature = "graphql")]
 		let router = router.merge(gql::router());
 
+		#[cfg(feature = "grpc")]
+		let router = router.merge(grpc::router());
+
 		router
 	}
 }
@@ -153,6 +164,7 @@ impl RouterFactory for CommunityComposer {
 pub struct AppState {
 	pub client_ip: client_ip::ClientIp,
 	pub datastore: Arc<Datastore>,
+	pub limiter: Arc<limit::Limiter>,
 }
 
 /// Configuration options for building a [`SurrealRouter`].
@@ -163,6 +175,7 @@ pub struct AppState {
 /// - `client_ip`: [`ClientIp::Socket`] (extract the client IP from the raw socket)
 /// - `no_identification_headers`: `false` (include `Server` and version headers)
 /// - `allow_origin`: empty (allow all origins)
+/// - `rate_limit`: no limits (only limits defined on users and access methods apply)
 ///
 /// # Example
 ///
@@ -176,6 +189,10 @@ pub struct AppState {
 ///     client_ip: ClientIp::None,
 ///     no_identification_headers: true,
 ///     allow_origin: vec!["https://example.com".to_string()],
+///     rate_limit: RateLimit {
+///         rate: Some(100),
+///         ..RateLimit::default()
+///     },
 /// };
 /// ```
 #[derive(Clone, Debug)]
@@ -187,6 +204,9 @@ pub struct RouterOptions {
 	/// Allowed CORS origins. When empty (the default), all origins are allowed.
 	/// Each entry should be a valid origin string (e.g. `"https://example.com"`).
 	pub allow_origin: Vec<String>,
+	/// Server-wide request limits, applied to each identity for any limit which
+	/// is not specified on the user or access method used to authenticate.
+	pub rate_limit: RateLimit,
 }
 
 impl Default for RouterOptions {
@@ -195,6 +215,7 @@ impl Default for RouterOptions {
 			client_ip: client_ip::ClientIp::Socket,
 			no_identification_headers: false,
 			allow_origin: Vec::new(),
+			rate_limit: RateLimit::default(),
 		}
 	}
 }
@@ -205,6 +226,7 @@ impl From<&Config> for RouterOptions {
 			client_ip: cfg.client_ip,
 			no_identification_headers: cfg.no_identification_headers,
 			allow_origin: cfg.allow_origin.clone(),
+			rate_limit: cfg.rate_limit.clone(),
 		}
 	}
 }
@@ -287,9 +309,11 @@ impl SurrealRouter {
 		ct: CancellationToken,
 	) -> Result<Self> {
 		let opt = opt.into();
+		let limiter = Arc::new(limit::Limiter::new(opt.rate_limit.clone()));
 		let app_state = AppState {
 			client_ip: opt.client_ip,
 			datastore: ds.clone(),
+			limiter: limiter.clone(),
 		};
 
 		// Specify headers to be obfuscated from all requests/responses
@@ -360,6 +384,7 @@ impl SurrealRouter {
 			.layer(HttpMetricsLayer)
 			.layer(SetSensitiveResponseHeadersLayer::from_shared(headers))
 			.layer(AsyncRequireAuthorizationLayer::new(auth::SurrealAuth))
+			.layer(middleware::from_fn(limit::limit_middleware))
 			.layer(headers::add_server_header(!opt.no_identification_headers)?)
 			.layer(headers::add_version_header(!opt.no_identification_headers)?)
 			// Apply CORS headers to relevant responses
@@ -385,8 +410,14 @@ impl SurrealRouter {
 		let axum_app = axum_app.layer(service);
 
 		// Create RpcState with persistent HTTP handler
-		let rpc_state =
-			Arc::new(RpcState::new(ds.clone(), surrealdb_core::dbs::Session::default()));
+		let rpc_state = Arc::new(
+			RpcState::new(ds.clone(), surrealdb_core::dbs::Session::default())
+				.with_limiter(limiter),
+		);
+
+		// Expire idle gRPC transactions and sessions until the server stops
+		#[cfg(feature = "grpc")]
+		tokio::spawn(crate::rpc::grpc::expiry(rpc_state.grpc.clone(), ct.clone()));
 
 		// Apply state
 		let axum_app = axum_app.with_state(rpc_state.clone());
@@ -17,6 +17,8 @@ impl From<&Accept> for Format {
 			Accept::ApplicationCbor => Format::Cbor,
 			Accept::ApplicationOctetStream => Format::Unsupported,
 			Accept::ApplicationFlatbuffers => Format::Flatbuffers,
+			Accept::ApplicationMsgpack => Format::MessagePack,
+			Accept::ApplicationProtobuf => Format::Protobuf,
 		}
 	}
 }
@@ -29,6 +31,8 @@ impl From<&ContentType> for Format {
 			ContentType::ApplicationCbor => Format::Cbor,
 			ContentType::ApplicationOctetStream => Format::Unsupported,
 			ContentType::ApplicationSurrealDBFlatbuffers => Format::Flatbuffers,
+			ContentType::ApplicationMsgpack => Format::MessagePack,
+			ContentType::ApplicationSurrealDBProtobuf => Format::Protobuf,
 		}
 	}
 }
@@ -39,6 +43,8 @@ impl From<&Format> for ContentType {
 			Format::Json => ContentType::ApplicationJson,
 			Format::Cbor => ContentType::ApplicationCbor,
 			Format::Flatbuffers => ContentType::ApplicationSurrealDBFlatbuffers,
+			Format::MessagePack => ContentType::ApplicationMsgpack,
+			Format::Protobuf => ContentType::ApplicationSurrealDBProtobuf,
 			Format::Unsupported => ContentType::ApplicationOctetStream,
 		}
 	}
@@ -83,6 +89,24 @@ impl WsFormat for Format {
 					Err(parse_error())
 				}
 			}
+			Format::MessagePack => {
+				let val = surrealdb_core::rpc::format::msgpack::decode(&val)
+					.map_err(|_| parse_error())?;
+				if let Value::Object(obj) = val {
+					Ok(Request::from_object(obj)?)
+				} else {
+					Err(parse_error())
+				}
+			}
+			Format::Protobuf => {
+				let val = surrealdb_core::rpc::format::protobuf::decode(&val)
+					.map_err(|_| parse_error())?;
+				if let Value::Object(obj) = val {
+					Ok(Request::from_object(obj)?)
+				} else {
+					Err(parse_error())
+				}
+			}
 			Format::Unsupported => Err(invalid_request()),
 		}
 	}
@@ -106,6 +130,16 @@ impl WsFormat for Format {
 					.map_err(|_| parse_error())?;
 				Ok((val.len(), Message::Binary(val.into())))
 			}
+			Format::MessagePack => {
+				let val = surrealdb_core::rpc::format::msgpack::encode(res.into_value())
+					.map_err(|_| parse_error())?;
+				Ok((val.len(), Message::Binary(val.into())))
+			}
+			Format::Protobuf => {
+				let val = surrealdb_core::rpc::format::protobuf::encode(res.into_value())
+					.map_err(|_| parse_error())?;
+				Ok((val.len(), Message::Binary(val.into())))
+			}
 			Format::Unsupported => Err(invalid_request()),
 		}
 	}
@@ -149,6 +183,24 @@ impl HttpFormat for Format {
 					Err(parse_error())
 				}
 			}
+			Format::MessagePack => {
+				let val = surrealdb_core::rpc::format::msgpack::decode(&body)
+					.map_err(|_| parse_error())?;
+				if let Value::Object(obj) = val {
+					Ok(Request::from_object(obj)?)
+				} else {
+					Err(parse_error())
+				}
+			}
+			Format::Protobuf => {
+				let val = surrealdb_core::rpc::format::protobuf::decode(&body)
+					.map_err(|_| parse_error())?;
+				if let Value::Object(obj) = val {
+					Ok(Request::from_object(obj)?)
+				} else {
+					Err(parse_error())
+				}
+			}
 			Format::Unsupported => Err(invalid_request()),
 		}
 	}
@@ -166,6 +218,10 @@ impl HttpFormat for Format {
 				surrealdb_core::rpc::format::flatbuffers::encode(&res_value)
 					.map_err(|_| parse_error())?
 			}
+			Format::MessagePack => surrealdb_core::rpc::format::msgpack::encode(res.into_value())
+				.map_err(|_| parse_error())?,
+			Format::Protobuf => surrealdb_core::rpc::format::protobuf::encode(res.into_value())
+				.map_err(|_| parse_error())?,
 			Format::Unsupported => return Err(invalid_request()),
 		};
 
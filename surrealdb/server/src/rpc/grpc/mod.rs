@@ -0,0 +1,714 @@
+//! The gRPC transport for the RPC protocol.
+//!
+//! The `surrealdb.rpc.v1.Surreal` service (see `proto/surrealdb/rpc/v1/rpc.proto`)
+//! exposes each RPC method as a typed call, with values encoded as in
+//! `value.proto`. The `Live` call creates a LIVE query, and streams its
+//! notifications until the client cancels the stream, or the LIVE query is
+//! killed.
+//!
+//! Each call runs with the session built from the request metadata, so calls
+//! are stateless by default, as with the HTTP RPC endpoint. Sessions created
+//! with the `attach` method, and transactions started with the `begin` method,
+//! are stored on the server and can be used across calls. They are bound to
+//! the user who created them, and are removed once they have been idle for
+//! longer than the configured timeout.
+
+pub mod proto;
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use futures::Stream;
+use prost::Message;
+use surrealdb_core::dbs::Session;
+use surrealdb_core::iam::Auth;
+use surrealdb_core::kvs::{Datastore, LockType, Transaction, TransactionType};
+use surrealdb_core::rpc::format::protobuf::{from_value, to_value};
+use surrealdb_core::rpc::{
+	DbResult, Method, RpcProtocol, invalid_params, method_not_allowed, session_exists,
+	session_not_found,
+};
+use surrealdb_types::{Array, Error as TypesError, HashMap, Notification, SurrealValue, Value};
+use tokio::sync::RwLock;
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio_util::sync::CancellationToken;
+use tonic::{Code, Status};
+use uuid::Uuid;
+
+use self::proto::live_response::Event;
+use crate::cnf::{
+	GRPC_LIVE_CHANNEL_SIZE, GRPC_MAX_SESSIONS, GRPC_MAX_TRANSACTIONS, GRPC_SESSION_TIMEOUT,
+	GRPC_TRANSACTION_TIMEOUT, PKG_NAME, PKG_VERSION,
+};
+
+/// How often idle transactions and sessions are removed
+const EXPIRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A parsed gRPC call request
+pub(crate) struct Call {
+	pub(crate) method: Method,
+	pub(crate) params: Array,
+	pub(crate) session_id: Option<Uuid>,
+	pub(crate) txn: Option<Uuid>,
+}
+
+impl Call {
+	fn new(method: Method, session: &str, txn: &str, params: Vec<Value>) -> Result<Self, Status> {
+		let uuid = |v: &str, name: &str| match v {
+			"" => Ok(None),
+			v => Uuid::parse_str(v)
+				.map(Some)
+				.map_err(|_| Status::invalid_argument(format!("Invalid {name} id: {v}"))),
+		};
+		Ok(Self {
+			method,
+			params: Array::from(params),
+			session_id: uuid(session, "session")?,
+			txn: uuid(txn, "transaction")?,
+		})
+	}
+}
+
+/// A gRPC request message which executes an RPC method
+pub(crate) trait GrpcRequest: Message + Default + 'static {
+	/// Convert the request into a call of the RPC method
+	fn into_call(self, method: Method) -> Result<Call, Status>;
+}
+
+/// Decode a Protobuf value, which is NONE when not set
+fn value(val: Option<proto::Value>) -> Result<Value, Status> {
+	match val {
+		Some(val) => to_value(val).map_err(|e| Status::invalid_argument(e.to_string())),
+		None => Ok(Value::None),
+	}
+}
+
+/// Decode a Protobuf object, which is NONE when not set
+fn object(val: Option<proto::Object>) -> Result<Value, Status> {
+	value(val.map(|v| proto::Value {
+		kind: Some(proto::value::Kind::Object(v)),
+	}))
+}
+
+impl GrpcRequest for proto::EmptyRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		Call::new(method, "", "", Vec::new())
+	}
+}
+
+impl GrpcRequest for proto::SessionRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		Call::new(method, &self.session, "", Vec::new())
+	}
+}
+
+impl GrpcRequest for proto::TransactionRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		Call::new(method, &self.session, &self.txn, Vec::new())
+	}
+}
+
+impl GrpcRequest for proto::UseRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let params = vec![value(self.namespace)?, value(self.database)?];
+		Call::new(method, &self.session, "", params)
+	}
+}
+
+impl GrpcRequest for proto::CredentialsRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let params = vec![object(self.credentials)?];
+		Call::new(method, &self.session, "", params)
+	}
+}
+
+impl GrpcRequest for proto::TokenRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let params = vec![value(self.token)?];
+		Call::new(method, &self.session, "", params)
+	}
+}
+
+impl GrpcRequest for proto::SetRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let mut params = vec![Value::String(self.key)];
+		if self.value.is_some() {
+			params.push(value(self.value)?);
+		}
+		Call::new(method, &self.session, "", params)
+	}
+}
+
+impl GrpcRequest for proto::UnsetRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let params = vec![Value::String(self.key)];
+		Call::new(method, &self.session, "", params)
+	}
+}
+
+impl GrpcRequest for proto::QueryRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let mut params = vec![Value::String(self.query)];
+		if self.variables.is_some() {
+			params.push(object(self.variables)?);
+		}
+		Call::new(method, &self.session, &self.txn, params)
+	}
+}
+
+impl GrpcRequest for proto::ResourceRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let mut params = vec![value(self.what)?];
+		if self.data.is_some() {
+			params.push(value(self.data)?);
+		}
+		Call::new(method, &self.session, &self.txn, params)
+	}
+}
+
+impl GrpcRequest for proto::PatchRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let params = vec![value(self.what)?, value(self.patches)?, Value::Bool(self.diff)];
+		Call::new(method, &self.session, &self.txn, params)
+	}
+}
+
+impl GrpcRequest for proto::RelateRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let mut params = vec![value(self.from)?, value(self.kind)?, value(self.with)?];
+		if self.data.is_some() {
+			params.push(value(self.data)?);
+		}
+		Call::new(method, &self.session, &self.txn, params)
+	}
+}
+
+impl GrpcRequest for proto::RunRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let version = match self.version.is_empty() {
+			true => Value::None,
+			false => Value::String(self.version),
+		};
+		let args = self.args.into_iter().map(|v| value(Some(v))).collect::<Result<Vec<_>, _>>()?;
+		let params = vec![Value::String(self.name), version, Value::Array(Array::from(args))];
+		Call::new(method, &self.session, &self.txn, params)
+	}
+}
+
+impl GrpcRequest for proto::LiveRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let params = vec![value(self.what)?, Value::Bool(self.diff)];
+		Call::new(method, &self.session, &self.txn, params)
+	}
+}
+
+impl GrpcRequest for proto::KillRequest {
+	fn into_call(self, method: Method) -> Result<Call, Status> {
+		let params = vec![value(self.id)?];
+		Call::new(method, &self.session, &self.txn, params)
+	}
+}
+
+/// Convert an RPC error into a gRPC status, with the error object encoded
+/// as a Protobuf value in the status details
+pub(crate) fn status(err: TypesError) -> Status {
+	let code = if err.is_validation() || err.is_serialization() {
+		Code::InvalidArgument
+	} else if err.is_not_found() {
+		Code::NotFound
+	} else if err.is_not_allowed() {
+		Code::PermissionDenied
+	} else if err.is_already_exists() {
+		Code::AlreadyExists
+	} else if err.is_configuration() {
+		Code::FailedPrecondition
+	} else if err.is_internal() {
+		Code::Internal
+	} else {
+		Code::Unknown
+	};
+	let message = err.message().to_owned();
+	match from_value(err.into_value()) {
+		Ok(details) => Status::with_details(code, message, details.encode_to_vec().into()),
+		Err(_) => Status::new(code, message),
+	}
+}
+
+/// Encode a value as a Protobuf value
+fn encode(val: Value) -> Result<proto::Value, Status> {
+	from_value(val).map_err(|e| Status::internal(e.to_string()))
+}
+
+/// Encode the result of an RPC method
+pub(crate) fn response(result: Result<DbResult, TypesError>) -> Result<proto::Response, Status> {
+	Ok(proto::Response {
+		result: Some(encode(result.map_err(status)?.into_value())?),
+	})
+}
+
+/// The user who created a gRPC transaction or session, and when it was last
+/// used
+pub(crate) struct Lease {
+	owner: Arc<Auth>,
+	used: Instant,
+}
+
+impl Lease {
+	fn new(owner: Arc<Auth>) -> Self {
+		Self {
+			owner,
+			used: Instant::now(),
+		}
+	}
+
+	/// Whether the lease belongs to the user
+	fn owned_by(&self, owner: &Auth) -> bool {
+		*self.owner == *owner
+	}
+
+	/// Mark the lease as used, if it belongs to the user
+	fn renew(&mut self, owner: &Auth) -> bool {
+		if !self.owned_by(owner) {
+			return false;
+		}
+		self.used = Instant::now();
+		true
+	}
+
+	/// Whether the lease has been idle for at least the timeout
+	fn expired(&self, timeout: Duration) -> bool {
+		self.used.elapsed() >= timeout
+	}
+}
+
+/// The state shared by all gRPC calls
+pub struct Grpc {
+	/// The datastore accessible to all gRPC calls
+	pub(crate) kvs: Arc<Datastore>,
+	/// The sessions attached over gRPC
+	pub(crate) sessions: HashMap<Option<Uuid>, Arc<RwLock<Session>>>,
+	/// The owner of each session attached over gRPC
+	pub(crate) owners: DashMap<Uuid, Lease>,
+	/// The transactions started over gRPC, with their owner
+	pub(crate) transactions: DashMap<Uuid, (Lease, Arc<Transaction>)>,
+	/// Mapping of LIVE query ID to the attached session ID
+	pub(crate) live_queries: DashMap<Uuid, Option<Uuid>>,
+	/// Mapping of LIVE query ID to the notification stream
+	pub(crate) streams: DashMap<Uuid, Sender<Notification>>,
+}
+
+impl Grpc {
+	pub fn new(kvs: Arc<Datastore>) -> Self {
+		Self {
+			kvs,
+			sessions: HashMap::new(),
+			owners: DashMap::new(),
+			transactions: DashMap::new(),
+			live_queries: DashMap::new(),
+			streams: DashMap::new(),
+		}
+	}
+
+	/// Deliver a LIVE query notification to its gRPC stream
+	pub(crate) fn notify(&self, notification: &Notification) {
+		let lqid: &Uuid = notification.id.as_ref();
+		if let Some(stream) = self.streams.get(lqid)
+			&& stream.try_send(notification.clone()).is_err()
+		{
+			warn!("Dropping notification for gRPC LIVE query {lqid}");
+		}
+	}
+
+	/// Create a notification stream for a LIVE query
+	pub(crate) fn subscribe(self: &Arc<Self>, lqid: Uuid) -> LiveStream {
+		let (sender, receiver) = channel(*GRPC_LIVE_CHANNEL_SIZE);
+		self.streams.insert(lqid, sender);
+		LiveStream {
+			first: true,
+			receiver,
+			grpc: self.clone(),
+			lqid,
+		}
+	}
+
+	/// Kill a LIVE query when its stream is closed
+	fn unsubscribe(self: Arc<Self>, lqid: Uuid) {
+		self.streams.remove(&lqid);
+		// The LIVE query may have already been killed
+		if self.live_queries.remove(&lqid).is_some() {
+			tokio::spawn(async move {
+				if let Err(err) = self.kvs.delete_queries(vec![lqid]).await {
+					error!("Error killing gRPC LIVE query {lqid}: {err}");
+				}
+			});
+		}
+	}
+
+	/// Kill the LIVE queries of an attached session
+	async fn cleanup_lqs(&self, session_id: &Uuid) {
+		let mut gc = Vec::new();
+		// Find all live queries for to this session
+		self.live_queries.retain(|key, value| {
+			if value.as_ref() == Some(session_id) {
+				trace!("Removing live query: {key}");
+				gc.push(*key);
+				return false;
+			}
+			true
+		});
+		for lqid in gc.iter() {
+			self.streams.remove(lqid);
+		}
+		// Garbage collect the live queries on this session
+		if let Err(err) = self.kvs.delete_queries(gc).await {
+			error!("Error handling RPC connection: {err}");
+		}
+	}
+
+	/// Cancel idle transactions, and detach idle sessions
+	async fn expire(&self) {
+		let timeout = Duration::from_secs(*GRPC_TRANSACTION_TIMEOUT);
+		let mut expired = Vec::new();
+		self.transactions.retain(|id, (lease, tx)| {
+			// A transaction which is executing a method is still in use
+			if !lease.expired(timeout) || Arc::strong_count(tx) > 1 {
+				return true;
+			}
+			expired.push((*id, tx.clone()));
+			false
+		});
+		for (id, tx) in expired {
+			trace!("Cancelling idle gRPC transaction {id}");
+			if let Err(err) = tx.cancel().await {
+				error!("Error cancelling idle gRPC transaction {id}: {err}");
+			}
+		}
+		let timeout = Duration::from_secs(*GRPC_SESSION_TIMEOUT);
+		let mut expired = Vec::new();
+		self.owners.retain(|id, lease| {
+			if !lease.expired(timeout) {
+				return true;
+			}
+			expired.push(*id);
+			false
+		});
+		for id in expired {
+			trace!("Detaching idle gRPC session {id}");
+			self.sessions.remove(&Some(id));
+			self.cleanup_lqs(&id).await;
+		}
+	}
+}
+
+/// Cancels idle gRPC transactions, and detaches idle gRPC sessions, until the
+/// canceller is cancelled
+pub async fn expiry(grpc: Arc<Grpc>, canceller: CancellationToken) {
+	let mut interval = tokio::time::interval(EXPIRY_INTERVAL);
+	loop {
+		tokio::select! {
+			_ = canceller.cancelled() => break,
+			_ = interval.tick() => grpc.expire().await,
+		}
+	}
+}
+
+/// The stream of responses for the `Live` gRPC call
+pub struct LiveStream {
+	/// Whether the LIVE query id has yet to be sent
+	first: bool,
+	/// The notifications for the LIVE query
+	receiver: Receiver<Notification>,
+	/// The state which the LIVE query is removed from when the stream is dropped
+	grpc: Arc<Grpc>,
+	/// The LIVE query which is killed when the stream is dropped
+	lqid: Uuid,
+}
+
+impl Stream for LiveStream {
+	type Item = Result<proto::LiveResponse, Status>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		if std::mem::take(&mut self.first) {
+			let id = encode(Value::Uuid(self.lqid.into())).map(|id| proto::LiveResponse {
+				event: Some(Event::Id(id)),
+			});
+			return Poll::Ready(Some(id));
+		}
+		self.receiver.poll_recv(cx).map(|v| {
+			v.map(|v| {
+				let notification = proto::Notification {
+					id: Some(encode(Value::Uuid(v.id))?),
+					action: v.action.to_string(),
+					record: Some(encode(v.record)?),
+					result: Some(encode(v.result)?),
+				};
+				Ok(proto::LiveResponse {
+					event: Some(Event::Notification(notification)),
+				})
+			})
+		})
+	}
+}
+
+impl Drop for LiveStream {
+	fn drop(&mut self) {
+		self.grpc.clone().unsubscribe(self.lqid);
+	}
+}
+
+/// A single gRPC call, executed with the session from the request metadata
+pub struct GrpcCall {
+	/// The state shared by all gRPC calls
+	grpc: Arc<Grpc>,
+	/// The session from the request metadata
+	session: Arc<RwLock<Session>>,
+	/// The user from the request metadata, who owns the transactions and
+	/// sessions created by this call
+	owner: Arc<Auth>,
+}
+
+impl GrpcCall {
+	pub fn new(grpc: Arc<Grpc>, session: Session) -> Self {
+		Self {
+			grpc,
+			owner: session.au.clone(),
+			session: Arc::new(RwLock::new(session.with_rt(true))),
+		}
+	}
+}
+
+impl RpcProtocol for GrpcCall {
+	/// The datastore for this RPC interface
+	fn kvs(&self) -> &Datastore {
+		&self.grpc.kvs
+	}
+
+	/// The version information for this RPC context
+	fn version_data(&self) -> DbResult {
+		let value = Value::String(format!("{PKG_NAME}-{}", *PKG_VERSION));
+		DbResult::Other(value)
+	}
+
+	/// A pointer to all attached sessions
+	fn session_map(&self) -> &HashMap<Option<Uuid>, Arc<RwLock<Session>>> {
+		&self.grpc.sessions
+	}
+
+	/// Registers a new session, owned by the user of this call
+	async fn attach(&self, session_id: Option<Uuid>) -> Result<DbResult, TypesError> {
+		self.check_owner(Method::Attach)?;
+		let Some(id) = session_id else {
+			return Err(invalid_params("Expected a session ID"));
+		};
+		if self.grpc.owners.len() >= *GRPC_MAX_SESSIONS {
+			return Err(too_many("sessions"));
+		}
+		// Claim the session id before the session is stored
+		match self.grpc.owners.entry(id) {
+			Entry::Occupied(_) => return Err(session_exists(id)),
+			Entry::Vacant(entry) => {
+				entry.insert(Lease::new(self.owner.clone()));
+			}
+		}
+		let mut session = Session::default().with_rt(Self::LQ_SUPPORT);
+		session.id = Some(id);
+		self.session_map().insert(Some(id), Arc::new(RwLock::new(session)));
+		Ok(DbResult::Other(Value::None))
+	}
+
+	/// Detaches a session owned by the user of this call
+	async fn detach(&self, session_id: Option<Uuid>) -> Result<DbResult, TypesError> {
+		let Some(id) = session_id else {
+			return Err(invalid_params("Expected a session ID"));
+		};
+		if self.grpc.owners.remove_if(&id, |_, lease| lease.owned_by(&self.owner)).is_none() {
+			return Err(session_not_found(Some(id)));
+		}
+		self.del_session(&id).await;
+		Ok(DbResult::Other(Value::None))
+	}
+
+	/// The default session is specific to each call, and attached sessions
+	/// can only be used by the user who attached them
+	fn get_session(&self, id: &Option<Uuid>) -> Result<Arc<RwLock<Session>>, TypesError> {
+		let Some(uuid) = id else {
+			return Ok(self.session.clone());
+		};
+		if !self.grpc.owners.get_mut(uuid).is_some_and(|mut lease| lease.renew(&self.owner)) {
+			return Err(session_not_found(*id));
+		}
+		match self.session_map().get(id) {
+			Some(session) => Ok(session),
+			None => Err(session_not_found(*id)),
+		}
+	}
+
+	/// Lists the sessions owned by the user of this call
+	async fn sessions(&self) -> Result<DbResult, TypesError> {
+		let array = self
+			.grpc
+			.owners
+			.iter()
+			.filter(|entry| entry.value().owned_by(&self.owner))
+			.map(|entry| Value::Uuid((*entry.key()).into()))
+			.collect();
+		Ok(DbResult::Other(Value::Array(array)))
+	}
+
+	// ------------------------------
+	// Transactions
+	// ------------------------------
+
+	/// Retrieves a transaction owned by the user of this call
+	async fn get_tx(&self, id: Uuid) -> Result<Arc<Transaction>, TypesError> {
+		let Some(mut entry) = self.grpc.transactions.get_mut(&id) else {
+			return Err(invalid_params("Transaction not found"));
+		};
+		let (lease, tx) = entry.value_mut();
+		if !lease.renew(&self.owner) {
+			return Err(invalid_params("Transaction not found"));
+		}
+		Ok(tx.clone())
+	}
+
+	/// Stores a transaction, owned by the user of this call
+	async fn set_tx(&self, id: Uuid, tx: Arc<Transaction>) -> Result<(), TypesError> {
+		self.grpc.transactions.insert(id, (Lease::new(self.owner.clone()), tx));
+		Ok(())
+	}
+
+	// ------------------------------
+	// Realtime
+	// ------------------------------
+
+	/// Live queries are enabled on gRPC
+	const LQ_SUPPORT: bool = true;
+
+	/// Handles the execution of a LIVE statement
+	async fn handle_live(&self, lqid: &Uuid, session_id: Option<Uuid>) {
+		self.grpc.live_queries.insert(*lqid, session_id);
+		trace!("Registered gRPC live query {lqid}");
+	}
+
+	/// Handles the execution of a KILL statement
+	async fn handle_kill(&self, lqid: &Uuid) {
+		self.grpc.live_queries.remove(lqid);
+		// Dropping the sender closes the notification stream
+		self.grpc.streams.remove(lqid);
+		trace!("Unregistered gRPC live query {lqid}");
+	}
+
+	/// Handles the cleanup of live queries
+	async fn cleanup_lqs(&self, session_id: Option<&Uuid>) {
+		// Live queries on the default session are owned by their stream
+		if let Some(session_id) = session_id {
+			self.grpc.cleanup_lqs(session_id).await;
+		}
+	}
+
+	/// Handles the cleanup of live queries
+	async fn cleanup_all_lqs(&self) {
+		// Do nothing as gRPC calls are not connections
+	}
+
+	// ------------------------------
+	// Methods for transactions
+	// ------------------------------
+
+	/// Begin a new transaction, owned by the user of this call
+	async fn begin(
+		&self,
+		_txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+	) -> Result<DbResult, TypesError> {
+		self.check_owner(Method::Begin)?;
+		if self.grpc.transactions.len() >= *GRPC_MAX_TRANSACTIONS {
+			return Err(too_many("transactions"));
+		}
+		// Create a new transaction
+		let tx = self
+			.kvs()
+			.transaction(TransactionType::Write, LockType::Optimistic)
+			.await
+			.map_err(surrealdb_core::rpc::types_error_from_anyhow)?;
+		// Generate a unique transaction ID
+		let id = Uuid::now_v7();
+		// Store the transaction in the map
+		self.set_tx(id, Arc::new(tx)).await?;
+		// Return the transaction ID to the client
+		Ok(DbResult::Other(Value::Uuid(surrealdb::types::Uuid::from(id))))
+	}
+
+	/// Commit a transaction
+	async fn commit(
+		&self,
+		txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: Array,
+	) -> Result<DbResult, TypesError> {
+		// Retrieve and remove the transaction from the map
+		let tx = self.take_tx(txn)?;
+		// Commit the transaction
+		tx.commit().await.map_err(surrealdb_core::rpc::types_error_from_anyhow)?;
+		// Return success
+		Ok(DbResult::Other(Value::None))
+	}
+
+	/// Cancel a transaction
+	async fn cancel(
+		&self,
+		txn: Option<Uuid>,
+		_session_id: Option<Uuid>,
+		_params: Array,
+	) -> Result<DbResult, TypesError> {
+		// Retrieve and remove the transaction from the map
+		let tx = self.take_tx(txn)?;
+		// Cancel the transaction
+		tx.cancel().await.map_err(surrealdb_core::rpc::types_error_from_anyhow)?;
+		// Return success
+		Ok(DbResult::Other(Value::None))
+	}
+}
+
+/// The error returned when too many transactions or sessions are stored
+fn too_many(what: &str) -> TypesError {
+	TypesError::not_allowed(format!("Too many open gRPC {what}"), None)
+}
+
+impl GrpcCall {
+	/// Check that the user of this call can store transactions and sessions
+	/// on the server
+	fn check_owner(&self, method: Method) -> Result<(), TypesError> {
+		// Stored state is bound to its user, so anonymous users can not store any
+		if self.grpc.kvs.is_auth_enabled() && self.owner.is_anon() {
+			return Err(method_not_allowed(method.to_string()));
+		}
+		// Check if the user is allowed to query
+		if !self.grpc.kvs.allows_query_by_subject(self.owner.as_ref()) {
+			return Err(method_not_allowed(method.to_string()));
+		}
+		Ok(())
+	}
+
+	/// Remove the transaction in the `txn` field of the request, if it is
+	/// owned by the user of this call
+	fn take_tx(&self, txn: Option<Uuid>) -> Result<Arc<Transaction>, TypesError> {
+		let Some(txn) = txn else {
+			return Err(invalid_params("Expected a transaction id"));
+		};
+		match self.grpc.transactions.remove_if(&txn, |_, (lease, _)| lease.owned_by(&self.owner)) {
+			Some((_, (_, tx))) => Ok(tx),
+			None => Err(invalid_params("Transaction not found")),
+		}
+	}
+
+	/// Execute an RPC method on this call
+	pub(crate) async fn execute_call(&self, call: Call) -> Result<DbResult, TypesError> {
+		debug!("Process gRPC RPC request");
+		RpcProtocol::execute(self, call.txn, call.session_id, call.method, call.params).await
+	}
+}
@@ -0,0 +1,210 @@
+//! The Protobuf messages of the `surrealdb.rpc.v1.Surreal` service.
+//!
+//! These messages mirror `rpc.proto`, and values are encoded with the
+//! messages of `value.proto`.
+
+pub use surrealdb_core::rpc::format::protobuf::proto::{Object, Value, value};
+
+/// A request without any parameters
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct EmptyRequest {}
+
+/// A request which runs on a session
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SessionRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+}
+
+/// A request which runs on a session, within a transaction
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TransactionRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+}
+
+/// A request for the `use` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UseRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(message, optional, tag = "2")]
+	pub namespace: Option<Value>,
+	#[prost(message, optional, tag = "3")]
+	pub database: Option<Value>,
+}
+
+/// A request for the `signup` and `signin` methods
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CredentialsRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(message, optional, tag = "2")]
+	pub credentials: Option<Object>,
+}
+
+/// A request for the `authenticate`, `refresh` and `revoke` methods
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TokenRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(message, optional, tag = "2")]
+	pub token: Option<Value>,
+}
+
+/// A request for the `set` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SetRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub key: String,
+	#[prost(message, optional, tag = "3")]
+	pub value: Option<Value>,
+}
+
+/// A request for the `unset` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct UnsetRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub key: String,
+}
+
+/// A request for the `query` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+	#[prost(string, tag = "3")]
+	pub query: String,
+	#[prost(message, optional, tag = "4")]
+	pub variables: Option<Object>,
+}
+
+/// A request for the methods which read or write records
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ResourceRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+	#[prost(message, optional, tag = "3")]
+	pub what: Option<Value>,
+	#[prost(message, optional, tag = "4")]
+	pub data: Option<Value>,
+}
+
+/// A request for the `patch` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct PatchRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+	#[prost(message, optional, tag = "3")]
+	pub what: Option<Value>,
+	#[prost(message, optional, tag = "4")]
+	pub patches: Option<Value>,
+	#[prost(bool, tag = "5")]
+	pub diff: bool,
+}
+
+/// A request for the `relate` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RelateRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+	#[prost(message, optional, tag = "3")]
+	pub from: Option<Value>,
+	#[prost(message, optional, tag = "4")]
+	pub kind: Option<Value>,
+	#[prost(message, optional, tag = "5")]
+	pub with: Option<Value>,
+	#[prost(message, optional, tag = "6")]
+	pub data: Option<Value>,
+}
+
+/// A request for the `run` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RunRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+	#[prost(string, tag = "3")]
+	pub name: String,
+	#[prost(string, tag = "4")]
+	pub version: String,
+	#[prost(message, repeated, tag = "5")]
+	pub args: Vec<Value>,
+}
+
+/// A request for the `live` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LiveRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+	#[prost(message, optional, tag = "3")]
+	pub what: Option<Value>,
+	#[prost(bool, tag = "4")]
+	pub diff: bool,
+}
+
+/// A request for the `kill` method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct KillRequest {
+	#[prost(string, tag = "1")]
+	pub session: String,
+	#[prost(string, tag = "2")]
+	pub txn: String,
+	#[prost(message, optional, tag = "3")]
+	pub id: Option<Value>,
+}
+
+/// The result of an RPC method
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Response {
+	#[prost(message, optional, tag = "1")]
+	pub result: Option<Value>,
+}
+
+/// The LIVE query id, or a LIVE query notification
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct LiveResponse {
+	#[prost(oneof = "live_response::Event", tags = "1, 2")]
+	pub event: Option<live_response::Event>,
+}
+
+pub mod live_response {
+	/// An event on a LIVE query stream
+	#[derive(Clone, PartialEq, prost::Oneof)]
+	pub enum Event {
+		#[prost(message, tag = "1")]
+		Id(super::Value),
+		#[prost(message, tag = "2")]
+		Notification(super::Notification),
+	}
+}
+
+/// A LIVE query notification
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Notification {
+	#[prost(message, optional, tag = "1")]
+	pub id: Option<Value>,
+	#[prost(string, tag = "2")]
+	pub action: String,
+	#[prost(message, optional, tag = "3")]
+	pub record: Option<Value>,
+	#[prost(message, optional, tag = "4")]
+	pub result: Option<Value>,
+}
@@ -1,4 +1,6 @@
 pub mod format;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 pub mod http;
 pub mod response;
 pub mod websocket;
@@ -20,6 +22,7 @@ use uuid::Uuid;
 
 #[cfg(feature = "graphql")]
 use crate::cnf::GQL_SUBSCRIPTION_CHANNEL_CAPACITY;
+use crate::ntw::limit::Limiter;
 use crate::rpc::websocket::Websocket;
 use crate::telemetry::metrics::ws::NotificationContext;
 
@@ -38,6 +41,11 @@ pub struct RpcState {
 	pub live_queries: LiveQueries,
 	/// HTTP RPC handler with persistent sessions
 	pub http: Arc<crate::rpc::http::Http>,
+	/// gRPC RPC state with attached sessions, transactions and LIVE streams
+	#[cfg(feature = "grpc")]
+	pub grpc: Arc<crate::rpc::grpc::Grpc>,
+	/// Enforces the request limits for each identity
+	pub limiter: Arc<Limiter>,
 	#[cfg(feature = "graphql")]
 	pub(crate) notification_router: Arc<NotificationRouter>,
 }
@@ -50,19 +58,29 @@ impl RpcState {
 		Self {
 			web_sockets: RwLock::new(HashMap::new()),
 			live_queries: RwLock::new(HashMap::new()),
+			#[cfg(feature = "grpc")]
+			grpc: Arc::new(crate::rpc::grpc::Grpc::new(datastore.clone())),
 			http: Arc::new(crate::rpc::http::Http::new(datastore, session)),
+			limiter: Arc::new(Limiter::default()),
 			#[cfg(feature = "graphql")]
 			notification_router: Arc::new(NotificationRouter::new(
 				*GQL_SUBSCRIPTION_CHANNEL_CAPACITY,
 			)),
 		}
 	}
+
+	/// Use the specified limiter to enforce request limits on WebSocket messages
+	pub fn with_limiter(mut self, limiter: Arc<Limiter>) -> Self {
+		self.limiter = limiter;
+		self
+	}
 }
 
 /// Performs notification delivery to the WebSockets.
 ///
 /// This function listens on the datastore's notification channel and forwards
-/// LIVE query notifications to the appropriate WebSocket connections. It runs
+/// LIVE query notifications to the appropriate WebSocket connections and gRPC
+/// streams. It runs
 /// in a loop until the provided [`CancellationToken`] is cancelled.
 ///
 /// # Parameters
@@ -95,6 +113,8 @@ pub async fn notifications(ds: Arc<Datastore>, state: Arc<RpcState>, canceller:
 					if state.notification_router.has_subscribers() {
 						state.notification_router.dispatch(&notification);
 					}
+					#[cfg(feature = "grpc")]
+					state.grpc.notify(&notification);
 					// Get the id for this notification
 					let id = notification.id.as_ref();
 					// Get the WebSocket for this notification
@@ -6,16 +6,17 @@ use axum::extract::ws::close_code::AGAIN;
 use axum::extract::ws::{CloseFrame, Message, WebSocket};
 use bytes::Bytes;
 use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
 use futures::stream::FuturesUnordered;
 use futures::{Sink, SinkExt, StreamExt};
 use opentelemetry::Context as TelemetryContext;
 use opentelemetry::trace::FutureExt;
 use surrealdb_core::dbs::Session;
-use surrealdb_core::kvs::{Datastore, LockType, Transaction, TransactionType};
+use surrealdb_core::kvs::{ChangeStream, Datastore, LockType, Transaction, TransactionType};
 use surrealdb_core::mem::ALLOC;
 use surrealdb_core::rpc::format::Format;
 use surrealdb_core::rpc::{DbResponse, DbResult, Method, RpcProtocol};
-use surrealdb_types::{Array, Error as TypesError, HashMap, Value};
+use surrealdb_types::{Action, Array, Error as TypesError, HashMap, Notification, Value};
 use tokio::sync::RwLock;
 use tokio::sync::mpsc::{Receiver, Sender, channel};
 use tokio::task::JoinSet;
@@ -53,6 +54,8 @@ pub struct Websocket {
 	pub(crate) sessions: HashMap<Option<Uuid>, Arc<RwLock<Session>>>,
 	/// The active transactions for this WebSocket connection
 	pub(crate) transactions: DashMap<Uuid, Arc<Transaction>>,
+	/// The active change data capture streams for this WebSocket connection
+	pub(crate) changes: DashMap<Uuid, (Option<Uuid>, CancellationToken)>,
 	/// A cancellation token called when shutting down the server
 	pub(crate) shutdown: CancellationToken,
 	/// A cancellation token for cancelling all spawned tasks
@@ -84,6 +87,7 @@ impl Websocket {
 			canceller: CancellationToken::new(),
 			sessions: HashMap::new(),
 			transactions: DashMap::new(),
+			changes: DashMap::new(),
 			channel: sender.clone(),
 			datastore,
 		});
@@ -443,6 +447,18 @@ impl Websocket {
 				}),
 			));
 		}
+		// Check the request limits for the session
+		let session = rpc.get_session(&session_id)?;
+		let (permit, changed) = {
+			let session = session.read().await;
+			let permit = rpc.state.limiter.acquire(&rpc.datastore, &session).await?;
+			let changed = session.timeout != permit.timeout();
+			(permit, changed)
+		};
+		// Apply the query time limit to the session
+		if changed {
+			session.write().await.timeout = permit.timeout();
+		}
 		// Execute the specified method
 		RpcProtocol::execute(rpc.as_ref(), txn, session_id, method, params).await
 	}
@@ -541,8 +557,92 @@ impl RpcProtocol for Websocket {
 		}
 	}
 
+	/// Change data capture streams are enabled on WebSockets
+	const CDC_SUPPORT: bool = true;
+
+	/// Handles the delivery of a change data capture stream
+	async fn handle_changes(
+		&self,
+		id: &Uuid,
+		session_id: Option<Uuid>,
+		mut stream: ChangeStream,
+	) -> Result<(), TypesError> {
+		let id = *id;
+		// The id is used to kill the stream, so it must not already be in use
+		if self.state.live_queries.read().await.contains_key(&id) {
+			return Err(surrealdb_core::rpc::invalid_params("The change stream id is already in use"));
+		}
+		let canceller = self.canceller.child_token();
+		match self.changes.entry(id) {
+			Entry::Occupied(_) => {
+				return Err(surrealdb_core::rpc::invalid_params(
+					"The change stream id is already in use",
+				));
+			}
+			Entry::Vacant(v) => {
+				v.insert((session_id, canceller.clone()));
+			}
+		}
+		trace!("Registered change stream {id} on websocket {}", self.id);
+		// Get the WebSocket output format
+		let format = self.format;
+		// Get the WebSocket sending channel
+		let sender = self.channel.clone();
+		// Deliver the change sets in order, one at a time
+		tokio::spawn(async move {
+			loop {
+				let result = tokio::select! {
+					biased;
+					// Check if the stream has been killed
+					_ = canceller.cancelled() => break,
+					// Receive the next change set
+					result = stream.next() => result,
+				};
+				// Deliver the change set, or end the stream on an error
+				let (action, result) = match result {
+					Some(Ok(value)) => (Action::Changes, value),
+					Some(Err(err)) => {
+						warn!("Change stream {id} failed: {err}");
+						(Action::Killed, Value::None)
+					}
+					None => (Action::Killed, Value::None),
+				};
+				let session = session_id.map(Into::into);
+				let notification =
+					Notification::new(id.into(), session, action, Value::None, result);
+				let message = DbResponse::success(None, session_id, DbResult::Live(notification));
+				let cx = Arc::new(TelemetryContext::new());
+				crate::rpc::response::send(message, cx, format, sender.clone()).await;
+				if action == Action::Killed {
+					break;
+				}
+			}
+		});
+		Ok(())
+	}
+
+	/// Handles the cancellation of a change data capture stream
+	async fn handle_kill_changes(&self, id: &Uuid) -> bool {
+		match self.changes.remove(id) {
+			Some((_, (_, canceller))) => {
+				canceller.cancel();
+				trace!("Unregistered change stream {id} on websocket {}", self.id);
+				true
+			}
+			None => false,
+		}
+	}
+
 	/// Handles the cleanup of live queries
 	async fn cleanup_lqs(&self, session_id: Option<&Uuid>) {
+		// Stop the change streams for this session
+		self.changes.retain(|_, (session, canceller)| {
+			if session.as_ref() == session_id {
+				canceller.cancel();
+				return false;
+			}
+			true
+		});
 		let mut gc = Vec::new();
 		// Find all live queries for to this connection
 		self.state.live_queries.write().await.retain(|key, value| {
@@ -561,6 +661,11 @@ impl RpcProtocol for Websocket {
 
 	/// Handles the cleanup of live queries
 	async fn cleanup_all_lqs(&self) {
+		// Stop the change streams for this connection
+		self.changes.retain(|_, (_, canceller)| {
+			canceller.cancel();
+			false
+		});
 		let mut gc = Vec::new();
 		// Find all live queries for to this connection
 		self.state.live_queries.write().await.retain(|key, value| {
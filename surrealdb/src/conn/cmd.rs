@@ -82,6 +82,20 @@ pub(crate) enum Command {
 	Kill {
 		uuid: Uuid,
 	},
+	Changes {
+		uuid: Uuid,
+		table: String,
+		group: String,
+	},
+	Ack {
+		table: String,
+		group: String,
+		versionstamp: u64,
+	},
+	RemoveGroup {
+		table: String,
+		group: String,
+	},
 	Attach {
 		session_id: Uuid,
 	},
@@ -127,6 +127,27 @@ impl conn::Sealed for Any {
 				));
 				}
 
+				EndpointKind::Redb => {
+					#[cfg(feature = "kv-redb")]
+					{
+						features.insert(ExtraFeatures::Backup);
+						features.insert(ExtraFeatures::LiveQueries);
+						tokio::spawn(engine::local::native::run_router(
+							address,
+							conn_tx,
+							route_rx,
+							session_clone.receiver.clone(),
+						));
+						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??
+					}
+
+					#[cfg(not(feature = "kv-redb"))]
+				return Err(Error::configuration(
+					"Cannot connect to the `redb` storage engine as it is not enabled in this build of SurrealDB".to_string(),
+					None,
+				));
+				}
+
 				EndpointKind::Http | EndpointKind::Https => {
 					#[cfg(feature = "protocol-http")]
 					{
@@ -111,6 +111,25 @@ impl conn::Sealed for Any {
 			));
 				}
 
+				EndpointKind::Redb => {
+					#[cfg(feature = "kv-redb")]
+					{
+						features.insert(ExtraFeatures::LiveQueries);
+						spawn_local(engine::local::wasm::run_router(
+							address,
+							conn_tx,
+							route_rx,
+							session_clone.receiver.clone(),
+						));
+						conn_rx.recv().await.map_err(crate::std_error_to_types_error)??;
+					}
+
+					#[cfg(not(feature = "kv-redb"))]
+				return Err(Error::internal(
+				"Cannot connect to the `redb` storage engine as it is not enabled in this build of SurrealDB".to_owned(),
+			));
+				}
+
 				EndpointKind::TiKv => {
 					#[cfg(feature = "kv-tikv")]
 					{
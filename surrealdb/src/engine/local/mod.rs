@@ -163,7 +163,6 @@ use std::task::{Poll, ready};
 use std::{future::Future, path::PathBuf};
 
 use async_channel::Sender;
-#[cfg(all(not(target_family = "wasm"), feature = "ml"))]
 use futures::StreamExt;
 #[cfg(not(target_family = "wasm"))]
 use futures::stream::poll_fn;
@@ -178,6 +177,8 @@ use surrealdb_core::{
 	ml::storage::surml_file::SurMlFile,
 };
 use surrealdb_types::Error as TypesError;
+#[cfg(not(target_family = "wasm"))]
+use tokio::spawn;
 use tokio::sync::RwLock;
 #[cfg(not(target_family = "wasm"))]
 use tokio::{
@@ -186,7 +187,10 @@ use tokio::{
 };
 #[cfg(not(target_family = "wasm"))]
 use tokio_util::bytes::BytesMut;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_futures::spawn_local as spawn;
 
 use crate::conn::Command;
 #[cfg(all(not(target_family = "wasm"), feature = "ml"))]
@@ -194,7 +198,7 @@ use crate::conn::MlExportConfig;
 use crate::engine::SessionError;
 use crate::opt::IntoEndpoint;
 use crate::opt::auth::{AccessToken, RefreshToken, SecureToken, Token};
-use crate::types::{HashMap, Notification, SurrealValue, ToSql, Value, Variables};
+use crate::types::{Action, HashMap, Notification, SurrealValue, ToSql, Value, Variables};
 use crate::{Connect, Surreal};
 
 /// In-memory database
@@ -395,6 +399,42 @@ pub struct TiKv;
 #[derive(Debug)]
 pub struct SurrealKv;
 
+/// redb database
+///
+/// # Examples
+///
+/// Instantiating a redb-backed instance
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::local::Redb;
+///
+/// let db = Surreal::new::<Redb>("path/to/database.redb").await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Instantiating a redb-backed strict instance
+///
+/// ```no_run
+/// # #[tokio::main]
+/// # async fn main() -> surrealdb::Result<()> {
+/// use surrealdb::opt::Config;
+/// use surrealdb::Surreal;
+/// use surrealdb::engine::local::Redb;
+///
+/// let config = Config::default().strict();
+/// let db = Surreal::new::<Redb>(("path/to/database.redb", config)).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "kv-redb")]
+#[cfg_attr(docsrs, doc(cfg(feature = "kv-redb")))]
+#[derive(Debug)]
+pub struct Redb;
+
 /// An embedded database
 #[derive(Debug, Clone)]
 pub struct Db(());
@@ -434,6 +474,7 @@ impl RouterState {
 					vars: RwLock::new(state.vars.read().await.clone()),
 					transactions: HashMap::new(),
 					live_queries: HashMap::new(),
+					changes: HashMap::new(),
 				}))
 			}
 			Some(Err(error)) => Err(error),
@@ -444,7 +485,12 @@ impl RouterState {
 
 	/// Handle a session being dropped.
 	fn handle_session_drop(&self, session_id: Uuid) {
-		self.sessions.remove(&session_id);
+		if let Some(Ok(state)) = self.sessions.take(&session_id) {
+			// Stop the change streams of this session
+			for canceller in state.changes.values() {
+				canceller.cancel();
+			}
+		}
 	}
 }
 
@@ -456,6 +502,7 @@ struct SessionState {
 	vars: RwLock<Variables>,
 	transactions: HashMap<Uuid, Arc<Transaction>>,
 	live_queries: HashMap<Uuid, Sender<crate::Result<Notification>>>,
+	changes: HashMap<Uuid, CancellationToken>,
 }
 
 impl SessionState {
@@ -467,6 +514,7 @@ impl SessionState {
 			vars: RwLock::new(Variables::default()),
 			transactions: HashMap::new(),
 			live_queries: HashMap::new(),
+			changes: HashMap::new(),
 		}
 	}
 }
@@ -1126,6 +1174,11 @@ async fn router(
 			uuid,
 		} => {
 			state.live_queries.remove(&uuid);
+			// Stop the change stream if this is not a live query
+			if let Some(canceller) = state.changes.take(&uuid) {
+				canceller.cancel();
+				return Ok(vec![QueryResultBuilder::instant_none()]);
+			}
 			let results = kill_live_query(
 				kvs,
 				uuid,
@@ -1135,6 +1188,81 @@ async fn router(
 			.await?;
 			Ok(results)
 		}
+		Command::Changes {
+			uuid,
+			table,
+			group,
+		} => {
+			let query_result = QueryResultBuilder::started_now();
+			// The stream is delivered through the registered notification channel
+			let Some(sender) = state.live_queries.get(&uuid) else {
+				return Err(crate::Error::internal(format!(
+					"Change stream '{uuid}' has not been subscribed to"
+				)));
+			};
+			let session = state.session.read().await;
+			let session_id = session.id.map(Into::into);
+			let stream = kvs
+				.changes(&session, &table, &group)
+				.await
+				.map_err(crate::std_error_to_types_error)?;
+			drop(session);
+			// Deliver the change sets in order, until the stream is killed
+			let canceller = CancellationToken::new();
+			state.changes.insert(uuid, canceller.clone());
+			spawn(async move {
+				let mut stream = std::pin::pin!(stream.take_until(canceller.cancelled_owned()));
+				while let Some(result) = stream.next().await {
+					let value = match result {
+						Ok(value) => value,
+						Err(error) => {
+							sender.send(Err(crate::std_error_to_types_error(error))).await.ok();
+							let notification = Notification::new(
+								uuid.into(),
+								session_id,
+								Action::Killed,
+								Value::None,
+								Value::None,
+							);
+							sender.send(Ok(notification)).await.ok();
+							break;
+						}
+					};
+					let notification = Notification::new(
+						uuid.into(),
+						session_id,
+						Action::Changes,
+						Value::None,
+						value,
+					);
+					if sender.send(Ok(notification)).await.is_err() {
+						break;
+					}
+				}
+			});
+			Ok(vec![query_result.finish()])
+		}
+		Command::Ack {
+			table,
+			group,
+			versionstamp,
+		} => {
+			let query_result = QueryResultBuilder::started_now();
+			kvs.ack_changes(&*state.session.read().await, &table, &group, versionstamp)
+				.await
+				.map_err(crate::std_error_to_types_error)?;
+			Ok(vec![query_result.finish()])
+		}
+		Command::RemoveGroup {
+			table,
+			group,
+		} => {
+			let query_result = QueryResultBuilder::started_now();
+			kvs.remove_consumer_group(&*state.session.read().await, &table, &group)
+				.await
+				.map_err(crate::std_error_to_types_error)?;
+			Ok(vec![query_result.finish()])
+		}
 
 		Command::Run {
 			name,
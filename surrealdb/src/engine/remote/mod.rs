@@ -312,6 +312,46 @@ impl Command {
 				txn: None,
 				session_id,
 			},
+			Command::Changes {
+				uuid,
+				table,
+				group,
+			} => RouterRequest {
+				id,
+				method: "changes",
+				params: Some(Value::from_t(vec![
+					Value::from_t(table),
+					Value::from_t(group),
+					Value::Uuid(Uuid::from(uuid)),
+				])),
+				txn: None,
+				session_id,
+			},
+			Command::Ack {
+				table,
+				group,
+				versionstamp,
+			} => RouterRequest {
+				id,
+				method: "ack",
+				params: Some(Value::from_t(vec![
+					Value::from_t(table),
+					Value::from_t(group),
+					Value::from_t(versionstamp),
+				])),
+				txn: None,
+				session_id,
+			},
+			Command::RemoveGroup {
+				table,
+				group,
+			} => RouterRequest {
+				id,
+				method: "remove_group",
+				params: Some(Value::from_t(vec![Value::from_t(table), Value::from_t(group)])),
+				txn: None,
+				session_id,
+			},
 			Command::Attach {
 				session_id,
 			} => RouterRequest {
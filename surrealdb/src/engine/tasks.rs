@@ -51,8 +51,10 @@ pub fn init(dbs: Arc<Datastore>, canceller: CancellationToken, opts: &EngineOpti
 	let task3 = spawn_task_node_membership_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task4 = spawn_task_changefeed_cleanup(dbs.clone(), canceller.clone(), opts);
 	let task5 = spawn_task_index_compaction(dbs.clone(), canceller.clone(), opts);
-	let task6 = spawn_task_event_processing(dbs, canceller, opts);
-	Tasks(vec![task1, task2, task3, task4, task5, task6])
+	let task6 = spawn_task_event_processing(dbs.clone(), canceller.clone(), opts);
+	let task7 = spawn_task_bucket_lifecycle(dbs.clone(), canceller.clone(), opts);
+	let task8 = spawn_task_history_cleanup(dbs, canceller, opts);
+	Tasks(vec![task1, task2, task3, task4, task5, task6, task7, task8])
 }
 
 fn spawn_task_node_membership_refresh(
@@ -264,6 +266,68 @@ fn spawn_task_event_processing(
 	}))
 }
 
+fn spawn_task_bucket_lifecycle(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.bucket_lifecycle_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running bucket lifecycle rules every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.bucket_lifecycle(interval).await {
+						error!("Error running bucket lifecycle rules: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running bucket lifecycle rules");
+	}))
+}
+
+fn spawn_task_history_cleanup(
+	dbs: Arc<Datastore>,
+	canceller: CancellationToken,
+	opts: &EngineOptions,
+) -> Task {
+	// Get the delay interval from the config
+	let interval = opts.history_gc_interval;
+	// Spawn a future
+	Box::pin(spawn(async move {
+		// Log the interval frequency
+		trace!("Running history garbage collection every {interval:?}");
+		// Create a new time-based interval ticket
+		let mut ticker = interval_ticker(interval).await;
+		// Loop continuously until the task is cancelled
+		loop {
+			tokio::select! {
+				biased;
+				// Check if this has shutdown
+				_ = canceller.cancelled() => break,
+				// Receive a notification on the channel
+				Some(_) = ticker.next() => {
+					if let Err(e) = dbs.history_process(&interval).await {
+						error!("Error running history garbage collection: {e}");
+					}
+				}
+			}
+		}
+		trace!("Background task exited: Running history garbage collection");
+	}))
+}
+
 async fn interval_ticker(interval: Duration) -> IntervalStream {
 	#[cfg(not(target_family = "wasm"))]
 	use tokio::{time, time::MissedTickBehavior};
@@ -0,0 +1,240 @@
+use std::borrow::Cow;
+use std::future::IntoFuture;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use async_channel::Receiver;
+use futures::StreamExt;
+use uuid::Uuid;
+
+use crate::conn::Command;
+use crate::engine::any::Any;
+use crate::method::live::{kill, register};
+use crate::method::{BoxFuture, OnceLockExt};
+use crate::types::{Action, Notification as CoreNotification, SurrealValue, Value};
+use crate::{Connection, Error, ExtraFeatures, Result, Surreal};
+
+/// A change data capture stream future
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Changes<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: String,
+	pub(super) group: String,
+}
+
+impl<C> Changes<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> Changes<'static, C> {
+		Changes {
+			client: Cow::Owned(self.client.into_owned()),
+			table: self.table,
+			group: self.group,
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for Changes<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<ChangeStream>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		let Changes {
+			client,
+			table,
+			group,
+		} = self;
+		Box::pin(async move {
+			let router = client.inner.router.extract()?;
+			if !router.features.contains(&ExtraFeatures::LiveQueries) {
+				return Err(Error::internal(
+					"The protocol or storage engine does not support change data capture on this architecture"
+						.to_string(),
+				));
+			}
+			// Stream the changes on a session of its own, which keeps the
+			// namespace and database of the client which started the stream
+			let session_id = Uuid::new_v4();
+			client.inner.clone_session(client.session_id, session_id);
+			let client = Surreal::<Any> {
+				inner: client.inner.clone(),
+				session_id,
+				engine: PhantomData,
+			};
+			// Subscribe before the stream starts, so that no change sets are missed
+			let id = Uuid::new_v4();
+			let rx = register(router, id, session_id).await?;
+			let stream = ChangeStream {
+				client,
+				id,
+				table: table.clone(),
+				group: group.clone(),
+				rx: Some(Box::pin(rx)),
+			};
+			router
+				.execute_value(
+					session_id,
+					Command::Changes {
+						uuid: id,
+						table,
+						group,
+					},
+				)
+				.await?;
+			Ok(stream)
+		})
+	}
+}
+
+/// A future which removes a change data capture consumer group
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RemoveConsumerGroup<'r, C: Connection> {
+	pub(super) client: Cow<'r, Surreal<C>>,
+	pub(super) table: String,
+	pub(super) group: String,
+}
+
+impl<C> RemoveConsumerGroup<'_, C>
+where
+	C: Connection,
+{
+	/// Converts to an owned type which can easily be moved to a different
+	/// thread
+	pub fn into_owned(self) -> RemoveConsumerGroup<'static, C> {
+		RemoveConsumerGroup {
+			client: Cow::Owned(self.client.into_owned()),
+			..self
+		}
+	}
+}
+
+impl<'r, Client> IntoFuture for RemoveConsumerGroup<'r, Client>
+where
+	Client: Connection,
+{
+	type Output = Result<()>;
+	type IntoFuture = BoxFuture<'r, Self::Output>;
+
+	fn into_future(self) -> Self::IntoFuture {
+		Box::pin(async move {
+			let router = self.client.inner.router.extract()?;
+			router
+				.execute_unit(
+					self.client.session_id,
+					Command::RemoveGroup {
+						table: self.table,
+						group: self.group,
+					},
+				)
+				.await
+		})
+	}
+}
+
+/// A set of changes which were committed to a table at a versionstamp
+#[derive(Debug, Clone, PartialEq, SurrealValue)]
+#[surreal(crate = "crate::types")]
+#[non_exhaustive]
+pub struct ChangeSet {
+	/// The versionstamp at which the changes were committed
+	pub versionstamp: u64,
+	/// The changes which were made to the table
+	pub changes: Vec<Value>,
+}
+
+/// A stream of change sets for a change data capture consumer group
+///
+/// Change sets are delivered in versionstamp order, starting after the last
+/// change set which the consumer group acknowledged. Change sets which are
+/// not acknowledged with [`ChangeStream::ack`] are delivered again when the
+/// consumer group next streams the table.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless you poll them"]
+pub struct ChangeStream {
+	client: Surreal<Any>,
+	id: Uuid,
+	table: String,
+	group: String,
+	rx: Option<Pin<Box<Receiver<Result<CoreNotification>>>>>,
+}
+
+impl ChangeStream {
+	/// Acknowledges all change sets up to and including a versionstamp
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// let mut stream = db.changes("person", "mailer").await?;
+	/// while let Some(change_set) = stream.next().await {
+	///     let change_set = change_set?;
+	///     // Process the changes, then mark them as consumed
+	///     stream.ack(change_set.versionstamp).await?;
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn ack(&self, versionstamp: u64) -> Result<()> {
+		let router = self.client.inner.router.extract()?;
+		router
+			.execute_unit(
+				self.client.session_id,
+				Command::Ack {
+					table: self.table.clone(),
+					group: self.group.clone(),
+					versionstamp,
+				},
+			)
+			.await
+	}
+}
+
+impl futures::Stream for ChangeStream {
+	type Item = Result<ChangeSet>;
+
+	fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+		let Some(ref mut rx) = self.as_mut().rx else {
+			return Poll::Ready(None);
+		};
+		match rx.poll_next_unpin(cx) {
+			Poll::Ready(Some(Ok(notification))) => match notification.action {
+				Action::Killed => Poll::Ready(None),
+				_ => Poll::Ready(Some(
+					ChangeSet::from_value(notification.result)
+						.map_err(|error| Error::internal(error.to_string())),
+				)),
+			},
+			Poll::Ready(Some(Err(error))) => Poll::Ready(Some(Err(error))),
+			Poll::Ready(None) => Poll::Ready(None),
+			Poll::Pending => Poll::Pending,
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, None)
+	}
+}
+
+impl Drop for ChangeStream {
+	/// Close the change data capture stream
+	///
+	/// This stops the delivery of change sets for this stream.
+	fn drop(&mut self) {
+		if self.rx.is_some() {
+			kill(&self.client, self.id);
+		}
+	}
+}
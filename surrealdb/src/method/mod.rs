@@ -17,6 +17,7 @@ pub(crate) mod query;
 mod authenticate;
 mod begin;
 mod cancel;
+mod changes;
 mod commit;
 mod content;
 mod create;
@@ -49,6 +50,7 @@ mod tests;
 pub use authenticate::Authenticate;
 pub use begin::Begin;
 pub use cancel::Cancel;
+pub use changes::{ChangeSet, ChangeStream, Changes, RemoveConsumerGroup};
 pub use commit::Commit;
 pub use content::Content;
 pub use create::Create;
@@ -1258,6 +1260,74 @@ where
 		}
 	}
 
+	/// Streams the changefeed of a table to a change data capture consumer
+	/// group
+	///
+	/// The stream starts after the last change set which the consumer group
+	/// acknowledged, and delivers change sets in versionstamp order as they
+	/// are committed. Change sets must be acknowledged with
+	/// [`ChangeStream::ack`], otherwise they are delivered again when the
+	/// consumer group next streams the table. The table, or its database,
+	/// must be defined with a changefeed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// use futures::StreamExt;
+	///
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # db.use_ns("namespace").use_db("database").await?;
+	/// let mut stream = db.changes("person", "search-indexer").await?;
+	/// while let Some(change_set) = stream.next().await {
+	///     let change_set = change_set?;
+	///     for change in change_set.changes {
+	///         println!("{change:?}");
+	///     }
+	///     stream.ack(change_set.versionstamp).await?;
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn changes(&'_ self, table: impl Into<String>, group: impl Into<String>) -> Changes<'_, C> {
+		Changes {
+			client: Cow::Borrowed(self),
+			table: table.into(),
+			group: group.into(),
+		}
+	}
+
+	/// Removes a change data capture consumer group from a table
+	///
+	/// The change sets which the consumer group has not acknowledged are no
+	/// longer kept by changefeed garbage collection. If the consumer group
+	/// streams the table again, it starts from the first change set in the
+	/// changefeed.
+	///
+	/// # Examples
+	///
+	/// ```no_run
+	/// # #[tokio::main]
+	/// # async fn main() -> surrealdb::Result<()> {
+	/// # let db = surrealdb::engine::any::connect("mem://").await?;
+	/// # db.use_ns("namespace").use_db("database").await?;
+	/// db.remove_consumer_group("person", "search-indexer").await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn remove_consumer_group(
+		&'_ self,
+		table: impl Into<String>,
+		group: impl Into<String>,
+	) -> RemoveConsumerGroup<'_, C> {
+		RemoveConsumerGroup {
+			client: Cow::Borrowed(self),
+			table: table.into(),
+			group: group.into(),
+		}
+	}
+
 	/// Returns the version of the server
 	///
 	/// # Examples
@@ -38,6 +38,15 @@ pub(super) fn mock(route_rx: Receiver<Route>) {
 				Command::Kill {
 					..
 				}
+				| Command::Changes {
+					..
+				}
+				| Command::Ack {
+					..
+				}
+				| Command::RemoveGroup {
+					..
+				}
 				| Command::Unset {
 					..
 				} => query_result,
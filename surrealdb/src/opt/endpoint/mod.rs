@@ -7,6 +7,8 @@ mod ws;
 mod indxdb;
 #[cfg(feature = "kv-mem")]
 mod mem;
+#[cfg(feature = "kv-redb")]
+mod redb;
 #[cfg(feature = "kv-rocksdb")]
 mod rocksdb;
 #[cfg(feature = "kv-surrealkv")]
@@ -154,6 +156,7 @@ pub enum EndpointKind {
 	TiKv,
 	Unsupported(String),
 	SurrealKv,
+	Redb,
 }
 
 impl From<&str> for EndpointKind {
@@ -169,6 +172,7 @@ impl From<&str> for EndpointKind {
 			"rocksdb" => Self::RocksDb,
 			"tikv" => Self::TiKv,
 			"surrealkv" => Self::SurrealKv,
+			"redb" => Self::Redb,
 			_ => Self::Unsupported(s.to_owned()),
 		}
 	}
@@ -4,6 +4,7 @@
 	feature = "kv-rocksdb",
 	feature = "kv-tikv",
 	feature = "kv-surrealkv",
+	feature = "kv-redb",
 	feature = "protocol-http",
 ))]
 
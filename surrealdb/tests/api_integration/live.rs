@@ -5,6 +5,7 @@
 	feature = "kv-rocksdb",
 	feature = "kv-tikv",
 	feature = "kv-surrealkv",
+	feature = "kv-redb",
 ))]
 
 // Tests for running live queries
@@ -603,6 +604,89 @@ pub async fn live_select_returns_uuid(new_db: impl CreateDb) {
 	drop(permit);
 }
 
+/// Test that change sets are streamed in order, and that change sets which
+/// were not acknowledged are delivered again to the consumer group
+pub async fn changes_resume_after_ack(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table} CHANGEFEED 1h")).await.unwrap().check().unwrap();
+	db.query(format!("CREATE {table}:one")).await.unwrap().check().unwrap();
+	db.query(format!("CREATE {table}:two")).await.unwrap().check().unwrap();
+
+	// Stream the change sets
+	let mut stream = db.changes(&table, "group").await.unwrap();
+	let first = tokio::time::timeout(LQ_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+	let second = tokio::time::timeout(LQ_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+	assert!(first.versionstamp < second.versionstamp);
+
+	// Only acknowledge the first change set
+	stream.ack(first.versionstamp).await.unwrap();
+	drop(stream);
+
+	// The consumer group resumes after the acknowledged change set
+	let mut stream = db.changes(&table, "group").await.unwrap();
+	let next = tokio::time::timeout(LQ_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(next, second);
+
+	// Another consumer group starts from the beginning of the changefeed
+	let mut stream = db.changes(&table, "other").await.unwrap();
+	let next = tokio::time::timeout(LQ_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(next, first);
+
+	drop(permit);
+}
+
+/// Test that a removed consumer group starts from the beginning of the
+/// changefeed when it streams the table again
+pub async fn changes_remove_consumer_group(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table} CHANGEFEED 1h")).await.unwrap().check().unwrap();
+	db.query(format!("CREATE {table}:one")).await.unwrap().check().unwrap();
+
+	// Stream and acknowledge the change set
+	let mut stream = db.changes(&table, "group").await.unwrap();
+	let first = tokio::time::timeout(LQ_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+	stream.ack(first.versionstamp).await.unwrap();
+	drop(stream);
+
+	// Remove the consumer group
+	db.remove_consumer_group(&table, "group").await.unwrap();
+	let error = db.remove_consumer_group(&table, "group").await.unwrap_err();
+	assert!(error.to_string().contains("does not exist"), "{error}");
+
+	// The consumer group starts from the beginning of the changefeed again
+	let mut stream = db.changes(&table, "group").await.unwrap();
+	let next = tokio::time::timeout(LQ_TIMEOUT, stream.next()).await.unwrap().unwrap().unwrap();
+	assert_eq!(next, first);
+
+	drop(permit);
+}
+
+/// Test that change data capture requires a changefeed on the table
+pub async fn changes_without_changefeed(new_db: impl CreateDb) {
+	let config = Config::new();
+	let (permit, db) = new_db.create_db(config).await;
+
+	db.use_ns(Ulid::new().to_string()).use_db(Ulid::new().to_string()).await.unwrap();
+
+	let table = format!("table_{}", Ulid::new());
+	db.query(format!("DEFINE TABLE {table}")).await.unwrap().check().unwrap();
+
+	let error = db.changes(&table, "group").await.unwrap_err();
+	assert!(error.to_string().contains("does not have a changefeed"), "{error}");
+
+	drop(permit);
+}
+
 define_include_tests!(live => {
 	#[test_log::test(tokio::test)]
 	live_select_table,
@@ -618,4 +702,10 @@ define_include_tests!(live => {
 	live_query_delete_notifications,
 	#[test_log::test(tokio::test)]
 	live_select_returns_uuid,
+	#[test_log::test(tokio::test)]
+	changes_resume_after_ack,
+	#[test_log::test(tokio::test)]
+	changes_remove_consumer_group,
+	#[test_log::test(tokio::test)]
+	changes_without_changefeed,
 });
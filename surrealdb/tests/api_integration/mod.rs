@@ -53,7 +53,7 @@ mod version;
 const ROOT_USER: &str = "root";
 const ROOT_PASS: &str = "root";
 
-#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv",))]
+#[cfg(any(feature = "kv-rocksdb", feature = "kv-surrealkv", feature = "kv-redb"))]
 static TEMP_DIR: std::sync::LazyLock<std::path::PathBuf> =
 	std::sync::LazyLock::new(|| temp_dir::TempDir::new().unwrap().child("sdb-test"));
 
@@ -608,6 +608,52 @@ mod surrealkv {
 	include_tests!(new_db => basic, serialisation, live, backup, session_isolation, run);
 }
 
+#[cfg(feature = "kv-redb")]
+mod redb {
+	use surrealdb::Surreal;
+	use surrealdb::engine::local::{Db, Redb};
+	use surrealdb::opt::Config;
+	use surrealdb::opt::auth::Root;
+	use tokio::sync::{Semaphore, SemaphorePermit};
+	use ulid::Ulid;
+
+	use super::{ROOT_PASS, ROOT_USER, TEMP_DIR};
+
+	static PERMITS: Semaphore = Semaphore::const_new(1);
+
+	async fn new_db(config: Config) -> (SemaphorePermit<'static>, Surreal<Db>) {
+		let permit = PERMITS.acquire().await.unwrap();
+		let path = TEMP_DIR.join(format!("{}.redb", Ulid::new()));
+		let root = Root {
+			username: ROOT_USER.to_string(),
+			password: ROOT_PASS.to_string(),
+		};
+		let config = config.user(root.clone());
+		let db = Surreal::new::<Redb>((path, config)).await.unwrap();
+		db.signin(root).await.unwrap();
+		(permit, db)
+	}
+
+	#[test_log::test(tokio::test)]
+	async fn any_engine_can_connect() {
+		let db_file = format!("{}.redb", Ulid::new());
+		// Create a database file using an absolute path
+		surrealdb::engine::any::connect(format!(
+			"redb://{}",
+			TEMP_DIR.join("absolute").join(&db_file).display()
+		))
+		.await
+		.unwrap();
+		// Switch to the temporary directory, if possible, to test relative paths
+		if std::env::set_current_dir(&*TEMP_DIR).is_ok() {
+			// Create a database file using a relative path
+			surrealdb::engine::any::connect(format!("redb://relative/{db_file}")).await.unwrap();
+		}
+	}
+
+	include_tests!(new_db => basic, serialisation, live, backup, session_isolation, run);
+}
+
 #[cfg(feature = "kv-tikv")]
 mod tikv {
 	use surrealdb::Surreal;
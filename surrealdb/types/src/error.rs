@@ -27,6 +27,7 @@ mod code {
 	pub const THROWN: i64 = -32006;
 	pub const SERIALIZATION_ERROR: i64 = -32007;
 	pub const DESERIALIZATION_ERROR: i64 = -32008;
+	pub const RATE_LIMITED: i64 = -32009;
 }
 
 /// Default wire code when none is specified (e.g. for deserialization of older wire format).
@@ -115,6 +116,7 @@ impl Error {
 					| AuthError::MissingUserOrPass
 					| AuthError::NoSigninTarget
 					| AuthError::InvalidPass
+					| AuthError::MissingTotp
 					| AuthError::TokenMakingFailed
 					| AuthError::InvalidRole {
 						..
@@ -134,6 +136,7 @@ impl Error {
 				| NotAllowedError::Target {
 					..
 				} => code::METHOD_NOT_ALLOWED,
+				NotAllowedError::RateLimited => code::RATE_LIMITED,
 			})
 			.unwrap_or(code::INTERNAL_ERROR);
 		Self {
@@ -660,6 +663,9 @@ pub enum AuthError {
 	/// The password did not verify.
 	#[surreal(skip_content)]
 	InvalidPass,
+	/// A one-time password is required but was not provided.
+	#[surreal(skip_content)]
+	MissingTotp,
 	/// Failed to create the authentication token.
 	#[surreal(skip_content)]
 	TokenMakingFailed,
@@ -752,6 +758,9 @@ pub enum NotAllowedError {
 		/// Name of the net target.
 		name: String,
 	},
+	/// Request rate or concurrency limit exceeded.
+	#[surreal(skip_content)]
+	RateLimited,
 }
 
 /// Configuration failure reason for [`ErrorKind::Configuration`] errors.
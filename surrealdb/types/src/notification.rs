@@ -24,6 +24,8 @@ pub enum Action {
 	Delete,
 	/// The live query was killed.
 	Killed,
+	/// A change set was read from a changefeed.
+	Changes,
 }
 
 impl Display for Action {
@@ -33,6 +35,7 @@ impl Display for Action {
 			Action::Update => write!(f, "UPDATE"),
 			Action::Delete => write!(f, "DELETE"),
 			Action::Killed => write!(f, "KILLED"),
+			Action::Changes => write!(f, "CHANGES"),
 		}
 	}
 }
@@ -46,6 +49,7 @@ impl FromStr for Action {
 			"UPDATE" => Ok(Action::Update),
 			"DELETE" => Ok(Action::Delete),
 			"KILLED" => Ok(Action::Killed),
+			"CHANGES" => Ok(Action::Changes),
 			_ => Err(crate::Error::validation(format!("Invalid action: {s}"), None)),
 		}
 	}
@@ -1177,6 +1177,28 @@ fn test_error_wire_not_allowed_target() {
 	}
 }
 
+#[test]
+fn test_error_wire_not_allowed_rate_limited() {
+	// Wire format:
+	// {
+	//   "code": -32009,
+	//   "message": "Too many requests",
+	//   "kind": "NotAllowed",
+	//   "details": { "kind": "RateLimited" }
+	// }
+	let err = Error::not_allowed("Too many requests".into(), NotAllowedError::RateLimited);
+	let val = err.into_value();
+
+	let Value::Object(ref obj) = val else {
+		panic!();
+	};
+	assert_eq!(obj.get("code"), Some(&Value::Number((-32009).into())));
+
+	let parsed = Error::from_value(val).unwrap();
+	assert!(parsed.is_not_allowed());
+	assert_eq!(parsed.not_allowed_details(), Some(&NotAllowedError::RateLimited));
+}
+
 #[test]
 fn test_error_wire_not_allowed_no_details() {
 	// Wire format:
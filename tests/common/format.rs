@@ -5,6 +5,8 @@ use std::string::ToString;
 pub enum Format {
 	Json,
 	Cbor,
+	Msgpack,
+	Protobuf,
 }
 
 impl fmt::Display for Format {
@@ -12,6 +14,8 @@ impl fmt::Display for Format {
 		match self {
 			Self::Json => "json".fmt(f),
 			Self::Cbor => "cbor".fmt(f),
+			Self::Msgpack => "msgpack".fmt(f),
+			Self::Protobuf => "protobuf".fmt(f),
 		}
 	}
 }
@@ -143,7 +143,7 @@ impl Socket {
 	fn to_msg(format: Format, message: &serde_json::Value) -> Result<Message> {
 		match format {
 			Format::Json => Ok(Message::Text(serde_json::to_string(message)?.into())),
-			Format::Cbor => {
+			Format::Cbor | Format::Msgpack | Format::Protobuf => {
 				// For tests we need to convert the serde_json::Value
 				// to a SurrealQL value, so that record ids, uuids,
 				// datetimes, and durations are stored properly.
@@ -151,10 +151,14 @@ impl Socket {
 				let json = message.to_string();
 				// Then we parse the JSON in to SurrealQL.
 				let surrealql = surrealdb_core::syn::value_legacy_strand(&json)?;
-				// Then we convert the SurrealQL in to CBOR.
-				let cbor = surrealdb_core::rpc::format::cbor::encode(surrealql)?;
+				// Then we convert the SurrealQL in to the binary format.
+				let data = match format {
+					Format::Msgpack => surrealdb_core::rpc::format::msgpack::encode(surrealql)?,
+					Format::Protobuf => surrealdb_core::rpc::format::protobuf::encode(surrealql)?,
+					_ => surrealdb_core::rpc::format::cbor::encode(surrealql)?,
+				};
 				// THen output the message.
-				Ok(Message::Binary(cbor.into()))
+				Ok(Message::Binary(data.into()))
 			}
 		}
 	}
@@ -175,12 +179,20 @@ impl Socket {
 			Message::Binary(msg) => {
 				debug!("Response {msg:?}");
 				match format {
-					Format::Cbor => {
+					Format::Cbor | Format::Msgpack | Format::Protobuf => {
 						// For tests we need to convert the binary data to
 						// a serde_json::Value so that test assertions work.
-						// First of all we deserialize the CBOR data.
+						// First of all we deserialize the binary data.
 						// Then we convert it to a SurrealQL Value.
-						let msg = surrealdb_core::rpc::format::cbor::decode(msg.as_ref())?;
+						let msg = match format {
+							Format::Msgpack => {
+								surrealdb_core::rpc::format::msgpack::decode(msg.as_ref())?
+							}
+							Format::Protobuf => {
+								surrealdb_core::rpc::format::protobuf::decode(msg.as_ref())?
+							}
+							_ => surrealdb_core::rpc::format::cbor::decode(msg.as_ref())?,
+						};
 						// Then we convert the SurrealQL to JSON.
 						let msg = msg.into_json_value();
 						// Then output the response.
@@ -373,6 +373,18 @@ mod graphql_integration {
 					{
 						"name": "_get_bar"
 					},
+					{
+						"name": "_connection_foo"
+					},
+					{
+						"name": "_connection_bar"
+					},
+					{
+						"name": "_aggregate_foo"
+					},
+					{
+						"name": "_aggregate_bar"
+					},
 					{
 						"name": "_get"
 					}
@@ -411,6 +423,12 @@ mod graphql_integration {
 					{
 						"name": "_get_foo"
 					},
+					{
+						"name": "_connection_foo"
+					},
+					{
+						"name": "_aggregate_foo"
+					},
 					{
 						"name": "_get"
 					}
@@ -1937,6 +1955,334 @@ mod graphql_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn connections_and_aggregates() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Set up schema and data
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+					DEFINE TABLE product SCHEMAFUL;
+					DEFINE FIELD name ON product TYPE string;
+					DEFINE FIELD price ON product TYPE float;
+					DEFINE FIELD quantity ON product TYPE int;
+					DEFINE FIELD created ON product TYPE datetime;
+
+					CREATE product:1 SET name = "Alpha Widget", price = 9.5, quantity = 100, created = d"2024-01-15T00:00:00Z";
+					CREATE product:2 SET name = "Beta Widget", price = 19.5, quantity = 50, created = d"2024-03-20T00:00:00Z";
+					CREATE product:3 SET name = "Gamma Tool", price = 29.5, quantity = 200, created = d"2024-06-01T00:00:00Z";
+					CREATE product:4 SET name = "Delta Tool", price = 4.5, quantity = 10, created = d"2024-09-10T00:00:00Z";
+					CREATE product:5 SET name = "Epsilon Widget", price = 49.5, quantity = 0, created = d"2025-01-05T00:00:00Z";
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// --- Paginate forwards with first / after ---
+		let end_cursor = {
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						_connection_product(first: 2) {
+							edges { cursor node { id } }
+							pageInfo { hasNextPage hasPreviousPage startCursor endCursor }
+							totalCount
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let connection = &body["data"]["_connection_product"];
+			let edges = connection["edges"].as_array().unwrap();
+			assert_eq!(edges.len(), 2);
+			assert_eq!(edges[0]["node"]["id"], "product:1");
+			assert_eq!(edges[1]["node"]["id"], "product:2");
+			assert_eq!(connection["pageInfo"]["hasNextPage"], true);
+			assert_eq!(connection["pageInfo"]["hasPreviousPage"], false);
+			assert_eq!(connection["pageInfo"]["startCursor"], edges[0]["cursor"]);
+			assert_eq!(connection["pageInfo"]["endCursor"], edges[1]["cursor"]);
+			assert_eq!(connection["totalCount"], 5);
+			connection["pageInfo"]["endCursor"].as_str().unwrap().to_string()
+		};
+
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"query": r#"query($after: String) {
+							_connection_product(first: 5, after: $after) {
+								edges { node { id } }
+								pageInfo { hasNextPage }
+							}
+						}"#,
+						"variables": { "after": end_cursor }
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let connection = &body["data"]["_connection_product"];
+			let ids: Vec<_> = connection["edges"]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|e| e["node"]["id"].as_str().unwrap())
+				.collect();
+			assert_eq!(ids, vec!["product:3", "product:4", "product:5"]);
+			assert_eq!(connection["pageInfo"]["hasNextPage"], false);
+		}
+
+		// --- Paginate backwards with last / before, using an ordering ---
+		let start_cursor = {
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						_connection_product(last: 2, order: { asc: price }) {
+							edges { node { id } }
+							pageInfo { hasNextPage hasPreviousPage startCursor }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let connection = &body["data"]["_connection_product"];
+			let ids: Vec<_> = connection["edges"]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|e| e["node"]["id"].as_str().unwrap())
+				.collect();
+			// The two most expensive products, in ascending order of price
+			assert_eq!(ids, vec!["product:3", "product:5"]);
+			assert_eq!(connection["pageInfo"]["hasNextPage"], false);
+			assert_eq!(connection["pageInfo"]["hasPreviousPage"], true);
+			connection["pageInfo"]["startCursor"].as_str().unwrap().to_string()
+		};
+
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"query": r#"query($before: String) {
+							_connection_product(last: 2, before: $before, order: { asc: price }) {
+								edges { node { id } }
+								pageInfo { hasPreviousPage }
+							}
+						}"#,
+						"variables": { "before": start_cursor }
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let connection = &body["data"]["_connection_product"];
+			let ids: Vec<_> = connection["edges"]
+				.as_array()
+				.unwrap()
+				.iter()
+				.map(|e| e["node"]["id"].as_str().unwrap())
+				.collect();
+			assert_eq!(ids, vec!["product:1", "product:2"]);
+			assert_eq!(connection["pageInfo"]["hasPreviousPage"], true);
+		}
+
+		// --- Filters apply to the edges and the total count ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						_connection_product(first: 1, filter: { quantity: { gte: 50 } }) {
+							edges { node { id } }
+							pageInfo { hasNextPage }
+							totalCount
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let connection = &body["data"]["_connection_product"];
+			assert_eq!(connection["edges"].as_array().unwrap().len(), 1);
+			assert_eq!(connection["pageInfo"]["hasNextPage"], true);
+			assert_eq!(connection["totalCount"], 3);
+		}
+
+		// --- Invalid arguments are rejected ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query { _connection_product(first: 1, last: 1) { totalCount } }"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected an error: {body}");
+
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query { _connection_product(after: "invalid") { totalCount } }"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected an error: {body}");
+
+			// Cursors are never parsed as SurrealQL
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query { _connection_product(after: "W3Byb2R1Y3Q6MV0") { totalCount } }"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected an error: {body}");
+
+			// Malformed cursors are rejected without allocating for their claimed lengths
+			let deep = "gYGB".repeat(100);
+			let long = "A".repeat(20_000);
+			for cursor in ["m___________", "mwAAAAD_____", "W___________", &deep, &long] {
+				let query = format!(
+					r#"query {{ _connection_product(after: "{cursor}") {{ totalCount }} }}"#
+				);
+				let res =
+					client.post(gql_url).body(json!({ "query": query }).to_string()).send().await?;
+				assert_eq!(res.status(), 200);
+				let body = res.json::<serde_json::Value>().await?;
+				assert!(body["errors"].is_array(), "Expected an error: {body}");
+			}
+		}
+
+		// --- Aggregates over all records ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						_aggregate_product {
+							count
+							sum { price quantity }
+							min { price quantity created }
+							max { price quantity created }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let aggregate = &body["data"]["_aggregate_product"];
+			assert_eq!(aggregate["count"], 5);
+			assert_eq!(aggregate["sum"]["price"], 112.5);
+			assert_eq!(aggregate["sum"]["quantity"], 360);
+			assert_eq!(aggregate["min"]["price"], 4.5);
+			assert_eq!(aggregate["min"]["quantity"], 0);
+			assert_eq!(aggregate["max"]["price"], 49.5);
+			assert_eq!(aggregate["max"]["quantity"], 200);
+			assert!(aggregate["min"]["created"].as_str().unwrap().starts_with("2024-01-15"));
+			assert!(aggregate["max"]["created"].as_str().unwrap().starts_with("2025-01-05"));
+		}
+
+		// --- Aggregates over filtered records ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						_aggregate_product(where: { name: { contains: "Tool" } }) {
+							count
+							sum { quantity }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let aggregate = &body["data"]["_aggregate_product"];
+			assert_eq!(aggregate["count"], 2);
+			assert_eq!(aggregate["sum"]["quantity"], 210);
+		}
+
+		// --- Aggregates with no matching records ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"query {
+						_aggregate_product(filter: { quantity: { gt: 1000 } }) {
+							count
+							max { quantity }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {body}");
+			let aggregate = &body["data"]["_aggregate_product"];
+			assert_eq!(aggregate["count"], 0);
+			assert!(aggregate["max"]["quantity"].is_null());
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn nested_objects() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_without_auth().await.unwrap();
@@ -3007,6 +3353,10 @@ mod graphql_integration {
 			assert!(field_names.contains(&"updateManyItem"), "Missing updateManyItem");
 			assert!(field_names.contains(&"upsertManyItem"), "Missing upsertManyItem");
 			assert!(field_names.contains(&"deleteManyItem"), "Missing deleteManyItem");
+			assert!(field_names.contains(&"mergeItem"), "Missing mergeItem");
+			assert!(field_names.contains(&"mergeManyItem"), "Missing mergeManyItem");
+			assert!(field_names.contains(&"relateLikes"), "Missing relateLikes");
+			assert!(!field_names.contains(&"relateItem"), "Unexpected relateItem");
 		}
 
 		// --- Test 12: Input type introspection ---
@@ -3060,7 +3410,7 @@ mod graphql_integration {
 	}
 
 	#[test(tokio::test)]
-	async fn depth_and_complexity_limits() -> Result<(), Box<dyn std::error::Error>> {
+	async fn merge_and_relate_mutations() -> Result<(), Box<dyn std::error::Error>> {
 		let (addr, _server) = common::start_server_without_auth().await.unwrap();
 		let gql_url = &format!("http://{addr}/graphql");
 		let sql_url = &format!("http://{addr}/sql");
@@ -3076,25 +3426,31 @@ mod graphql_integration {
 			.default_headers(headers)
 			.build()?;
 
-		// Set up schema with depth and complexity limits
+		// Set up schema and data
 		{
 			let res = client
 				.post(sql_url)
 				.body(
 					r#"
-					DEFINE CONFIG GRAPHQL AUTO DEPTH 3 COMPLEXITY 10;
+					DEFINE CONFIG GRAPHQL AUTO;
+					DEFINE TABLE item SCHEMAFUL;
+					DEFINE FIELD name ON item TYPE string;
+					DEFINE FIELD price ON item TYPE int;
+					DEFINE FIELD stock ON item TYPE option<int>;
 					DEFINE TABLE person SCHEMAFUL;
 					DEFINE FIELD name ON person TYPE string;
-					DEFINE FIELD age ON person TYPE int;
 					DEFINE TABLE post SCHEMAFUL;
 					DEFINE FIELD title ON post TYPE string;
-					DEFINE FIELD author ON post TYPE record<person>;
-					DEFINE TABLE comment SCHEMAFUL;
-					DEFINE FIELD text ON comment TYPE string;
-					DEFINE FIELD post ON comment TYPE record<post>;
-					CREATE person:1 SET name = 'Alice', age = 30;
-					CREATE post:1 SET title = 'Hello', author = person:1;
-					CREATE comment:1 SET text = 'Nice', post = post:1;
+					DEFINE TABLE likes TYPE RELATION FROM person TO post SCHEMAFUL;
+					DEFINE FIELD rating ON likes TYPE int;
+					DEFINE TABLE follows TYPE RELATION FROM person TO person SCHEMAFUL;
+
+					CREATE item:1 SET name = "Widget", price = 100, stock = 5;
+					CREATE item:2 SET name = "Gadget", price = 200, stock = 10;
+					CREATE item:3 SET name = "Gizmo", price = 300;
+					CREATE person:alice SET name = "Alice";
+					CREATE person:bob SET name = "Bob";
+					CREATE post:1 SET title = "Hello World";
 				"#,
 				)
 				.send()
@@ -3102,32 +3458,243 @@ mod graphql_integration {
 			assert_eq!(res.status(), 200);
 		}
 
-		// A simple shallow query should succeed (depth 2, within limit of 3)
+		// --- mergeItem keeps the fields which are not provided ---
 		{
 			let res = client
 				.post(gql_url)
-				.body(json!({"query": r#"{ person { id, name } }"#}).to_string())
+				.body(
+					json!({"query": r#"mutation {
+						mergeItem(id: "1", data: { price: 150 }) {
+							id
+							name
+							price
+							stock
+						}
+					}"#})
+					.to_string(),
+				)
 				.send()
 				.await?;
 			assert_eq!(res.status(), 200);
 			let body = res.json::<serde_json::Value>().await?;
-			assert!(body["errors"].is_null(), "Unexpected errors for shallow query: {:?}", body);
-			assert!(body["data"]["person"].is_array(), "Expected person data");
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			let item = &body["data"]["mergeItem"];
+			assert_eq!(item["id"], "item:1");
+			assert_eq!(item["name"], "Widget");
+			assert_eq!(item["price"], 150);
+			assert_eq!(item["stock"], 5);
 		}
 
-		// A deeply nested query should fail with depth limit error (depth > 3)
+		// --- updateItem also merges, keeping the fields which are not provided ---
 		{
 			let res = client
 				.post(gql_url)
 				.body(
-					json!({"query": r#"{ comment { text, post { title, author { name, age } } } }"#})
-						.to_string(),
-				)
-				.send()
-				.await?;
-			assert_eq!(res.status(), 200);
-			let body = res.json::<serde_json::Value>().await?;
-			let errors = &body["errors"];
+					json!({"query": r#"mutation {
+						updateItem(id: "1", data: { price: 175 }) {
+							id
+							name
+							price
+							stock
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			let item = &body["data"]["updateItem"];
+			assert_eq!(item["id"], "item:1");
+			assert_eq!(item["name"], "Widget");
+			assert_eq!(item["price"], 175);
+			assert_eq!(item["stock"], 5);
+		}
+
+		// --- mergeManyItem applies to the records matching the filter ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation {
+						mergeManyItem(where: { price: { gte: 200 } }, data: { stock: 0 }) {
+							id
+							stock
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			let items = body["data"]["mergeManyItem"].as_array().unwrap();
+			assert_eq!(items.len(), 2);
+			assert!(items.iter().all(|i| i["stock"] == 0));
+		}
+
+		// --- Field types are checked against the catalog ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation {
+						mergeItem(id: "1", data: { price: "expensive" }) { id }
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected a type error: {body}");
+		}
+
+		// --- relateLikes creates an edge with data ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation {
+						relateLikes(in: "person:alice", out: "post:1", id: "first", data: { rating: 4 }) {
+							id
+							rating
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			let likes = &body["data"]["relateLikes"];
+			assert_eq!(likes["id"], "likes:first");
+			assert_eq!(likes["rating"], 4);
+		}
+
+		// --- relateLikes rejects records outside the relation's FROM / TO tables ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation {
+						relateLikes(in: "post:1", out: "person:alice", data: { rating: 1 }) { id }
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected an error: {body}");
+			assert!(body["data"]["relateLikes"].is_null());
+		}
+
+		// --- relateFollows has no data argument as the relation defines no fields ---
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"mutation {
+						relateFollows(in: "person:alice", out: "person:bob") { id }
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			assert!(body["data"]["relateFollows"]["id"].as_str().unwrap().starts_with("follows:"));
+		}
+
+		// --- The edges are visible through SurrealQL ---
+		{
+			let res = client
+				.post(sql_url)
+				.body("SELECT VALUE ->likes->post.title FROM ONLY person:alice;")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert_eq!(body[0]["result"], json!(["Hello World"]));
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn depth_and_complexity_limits() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Set up schema with depth and complexity limits
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO DEPTH 3 COMPLEXITY 10;
+					DEFINE TABLE person SCHEMAFUL;
+					DEFINE FIELD name ON person TYPE string;
+					DEFINE FIELD age ON person TYPE int;
+					DEFINE TABLE post SCHEMAFUL;
+					DEFINE FIELD title ON post TYPE string;
+					DEFINE FIELD author ON post TYPE record<person>;
+					DEFINE TABLE comment SCHEMAFUL;
+					DEFINE FIELD text ON comment TYPE string;
+					DEFINE FIELD post ON comment TYPE record<post>;
+					CREATE person:1 SET name = 'Alice', age = 30;
+					CREATE post:1 SET title = 'Hello', author = person:1;
+					CREATE comment:1 SET text = 'Nice', post = post:1;
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// A simple shallow query should succeed (depth 2, within limit of 3)
+		{
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": r#"{ person { id, name } }"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors for shallow query: {:?}", body);
+			assert!(body["data"]["person"].is_array(), "Expected person data");
+		}
+
+		// A deeply nested query should fail with depth limit error (depth > 3)
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{ comment { text, post { title, author { name, age } } } }"#})
+						.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			let errors = &body["errors"];
 			assert!(errors.is_array(), "Expected errors for deep query, got: {:?}", body);
 			let error_msg = errors[0]["message"].as_str().unwrap_or("");
 			assert!(
@@ -3502,6 +4069,291 @@ mod graphql_integration {
 		Ok(())
 	}
 
+	#[test(tokio::test)]
+	async fn federation() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		// Set up schema and data, without federation
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO;
+					DEFINE TABLE person SCHEMAFUL;
+					DEFINE FIELD name ON person TYPE string;
+					CREATE person:alice SET name = "Alice";
+					CREATE person:bob SET name = "Bob";
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// The federation fields are not available by default
+		{
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": r#"{ _service { sdl } }"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected _service to be unavailable: {body}");
+		}
+
+		// Enable federation
+		{
+			let res = client
+				.post(sql_url)
+				.body("DEFINE CONFIG OVERWRITE GRAPHQL AUTO FEDERATION AUTO;")
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// The subgraph SDL marks tables as entities keyed by their record ID
+		{
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": r#"{ _service { sdl } }"#}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			let sdl = body["data"]["_service"]["sdl"].as_str().unwrap();
+			assert!(sdl.contains(r#"type person @key(fields: "id")"#), "sdl: {sdl}");
+		}
+
+		// Entity representations resolve to records, in order
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						_entities(representations: [
+							{ __typename: "person", id: "person:bob" },
+							{ __typename: "person", id: "alice" },
+							{ __typename: "person", id: "person:missing" }
+						]) {
+							... on person { id name }
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			let entities = body["data"]["_entities"].as_array().unwrap();
+			assert_eq!(entities.len(), 3);
+			assert_eq!(entities[0]["id"], "person:bob");
+			assert_eq!(entities[0]["name"], "Bob");
+			assert_eq!(entities[1]["id"], "person:alice");
+			assert_eq!(entities[1]["name"], "Alice");
+			assert!(entities[2].is_null());
+		}
+
+		// The number of entity representations in a request is limited
+		{
+			let representations = vec![json!({ "__typename": "person", "id": "alice" }); 1_001];
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"query": r#"query ($r: [_Any!]!) { _entities(representations: $r) { __typename } }"#,
+						"variables": { "r": representations },
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected an error: {body}");
+		}
+
+		// Unknown entity types are rejected
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({"query": r#"{
+						_entities(representations: [{ __typename: "secret", id: "secret:1" }]) {
+							__typename
+						}
+					}"#})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected an error: {body}");
+		}
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn persisted_queries() -> Result<(), Box<dyn std::error::Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let gql_url = &format!("http://{addr}/graphql");
+		let sql_url = &format!("http://{addr}/sql");
+
+		let mut headers = reqwest::header::HeaderMap::new();
+		let ns = Ulid::new().to_string();
+		let db = Ulid::new().to_string();
+		headers.insert("surreal-ns", ns.parse()?);
+		headers.insert("surreal-db", db.parse()?);
+		headers.insert(header::ACCEPT, "application/json".parse()?);
+		let client = Client::builder()
+			.connect_timeout(Duration::from_secs(10))
+			.default_headers(headers)
+			.build()?;
+
+		// The SHA-256 hash of the persisted query `{ person { id } }`
+		let hash = "e5e3979818deaff2f6cb9e4189805bdba6fbebcd21a0cc6f74f89342be437899";
+
+		// Set up schema and data with a persisted query allow-list
+		{
+			let res = client
+				.post(sql_url)
+				.body(
+					r#"
+					DEFINE CONFIG GRAPHQL AUTO PERSISTED ["{ person { id } }"];
+					DEFINE TABLE person SCHEMAFUL;
+					DEFINE FIELD name ON person TYPE string;
+					CREATE person:alice SET name = "Alice";
+				"#,
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+		}
+
+		// A persisted query can be sent in full
+		{
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": "{ person { id } }"}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			assert_eq!(body["data"]["person"], json!([{ "id": "person:alice" }]));
+		}
+
+		// A persisted query can be sent by its hash alone
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"extensions": { "persistedQuery": { "version": 1, "sha256Hash": hash } }
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			assert_eq!(body["data"]["person"], json!([{ "id": "person:alice" }]));
+		}
+
+		// Queries which are not persisted are rejected
+		{
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": "{ person { id name } }"}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["data"].is_null(), "Expected no data: {body}");
+			assert!(
+				body["errors"][0]["message"]
+					.as_str()
+					.unwrap()
+					.contains("Only persisted queries are allowed"),
+				"{body}"
+			);
+		}
+
+		// Unknown hashes are rejected
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"extensions": { "persistedQuery": { "version": 1, "sha256Hash": "unknown" } }
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert_eq!(body["errors"][0]["message"], "PersistedQueryNotFound", "{body}");
+		}
+
+		// A query which does not match its hash is rejected
+		{
+			let res = client
+				.post(gql_url)
+				.body(
+					json!({
+						"query": "{ person { id name } }",
+						"extensions": { "persistedQuery": { "version": 1, "sha256Hash": hash } }
+					})
+					.to_string(),
+				)
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_array(), "Expected an error: {body}");
+			assert!(body["data"].is_null(), "Expected no data: {body}");
+		}
+
+		// Removing the allow-list allows any query again
+		{
+			let res =
+				client.post(sql_url).body("DEFINE CONFIG OVERWRITE GRAPHQL AUTO;").send().await?;
+			assert_eq!(res.status(), 200);
+
+			let res = client
+				.post(gql_url)
+				.body(json!({"query": "{ person { id name } }"}).to_string())
+				.send()
+				.await?;
+			assert_eq!(res.status(), 200);
+			let body = res.json::<serde_json::Value>().await?;
+			assert!(body["errors"].is_null(), "Unexpected errors: {:?}", body["errors"]);
+			assert_eq!(body["data"]["person"][0]["name"], "Alice");
+		}
+
+		Ok(())
+	}
+
 	#[test(tokio::test)]
 	async fn schema_uses_surreal_comments_for_descriptions()
 	-> Result<(), Box<dyn std::error::Error>> {
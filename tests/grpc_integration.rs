@@ -0,0 +1,274 @@
+mod common;
+
+mod grpc_integration {
+	use std::error::Error;
+	use std::time::Duration;
+
+	use http::uri::PathAndQuery;
+	use serde_json::{Value as Json, json};
+	use surrealdb_core::rpc::format::protobuf::{from_value, to_value};
+	use surrealdb_server::rpc::grpc::proto::{
+		self, CredentialsRequest, EmptyRequest, LiveRequest, LiveResponse, QueryRequest,
+		ResourceRequest, Response, SessionRequest, TransactionRequest, live_response,
+	};
+	use surrealdb_types::{SurrealValue, Value, object};
+	use test_log::test;
+	use tonic::client::Grpc;
+	use tonic::transport::Channel;
+	use tonic::{Code, Status};
+	use tonic_prost::ProstCodec;
+	use ulid::Ulid;
+
+	use super::common::{self, PASS, USER};
+
+	struct Client {
+		grpc: Grpc<Channel>,
+		ns: String,
+		db: String,
+		token: Option<String>,
+	}
+
+	impl Client {
+		async fn connect(addr: &str) -> Result<Self, Box<dyn Error>> {
+			let channel = Channel::from_shared(format!("http://{addr}"))?.connect().await?;
+			Ok(Self {
+				grpc: Grpc::new(channel),
+				ns: Ulid::new().to_string(),
+				db: Ulid::new().to_string(),
+				token: None,
+			})
+		}
+
+		fn request<T>(&self, msg: T) -> tonic::Request<T> {
+			let mut req = tonic::Request::new(msg);
+			req.metadata_mut().insert("surreal-ns", self.ns.parse().unwrap());
+			req.metadata_mut().insert("surreal-db", self.db.parse().unwrap());
+			if let Some(token) = &self.token {
+				let auth = format!("Bearer {token}").parse().unwrap();
+				req.metadata_mut().insert("authorization", auth);
+			}
+			req
+		}
+
+		async fn call<T: prost::Message + 'static>(
+			&mut self,
+			method: &str,
+			msg: T,
+		) -> Result<Result<Json, Status>, Box<dyn Error>> {
+			let req = self.request(msg);
+			self.grpc.ready().await?;
+			let path = PathAndQuery::try_from(format!("/surrealdb.rpc.v1.Surreal/{method}"))?;
+			match self.grpc.unary::<_, Response, _>(req, path, ProstCodec::default()).await {
+				Ok(res) => Ok(Ok(decode(res.into_inner().result))),
+				Err(status) => Ok(Err(status)),
+			}
+		}
+
+		async fn query(&mut self, query: &str, txn: &str) -> Result<Json, Box<dyn Error>> {
+			let req = QueryRequest {
+				query: query.to_string(),
+				txn: txn.to_string(),
+				..Default::default()
+			};
+			Ok(self.call("Query", req).await??)
+		}
+
+		/// Define the namespace and database of the client
+		async fn define(&mut self) -> Result<(), Box<dyn Error>> {
+			let (ns, db) = (&self.ns, &self.db);
+			let query = format!("DEFINE NAMESPACE `{ns}`; USE NS `{ns}`; DEFINE DATABASE `{db}`");
+			let res = self.query(&query, "").await?;
+			for res in res.as_array().unwrap() {
+				assert_eq!(res["status"], "OK", "{res}");
+			}
+			Ok(())
+		}
+
+		async fn signin(&mut self, user: &str, pass: &str) -> Result<(), Box<dyn Error>> {
+			let credentials = object! { user: user.to_string(), pass: pass.to_string() };
+			let req = CredentialsRequest {
+				credentials: Some(object(credentials.into_value())),
+				..Default::default()
+			};
+			let token = self.call("Signin", req).await??;
+			self.token = Some(token.as_str().unwrap().to_string());
+			Ok(())
+		}
+	}
+
+	fn encode(val: Value) -> proto::Value {
+		from_value(val).unwrap()
+	}
+
+	fn object(val: Value) -> proto::Object {
+		match encode(val).kind {
+			Some(proto::value::Kind::Object(v)) => v,
+			_ => unreachable!(),
+		}
+	}
+
+	fn decode(val: Option<proto::Value>) -> Json {
+		to_value(val.unwrap()).unwrap().into_json_value()
+	}
+
+	fn resource(what: &str) -> ResourceRequest {
+		ResourceRequest {
+			what: Some(encode(Value::String(what.to_string()))),
+			..Default::default()
+		}
+	}
+
+	fn transaction(txn: &Json) -> TransactionRequest {
+		TransactionRequest {
+			txn: txn.as_str().unwrap().to_string(),
+			..Default::default()
+		}
+	}
+
+	#[test(tokio::test)]
+	async fn call() -> Result<(), Box<dyn Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let mut client = Client::connect(&addr).await?;
+		client.define().await?;
+
+		// Run a query
+		let res = client
+			.query("CREATE person:tobie SET name = 'Tobie'; SELECT name FROM person", "")
+			.await?;
+		assert_eq!(res[1]["result"], json!([{ "name": "Tobie" }]), "{res}");
+
+		// Select records
+		let res = client.call("Select", resource("person")).await??;
+		assert_eq!(res[0]["name"], "Tobie", "{res}");
+
+		// Methods without parameters
+		let res = client.call("Ping", EmptyRequest {}).await??;
+		assert_eq!(res, Json::Null);
+
+		// Invalid parameters are rejected with a status
+		let status = client.call("Signin", CredentialsRequest::default()).await?.unwrap_err();
+		assert_eq!(status.code(), Code::InvalidArgument, "{status}");
+
+		// Invalid ids are rejected
+		let req = TransactionRequest {
+			txn: "invalid".to_string(),
+			..Default::default()
+		};
+		let status = client.call("Commit", req).await?.unwrap_err();
+		assert_eq!(status.code(), Code::InvalidArgument, "{status}");
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn transactions() -> Result<(), Box<dyn Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let mut client = Client::connect(&addr).await?;
+		client.define().await?;
+
+		// Commit a transaction
+		let txn = client.call("Begin", SessionRequest::default()).await??;
+		client.query("CREATE person:tobie", txn.as_str().unwrap()).await?;
+		client.call("Commit", transaction(&txn)).await??;
+		let res = client.call("Select", resource("person")).await??;
+		assert_eq!(res.as_array().unwrap().len(), 1, "{res}");
+
+		// Cancel a transaction
+		let txn = client.call("Begin", SessionRequest::default()).await??;
+		client.query("CREATE person:jaime", txn.as_str().unwrap()).await?;
+		client.call("Cancel", transaction(&txn)).await??;
+		let res = client.call("Select", resource("person")).await??;
+		assert_eq!(res.as_array().unwrap().len(), 1, "{res}");
+
+		// The transaction has been completed
+		let status = client.call("Commit", transaction(&txn)).await?.unwrap_err();
+		assert!(status.message().contains("Transaction not found"), "{status}");
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn ownership() -> Result<(), Box<dyn Error>> {
+		let (addr, _server) = common::start_server_with_defaults().await.unwrap();
+		let mut owner = Client::connect(&addr).await?;
+		let mut other = Client::connect(&addr).await?;
+		other.ns = owner.ns.clone();
+		other.db = owner.db.clone();
+
+		// Anonymous users can not store transactions or sessions
+		let status = owner.call("Begin", SessionRequest::default()).await?.unwrap_err();
+		assert_eq!(status.code(), Code::PermissionDenied, "{status}");
+		let session = SessionRequest {
+			session: "0192f3a4-7b6c-7d8e-9f00-112233445566".to_string(),
+		};
+		let status = owner.call("Attach", session.clone()).await?.unwrap_err();
+		assert_eq!(status.code(), Code::PermissionDenied, "{status}");
+
+		owner.signin(USER, PASS).await?;
+		owner.query("DEFINE USER other ON ROOT PASSWORD 'other' ROLES OWNER", "").await?;
+		other.signin("other", "other").await?;
+
+		// Transactions can only be used by the user who started them
+		let txn = owner.call("Begin", SessionRequest::default()).await??;
+		let status = other.call("Commit", transaction(&txn)).await?.unwrap_err();
+		assert!(status.message().contains("Transaction not found"), "{status}");
+		owner.call("Commit", transaction(&txn)).await??;
+
+		// Sessions can only be used by the user who attached them
+		owner.call("Attach", session.clone()).await??;
+		let req = TransactionRequest {
+			session: session.session.clone(),
+			..Default::default()
+		};
+		let status = other.call("Info", req).await?.unwrap_err();
+		assert_eq!(status.code(), Code::NotFound, "{status}");
+		let res = other.call("Sessions", EmptyRequest {}).await??;
+		assert_eq!(res, json!([]), "{res}");
+		let status = other.call("Detach", session.clone()).await?.unwrap_err();
+		assert_eq!(status.code(), Code::NotFound, "{status}");
+		owner.call("Detach", session).await??;
+
+		Ok(())
+	}
+
+	#[test(tokio::test)]
+	async fn live() -> Result<(), Box<dyn Error>> {
+		let (addr, _server) = common::start_server_without_auth().await.unwrap();
+		let mut client = Client::connect(&addr).await?;
+		client.define().await?;
+		client.query("DEFINE TABLE person", "").await?;
+
+		// Start the LIVE query stream
+		let req = client.request(LiveRequest {
+			what: Some(encode(Value::String("person".to_string()))),
+			..Default::default()
+		});
+		client.grpc.ready().await?;
+		let mut stream = client
+			.grpc
+			.server_streaming::<_, LiveResponse, _>(
+				req,
+				PathAndQuery::from_static("/surrealdb.rpc.v1.Surreal/Live"),
+				ProstCodec::default(),
+			)
+			.await?
+			.into_inner();
+		let Some(live_response::Event::Id(lqid)) = stream.message().await?.unwrap().event else {
+			panic!("Expected the LIVE query id");
+		};
+		let lqid = decode(Some(lqid));
+		assert!(lqid.is_string(), "{lqid}");
+
+		// Receive a notification for a created record
+		client.query("CREATE person:tobie SET name = 'Tobie'", "").await?;
+		let msg = tokio::time::timeout(Duration::from_secs(10), stream.message()).await??.unwrap();
+		let Some(live_response::Event::Notification(notification)) = msg.event else {
+			panic!("Expected a notification");
+		};
+		assert_eq!(decode(notification.id), lqid);
+		assert_eq!(notification.action, "CREATE");
+		assert_eq!(decode(notification.result)["name"], "Tobie");
+
+		Ok(())
+	}
+}
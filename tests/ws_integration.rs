@@ -20,6 +20,18 @@ mod ws_integration {
 		use crate::common::Format;
 		crate::include_tests!(Some(Format::Cbor), Format::Cbor);
 	}
+
+	/// Tests for the MessagePack protocol format
+	mod msgpack {
+		use crate::common::Format;
+		crate::include_tests!(Some(Format::Msgpack), Format::Msgpack);
+	}
+
+	/// Tests for the Protobuf protocol format
+	mod protobuf {
+		use crate::common::Format;
+		crate::include_tests!(Some(Format::Protobuf), Format::Protobuf);
+	}
 }
 
 use std::future::Future;